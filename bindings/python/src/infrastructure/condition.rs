@@ -1,5 +1,7 @@
 use pyo3::prelude::*;
 
+use crate::subscription::sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind};
+
 use super::{error::into_pyerr, status::StatusKind};
 
 #[pyclass]
@@ -42,3 +44,111 @@ impl StatusCondition {
         self.0.get_trigger_value().map_err(into_pyerr)
     }
 }
+
+#[pyclass]
+#[derive(Clone)]
+pub struct ReadCondition(dust_dds::infrastructure::condition::ReadCondition);
+
+impl From<dust_dds::infrastructure::condition::ReadCondition> for ReadCondition {
+    fn from(value: dust_dds::infrastructure::condition::ReadCondition) -> Self {
+        Self(value)
+    }
+}
+
+impl From<ReadCondition> for dust_dds::infrastructure::condition::ReadCondition {
+    fn from(value: ReadCondition) -> Self {
+        value.0
+    }
+}
+
+#[pymethods]
+impl ReadCondition {
+    pub fn get_sample_state_mask(&self) -> Vec<SampleStateKind> {
+        self.0
+            .get_sample_state_mask()
+            .into_iter()
+            .map(SampleStateKind::from)
+            .collect()
+    }
+
+    pub fn get_view_state_mask(&self) -> Vec<ViewStateKind> {
+        self.0
+            .get_view_state_mask()
+            .into_iter()
+            .map(ViewStateKind::from)
+            .collect()
+    }
+
+    pub fn get_instance_state_mask(&self) -> Vec<InstanceStateKind> {
+        self.0
+            .get_instance_state_mask()
+            .into_iter()
+            .map(InstanceStateKind::from)
+            .collect()
+    }
+
+    pub fn get_trigger_value(&self) -> PyResult<bool> {
+        self.0.get_trigger_value().map_err(into_pyerr)
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct QueryCondition(dust_dds::infrastructure::condition::QueryCondition);
+
+impl From<dust_dds::infrastructure::condition::QueryCondition> for QueryCondition {
+    fn from(value: dust_dds::infrastructure::condition::QueryCondition) -> Self {
+        Self(value)
+    }
+}
+
+impl From<QueryCondition> for dust_dds::infrastructure::condition::QueryCondition {
+    fn from(value: QueryCondition) -> Self {
+        value.0
+    }
+}
+
+#[pymethods]
+impl QueryCondition {
+    pub fn get_sample_state_mask(&self) -> Vec<SampleStateKind> {
+        self.0
+            .get_sample_state_mask()
+            .into_iter()
+            .map(SampleStateKind::from)
+            .collect()
+    }
+
+    pub fn get_view_state_mask(&self) -> Vec<ViewStateKind> {
+        self.0
+            .get_view_state_mask()
+            .into_iter()
+            .map(ViewStateKind::from)
+            .collect()
+    }
+
+    pub fn get_instance_state_mask(&self) -> Vec<InstanceStateKind> {
+        self.0
+            .get_instance_state_mask()
+            .into_iter()
+            .map(InstanceStateKind::from)
+            .collect()
+    }
+
+    pub fn get_trigger_value(&self) -> PyResult<bool> {
+        self.0.get_trigger_value().map_err(into_pyerr)
+    }
+
+    pub fn get_query_expression(&self) -> String {
+        self.0.get_query_expression()
+    }
+
+    pub fn get_query_parameters(&self) -> PyResult<Vec<String>> {
+        self.0.get_query_parameters().map_err(into_pyerr)
+    }
+
+    pub fn set_query_parameters(&self, query_parameters: Vec<String>) -> PyResult<()> {
+        self.0
+            .set_query_parameters(&query_parameters)
+            .map_err(into_pyerr)
+    }
+}
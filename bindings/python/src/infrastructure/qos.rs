@@ -46,6 +46,8 @@ impl DomainParticipantFactoryQos {
     }
 }
 
+/// QoS policies applicable to the DomainParticipant.
+/// The time source policy is not available from Python so the participant always uses the system time.
 #[pyclass]
 #[derive(Clone)]
 pub struct DomainParticipantQos(dust_dds::infrastructure::qos::DomainParticipantQos);
@@ -70,6 +72,9 @@ impl DomainParticipantQos {
         Self(dust_dds::infrastructure::qos::DomainParticipantQos {
             user_data: user_data.clone().into(),
             entity_factory: entity_factory.into(),
+            // The logical time source is shared as an atomic counter which can not be
+            // exposed to Python so the default system time is used
+            ..Default::default()
         })
     }
 
@@ -380,6 +385,7 @@ impl DataWriterQos {
             ownership_strength: ownership_strength.into(),
             writer_data_lifecycle: writer_data_lifecycle.into(),
             representation: representation.into(),
+            flow_controller: Default::default(),
         })
     }
 
@@ -804,6 +804,22 @@ impl From<dust_dds::infrastructure::qos_policy::OwnershipStrengthQosPolicy>
     }
 }
 
+#[pymethods]
+impl OwnershipStrengthQosPolicy {
+    #[new]
+    pub fn new(value: i32) -> Self {
+        Self(dust_dds::infrastructure::qos_policy::OwnershipStrengthQosPolicy { value })
+    }
+
+    pub fn get_value(&self) -> i32 {
+        self.0.value
+    }
+
+    pub fn set_value(&mut self, value: i32) {
+        self.0.value = value
+    }
+}
+
 #[pyclass]
 #[derive(Clone, Default)]
 pub struct GroupDataQosPolicy(dust_dds::infrastructure::qos_policy::GroupDataQosPolicy);
@@ -873,6 +889,7 @@ impl PartitionQosPolicy {
 pub enum PresentationQosPolicyAccessScopeKind {
     Instance,
     Topic,
+    Group,
 }
 
 impl From<PresentationQosPolicyAccessScopeKind>
@@ -886,6 +903,9 @@ impl From<PresentationQosPolicyAccessScopeKind>
             PresentationQosPolicyAccessScopeKind::Topic => {
                 dust_dds::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind::Topic
             }
+            PresentationQosPolicyAccessScopeKind::Group => {
+                dust_dds::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind::Group
+            }
         }
     }
 }
@@ -899,6 +919,7 @@ impl From<dust_dds::infrastructure::qos_policy::PresentationQosPolicyAccessScope
         match value {
             dust_dds::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind::Instance => PresentationQosPolicyAccessScopeKind::Instance,
             dust_dds::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind::Topic => PresentationQosPolicyAccessScopeKind::Topic,
+            dust_dds::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind::Group => PresentationQosPolicyAccessScopeKind::Group,
         }
     }
 }
@@ -130,7 +130,7 @@ impl ParticipantProxy {
         self.domain_tag.0.as_ref()
     }
 
-    pub fn _protocol_version(&self) -> ProtocolVersion {
+    pub fn protocol_version(&self) -> ProtocolVersion {
         self.protocol_version
     }
 
@@ -219,7 +219,7 @@ impl SpdpDiscoveredParticipantData {
         &self.participant_proxy
     }
 
-    pub fn _lease_duration(&self) -> &Duration {
+    pub fn lease_duration(&self) -> &Duration {
         &self.lease_duration
     }
 
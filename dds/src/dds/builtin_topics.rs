@@ -63,7 +63,8 @@ pub struct ParticipantBuiltinTopicData {
 }
 
 impl ParticipantBuiltinTopicData {
-    pub(crate) fn new(key: BuiltInTopicKey, user_data: UserDataQosPolicy) -> Self {
+    /// Construct a new ParticipantBuiltinTopicData
+    pub fn new(key: BuiltInTopicKey, user_data: UserDataQosPolicy) -> Self {
         Self { key, user_data }
     }
 
@@ -285,7 +286,8 @@ pub struct PublicationBuiltinTopicData {
 
 impl PublicationBuiltinTopicData {
     #[allow(clippy::too_many_arguments)]
-    pub(crate) fn new(
+    /// Construct a new PublicationBuiltinTopicData
+    pub fn new(
         key: BuiltInTopicKey,
         participant_key: BuiltInTopicKey,
         topic_name: String,
@@ -1,7 +1,17 @@
-use std::time::Duration;
+use std::{env::VarError, fmt::Display, net::SocketAddr, str::FromStr, time::Duration};
 
 use crate::infrastructure::error::{DdsError, DdsResult};
 
+/// Transport used by the participants to exchange the user-defined data.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TransportKind {
+    /// User-defined data is sent over UDP datagrams.
+    Udp,
+    /// User-defined data is sent over TCP connections. Each RTPS message is framed with a 4-byte big-endian length prefix.
+    /// Participant discovery is still done over UDP.
+    Tcp,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 /// This struct specifies the high-level configuration for the DustDDS library. The configuration can be set for use by the
 /// [`DomainParticipantFactory::set_configuration`](dust_dds::domain::domain_participant_factory::DomainParticipantFactory::set_configuration) method.
@@ -11,6 +21,9 @@ pub struct DustDdsConfiguration {
     fragment_size: usize,
     udp_receive_buffer_size: Option<usize>,
     participant_announcement_interval: Duration,
+    participant_lease_duration: Duration,
+    transport: TransportKind,
+    initial_peers: Vec<SocketAddr>,
 }
 
 impl DustDdsConfiguration {
@@ -19,7 +32,7 @@ impl DustDdsConfiguration {
         self.domain_tag.as_ref()
     }
 
-    /// Network interface name to use for discovery
+    /// Network interface name to use for the communication. [`None`] means all the interfaces are used.
     pub fn interface_name(&self) -> Option<&String> {
         self.interface_name.as_ref()
     }
@@ -38,6 +51,83 @@ impl DustDdsConfiguration {
     pub fn participant_announcement_interval(&self) -> Duration {
         self.participant_announcement_interval
     }
+
+    /// Time during which the participant is considered alive by the remote participants after each announcement.
+    pub fn participant_lease_duration(&self) -> Duration {
+        self.participant_lease_duration
+    }
+
+    /// Transport used for the user-defined data.
+    pub fn transport(&self) -> TransportKind {
+        self.transport
+    }
+
+    /// Unicast addresses to which the participants are announced in addition to the discovery multicast group.
+    pub fn initial_peers(&self) -> &[SocketAddr] {
+        self.initial_peers.as_ref()
+    }
+
+    /// Create a configuration from the environment variables of the process. The options which are not set in the
+    /// environment keep their default value. The following variables are used:
+    /// - `DUST_DDS_DOMAIN_TAG`: Domain tag to use for the participants
+    /// - `DUST_DDS_INTERFACE`: Network interface name to use for the communication
+    /// - `DUST_DDS_DATA_MAX_SIZE`: Maximum size for the data fragments in bytes
+    /// - `DUST_DDS_SPDP_PERIOD_MS`: Participant announcement interval in milliseconds
+    /// - `DUST_DDS_INITIAL_PEERS`: Semicolon-separated list of socket addresses, e.g. `192.168.1.2:7400;192.168.1.3:7400`
+    ///
+    /// An error is returned if any of the variables has a malformed value or if the resulting configuration is not valid.
+    pub fn from_env() -> DdsResult<Self> {
+        Self::from_env_vars(|name| std::env::var(name))
+    }
+
+    fn from_env_vars(var: impl Fn(&str) -> Result<String, VarError>) -> DdsResult<Self> {
+        fn parse<T>(name: &str, value: &str) -> DdsResult<T>
+        where
+            T: FromStr,
+            T::Err: Display,
+        {
+            value.trim().parse().map_err(|e| {
+                DdsError::Error(format!(
+                    "Invalid value {value:?} of environment variable {name}: {e}"
+                ))
+            })
+        }
+
+        let read = |name: &str| match var(name) {
+            Ok(value) => Ok(Some(value)),
+            Err(VarError::NotPresent) => Ok(None),
+            Err(VarError::NotUnicode(_)) => Err(DdsError::Error(format!(
+                "Environment variable {name} is not valid unicode"
+            ))),
+        };
+
+        let mut builder = DustDdsConfigurationBuilder::new();
+        if let Some(domain_tag) = read("DUST_DDS_DOMAIN_TAG")? {
+            builder = builder.domain_tag(domain_tag);
+        }
+        if let Some(interface_name) = read("DUST_DDS_INTERFACE")? {
+            builder = builder.interface_name(Some(interface_name));
+        }
+        if let Some(fragment_size) = read("DUST_DDS_DATA_MAX_SIZE")? {
+            builder = builder.fragment_size(parse("DUST_DDS_DATA_MAX_SIZE", &fragment_size)?);
+        }
+        if let Some(spdp_period) = read("DUST_DDS_SPDP_PERIOD_MS")? {
+            builder = builder.participant_announcement_interval(Duration::from_millis(parse(
+                "DUST_DDS_SPDP_PERIOD_MS",
+                &spdp_period,
+            )?));
+        }
+        if let Some(initial_peers) = read("DUST_DDS_INITIAL_PEERS")? {
+            builder = builder.initial_peers(
+                initial_peers
+                    .split(';')
+                    .filter(|peer| !peer.trim().is_empty())
+                    .map(|peer| parse("DUST_DDS_INITIAL_PEERS", peer))
+                    .collect::<DdsResult<_>>()?,
+            );
+        }
+        builder.build()
+    }
 }
 
 impl Default for DustDdsConfiguration {
@@ -48,6 +138,9 @@ impl Default for DustDdsConfiguration {
             fragment_size: 1344,
             udp_receive_buffer_size: None,
             participant_announcement_interval: Duration::from_secs(5),
+            participant_lease_duration: Duration::from_secs(100),
+            transport: TransportKind::Udp,
+            initial_peers: vec![],
         }
     }
 }
@@ -74,6 +167,21 @@ impl DustDdsConfigurationBuilder {
                 "Interface size out of range. Value must be between in {:?}",
                 fragment_size_range
             )))
+        } else if self
+            .configuration
+            .participant_announcement_interval
+            .is_zero()
+        {
+            Err(DdsError::Error(
+                "Participant announcement interval must be greater than zero".to_string(),
+            ))
+        } else if self.configuration.participant_announcement_interval
+            >= self.configuration.participant_lease_duration
+        {
+            Err(DdsError::Error(
+                "Participant announcement interval must be smaller than the participant lease duration"
+                    .to_string(),
+            ))
         } else {
             Ok(self.configuration)
         }
@@ -85,7 +193,9 @@ impl DustDdsConfigurationBuilder {
         self
     }
 
-    /// Set the network interface name to use for discovery
+    /// Set the network interface name to use for the communication. The sockets are bound to the first IPv4 address
+    /// of the interface and the multicast traffic is only sent and received on it. [`None`] corresponds to using all the
+    /// interfaces, which is also the fallback if the interface has no IPv4 address.
     pub fn interface_name(mut self, interface_name: Option<String>) -> Self {
         self.configuration.interface_name = interface_name;
         self
@@ -112,4 +222,146 @@ impl DustDdsConfigurationBuilder {
         self.configuration.participant_announcement_interval = participant_announcement_interval;
         self
     }
+
+    /// Set the time during which the participant is considered alive by the remote participants after each
+    /// announcement. The participant announcement interval must be smaller than this value and it is recommended to
+    /// be at most one third of it so that a lost announcement does not cause the participant to be removed.
+    pub fn participant_lease_duration(mut self, participant_lease_duration: Duration) -> Self {
+        self.configuration.participant_lease_duration = participant_lease_duration;
+        self
+    }
+
+    /// Set the transport used for the user-defined data. Participants using different transports do not communicate
+    /// with each other.
+    pub fn transport(mut self, transport: TransportKind) -> Self {
+        self.configuration.transport = transport;
+        self
+    }
+
+    /// Set the unicast addresses to which the participants are announced in addition to the discovery multicast group.
+    /// This allows discovering participants in networks where multicast is not available. Only IPv4 addresses are
+    /// currently supported.
+    pub fn initial_peers(mut self, initial_peers: Vec<SocketAddr>) -> Self {
+        self.configuration.initial_peers = initial_peers;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_configuration_is_valid() {
+        assert_eq!(
+            DustDdsConfigurationBuilder::new().build().unwrap(),
+            DustDdsConfiguration::default()
+        );
+    }
+
+    #[test]
+    fn fragment_size_out_of_range_is_rejected() {
+        assert!(DustDdsConfigurationBuilder::new()
+            .fragment_size(0)
+            .build()
+            .is_err());
+        assert!(DustDdsConfigurationBuilder::new()
+            .fragment_size(65001)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn zero_participant_announcement_interval_is_rejected() {
+        assert!(DustDdsConfigurationBuilder::new()
+            .participant_announcement_interval(Duration::ZERO)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn participant_announcement_interval_not_smaller_than_lease_duration_is_rejected() {
+        assert!(DustDdsConfigurationBuilder::new()
+            .participant_announcement_interval(Duration::from_secs(10))
+            .participant_lease_duration(Duration::from_secs(10))
+            .build()
+            .is_err());
+        assert!(DustDdsConfigurationBuilder::new()
+            .participant_announcement_interval(Duration::from_millis(100))
+            .participant_lease_duration(Duration::from_secs(1))
+            .build()
+            .is_ok());
+    }
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Result<String, VarError> {
+        let vars: std::collections::HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned().ok_or(VarError::NotPresent)
+    }
+
+    #[test]
+    fn configuration_from_empty_env_is_default() {
+        assert_eq!(
+            DustDdsConfiguration::from_env_vars(env(&[])).unwrap(),
+            DustDdsConfiguration::default()
+        );
+    }
+
+    #[test]
+    fn configuration_from_env() {
+        let configuration = DustDdsConfiguration::from_env_vars(env(&[
+            ("DUST_DDS_DOMAIN_TAG", "my_tag"),
+            ("DUST_DDS_INTERFACE", "eth0"),
+            ("DUST_DDS_DATA_MAX_SIZE", "8000"),
+            ("DUST_DDS_SPDP_PERIOD_MS", "250"),
+            (
+                "DUST_DDS_INITIAL_PEERS",
+                "192.168.1.2:7400; 192.168.1.3:7410;",
+            ),
+        ]))
+        .unwrap();
+
+        assert_eq!(configuration.domain_tag(), "my_tag");
+        assert_eq!(configuration.interface_name(), Some(&"eth0".to_string()));
+        assert_eq!(configuration.fragment_size(), 8000);
+        assert_eq!(
+            configuration.participant_announcement_interval(),
+            Duration::from_millis(250)
+        );
+        assert_eq!(
+            configuration.initial_peers(),
+            &[
+                "192.168.1.2:7400".parse().unwrap(),
+                "192.168.1.3:7410".parse().unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_env_values_are_rejected() {
+        for (name, value) in [
+            ("DUST_DDS_DATA_MAX_SIZE", "big"),
+            ("DUST_DDS_DATA_MAX_SIZE", "70000"),
+            ("DUST_DDS_SPDP_PERIOD_MS", "-1"),
+            ("DUST_DDS_SPDP_PERIOD_MS", "0"),
+            ("DUST_DDS_INITIAL_PEERS", "192.168.1.2"),
+        ] {
+            assert!(
+                matches!(
+                    DustDdsConfiguration::from_env_vars(env(&[(name, value)])),
+                    Err(DdsError::Error(_))
+                ),
+                "{name}={value} not rejected"
+            );
+        }
+        assert_eq!(
+            DustDdsConfiguration::from_env_vars(env(&[("DUST_DDS_DATA_MAX_SIZE", "big")])),
+            Err(DdsError::Error(
+                "Invalid value \"big\" of environment variable DUST_DDS_DATA_MAX_SIZE: invalid digit found in string"
+                    .to_string()
+            ))
+        );
+    }
 }
@@ -18,9 +18,12 @@ use crate::{
     publication::{publisher::Publisher, publisher_listener::PublisherListener},
     subscription::{subscriber::Subscriber, subscriber_listener::SubscriberListener},
     topic_definition::{
+        content_filtered_topic::ContentFilteredTopic,
         topic::Topic,
         topic_listener::TopicListener,
-        type_support::{DdsHasKey, DdsKey, DdsTypeXml, DynamicTypeInterface},
+        type_support::{
+            DdsDeserialize, DdsGetField, DdsHasKey, DdsKey, DdsTypeXml, DynamicTypeInterface,
+        },
     },
 };
 
@@ -28,6 +31,31 @@ use super::{
     domain_participant_factory::DomainId, domain_participant_listener::DomainParticipantListener,
 };
 
+/// Runtime statistics of a [`DomainParticipant`] as returned by [`DomainParticipant::get_stats()`].
+/// All the counters are accumulated since the creation of the participant and include the traffic
+/// of the built-in discovery entities.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct DomainParticipantStats {
+    /// Number of RTPS messages sent. A message sent to several destinations is counted once per destination.
+    pub messages_sent: u64,
+    /// Number of RTPS messages received.
+    pub messages_received: u64,
+    /// Total size in bytes of the RTPS messages sent.
+    pub bytes_sent: u64,
+    /// Total size in bytes of the RTPS messages received.
+    pub bytes_received: u64,
+    /// Number of samples written by the data writers of the participant.
+    pub samples_written: u64,
+    /// Number of samples returned by the read and take operations of the data readers of the participant.
+    pub samples_read: u64,
+    /// Number of remote participants, publications, subscriptions and topics discovered or removed.
+    pub discovery_events: u64,
+    /// Number of HEARTBEAT submessages sent.
+    pub heartbeats_sent: u64,
+    /// Number of participant announcements ignored because they were sent by a participant of a different domain.
+    pub domain_isolation_violations: u64,
+}
+
 /// The [`DomainParticipant`] represents the participation of the application on a communication plane that isolates applications running on the
 /// same set of physical computers from each other. A domain establishes a *virtual network* linking all applications that
 /// share the same domain_id and isolating them from applications running on different domains. In this way, several
@@ -198,6 +226,32 @@ impl DomainParticipant {
         block_on(self.participant_async.delete_topic(a_topic.topic_async()))
     }
 
+    /// This operation creates a [`ContentFilteredTopic`]. A [`ContentFilteredTopic`] can be used to do content-based subscriptions.
+    /// The related [`Topic`] being subscribed to is specified by means of the `related_topic` parameter and the type used to
+    /// evaluate the filter on the received samples is given as a generic type argument `Foo`.
+    /// The `filter_expression` and `expression_parameters` follow the same syntax as the query of a
+    /// [`QueryCondition`](crate::infrastructure::condition::QueryCondition). If the expression is not valid or refers to a
+    /// parameter that is not provided, the operation will return [`DdsError::BadParameter`](crate::infrastructure::error::DdsError).
+    #[tracing::instrument(skip(self, related_topic))]
+    pub fn create_contentfilteredtopic<Foo>(
+        &self,
+        name: &str,
+        related_topic: &Topic,
+        filter_expression: &str,
+        expression_parameters: &[String],
+    ) -> DdsResult<ContentFilteredTopic>
+    where
+        Foo: DdsGetField + for<'de> DdsDeserialize<'de>,
+    {
+        block_on(self.participant_async.create_contentfilteredtopic::<Foo>(
+            name,
+            related_topic.topic_async(),
+            filter_expression,
+            expression_parameters,
+        ))
+        .map(ContentFilteredTopic::new)
+    }
+
     /// This operation gives access to an existing (or ready to exist) enabled [`Topic`], based on its name. The operation takes
     /// as arguments the name of the [`Topic`], a timeout and the type as a generic type argument `Foo`.
     /// If a [`Topic`] of the same name and type already exists, it gives access to it, otherwise it waits (blocks the caller) until another mechanism
@@ -453,6 +507,13 @@ impl DomainParticipant {
     pub fn get_current_time(&self) -> DdsResult<Time> {
         block_on(self.participant_async.get_current_time())
     }
+
+    /// This operation returns the [`DomainParticipantStats`] with the runtime statistics of the participant. These are
+    /// not part of the DDS standard and are intended to help diagnosing communication issues such as lost data.
+    #[tracing::instrument(skip(self))]
+    pub fn get_stats(&self) -> DdsResult<DomainParticipantStats> {
+        block_on(self.participant_async.get_stats())
+    }
 }
 
 /// This implementation block contains the Entity operations for the [`DomainParticipant`].
@@ -543,6 +604,9 @@ impl DomainParticipant {
     }
 
     /// This operation returns the [`InstanceHandle`] that represents the Entity.
+    /// The handle is derived from the GUID prefix of the participant. If, shortly after being enabled, the participant
+    /// discovers another participant announcing the same GUID prefix, a new GUID prefix is generated and the instance
+    /// handles of the participant and of all its contained entities change. Handles obtained before that are no longer valid.
     #[tracing::instrument(skip(self))]
     pub fn get_instance_handle(&self) -> DdsResult<InstanceHandle> {
         block_on(self.participant_async.get_instance_handle())
@@ -14,6 +14,10 @@ use crate::{
     },
 };
 
+#[cfg(feature = "xml-profiles")]
+use crate::infrastructure::qos_profile::QosProfile;
+#[cfg(feature = "xml-profiles")]
+use std::path::Path;
 use std::sync::OnceLock;
 use tracing::warn;
 
@@ -138,3 +142,35 @@ impl DomainParticipantFactory {
         block_on(self.participant_factory_async.get_configuration())
     }
 }
+
+#[cfg(feature = "xml-profiles")]
+impl DomainParticipantFactory {
+    /// Load the QoS profiles of a file following the DDS XML QoS profile schema. The profiles are afterwards
+    /// identified by their fully qualified name `<library name>::<profile name>`. Loading a profile with the
+    /// same name as a previously loaded one replaces it.
+    /// The policies which are not present in a profile keep the default value of the corresponding QoS.
+    pub fn load_profiles_from_file(&self, path: &Path) -> DdsResult<()> {
+        block_on(self.participant_factory_async.load_profiles_from_file(path))
+    }
+
+    /// Get a previously loaded [`QosProfile`]. This can be used to create the entities with the
+    /// QoS of the profile. If no profile with the given name was loaded the error
+    /// [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError::PreconditionNotMet) is returned.
+    pub fn get_qos_profile(&self, profile_name: &str) -> DdsResult<QosProfile> {
+        block_on(self.participant_factory_async.get_qos_profile(profile_name))
+    }
+
+    /// Create a [`DomainParticipant`] with the participant QoS of a previously loaded [`QosProfile`]. The publisher,
+    /// subscriber and topic QoS of the profile are set as the default QoS of the created [`DomainParticipant`].
+    pub fn create_participant_with_profile(
+        &self,
+        domain_id: DomainId,
+        profile_name: &str,
+    ) -> DdsResult<DomainParticipant> {
+        block_on(
+            self.participant_factory_async
+                .create_participant_with_profile(domain_id, profile_name),
+        )
+        .map(DomainParticipant::new)
+    }
+}
@@ -1,7 +1,10 @@
 use crate::{
-    dds_async::condition::StatusConditionAsync, implementation::runtime::executor::block_on,
+    dds_async::condition::{QueryConditionAsync, ReadConditionAsync, StatusConditionAsync},
+    implementation::runtime::executor::block_on,
     infrastructure::error::DdsResult,
+    subscription::sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
 };
+use std::ops::Deref;
 
 use super::status::StatusKind;
 
@@ -54,3 +57,120 @@ impl StatusCondition {
         block_on(self.condition_async.get_trigger_value())
     }
 }
+
+/// A [`ReadCondition`] object is a specific Condition that is associated with a DataReader. It is created by calling
+/// [`DataReader::create_readcondition`](crate::subscription::data_reader::DataReader::create_readcondition).
+/// The *trigger_value* of the [`ReadCondition`] is [`true`] if there is at least one sample in the DataReader whose
+/// [`SampleStateKind`], [`ViewStateKind`] and [`InstanceStateKind`] match the masks of the condition.
+/// The [`ReadCondition`] can also be used to access the matching samples by calling
+/// [`DataReader::read_w_condition`](crate::subscription::data_reader::DataReader::read_w_condition) or
+/// [`DataReader::take_w_condition`](crate::subscription::data_reader::DataReader::take_w_condition).
+#[derive(Clone)]
+pub struct ReadCondition {
+    condition_async: ReadConditionAsync,
+}
+
+impl ReadCondition {
+    pub(crate) fn new(condition_async: ReadConditionAsync) -> Self {
+        Self { condition_async }
+    }
+
+    pub(crate) fn condition_async(&self) -> &ReadConditionAsync {
+        &self.condition_async
+    }
+}
+
+impl ReadCondition {
+    /// This operation returns the set of sample_states that are taken into account to determine the *trigger_value* of the [`ReadCondition`].
+    /// These are the sample_states specified when the [`ReadCondition`] was created.
+    #[tracing::instrument(skip(self))]
+    pub fn get_sample_state_mask(&self) -> Vec<SampleStateKind> {
+        self.condition_async.get_sample_state_mask()
+    }
+
+    /// This operation returns the set of view_states that are taken into account to determine the *trigger_value* of the [`ReadCondition`].
+    /// These are the view_states specified when the [`ReadCondition`] was created.
+    #[tracing::instrument(skip(self))]
+    pub fn get_view_state_mask(&self) -> Vec<ViewStateKind> {
+        self.condition_async.get_view_state_mask()
+    }
+
+    /// This operation returns the set of instance_states that are taken into account to determine the *trigger_value* of the [`ReadCondition`].
+    /// These are the instance_states specified when the [`ReadCondition`] was created.
+    #[tracing::instrument(skip(self))]
+    pub fn get_instance_state_mask(&self) -> Vec<InstanceStateKind> {
+        self.condition_async.get_instance_state_mask()
+    }
+}
+
+/// This implementation block contains the Condition operations for the [`ReadCondition`].
+impl ReadCondition {
+    /// This operation retrieves the *trigger_value* of the [`ReadCondition`].
+    #[tracing::instrument(skip(self))]
+    pub fn get_trigger_value(&self) -> DdsResult<bool> {
+        block_on(self.condition_async.get_trigger_value())
+    }
+}
+
+/// A [`QueryCondition`] object is a specialized [`ReadCondition`] which allows the application to also specify a filter on
+/// the locally available data. It is created by calling
+/// [`DataReader::create_querycondition`](crate::subscription::data_reader::DataReader::create_querycondition).
+/// The *trigger_value* of the [`QueryCondition`] is [`true`] if there is at least one sample in the DataReader whose
+/// [`SampleStateKind`], [`ViewStateKind`] and [`InstanceStateKind`] match the masks of the condition and whose data
+/// satisfies the *query_expression*. The [`ReadCondition`] operations are available on the [`QueryCondition`] through [`Deref`].
+///
+/// The *query_expression* is a subset of the SQL WHERE clause consisting of comparisons (`=`, `<>`, `<`, `<=`, `>`, `>=`),
+/// `BETWEEN` and `LIKE` predicates on the members of the type combined with `AND`, `OR` and parentheses. The members are
+/// accessed using the [`DdsGetField`](crate::topic_definition::type_support::DdsGetField) trait. The *query_parameters*
+/// are referenced in the expression as `%0`, `%1`, ...
+#[derive(Clone)]
+pub struct QueryCondition {
+    read_condition: ReadCondition,
+    condition_async: QueryConditionAsync,
+}
+
+impl QueryCondition {
+    pub(crate) fn new(condition_async: QueryConditionAsync) -> Self {
+        Self {
+            read_condition: ReadCondition::new(condition_async.deref().clone()),
+            condition_async,
+        }
+    }
+
+    pub(crate) fn condition_async(&self) -> &QueryConditionAsync {
+        &self.condition_async
+    }
+}
+
+impl Deref for QueryCondition {
+    type Target = ReadCondition;
+
+    fn deref(&self) -> &Self::Target {
+        &self.read_condition
+    }
+}
+
+impl QueryCondition {
+    /// This operation returns the *query_expression* associated with the [`QueryCondition`]. That is, the expression specified
+    /// when the [`QueryCondition`] was created.
+    #[tracing::instrument(skip(self))]
+    pub fn get_query_expression(&self) -> String {
+        self.condition_async.get_query_expression()
+    }
+
+    /// This operation returns the *query_parameters* associated with the [`QueryCondition`]. That is, the parameters specified
+    /// on the last successful call to [`QueryCondition::set_query_parameters`], or if it was never called, the arguments
+    /// specified when the [`QueryCondition`] was created.
+    #[tracing::instrument(skip(self))]
+    pub fn get_query_parameters(&self) -> DdsResult<Vec<String>> {
+        block_on(self.condition_async.get_query_parameters())
+    }
+
+    /// This operation changes the *query_parameters* associated with the [`QueryCondition`]. If the *query_expression*
+    /// references a parameter which is not part of the new list the operation returns
+    /// [`DdsError::BadParameter`](crate::infrastructure::error::DdsError) and the parameters are not modified.
+    #[tracing::instrument(skip(self))]
+    pub fn set_query_parameters(&self, query_parameters: &[String]) -> DdsResult<()> {
+        block_on(self.condition_async.set_query_parameters(query_parameters))
+    }
+}
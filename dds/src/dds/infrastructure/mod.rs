@@ -13,6 +13,10 @@ pub mod qos;
 /// Classes related to the qos policies.
 pub mod qos_policy;
 
+/// Classes related to the qos profiles loaded from XML files.
+#[cfg(feature = "xml-profiles")]
+pub mod qos_profile;
+
 /// Classes related to communication statuses.
 pub mod status;
 
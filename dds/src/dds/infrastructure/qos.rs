@@ -6,12 +6,13 @@ use crate::infrastructure::{
 use super::{
     qos_policy::{
         DataRepresentationQosPolicy, DeadlineQosPolicy, DestinationOrderQosPolicy,
-        DurabilityQosPolicy, EntityFactoryQosPolicy, GroupDataQosPolicy, HistoryQosPolicy,
-        HistoryQosPolicyKind, LatencyBudgetQosPolicy, LifespanQosPolicy, LivelinessQosPolicy,
-        OwnershipQosPolicy, OwnershipStrengthQosPolicy, PartitionQosPolicy, PresentationQosPolicy,
-        ReaderDataLifecycleQosPolicy, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
-        ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy, TopicDataQosPolicy,
-        TransportPriorityQosPolicy, UserDataQosPolicy, WriterDataLifecycleQosPolicy,
+        DurabilityQosPolicy, EntityFactoryQosPolicy, FlowControllerQosPolicy, GroupDataQosPolicy,
+        HistoryQosPolicy, HistoryQosPolicyKind, LatencyBudgetQosPolicy, LifespanQosPolicy,
+        LivelinessQosPolicy, OwnershipQosPolicy, OwnershipStrengthQosPolicy, PartitionQosPolicy,
+        PresentationQosPolicy, ReaderDataLifecycleQosPolicy, ReliabilityQosPolicy,
+        ReliabilityQosPolicyKind, ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy,
+        TimeSourceQosPolicy, TopicDataQosPolicy, TransportPriorityQosPolicy, UserDataQosPolicy,
+        WriterDataLifecycleQosPolicy,
     },
     time::DurationKind,
 };
@@ -39,6 +40,8 @@ pub struct DomainParticipantQos {
     pub user_data: UserDataQosPolicy,
     /// Value of the entity factory QoS policy.
     pub entity_factory: EntityFactoryQosPolicy,
+    /// Value of the time source QoS policy.
+    pub time_source: TimeSourceQosPolicy,
 }
 
 /// QoS policies applicable to the [`Publisher`](crate::publication::publisher::Publisher)
@@ -87,6 +90,8 @@ pub struct DataWriterQos {
     pub writer_data_lifecycle: WriterDataLifecycleQosPolicy,
     /// Value of the data representation QoS policy.
     pub representation: DataRepresentationQosPolicy,
+    /// Value of the flow controller QoS policy.
+    pub flow_controller: FlowControllerQosPolicy,
 }
 
 impl Default for DataWriterQos {
@@ -113,6 +118,7 @@ impl Default for DataWriterQos {
             transport_priority: TransportPriorityQosPolicy::default(),
             writer_data_lifecycle: WriterDataLifecycleQosPolicy::default(),
             representation: DataRepresentationQosPolicy::default(),
+            flow_controller: FlowControllerQosPolicy::default(),
         }
     }
 }
@@ -124,6 +130,11 @@ impl DataWriterQos {
             return Err(DdsError::InconsistentPolicy);
         }
 
+        // A flow controller without any throughput would never send data
+        if self.flow_controller.max_bytes_per_second == Some(0) {
+            return Err(DdsError::InconsistentPolicy);
+        }
+
         // The setting of RESOURCE_LIMITS max_samples must be consistent with the max_samples_per_instance. For these two
         // values to be consistent they must verify that *max_samples >= max_samples_per_instanc
         if self.resource_limits.max_samples < self.resource_limits.max_samples_per_instance {
@@ -134,7 +145,8 @@ impl DataWriterQos {
         // QoS to be consistent, they must verify that *depth <= max_samples_per_instance.*
         match self.history.kind {
             HistoryQosPolicyKind::KeepLast(depth) => {
-                if depth as usize > self.resource_limits.max_samples_per_instance {
+                // A KEEP_LAST history must keep at least one sample
+                if depth == 0 || depth as usize > self.resource_limits.max_samples_per_instance {
                     Err(DdsError::InconsistentPolicy)
                 } else {
                     Ok(())
@@ -152,6 +164,8 @@ impl DataWriterQos {
             || self.history != other.history
             || self.resource_limits != other.resource_limits
             || self.ownership != other.ownership
+            || self.flow_controller != other.flow_controller
+            || self.representation != other.representation
         {
             Err(DdsError::ImmutablePolicy)
         } else {
@@ -252,7 +266,8 @@ impl DataReaderQos {
         // QoS to be consistent, they must verify that *depth <= max_samples_per_instance.*
         match self.history.kind {
             HistoryQosPolicyKind::KeepLast(depth) => {
-                if depth as usize > self.resource_limits.max_samples_per_instance {
+                // A KEEP_LAST history must keep at least one sample
+                if depth == 0 || depth as usize > self.resource_limits.max_samples_per_instance {
                     return Err(DdsError::InconsistentPolicy);
                 }
             }
@@ -276,6 +291,7 @@ impl DataReaderQos {
             || self.history != other.history
             || self.resource_limits != other.resource_limits
             || self.ownership != other.ownership
+            || self.representation != other.representation
         {
             Err(DdsError::ImmutablePolicy)
         } else {
@@ -353,7 +369,8 @@ impl TopicQos {
         // QoS to be consistent, they must verify that *depth <= max_samples_per_instance.*
         match self.history.kind {
             HistoryQosPolicyKind::KeepLast(depth) => {
-                if depth as usize > self.resource_limits.max_samples_per_instance {
+                // A KEEP_LAST history must keep at least one sample
+                if depth == 0 || depth as usize > self.resource_limits.max_samples_per_instance {
                     Err(DdsError::InconsistentPolicy)
                 } else {
                     Ok(())
@@ -414,6 +431,16 @@ mod tests {
             .is_consistent(),
             Err(DdsError::InconsistentPolicy)
         );
+        assert_eq!(
+            DataWriterQos {
+                history: HistoryQosPolicy {
+                    kind: HistoryQosPolicyKind::KeepLast(0),
+                },
+                ..Default::default()
+            }
+            .is_consistent(),
+            Err(DdsError::InconsistentPolicy)
+        );
     }
 
     #[test]
@@ -445,6 +472,29 @@ mod tests {
             .is_consistent(),
             Err(DdsError::InconsistentPolicy)
         );
+        assert_eq!(
+            DataReaderQos {
+                history: HistoryQosPolicy {
+                    kind: HistoryQosPolicyKind::KeepLast(0),
+                },
+                ..Default::default()
+            }
+            .is_consistent(),
+            Err(DdsError::InconsistentPolicy)
+        );
+        assert_eq!(
+            DataReaderQos {
+                deadline: DeadlineQosPolicy {
+                    period: DurationKind::Finite(Duration::new(1, 0)),
+                },
+                time_based_filter: TimeBasedFilterQosPolicy {
+                    minimum_separation: DurationKind::Finite(Duration::new(2, 0)),
+                },
+                ..Default::default()
+            }
+            .is_consistent(),
+            Err(DdsError::InconsistentPolicy)
+        );
     }
 
     #[test]
@@ -476,5 +526,15 @@ mod tests {
             .is_consistent(),
             Err(DdsError::InconsistentPolicy)
         );
+        assert_eq!(
+            TopicQos {
+                history: HistoryQosPolicy {
+                    kind: HistoryQosPolicyKind::KeepLast(0),
+                },
+                ..Default::default()
+            }
+            .is_consistent(),
+            Err(DdsError::InconsistentPolicy)
+        );
     }
 }
@@ -1,4 +1,5 @@
 use core::cmp::Ordering;
+use std::sync::{atomic::AtomicU64, Arc};
 
 use crate::{
     infrastructure::time::{Duration, DurationKind},
@@ -140,6 +141,7 @@ const TRANSPORTPRIORITY_QOS_POLICY_NAME: &str = "TransportPriority";
 const GROUPDATA_QOS_POLICY_NAME: &str = "GroupData";
 const LIFESPAN_QOS_POLICY_NAME: &str = "Lifespan";
 const DATA_REPRESENTATION_QOS_POLICY_NAME: &str = "DataRepresentation";
+const FLOWCONTROLLER_QOS_POLICY_NAME: &str = "FlowController";
 
 /// QosPolicy Id representing an invalid QoS policy
 pub const INVALID_QOS_POLICY_ID: QosPolicyId = 0;
@@ -402,6 +404,8 @@ pub enum PresentationQosPolicyAccessScopeKind {
     Instance,
     /// Access scope per topic
     Topic,
+    /// Access scope per group
+    Group,
 }
 
 impl CdrSerialize for PresentationQosPolicyAccessScopeKind {
@@ -409,6 +413,7 @@ impl CdrSerialize for PresentationQosPolicyAccessScopeKind {
         match self {
             PresentationQosPolicyAccessScopeKind::Instance => 0u8,
             PresentationQosPolicyAccessScopeKind::Topic => 1,
+            PresentationQosPolicyAccessScopeKind::Group => 2,
         }
         .serialize(serializer)
     }
@@ -420,6 +425,7 @@ impl<'de> CdrDeserialize<'de> for PresentationQosPolicyAccessScopeKind {
         match value {
             0 => Ok(PresentationQosPolicyAccessScopeKind::Instance),
             1 => Ok(PresentationQosPolicyAccessScopeKind::Topic),
+            2 => Ok(PresentationQosPolicyAccessScopeKind::Group),
             _ => Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
                 format!(
@@ -437,10 +443,17 @@ impl PartialOrd for PresentationQosPolicyAccessScopeKind {
             PresentationQosPolicyAccessScopeKind::Instance => match other {
                 PresentationQosPolicyAccessScopeKind::Instance => Some(Ordering::Equal),
                 PresentationQosPolicyAccessScopeKind::Topic => Some(Ordering::Less),
+                PresentationQosPolicyAccessScopeKind::Group => Some(Ordering::Less),
             },
             PresentationQosPolicyAccessScopeKind::Topic => match other {
                 PresentationQosPolicyAccessScopeKind::Instance => Some(Ordering::Greater),
                 PresentationQosPolicyAccessScopeKind::Topic => Some(Ordering::Equal),
+                PresentationQosPolicyAccessScopeKind::Group => Some(Ordering::Less),
+            },
+            PresentationQosPolicyAccessScopeKind::Group => match other {
+                PresentationQosPolicyAccessScopeKind::Instance => Some(Ordering::Greater),
+                PresentationQosPolicyAccessScopeKind::Topic => Some(Ordering::Greater),
+                PresentationQosPolicyAccessScopeKind::Group => Some(Ordering::Equal),
             },
         }
     }
@@ -456,22 +469,28 @@ impl PartialOrd for PresentationQosPolicyAccessScopeKind {
 /// If [`PresentationQosPolicy::coherent_access`] is set, then the [`PresentationQosPolicy::access_scope`] controls the maximum extent of coherent changes.
 /// The behavior is as follows:
 /// - If access_scope is set to INSTANCE, the use of begin_coherent_change and end_coherent_change has no effect on
-/// how the subscriber can access the data because with the scope limited to each instance, changes to separate instances
-/// are considered independent and thus cannot be grouped by a coherent change.
+///   how the subscriber can access the data because with the scope limited to each instance, changes to separate instances
+///   are considered independent and thus cannot be grouped by a coherent change.
 /// - If access_scope is set to TOPIC, then coherent changes (indicated by their enclosure within calls to
-/// begin_coherent_change and end_coherent_change) will be made available as such to each remote DataReader
-/// independently. That is, changes made to instances within each individual DataWriter will be available as coherent with
-/// respect to other changes to instances in that same DataWriter, but will not be grouped with changes made to instances
-/// belonging to a different DataWriter.
+///   begin_coherent_change and end_coherent_change) will be made available as such to each remote DataReader
+///   independently. That is, changes made to instances within each individual DataWriter will be available as coherent with
+///   respect to other changes to instances in that same DataWriter, but will not be grouped with changes made to instances
+///   belonging to a different DataWriter.
+/// - If access_scope is set to GROUP, then coherent changes made to instances through DataWriter entities attached to a
+///   common Publisher are made available as a unit to remote subscribers.
+///
 /// If ordered_access is set, then the access_scope controls the maximum extent for which order will be preserved by the Service.
 /// - If access_scope is set to INSTANCE (the lowest level), then changes to each instance are considered unordered relative
-/// to changes to any other instance. That means that changes (creations, deletions, modifications) made to two instances
-/// are not necessarily seen in the order they occur. This is the case even if it is the same application thread making the
-/// changes using the same DataWriter.
+///   to changes to any other instance. That means that changes (creations, deletions, modifications) made to two instances
+///   are not necessarily seen in the order they occur. This is the case even if it is the same application thread making the
+///   changes using the same DataWriter.
 /// - If access_scope is set to TOPIC, changes (creations, deletions, modifications) made by a single DataWriter are made
-/// available to subscribers in the same order they occur. Changes made to instances through different DataWriter entities
-/// are not necessarily seen in the order they occur. This is the case, even if the changes are made by a single application
-/// thread using DataWriter objects attached to the same Publisher.
+///   available to subscribers in the same order they occur. Changes made to instances through different DataWriter entities
+///   are not necessarily seen in the order they occur. This is the case, even if the changes are made by a single application
+///   thread using DataWriter objects attached to the same Publisher.
+/// - If access_scope is set to GROUP, changes made to instances via DataWriter entities attached to the same Publisher
+///   object are made available to subscribers in the same order they occur.
+///
 /// Note that this QoS policy controls the scope at which related changes are made available to the subscriber. This means the
 /// subscriber can access the changes in a coherent manner and in the proper order; however, it does not necessarily imply that the
 /// Subscriber will indeed access the changes in the correct order. For that to occur, the application at the subscriber end must use
@@ -661,7 +680,8 @@ impl Default for OwnershipQosPolicy {
 /// DataWriter.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Clone, CdrSerialize, CdrDeserialize, Default)]
 pub struct OwnershipStrengthQosPolicy {
-    value: i32,
+    /// Ownership strength value
+    pub value: i32,
 }
 
 impl QosPolicy for OwnershipStrengthQosPolicy {
@@ -1195,6 +1215,48 @@ impl Default for EntityFactoryQosPolicy {
     }
 }
 
+/// This policy selects the source of the time returned by
+/// [`DomainParticipant::get_current_time`](crate::domain::domain_participant::DomainParticipant::get_current_time).
+/// This time is also used by the entities of the participant, for example as the source timestamp of the written samples
+/// and as the reception timestamp of the received samples.
+///
+/// The setting [`TimeSourceQosPolicy::SystemTime`] uses the clock of the system. The setting [`TimeSourceQosPolicy::Logical`] uses a logical time
+/// controlled by the application, which is useful for simulation scenarios. The logical time is the number of nanoseconds since
+/// the Unix epoch stored in the shared counter and it only advances when the application changes the value of the counter.
+/// The default setting is [`TimeSourceQosPolicy::SystemTime`].
+///
+/// The deadline, liveliness and lifespan timers of the entities of the participant also measure their periods with the
+/// selected time source. When the logical time is used these timers check the value of the counter periodically, so they
+/// expire shortly after the counter has been advanced past their period. The periods of the discovery and reliability
+/// protocols, such as the participant announcements and leases and the heartbeats, are always measured with the clock
+/// of the system.
+///
+/// Two [`TimeSourceQosPolicy::Logical`] values are only equal when they share the same counter, that is when the [`Arc`]
+/// points to the same allocation. Two participants using distinct counters therefore have different QoS even if the
+/// counters hold the same value.
+#[derive(Debug, Default, Clone)]
+pub enum TimeSourceQosPolicy {
+    /// Time given by the clock of the system
+    #[default]
+    SystemTime,
+    /// Logical time in nanoseconds since the Unix epoch
+    Logical(Arc<AtomicU64>),
+}
+
+impl PartialEq for TimeSourceQosPolicy {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (TimeSourceQosPolicy::SystemTime, TimeSourceQosPolicy::SystemTime) => true,
+            (TimeSourceQosPolicy::Logical(l1), TimeSourceQosPolicy::Logical(l2)) => {
+                Arc::ptr_eq(l1, l2)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for TimeSourceQosPolicy {}
+
 /// This policy controls the behavior of the [`DataWriter`](crate::publication::data_writer::DataWriter) with regards to the lifecycle
 /// of the data-instances it manages, that is, the data-instances that have been either explicitly registered with the
 /// [`DataWriter::register`](crate::publication::data_writer::DataWriter) or implicitly by using [`DataWriter::write`](crate::publication::data_writer::DataWriter)
@@ -1299,6 +1361,23 @@ impl QosPolicy for DataRepresentationQosPolicy {
     }
 }
 
+/// This policy is a Dust DDS extension which limits the throughput of a [`DataWriter`](crate::publication::data_writer::DataWriter).
+/// The data sent by the [`DataWriter`](crate::publication::data_writer::DataWriter) to all its matched readers is rate-limited
+/// to [`FlowControllerQosPolicy::max_bytes_per_second`]. Changes which exceed the rate are kept in the
+/// writer history and sent as soon as the rate allows it. This policy is not propagated through discovery.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct FlowControllerQosPolicy {
+    /// Maximum number of data bytes sent per second. [`None`] means the throughput is unlimited.
+    /// A value of 0 is not consistent.
+    pub max_bytes_per_second: Option<u64>,
+}
+
+impl QosPolicy for FlowControllerQosPolicy {
+    fn name(&self) -> &str {
+        FLOWCONTROLLER_QOS_POLICY_NAME
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
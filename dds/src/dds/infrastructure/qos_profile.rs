@@ -0,0 +1,951 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use super::{
+    error::{DdsError, DdsResult},
+    qos::{
+        DataReaderQos, DataWriterQos, DomainParticipantQos, PublisherQos, SubscriberQos, TopicQos,
+    },
+    qos_policy::{
+        DataRepresentationQosPolicy, DeadlineQosPolicy, DestinationOrderQosPolicy,
+        DestinationOrderQosPolicyKind, DurabilityQosPolicy, DurabilityQosPolicyKind,
+        EntityFactoryQosPolicy, GroupDataQosPolicy, HistoryQosPolicy, HistoryQosPolicyKind,
+        LatencyBudgetQosPolicy, Length, LifespanQosPolicy, LivelinessQosPolicy,
+        LivelinessQosPolicyKind, OwnershipQosPolicy, OwnershipQosPolicyKind,
+        OwnershipStrengthQosPolicy, PartitionQosPolicy, PresentationQosPolicy,
+        PresentationQosPolicyAccessScopeKind, ReaderDataLifecycleQosPolicy, ReliabilityQosPolicy,
+        ReliabilityQosPolicyKind, ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy,
+        TopicDataQosPolicy, TransportPriorityQosPolicy, UserDataQosPolicy,
+        WriterDataLifecycleQosPolicy, XCDR2_DATA_REPRESENTATION, XCDR_DATA_REPRESENTATION,
+        XML_DATA_REPRESENTATION,
+    },
+    time::{Duration, DurationKind},
+};
+
+/// Set of QoS defined by a `qos_profile` element of a DDS XML QoS profile file.
+/// The QoS of the entity kinds which are not part of the profile are [`None`]. The policies
+/// which are not given in the profile keep the default value of the corresponding QoS.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct QosProfile {
+    /// QoS of the `domain_participant_qos` element.
+    pub participant_qos: Option<DomainParticipantQos>,
+    /// QoS of the `publisher_qos` element.
+    pub publisher_qos: Option<PublisherQos>,
+    /// QoS of the `subscriber_qos` element.
+    pub subscriber_qos: Option<SubscriberQos>,
+    /// QoS of the `topic_qos` element.
+    pub topic_qos: Option<TopicQos>,
+    /// QoS of the `datawriter_qos` element.
+    pub datawriter_qos: Option<DataWriterQos>,
+    /// QoS of the `datareader_qos` element.
+    pub datareader_qos: Option<DataReaderQos>,
+}
+
+/// Parse the content of a DDS XML QoS profile file into the QoS profiles it contains. The
+/// profiles are identified by their fully qualified name, i.e. `<library name>::<profile name>`.
+pub(crate) fn parse_qos_profiles(xml: &str) -> DdsResult<HashMap<String, QosProfile>> {
+    let dds: DdsXml = quick_xml::de::from_str(xml)
+        .map_err(|e| DdsError::Error(format!("Failed to parse QoS profiles: {e}")))?;
+
+    let mut qos_profiles = HashMap::new();
+    for qos_library in dds.qos_library {
+        for qos_profile in qos_library.qos_profile {
+            let profile_name = format!("{}::{}", qos_library.name, qos_profile.name);
+            let qos_profile = qos_profile.into_qos_profile().map_err(|e| match e {
+                DdsError::Error(message) => {
+                    DdsError::Error(format!("Invalid QoS profile {profile_name}: {message}"))
+                }
+                e => e,
+            })?;
+            qos_profiles.insert(profile_name, qos_profile);
+        }
+    }
+    Ok(qos_profiles)
+}
+
+fn parse_enumerator<T: Copy>(value: &str, enumerators: &[(&str, T)]) -> DdsResult<T> {
+    enumerators
+        .iter()
+        .find(|(name, _)| *name == value.trim())
+        .map(|(_, kind)| *kind)
+        .ok_or_else(|| DdsError::Error(format!("Unknown enumerator {value}")))
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> DdsResult<T> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| DdsError::Error(format!("Invalid number {value}")))
+}
+
+/// Policies present in the XML overwrite the corresponding value of the QoS and the missing
+/// ones keep their current value.
+trait ApplyXml<T> {
+    fn apply(self, policy: &mut T) -> DdsResult<()>;
+}
+
+impl<T, X: ApplyXml<T>> ApplyXml<T> for Option<X> {
+    fn apply(self, policy: &mut T) -> DdsResult<()> {
+        match self {
+            Some(xml) => xml.apply(policy),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct DdsXml {
+    #[serde(default)]
+    qos_library: Vec<QosLibraryXml>,
+}
+
+#[derive(Deserialize)]
+struct QosLibraryXml {
+    #[serde(rename = "@name")]
+    name: String,
+    #[serde(default)]
+    qos_profile: Vec<QosProfileXml>,
+}
+
+#[derive(Deserialize)]
+struct QosProfileXml {
+    #[serde(rename = "@name")]
+    name: String,
+    domain_participant_qos: Option<DomainParticipantQosXml>,
+    publisher_qos: Option<PublisherQosXml>,
+    subscriber_qos: Option<SubscriberQosXml>,
+    topic_qos: Option<TopicQosXml>,
+    datawriter_qos: Option<DataWriterQosXml>,
+    datareader_qos: Option<DataReaderQosXml>,
+}
+
+impl QosProfileXml {
+    fn into_qos_profile(self) -> DdsResult<QosProfile> {
+        fn into_qos<T: Default, X: ApplyXml<T>>(xml: Option<X>) -> DdsResult<Option<T>> {
+            xml.map(|xml| {
+                let mut qos = T::default();
+                xml.apply(&mut qos)?;
+                Ok(qos)
+            })
+            .transpose()
+        }
+
+        Ok(QosProfile {
+            participant_qos: into_qos(self.domain_participant_qos)?,
+            publisher_qos: into_qos(self.publisher_qos)?,
+            subscriber_qos: into_qos(self.subscriber_qos)?,
+            topic_qos: into_qos(self.topic_qos)?,
+            datawriter_qos: into_qos(self.datawriter_qos)?,
+            datareader_qos: into_qos(self.datareader_qos)?,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct DomainParticipantQosXml {
+    user_data: Option<OctetSeqPolicyXml>,
+    entity_factory: Option<EntityFactoryXml>,
+}
+
+impl ApplyXml<DomainParticipantQos> for DomainParticipantQosXml {
+    fn apply(self, qos: &mut DomainParticipantQos) -> DdsResult<()> {
+        self.user_data.apply(&mut qos.user_data)?;
+        self.entity_factory.apply(&mut qos.entity_factory)
+    }
+}
+
+#[derive(Deserialize)]
+struct PublisherQosXml {
+    presentation: Option<PresentationXml>,
+    partition: Option<PartitionXml>,
+    group_data: Option<OctetSeqPolicyXml>,
+    entity_factory: Option<EntityFactoryXml>,
+}
+
+impl ApplyXml<PublisherQos> for PublisherQosXml {
+    fn apply(self, qos: &mut PublisherQos) -> DdsResult<()> {
+        self.presentation.apply(&mut qos.presentation)?;
+        self.partition.apply(&mut qos.partition)?;
+        self.group_data.apply(&mut qos.group_data)?;
+        self.entity_factory.apply(&mut qos.entity_factory)
+    }
+}
+
+#[derive(Deserialize)]
+struct SubscriberQosXml {
+    presentation: Option<PresentationXml>,
+    partition: Option<PartitionXml>,
+    group_data: Option<OctetSeqPolicyXml>,
+    entity_factory: Option<EntityFactoryXml>,
+}
+
+impl ApplyXml<SubscriberQos> for SubscriberQosXml {
+    fn apply(self, qos: &mut SubscriberQos) -> DdsResult<()> {
+        self.presentation.apply(&mut qos.presentation)?;
+        self.partition.apply(&mut qos.partition)?;
+        self.group_data.apply(&mut qos.group_data)?;
+        self.entity_factory.apply(&mut qos.entity_factory)
+    }
+}
+
+#[derive(Deserialize)]
+struct TopicQosXml {
+    topic_data: Option<OctetSeqPolicyXml>,
+    durability: Option<DurabilityXml>,
+    deadline: Option<DeadlineXml>,
+    latency_budget: Option<LatencyBudgetXml>,
+    liveliness: Option<LivelinessXml>,
+    reliability: Option<ReliabilityXml>,
+    destination_order: Option<DestinationOrderXml>,
+    history: Option<HistoryXml>,
+    resource_limits: Option<ResourceLimitsXml>,
+    transport_priority: Option<TransportPriorityXml>,
+    lifespan: Option<LifespanXml>,
+    ownership: Option<OwnershipXml>,
+    representation: Option<RepresentationXml>,
+}
+
+impl ApplyXml<TopicQos> for TopicQosXml {
+    fn apply(self, qos: &mut TopicQos) -> DdsResult<()> {
+        self.topic_data.apply(&mut qos.topic_data)?;
+        self.durability.apply(&mut qos.durability)?;
+        self.deadline.apply(&mut qos.deadline)?;
+        self.latency_budget.apply(&mut qos.latency_budget)?;
+        self.liveliness.apply(&mut qos.liveliness)?;
+        self.reliability.apply(&mut qos.reliability)?;
+        self.destination_order.apply(&mut qos.destination_order)?;
+        self.history.apply(&mut qos.history)?;
+        self.resource_limits.apply(&mut qos.resource_limits)?;
+        self.transport_priority.apply(&mut qos.transport_priority)?;
+        self.lifespan.apply(&mut qos.lifespan)?;
+        self.ownership.apply(&mut qos.ownership)?;
+        self.representation.apply(&mut qos.representation)
+    }
+}
+
+#[derive(Deserialize)]
+struct DataWriterQosXml {
+    durability: Option<DurabilityXml>,
+    deadline: Option<DeadlineXml>,
+    latency_budget: Option<LatencyBudgetXml>,
+    liveliness: Option<LivelinessXml>,
+    reliability: Option<ReliabilityXml>,
+    destination_order: Option<DestinationOrderXml>,
+    history: Option<HistoryXml>,
+    resource_limits: Option<ResourceLimitsXml>,
+    transport_priority: Option<TransportPriorityXml>,
+    lifespan: Option<LifespanXml>,
+    user_data: Option<OctetSeqPolicyXml>,
+    ownership: Option<OwnershipXml>,
+    ownership_strength: Option<OwnershipStrengthXml>,
+    writer_data_lifecycle: Option<WriterDataLifecycleXml>,
+    representation: Option<RepresentationXml>,
+}
+
+impl ApplyXml<DataWriterQos> for DataWriterQosXml {
+    fn apply(self, qos: &mut DataWriterQos) -> DdsResult<()> {
+        self.durability.apply(&mut qos.durability)?;
+        self.deadline.apply(&mut qos.deadline)?;
+        self.latency_budget.apply(&mut qos.latency_budget)?;
+        self.liveliness.apply(&mut qos.liveliness)?;
+        self.reliability.apply(&mut qos.reliability)?;
+        self.destination_order.apply(&mut qos.destination_order)?;
+        self.history.apply(&mut qos.history)?;
+        self.resource_limits.apply(&mut qos.resource_limits)?;
+        self.transport_priority.apply(&mut qos.transport_priority)?;
+        self.lifespan.apply(&mut qos.lifespan)?;
+        self.user_data.apply(&mut qos.user_data)?;
+        self.ownership.apply(&mut qos.ownership)?;
+        self.ownership_strength.apply(&mut qos.ownership_strength)?;
+        self.writer_data_lifecycle
+            .apply(&mut qos.writer_data_lifecycle)?;
+        self.representation.apply(&mut qos.representation)
+    }
+}
+
+#[derive(Deserialize)]
+struct DataReaderQosXml {
+    durability: Option<DurabilityXml>,
+    deadline: Option<DeadlineXml>,
+    latency_budget: Option<LatencyBudgetXml>,
+    liveliness: Option<LivelinessXml>,
+    reliability: Option<ReliabilityXml>,
+    destination_order: Option<DestinationOrderXml>,
+    history: Option<HistoryXml>,
+    resource_limits: Option<ResourceLimitsXml>,
+    user_data: Option<OctetSeqPolicyXml>,
+    ownership: Option<OwnershipXml>,
+    time_based_filter: Option<TimeBasedFilterXml>,
+    reader_data_lifecycle: Option<ReaderDataLifecycleXml>,
+    representation: Option<RepresentationXml>,
+}
+
+impl ApplyXml<DataReaderQos> for DataReaderQosXml {
+    fn apply(self, qos: &mut DataReaderQos) -> DdsResult<()> {
+        self.durability.apply(&mut qos.durability)?;
+        self.deadline.apply(&mut qos.deadline)?;
+        self.latency_budget.apply(&mut qos.latency_budget)?;
+        self.liveliness.apply(&mut qos.liveliness)?;
+        self.reliability.apply(&mut qos.reliability)?;
+        self.destination_order.apply(&mut qos.destination_order)?;
+        self.history.apply(&mut qos.history)?;
+        self.resource_limits.apply(&mut qos.resource_limits)?;
+        self.user_data.apply(&mut qos.user_data)?;
+        self.ownership.apply(&mut qos.ownership)?;
+        self.time_based_filter.apply(&mut qos.time_based_filter)?;
+        self.reader_data_lifecycle
+            .apply(&mut qos.reader_data_lifecycle)?;
+        self.representation.apply(&mut qos.representation)
+    }
+}
+
+#[derive(Deserialize)]
+struct DurationXml {
+    sec: Option<String>,
+    nanosec: Option<String>,
+}
+
+impl ApplyXml<DurationKind> for DurationXml {
+    fn apply(self, duration: &mut DurationKind) -> DdsResult<()> {
+        let is_infinite = self
+            .sec
+            .as_deref()
+            .is_some_and(|s| s.trim() == "DURATION_INFINITE_SEC")
+            || self
+                .nanosec
+                .as_deref()
+                .is_some_and(|n| n.trim() == "DURATION_INFINITE_NSEC");
+        if is_infinite {
+            *duration = DurationKind::Infinite;
+            return Ok(());
+        }
+
+        let (mut sec, mut nanosec) = match duration {
+            DurationKind::Finite(d) => (d.sec(), d.nanosec()),
+            DurationKind::Infinite => (0, 0),
+        };
+        if let Some(s) = &self.sec {
+            sec = parse_number(s)?;
+        }
+        if let Some(n) = &self.nanosec {
+            nanosec = parse_number(n)?;
+        }
+        *duration = DurationKind::Finite(Duration::new(sec, nanosec));
+        Ok(())
+    }
+}
+
+fn apply_length(value: &str, length: &mut Length) -> DdsResult<()> {
+    *length = if value.trim() == "LENGTH_UNLIMITED" {
+        Length::Unlimited
+    } else {
+        Length::Limited(parse_number(value)?)
+    };
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct OctetSeqXml {
+    #[serde(default)]
+    element: Vec<u8>,
+}
+
+#[derive(Deserialize)]
+struct OctetSeqPolicyXml {
+    value: Option<OctetSeqXml>,
+}
+
+impl ApplyXml<UserDataQosPolicy> for OctetSeqPolicyXml {
+    fn apply(self, policy: &mut UserDataQosPolicy) -> DdsResult<()> {
+        if let Some(value) = self.value {
+            policy.value = value.element;
+        }
+        Ok(())
+    }
+}
+
+impl ApplyXml<TopicDataQosPolicy> for OctetSeqPolicyXml {
+    fn apply(self, policy: &mut TopicDataQosPolicy) -> DdsResult<()> {
+        if let Some(value) = self.value {
+            policy.value = value.element;
+        }
+        Ok(())
+    }
+}
+
+impl ApplyXml<GroupDataQosPolicy> for OctetSeqPolicyXml {
+    fn apply(self, policy: &mut GroupDataQosPolicy) -> DdsResult<()> {
+        if let Some(value) = self.value {
+            policy.value = value.element;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct StringSeqXml {
+    #[serde(default)]
+    element: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct EntityFactoryXml {
+    autoenable_created_entities: Option<bool>,
+}
+
+impl ApplyXml<EntityFactoryQosPolicy> for EntityFactoryXml {
+    fn apply(self, policy: &mut EntityFactoryQosPolicy) -> DdsResult<()> {
+        if let Some(autoenable_created_entities) = self.autoenable_created_entities {
+            policy.autoenable_created_entities = autoenable_created_entities;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct PresentationXml {
+    access_scope: Option<String>,
+    coherent_access: Option<bool>,
+    ordered_access: Option<bool>,
+}
+
+impl ApplyXml<PresentationQosPolicy> for PresentationXml {
+    fn apply(self, policy: &mut PresentationQosPolicy) -> DdsResult<()> {
+        if let Some(access_scope) = &self.access_scope {
+            policy.access_scope = parse_enumerator(
+                access_scope,
+                &[
+                    (
+                        "INSTANCE_PRESENTATION_QOS",
+                        PresentationQosPolicyAccessScopeKind::Instance,
+                    ),
+                    (
+                        "TOPIC_PRESENTATION_QOS",
+                        PresentationQosPolicyAccessScopeKind::Topic,
+                    ),
+                    (
+                        "GROUP_PRESENTATION_QOS",
+                        PresentationQosPolicyAccessScopeKind::Group,
+                    ),
+                ],
+            )?;
+        }
+        if let Some(coherent_access) = self.coherent_access {
+            policy.coherent_access = coherent_access;
+        }
+        if let Some(ordered_access) = self.ordered_access {
+            policy.ordered_access = ordered_access;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct PartitionXml {
+    name: Option<StringSeqXml>,
+}
+
+impl ApplyXml<PartitionQosPolicy> for PartitionXml {
+    fn apply(self, policy: &mut PartitionQosPolicy) -> DdsResult<()> {
+        if let Some(name) = self.name {
+            policy.name = name.element;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct DurabilityXml {
+    kind: Option<String>,
+}
+
+impl ApplyXml<DurabilityQosPolicy> for DurabilityXml {
+    fn apply(self, policy: &mut DurabilityQosPolicy) -> DdsResult<()> {
+        if let Some(kind) = &self.kind {
+            policy.kind = parse_enumerator(
+                kind,
+                &[
+                    ("VOLATILE_DURABILITY_QOS", DurabilityQosPolicyKind::Volatile),
+                    (
+                        "TRANSIENT_LOCAL_DURABILITY_QOS",
+                        DurabilityQosPolicyKind::TransientLocal,
+                    ),
+                    (
+                        "TRANSIENT_DURABILITY_QOS",
+                        DurabilityQosPolicyKind::Transient,
+                    ),
+                    (
+                        "PERSISTENT_DURABILITY_QOS",
+                        DurabilityQosPolicyKind::Persistent,
+                    ),
+                ],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct DeadlineXml {
+    period: Option<DurationXml>,
+}
+
+impl ApplyXml<DeadlineQosPolicy> for DeadlineXml {
+    fn apply(self, policy: &mut DeadlineQosPolicy) -> DdsResult<()> {
+        self.period.apply(&mut policy.period)
+    }
+}
+
+#[derive(Deserialize)]
+struct LatencyBudgetXml {
+    duration: Option<DurationXml>,
+}
+
+impl ApplyXml<LatencyBudgetQosPolicy> for LatencyBudgetXml {
+    fn apply(self, policy: &mut LatencyBudgetQosPolicy) -> DdsResult<()> {
+        self.duration.apply(&mut policy.duration)
+    }
+}
+
+#[derive(Deserialize)]
+struct LivelinessXml {
+    kind: Option<String>,
+    lease_duration: Option<DurationXml>,
+}
+
+impl ApplyXml<LivelinessQosPolicy> for LivelinessXml {
+    fn apply(self, policy: &mut LivelinessQosPolicy) -> DdsResult<()> {
+        if let Some(kind) = &self.kind {
+            policy.kind = parse_enumerator(
+                kind,
+                &[
+                    (
+                        "AUTOMATIC_LIVELINESS_QOS",
+                        LivelinessQosPolicyKind::Automatic,
+                    ),
+                    (
+                        "MANUAL_BY_PARTICIPANT_LIVELINESS_QOS",
+                        LivelinessQosPolicyKind::ManualByParticipant,
+                    ),
+                    (
+                        "MANUAL_BY_TOPIC_LIVELINESS_QOS",
+                        LivelinessQosPolicyKind::ManualByTopic,
+                    ),
+                ],
+            )?;
+        }
+        self.lease_duration.apply(&mut policy.lease_duration)
+    }
+}
+
+#[derive(Deserialize)]
+struct ReliabilityXml {
+    kind: Option<String>,
+    max_blocking_time: Option<DurationXml>,
+}
+
+impl ApplyXml<ReliabilityQosPolicy> for ReliabilityXml {
+    fn apply(self, policy: &mut ReliabilityQosPolicy) -> DdsResult<()> {
+        if let Some(kind) = &self.kind {
+            policy.kind = parse_enumerator(
+                kind,
+                &[
+                    (
+                        "BEST_EFFORT_RELIABILITY_QOS",
+                        ReliabilityQosPolicyKind::BestEffort,
+                    ),
+                    (
+                        "RELIABLE_RELIABILITY_QOS",
+                        ReliabilityQosPolicyKind::Reliable,
+                    ),
+                ],
+            )?;
+        }
+        self.max_blocking_time.apply(&mut policy.max_blocking_time)
+    }
+}
+
+#[derive(Deserialize)]
+struct DestinationOrderXml {
+    kind: Option<String>,
+}
+
+impl ApplyXml<DestinationOrderQosPolicy> for DestinationOrderXml {
+    fn apply(self, policy: &mut DestinationOrderQosPolicy) -> DdsResult<()> {
+        if let Some(kind) = &self.kind {
+            policy.kind = parse_enumerator(
+                kind,
+                &[
+                    (
+                        "BY_RECEPTION_TIMESTAMP_DESTINATIONORDER_QOS",
+                        DestinationOrderQosPolicyKind::ByReceptionTimestamp,
+                    ),
+                    (
+                        "BY_SOURCE_TIMESTAMP_DESTINATIONORDER_QOS",
+                        DestinationOrderQosPolicyKind::BySourceTimestamp,
+                    ),
+                ],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct HistoryXml {
+    kind: Option<String>,
+    depth: Option<String>,
+}
+
+impl ApplyXml<HistoryQosPolicy> for HistoryXml {
+    fn apply(self, policy: &mut HistoryQosPolicy) -> DdsResult<()> {
+        #[derive(Clone, Copy)]
+        enum Kind {
+            KeepLast,
+            KeepAll,
+        }
+
+        let kind = match &self.kind {
+            Some(kind) => parse_enumerator(
+                kind,
+                &[
+                    ("KEEP_LAST_HISTORY_QOS", Kind::KeepLast),
+                    ("KEEP_ALL_HISTORY_QOS", Kind::KeepAll),
+                ],
+            )?,
+            None => match policy.kind {
+                HistoryQosPolicyKind::KeepLast(_) => Kind::KeepLast,
+                HistoryQosPolicyKind::KeepAll => Kind::KeepAll,
+            },
+        };
+        policy.kind = match kind {
+            Kind::KeepLast => {
+                let depth = match (&self.depth, &policy.kind) {
+                    (Some(depth), _) => parse_number(depth)?,
+                    (None, HistoryQosPolicyKind::KeepLast(depth)) => *depth,
+                    (None, HistoryQosPolicyKind::KeepAll) => 1,
+                };
+                HistoryQosPolicyKind::KeepLast(depth)
+            }
+            Kind::KeepAll => HistoryQosPolicyKind::KeepAll,
+        };
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct ResourceLimitsXml {
+    max_samples: Option<String>,
+    max_instances: Option<String>,
+    max_samples_per_instance: Option<String>,
+}
+
+impl ApplyXml<ResourceLimitsQosPolicy> for ResourceLimitsXml {
+    fn apply(self, policy: &mut ResourceLimitsQosPolicy) -> DdsResult<()> {
+        if let Some(max_samples) = &self.max_samples {
+            apply_length(max_samples, &mut policy.max_samples)?;
+        }
+        if let Some(max_instances) = &self.max_instances {
+            apply_length(max_instances, &mut policy.max_instances)?;
+        }
+        if let Some(max_samples_per_instance) = &self.max_samples_per_instance {
+            apply_length(
+                max_samples_per_instance,
+                &mut policy.max_samples_per_instance,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct TransportPriorityXml {
+    value: Option<String>,
+}
+
+impl ApplyXml<TransportPriorityQosPolicy> for TransportPriorityXml {
+    fn apply(self, policy: &mut TransportPriorityQosPolicy) -> DdsResult<()> {
+        if let Some(value) = &self.value {
+            policy.value = parse_number(value)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct LifespanXml {
+    duration: Option<DurationXml>,
+}
+
+impl ApplyXml<LifespanQosPolicy> for LifespanXml {
+    fn apply(self, policy: &mut LifespanQosPolicy) -> DdsResult<()> {
+        self.duration.apply(&mut policy.duration)
+    }
+}
+
+#[derive(Deserialize)]
+struct OwnershipXml {
+    kind: Option<String>,
+}
+
+impl ApplyXml<OwnershipQosPolicy> for OwnershipXml {
+    fn apply(self, policy: &mut OwnershipQosPolicy) -> DdsResult<()> {
+        if let Some(kind) = &self.kind {
+            policy.kind = parse_enumerator(
+                kind,
+                &[
+                    ("SHARED_OWNERSHIP_QOS", OwnershipQosPolicyKind::Shared),
+                    ("EXCLUSIVE_OWNERSHIP_QOS", OwnershipQosPolicyKind::Exclusive),
+                ],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct OwnershipStrengthXml {
+    value: Option<String>,
+}
+
+impl ApplyXml<OwnershipStrengthQosPolicy> for OwnershipStrengthXml {
+    fn apply(self, policy: &mut OwnershipStrengthQosPolicy) -> DdsResult<()> {
+        if let Some(value) = &self.value {
+            policy.value = parse_number(value)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct WriterDataLifecycleXml {
+    autodispose_unregistered_instances: Option<bool>,
+}
+
+impl ApplyXml<WriterDataLifecycleQosPolicy> for WriterDataLifecycleXml {
+    fn apply(self, policy: &mut WriterDataLifecycleQosPolicy) -> DdsResult<()> {
+        if let Some(autodispose_unregistered_instances) = self.autodispose_unregistered_instances {
+            policy.autodispose_unregistered_instances = autodispose_unregistered_instances;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct TimeBasedFilterXml {
+    minimum_separation: Option<DurationXml>,
+}
+
+impl ApplyXml<TimeBasedFilterQosPolicy> for TimeBasedFilterXml {
+    fn apply(self, policy: &mut TimeBasedFilterQosPolicy) -> DdsResult<()> {
+        self.minimum_separation
+            .apply(&mut policy.minimum_separation)
+    }
+}
+
+#[derive(Deserialize)]
+struct ReaderDataLifecycleXml {
+    autopurge_nowriter_samples_delay: Option<DurationXml>,
+    autopurge_disposed_samples_delay: Option<DurationXml>,
+}
+
+impl ApplyXml<ReaderDataLifecycleQosPolicy> for ReaderDataLifecycleXml {
+    fn apply(self, policy: &mut ReaderDataLifecycleQosPolicy) -> DdsResult<()> {
+        self.autopurge_nowriter_samples_delay
+            .apply(&mut policy.autopurge_nowriter_samples_delay)?;
+        self.autopurge_disposed_samples_delay
+            .apply(&mut policy.autopurge_disposed_samples_delay)
+    }
+}
+
+#[derive(Deserialize)]
+struct RepresentationXml {
+    value: Option<StringSeqXml>,
+}
+
+impl ApplyXml<DataRepresentationQosPolicy> for RepresentationXml {
+    fn apply(self, policy: &mut DataRepresentationQosPolicy) -> DdsResult<()> {
+        if let Some(value) = self.value {
+            policy.value = value
+                .element
+                .iter()
+                .map(|representation| {
+                    parse_enumerator(
+                        representation,
+                        &[
+                            ("XCDR_DATA_REPRESENTATION", XCDR_DATA_REPRESENTATION),
+                            ("XML_DATA_REPRESENTATION", XML_DATA_REPRESENTATION),
+                            ("XCDR2_DATA_REPRESENTATION", XCDR2_DATA_REPRESENTATION),
+                        ],
+                    )
+                })
+                .collect::<DdsResult<_>>()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_profile_of_each_entity() {
+        let xml = r#"
+            <dds xmlns="http://www.omg.org/spec/DDS-XML">
+                <qos_library name="MyLibrary">
+                    <qos_profile name="MyProfile">
+                        <domain_participant_qos>
+                            <user_data><value><element>1</element><element>2</element></value></user_data>
+                            <entity_factory><autoenable_created_entities>false</autoenable_created_entities></entity_factory>
+                        </domain_participant_qos>
+                        <publisher_qos>
+                            <presentation>
+                                <access_scope>GROUP_PRESENTATION_QOS</access_scope>
+                                <coherent_access>true</coherent_access>
+                            </presentation>
+                            <partition><name><element>A</element><element>B*</element></name></partition>
+                        </publisher_qos>
+                        <subscriber_qos>
+                            <group_data><value><element>3</element></value></group_data>
+                        </subscriber_qos>
+                        <topic_qos>
+                            <durability><kind>TRANSIENT_LOCAL_DURABILITY_QOS</kind></durability>
+                            <history><kind>KEEP_LAST_HISTORY_QOS</kind><depth>5</depth></history>
+                        </topic_qos>
+                        <datawriter_qos>
+                            <reliability>
+                                <kind>BEST_EFFORT_RELIABILITY_QOS</kind>
+                                <max_blocking_time><sec>1</sec><nanosec>500</nanosec></max_blocking_time>
+                            </reliability>
+                            <resource_limits>
+                                <max_samples>LENGTH_UNLIMITED</max_samples>
+                                <max_instances>10</max_instances>
+                            </resource_limits>
+                            <ownership_strength><value>7</value></ownership_strength>
+                        </datawriter_qos>
+                        <datareader_qos>
+                            <deadline><period><sec>DURATION_INFINITE_SEC</sec><nanosec>DURATION_INFINITE_NSEC</nanosec></period></deadline>
+                            <time_based_filter><minimum_separation><sec>2</sec></minimum_separation></time_based_filter>
+                            <representation><value><element>XCDR2_DATA_REPRESENTATION</element></value></representation>
+                        </datareader_qos>
+                    </qos_profile>
+                </qos_library>
+            </dds>
+        "#;
+
+        let qos_profiles = parse_qos_profiles(xml).unwrap();
+        let qos_profile = &qos_profiles["MyLibrary::MyProfile"];
+
+        let participant_qos = qos_profile.participant_qos.as_ref().unwrap();
+        assert_eq!(participant_qos.user_data.value, vec![1, 2]);
+        assert!(!participant_qos.entity_factory.autoenable_created_entities);
+
+        let publisher_qos = qos_profile.publisher_qos.as_ref().unwrap();
+        assert_eq!(
+            publisher_qos.presentation,
+            PresentationQosPolicy {
+                access_scope: PresentationQosPolicyAccessScopeKind::Group,
+                coherent_access: true,
+                ordered_access: false,
+            }
+        );
+        assert_eq!(publisher_qos.partition.name, vec!["A", "B*"]);
+
+        let subscriber_qos = qos_profile.subscriber_qos.as_ref().unwrap();
+        assert_eq!(subscriber_qos.group_data.value, vec![3]);
+
+        let topic_qos = qos_profile.topic_qos.as_ref().unwrap();
+        assert_eq!(
+            topic_qos.durability.kind,
+            DurabilityQosPolicyKind::TransientLocal
+        );
+        assert_eq!(topic_qos.history.kind, HistoryQosPolicyKind::KeepLast(5));
+        assert_eq!(topic_qos.reliability, TopicQos::default().reliability);
+
+        let datawriter_qos = qos_profile.datawriter_qos.as_ref().unwrap();
+        assert_eq!(
+            datawriter_qos.reliability,
+            ReliabilityQosPolicy {
+                kind: ReliabilityQosPolicyKind::BestEffort,
+                max_blocking_time: DurationKind::Finite(Duration::new(1, 500)),
+            }
+        );
+        assert_eq!(
+            datawriter_qos.resource_limits,
+            ResourceLimitsQosPolicy {
+                max_samples: Length::Unlimited,
+                max_instances: Length::Limited(10),
+                max_samples_per_instance: Length::Unlimited,
+            }
+        );
+        assert_eq!(datawriter_qos.ownership_strength.value, 7);
+
+        let datareader_qos = qos_profile.datareader_qos.as_ref().unwrap();
+        assert_eq!(datareader_qos.deadline.period, DurationKind::Infinite);
+        assert_eq!(
+            datareader_qos.time_based_filter.minimum_separation,
+            DurationKind::Finite(Duration::new(2, 0))
+        );
+        assert_eq!(
+            datareader_qos.representation.value,
+            vec![XCDR2_DATA_REPRESENTATION]
+        );
+    }
+
+    #[test]
+    fn entities_missing_in_profile_have_no_qos() {
+        let xml = r#"
+            <dds>
+                <qos_library name="MyLibrary">
+                    <qos_profile name="Empty"/>
+                    <qos_profile name="WriterOnly">
+                        <datawriter_qos/>
+                    </qos_profile>
+                </qos_library>
+            </dds>
+        "#;
+
+        let qos_profiles = parse_qos_profiles(xml).unwrap();
+
+        assert_eq!(qos_profiles["MyLibrary::Empty"], QosProfile::default());
+        assert_eq!(
+            qos_profiles["MyLibrary::WriterOnly"],
+            QosProfile {
+                datawriter_qos: Some(DataWriterQos::default()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_enumerator_is_rejected() {
+        let xml = r#"
+            <dds>
+                <qos_library name="MyLibrary">
+                    <qos_profile name="MyProfile">
+                        <datareader_qos>
+                            <reliability><kind>SOMETIMES_RELIABILITY_QOS</kind></reliability>
+                        </datareader_qos>
+                    </qos_profile>
+                </qos_library>
+            </dds>
+        "#;
+
+        assert!(matches!(
+            parse_qos_profiles(xml),
+            Err(DdsError::Error(message))
+                if message.contains("MyLibrary::MyProfile")
+                    && message.contains("SOMETIMES_RELIABILITY_QOS")
+        ));
+    }
+
+    #[test]
+    fn malformed_xml_is_rejected() {
+        assert!(matches!(
+            parse_qos_profiles("<dds><qos_library name=\"MyLibrary\">"),
+            Err(DdsError::Error(_))
+        ));
+    }
+}
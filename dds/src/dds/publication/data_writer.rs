@@ -206,6 +206,17 @@ where
         block_on(self.writer_async.write_w_timestamp(data, handle, timestamp))
     }
 
+    /// This operation writes all the samples in `data` as if [`DataWriter::write_w_timestamp`] was called for each
+    /// of them in order, using the same `timestamp` for all of them. The samples are assigned consecutive sequence numbers
+    /// without changes from other write operations interleaved. If `timestamp` is [`None`] the current time is used.
+    /// The instance of each sample is deduced from its key and the operation may block and return the same errors described
+    /// for [`DataWriter::write`]. The resource limits are checked for the whole batch before any of the samples is added
+    /// to the writer history, so if an error is returned none of the samples is written.
+    #[tracing::instrument(skip(self, data))]
+    pub fn write_batch(&self, data: &[Foo], timestamp: Option<Time>) -> DdsResult<()> {
+        block_on(self.writer_async.write_batch(data, timestamp))
+    }
+
     /// This operation requests the middleware to delete the data (the actual deletion is postponed until there is no more use for that
     /// data in the whole system). In general, applications are made aware of the deletion by means of operations on the
     /// [`DataReader`](crate::subscription::data_reader::DataReader) objects that already knew the instance.
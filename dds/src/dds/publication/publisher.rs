@@ -208,10 +208,13 @@ impl Publisher {
     #[tracing::instrument(skip(self))]
     pub fn copy_from_topic_qos(
         &self,
-        _a_datawriter_qos: &mut DataWriterQos,
-        _a_topic_qos: &TopicQos,
+        a_datawriter_qos: &mut DataWriterQos,
+        a_topic_qos: &TopicQos,
     ) -> DdsResult<()> {
-        todo!()
+        block_on(
+            self.publisher_async
+                .copy_from_topic_qos(a_datawriter_qos, a_topic_qos),
+        )
     }
 }
 
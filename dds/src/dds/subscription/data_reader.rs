@@ -3,7 +3,7 @@ use crate::{
     dds_async::{data_reader::DataReaderAsync, data_reader_listener::DataReaderListenerAsync},
     implementation::runtime::executor::block_on,
     infrastructure::{
-        condition::StatusCondition,
+        condition::{QueryCondition, ReadCondition, StatusCondition},
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataReaderQos, QosKind},
@@ -15,7 +15,10 @@ use crate::{
     },
     rtps::messages::submessage_elements::Data,
     subscription::data_reader_listener::DataReaderListener,
-    topic_definition::{topic::Topic, type_support::DdsDeserialize},
+    topic_definition::{
+        topic::Topic,
+        type_support::{DdsDeserialize, DdsGetField, DdsKey, DdsSerialize},
+    },
 };
 
 use std::marker::PhantomData;
@@ -26,6 +29,10 @@ use super::{
 };
 
 /// A [`Sample`] contains the data and [`SampleInfo`] read by the [`DataReader`].
+///
+/// The sample shares the serialized data buffer received by the [`DataReader`] instead of copying it and
+/// the data is only deserialized when calling [`Sample::data`]. The buffer is released once the sample is dropped,
+/// so there is no need to return a loan to the [`DataReader`].
 #[derive(Debug, PartialEq, Eq)]
 pub struct Sample<Foo> {
     /// Data received by the [`DataReader`]. A sample might contain no valid data in which case this field is [`None`].
@@ -164,6 +171,43 @@ impl<Foo> DataReader<Foo> {
         )
     }
 
+    /// This operation accesses via [`DataReader::read`] the samples that match the criteria specified in the
+    /// [`ReadCondition`]. This operation is especially useful in combination with [`QueryCondition`] to filter data
+    /// samples based on the content.
+    /// The specified [`ReadCondition`] must be attached to the [`DataReader`]; otherwise the operation will fail and return
+    /// [`DdsError::PreconditionNotMet`].
+    /// In case the [`ReadCondition`] is a 'plain' [`ReadCondition`] and not the more specialized [`QueryCondition`], the operation
+    /// is equivalent to calling [`DataReader::read`] and passing as `sample_states`, `view_states` and `instance_states`
+    /// the value of the corresponding attributes in the condition.
+    /// If the [`DataReader`] has no samples that meet the constraints, the return value will be [`DdsError::NoData`].
+    #[tracing::instrument(skip(self, a_condition))]
+    pub fn read_w_condition(
+        &self,
+        max_samples: i32,
+        a_condition: &ReadCondition,
+    ) -> DdsResult<Vec<Sample<Foo>>> {
+        block_on(
+            self.reader_async
+                .read_w_condition(max_samples, a_condition.condition_async()),
+        )
+    }
+
+    /// This operation is analogous to [`DataReader::read_w_condition`] except it accesses samples via the [`DataReader::take`] operation.
+    /// The specified [`ReadCondition`] must be attached to the [`DataReader`]; otherwise the operation will fail and return
+    /// [`DdsError::PreconditionNotMet`].
+    /// If the [`DataReader`] has no samples that meet the constraints, the return value will be [`DdsError::NoData`].
+    #[tracing::instrument(skip(self, a_condition))]
+    pub fn take_w_condition(
+        &self,
+        max_samples: i32,
+        a_condition: &ReadCondition,
+    ) -> DdsResult<Vec<Sample<Foo>>> {
+        block_on(
+            self.reader_async
+                .take_w_condition(max_samples, a_condition.condition_async()),
+        )
+    }
+
     /// This operation reads the next, non-previously accessed [`Sample`] value from the [`DataReader`].
     /// The implied order among the samples stored in the [`DataReader`] is the same as for the [`DataReader::read`]
     /// operation. This operation is semantically equivalent to the read operation where the input Data sequence has
@@ -307,7 +351,10 @@ impl<Foo> DataReader<Foo> {
     /// This operation may return [`DdsError::BadParameter`](crate::infrastructure::error::DdsError)
     /// if the [`InstanceHandle`] `handle` does not correspond to an existing data object known to the [`DataReader`].
     #[tracing::instrument(skip(self, key_holder))]
-    pub fn get_key_value(&self, key_holder: &mut Foo, handle: InstanceHandle) -> DdsResult<()> {
+    pub fn get_key_value(&self, key_holder: &mut Foo, handle: InstanceHandle) -> DdsResult<()>
+    where
+        Foo: DdsKey,
+    {
         block_on(self.reader_async.get_key_value(key_holder, handle))
     }
 
@@ -318,9 +365,49 @@ impl<Foo> DataReader<Foo> {
     /// been previously registered, or if for any other reason the Service is unable to provide
     /// an instance handle, the operation will succeed and return [`None`].
     #[tracing::instrument(skip(self, instance))]
-    pub fn lookup_instance(&self, instance: &Foo) -> DdsResult<Option<InstanceHandle>> {
+    pub fn lookup_instance(&self, instance: &Foo) -> DdsResult<Option<InstanceHandle>>
+    where
+        Foo: DdsSerialize,
+    {
         block_on(self.reader_async.lookup_instance(instance))
     }
+
+    /// This operation creates a [`ReadCondition`]. The returned [`ReadCondition`] will be attached and belong to the [`DataReader`].
+    #[tracing::instrument(skip(self))]
+    pub fn create_readcondition(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> DdsResult<ReadCondition> {
+        block_on(self.reader_async.create_readcondition(
+            sample_states,
+            view_states,
+            instance_states,
+        ))
+        .map(ReadCondition::new)
+    }
+
+    /// This operation deletes a [`ReadCondition`] attached to the [`DataReader`]. Since [`QueryCondition`] specializes
+    /// [`ReadCondition`] it can also be used to delete a [`QueryCondition`].
+    /// If the [`ReadCondition`] is not attached to the [`DataReader`], the operation will return the error
+    /// [`DdsError::PreconditionNotMet`].
+    #[tracing::instrument(skip(self, a_condition))]
+    pub fn delete_readcondition(&self, a_condition: &ReadCondition) -> DdsResult<()> {
+        block_on(
+            self.reader_async
+                .delete_readcondition(a_condition.condition_async()),
+        )
+    }
+
+    /// This operation deletes all the entities that were created by means of the “create” operations on the [`DataReader`].
+    /// That is, it deletes all contained [`ReadCondition`] and [`QueryCondition`] objects.
+    /// Once this operation returns successfully, the application may delete the [`DataReader`] knowing that it has no
+    /// contained [`ReadCondition`] and [`QueryCondition`] objects.
+    #[tracing::instrument(skip(self))]
+    pub fn delete_contained_entities(&self) -> DdsResult<()> {
+        block_on(self.reader_async.delete_contained_entities())
+    }
 }
 
 impl<Foo> DataReader<Foo> {
@@ -522,3 +609,32 @@ where
         )
     }
 }
+
+impl<Foo> DataReader<Foo>
+where
+    Foo: DdsGetField + for<'de> DdsDeserialize<'de>,
+{
+    /// This operation creates a [`QueryCondition`]. The returned [`QueryCondition`] will be attached and belong to the [`DataReader`].
+    /// The `query_expression` is evaluated on the members of the received samples accessed through the [`DdsGetField`] trait
+    /// and the `query_parameters` are referenced in the expression as `%0`, `%1`, ...
+    /// If the `query_expression` can not be parsed or it references a parameter which is not part of `query_parameters`
+    /// the operation returns [`DdsError::BadParameter`].
+    #[tracing::instrument(skip(self))]
+    pub fn create_querycondition(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+        query_expression: &str,
+        query_parameters: &[String],
+    ) -> DdsResult<QueryCondition> {
+        block_on(self.reader_async.create_querycondition(
+            sample_states,
+            view_states,
+            instance_states,
+            query_expression,
+            query_parameters,
+        ))
+        .map(QueryCondition::new)
+    }
+}
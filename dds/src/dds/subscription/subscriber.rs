@@ -12,7 +12,8 @@ use crate::{
         qos::{DataReaderQos, QosKind, SubscriberQos, TopicQos},
         status::{SampleLostStatus, StatusKind},
     },
-    topic_definition::topic::Topic,
+    subscription::sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
+    topic_definition::topic_description::TopicDescription,
 };
 
 use super::{
@@ -41,9 +42,11 @@ impl Subscriber {
 
 impl Subscriber {
     /// This operation creates a [`DataReader`]. The returned [`DataReader`] will be attached and belong to the [`Subscriber`].
-    /// The [`DataReader`] returned by this operation has an associated [`Topic`] and a type `Foo`.
-    /// The [`Topic`] passed to this operation must have been created from the same [`DomainParticipant`] that was used to create this
-    /// [`Subscriber`]. If the [`Topic`] was created from a different [`DomainParticipant`], the operation will fail and
+    /// The [`DataReader`] returned by this operation has an associated [`Topic`](crate::topic_definition::topic::Topic) and a type `Foo`.
+    /// The `a_topic` can either be a [`Topic`](crate::topic_definition::topic::Topic) or a [`ContentFilteredTopic`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic),
+    /// in which case only the samples that pass the filter are stored by the [`DataReader`].
+    /// The [`Topic`](crate::topic_definition::topic::Topic) passed to this operation must have been created from the same [`DomainParticipant`] that was used to create this
+    /// [`Subscriber`]. If the [`Topic`](crate::topic_definition::topic::Topic) was created from a different [`DomainParticipant`], the operation will fail and
     /// return a [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError). In case of failure, the operation
     /// will return an error and no writer will be created.
     ///
@@ -52,14 +55,14 @@ impl Subscriber {
     /// [`DataReaderQos`] by means of the operation [`Subscriber::get_default_datareader_qos`] and using the resulting qos
     /// to create the [`DataReader`]. A common application pattern to construct the [`DataReaderQos`] to ensure consistency with the
     /// associated [`TopicQos`] is to:
-    /// 1. Retrieve the QoS policies on the associated [`Topic`] by means of the [`Topic::get_qos`] operation.
+    /// 1. Retrieve the QoS policies on the associated [`Topic`](crate::topic_definition::topic::Topic) by means of the [`Topic::get_qos`](crate::topic_definition::topic::Topic::get_qos) operation.
     /// 2. Retrieve the default [`DataReaderQos`] qos by means of the [`Subscriber::get_default_datareader_qos`] operation.
     /// 3. Combine those two qos policies using the [`Subscriber::copy_from_topic_qos`] and selectively modify policies as desired and
     /// use the resulting [`DataReaderQos`] to construct the [`DataReader`].
     #[tracing::instrument(skip(self, a_topic, a_listener))]
     pub fn create_datareader<'a, Foo>(
         &self,
-        a_topic: &Topic,
+        a_topic: &impl TopicDescription,
         qos: QosKind<DataReaderQos>,
         a_listener: Option<Box<dyn DataReaderListener<'a, Foo = Foo> + Send + 'a>>,
         mask: &[StatusKind],
@@ -69,7 +72,7 @@ impl Subscriber {
     {
         block_on(
             self.subscriber_async.create_datareader::<Foo>(
-                a_topic.topic_async(),
+                a_topic,
                 qos,
                 a_listener.map::<Box<dyn DataReaderListenerAsync<Foo = Foo> + Send + 'a>, _>(|b| {
                     Box::new(b)
@@ -91,7 +94,7 @@ impl Subscriber {
         )
     }
 
-    /// This operation retrieves a previously created [`DataReader`] belonging to the [`Subscriber`] that is attached to a [`Topic`].
+    /// This operation retrieves a previously created [`DataReader`] belonging to the [`Subscriber`] that is attached to a [`Topic`](crate::topic_definition::topic::Topic).
     /// If no such [`DataReader`] exists, the operation will succeed but return [`None`].
     /// If multiple [`DataReader`] attached to the [`Subscriber`] satisfy this condition, then the operation will return one of them. It is not
     /// specified which one.
@@ -104,6 +107,53 @@ impl Subscriber {
         )
     }
 
+    /// This operation indicates that the application is about to access the data samples in any of the [`DataReader`] objects
+    /// attached to the [`Subscriber`].
+    /// The application is required to use this operation only if the [`PresentationQosPolicy::access_scope`](crate::infrastructure::qos_policy::PresentationQosPolicy)
+    /// of the [`Subscriber`] is set to [`PresentationQosPolicyAccessScopeKind::Group`](crate::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind).
+    /// In that case, the operation must be called prior to calling [`Subscriber::get_datareaders`] and any of the sample-accessing
+    /// operations on the [`DataReader`] objects.
+    /// Calls to [`Subscriber::begin_access`] can be nested and must be matched with corresponding calls to [`Subscriber::end_access`].
+    #[tracing::instrument(skip(self))]
+    pub fn begin_access(&self) -> DdsResult<()> {
+        block_on(self.subscriber_async.begin_access())
+    }
+
+    /// This operation indicates that the application has finished accessing the data samples in [`DataReader`] objects managed
+    /// by the [`Subscriber`]. This operation must be used to close a corresponding [`Subscriber::begin_access`].
+    /// If there is no matching call to [`Subscriber::begin_access`], the operation will return
+    /// [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
+    #[tracing::instrument(skip(self))]
+    pub fn end_access(&self) -> DdsResult<()> {
+        block_on(self.subscriber_async.end_access())
+    }
+
+    /// This operation allows the application to access the [`DataReader`] objects that contain samples with the specified
+    /// `sample_states`, `view_states`, and `instance_states`.
+    /// If the [`PresentationQosPolicy`](crate::infrastructure::qos_policy::PresentationQosPolicy) of the [`Subscriber`] has
+    /// `access_scope` set to [`PresentationQosPolicyAccessScopeKind::Group`](crate::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind)
+    /// and `ordered_access` set to [`true`], this operation must be called within a [`Subscriber::begin_access`] and
+    /// [`Subscriber::end_access`] block, otherwise it returns [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
+    /// In that case the returned collection is a list where the same [`DataReader`] may appear more than once. The order in which
+    /// the readers appear is the order in which the application should read one sample from each of them to access the samples
+    /// in the order in which they were published. Otherwise, each [`DataReader`] appears at most once in the returned collection.
+    #[tracing::instrument(skip(self))]
+    pub fn get_datareaders(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> DdsResult<Vec<DataReader<()>>> {
+        Ok(block_on(self.subscriber_async.get_datareaders(
+            sample_states,
+            view_states,
+            instance_states,
+        ))?
+        .into_iter()
+        .map(DataReader::new)
+        .collect())
+    }
+
     /// This operation invokes the operation [`DataReaderListener::on_data_available`] on the listener objects attached to contained [`DataReader`]
     /// entities with a [`StatusKind::DataAvailable`] that is considered changed.
     /// This operation is typically invoked from the [`SubscriberListener::on_data_on_readers`] operation. That way the
@@ -158,16 +208,19 @@ impl Subscriber {
 
     /// This operation copies the policies in the `a_topic_qos` to the corresponding policies in the `a_datareader_qos`.
     /// This is a *convenience* operation most useful in combination with the operations [`Subscriber::get_default_datareader_qos`] and
-    /// [`Topic::get_qos`]. This operation can be used to merge the [`DataReader`] default qos policies with the
-    /// corresponding ones on the [`Topic`]. The resulting qos can then be used to create a new [`DataReader`], or set its qos.
+    /// [`Topic::get_qos`](crate::topic_definition::topic::Topic::get_qos). This operation can be used to merge the [`DataReader`] default qos policies with the
+    /// corresponding ones on the [`Topic`](crate::topic_definition::topic::Topic). The resulting qos can then be used to create a new [`DataReader`], or set its qos.
     /// This operation does not check the resulting `a_datareader_qos` for consistency. This is because the merged `a_datareader_qos`
     /// may not be the final one, as the application can still modify some policies prior to applying the policies to the [`DataReader`].
     #[tracing::instrument]
     pub fn copy_from_topic_qos(
-        _a_datareader_qos: &mut DataReaderQos,
-        _a_topic_qos: &TopicQos,
+        a_datareader_qos: &mut DataReaderQos,
+        a_topic_qos: &TopicQos,
     ) -> DdsResult<()> {
-        todo!()
+        block_on(SubscriberAsync::copy_from_topic_qos(
+            a_datareader_qos,
+            a_topic_qos,
+        ))
     }
 
     /// This operation is used to set the QoS policies of the Entity and replacing the values of any policies previously set.
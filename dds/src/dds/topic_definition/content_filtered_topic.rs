@@ -0,0 +1,83 @@
+use crate::{
+    dds_async::{content_filtered_topic::ContentFilteredTopicAsync, topic::TopicAsync},
+    implementation::actors::data_reader_actor::QueryFilter,
+};
+
+use super::{topic::Topic, topic_description::TopicDescription};
+
+/// The [`ContentFilteredTopic`] describes a more sophisticated subscription that indicates the subscriber does not want to
+/// necessarily see all values of each instance published under the [`Topic`]. Rather, it wants to see only the values whose
+/// contents satisfy certain criteria. This class therefore can be used to request content-based subscriptions.
+///
+/// The selection of the content is done using the `filter_expression` with parameters `expression_parameters`. The filter
+/// expression uses the same subset of the SQL syntax as the [`QueryCondition`](crate::infrastructure::condition::QueryCondition).
+/// Samples that do not satisfy the filter are not stored by the [`DataReader`](crate::subscription::data_reader::DataReader).
+#[derive(Clone)]
+pub struct ContentFilteredTopic {
+    content_filtered_topic_async: ContentFilteredTopicAsync,
+}
+
+impl ContentFilteredTopic {
+    pub(crate) fn new(content_filtered_topic_async: ContentFilteredTopicAsync) -> Self {
+        Self {
+            content_filtered_topic_async,
+        }
+    }
+}
+
+impl ContentFilteredTopic {
+    /// This operation returns the `filter_expression` associated with the [`ContentFilteredTopic`]. That is, the expression
+    /// specified when the [`ContentFilteredTopic`] was created.
+    #[tracing::instrument(skip(self))]
+    pub fn get_filter_expression(&self) -> String {
+        self.content_filtered_topic_async.get_filter_expression()
+    }
+
+    /// This operation returns the `expression_parameters` associated with the [`ContentFilteredTopic`]. That is, the parameters
+    /// specified when the [`ContentFilteredTopic`] was created.
+    #[tracing::instrument(skip(self))]
+    pub fn get_expression_parameters(&self) -> Vec<String> {
+        self.content_filtered_topic_async
+            .get_expression_parameters()
+    }
+
+    /// This operation returns the [`Topic`] associated with the [`ContentFilteredTopic`]. That is, the [`Topic`] specified
+    /// when the [`ContentFilteredTopic`] was created.
+    #[tracing::instrument(skip(self))]
+    pub fn get_related_topic(&self) -> Topic {
+        Topic::new(self.content_filtered_topic_async.get_related_topic())
+    }
+}
+
+/// This implementation block represents the TopicDescription operations for the [`ContentFilteredTopic`].
+impl ContentFilteredTopic {
+    /// The name of the type used to create the related [`Topic`]
+    #[tracing::instrument(skip(self))]
+    pub fn get_type_name(&self) -> String {
+        self.content_filtered_topic_async.get_type_name()
+    }
+
+    /// The name used to create the [`ContentFilteredTopic`]
+    #[tracing::instrument(skip(self))]
+    pub fn get_name(&self) -> String {
+        self.content_filtered_topic_async.get_name()
+    }
+}
+
+impl TopicDescription for ContentFilteredTopic {
+    fn get_type_name(&self) -> String {
+        self.get_type_name()
+    }
+
+    fn get_name(&self) -> String {
+        self.get_name()
+    }
+
+    fn related_topic_async(&self) -> &TopicAsync {
+        self.content_filtered_topic_async.related_topic_async()
+    }
+
+    fn content_filter(&self) -> Option<QueryFilter> {
+        self.content_filtered_topic_async.content_filter()
+    }
+}
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use crate::{
     dds_async::topic::TopicAsync,
     domain::domain_participant::DomainParticipant,
-    implementation::runtime::executor::block_on,
+    implementation::{actors::data_reader_actor::QueryFilter, runtime::executor::block_on},
     infrastructure::{
         condition::StatusCondition,
         error::DdsResult,
@@ -13,7 +13,10 @@ use crate::{
     },
 };
 
-use super::{topic_listener::TopicListener, type_support::DynamicTypeInterface};
+use super::{
+    topic_description::TopicDescription, topic_listener::TopicListener,
+    type_support::DynamicTypeInterface,
+};
 
 /// The [`Topic`] represents the fact that both publications and subscriptions are tied to a single data-type. Its attributes
 /// `type_name` defines a unique resulting type for the publication or the subscription. It has also a `name` that allows it to
@@ -61,6 +64,24 @@ impl Topic {
     }
 }
 
+impl TopicDescription for Topic {
+    fn get_type_name(&self) -> String {
+        self.get_type_name()
+    }
+
+    fn get_name(&self) -> String {
+        self.get_name()
+    }
+
+    fn related_topic_async(&self) -> &TopicAsync {
+        &self.topic_async
+    }
+
+    fn content_filter(&self) -> Option<QueryFilter> {
+        None
+    }
+}
+
 /// This implementation block contains the Entity operations for the [`Topic`].
 impl Topic {
     /// This operation is used to set the QoS policies of the Entity and replacing the values of any policies previously set.
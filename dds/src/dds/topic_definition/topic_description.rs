@@ -0,0 +1,19 @@
+use crate::{dds_async::topic::TopicAsync, implementation::actors::data_reader_actor::QueryFilter};
+
+/// The [`TopicDescription`] represents the fact that both publications and subscriptions are tied to a single data-type.
+/// It is implemented by the [`Topic`](crate::topic_definition::topic::Topic) and the
+/// [`ContentFilteredTopic`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic), as well as by their
+/// async versions, so that any of them can be used to create a [`DataReader`](crate::subscription::data_reader::DataReader).
+pub trait TopicDescription {
+    /// The name of the type used to create the topic description.
+    fn get_type_name(&self) -> String;
+
+    /// The name used to create the topic description.
+    fn get_name(&self) -> String;
+
+    #[doc(hidden)]
+    fn related_topic_async(&self) -> &TopicAsync;
+
+    #[doc(hidden)]
+    fn content_filter(&self) -> Option<QueryFilter>;
+}
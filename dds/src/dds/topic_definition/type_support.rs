@@ -19,7 +19,7 @@ use crate::{
 };
 use std::io::{Read, Write};
 
-pub use dust_dds_derive::{DdsDeserialize, DdsHasKey, DdsSerialize, DdsTypeXml};
+pub use dust_dds_derive::{DdsDeserialize, DdsGetField, DdsHasKey, DdsSerialize, DdsTypeXml};
 
 #[doc(hidden)]
 pub trait DynamicTypeInterface {
@@ -103,6 +103,12 @@ pub trait DdsKey {
 
     /// Method to get the key from a serialized instance of the type.
     fn get_key_from_serialized_data(serialized_foo: &[u8]) -> DdsResult<Self::Key>;
+
+    /// Method to set the fields that form the key of the instance from the provided key object.
+    /// The default implementation returns [`DdsError::Unsupported`].
+    fn set_key_fields(&mut self, _key: Self::Key) -> DdsResult<()> {
+        Err(DdsError::Unsupported)
+    }
 }
 
 /// This trait defines the optional type representation for a user type. The type representation
@@ -117,6 +123,114 @@ pub trait DdsTypeXml {
     fn get_type_xml() -> Option<String>;
 }
 
+/// Value of a member of a basic type as returned by [`DdsGetField`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    /// Boolean value
+    Boolean(bool),
+    /// Character value
+    Char(char),
+    /// Signed integer value of any width
+    Int(i64),
+    /// Unsigned integer value of any width
+    UInt(u64),
+    /// Floating point value of any width
+    Float(f64),
+    /// String value
+    String(String),
+}
+
+impl From<bool> for FieldValue {
+    fn from(value: bool) -> Self {
+        FieldValue::Boolean(value)
+    }
+}
+
+impl From<char> for FieldValue {
+    fn from(value: char) -> Self {
+        FieldValue::Char(value)
+    }
+}
+
+impl From<i8> for FieldValue {
+    fn from(value: i8) -> Self {
+        FieldValue::Int(value.into())
+    }
+}
+
+impl From<i16> for FieldValue {
+    fn from(value: i16) -> Self {
+        FieldValue::Int(value.into())
+    }
+}
+
+impl From<i32> for FieldValue {
+    fn from(value: i32) -> Self {
+        FieldValue::Int(value.into())
+    }
+}
+
+impl From<i64> for FieldValue {
+    fn from(value: i64) -> Self {
+        FieldValue::Int(value)
+    }
+}
+
+impl From<u8> for FieldValue {
+    fn from(value: u8) -> Self {
+        FieldValue::UInt(value.into())
+    }
+}
+
+impl From<u16> for FieldValue {
+    fn from(value: u16) -> Self {
+        FieldValue::UInt(value.into())
+    }
+}
+
+impl From<u32> for FieldValue {
+    fn from(value: u32) -> Self {
+        FieldValue::UInt(value.into())
+    }
+}
+
+impl From<u64> for FieldValue {
+    fn from(value: u64) -> Self {
+        FieldValue::UInt(value)
+    }
+}
+
+impl From<f32> for FieldValue {
+    fn from(value: f32) -> Self {
+        FieldValue::Float(value.into())
+    }
+}
+
+impl From<f64> for FieldValue {
+    fn from(value: f64) -> Self {
+        FieldValue::Float(value)
+    }
+}
+
+impl From<String> for FieldValue {
+    fn from(value: String) -> Self {
+        FieldValue::String(value)
+    }
+}
+
+/// This trait provides access by name to the members of a type which are of a basic type (i.e. numbers, characters,
+/// booleans and strings). It is used to evaluate the query expression of a
+/// [`QueryCondition`](crate::infrastructure::condition::QueryCondition) on the samples received by a DataReader.
+///
+/// ## Derivable
+///
+/// This trait can be automatically derived. Members of tuple structs are accessed by their index (e.g. "0").
+/// Members which are not of a basic type are not accessible.
+pub trait DdsGetField {
+    /// Method to get the value of the member named `field_name`. Returns [`None`] if no such member exists.
+    fn get_field(&self, field_name: &str) -> Option<FieldValue>;
+}
+
 /// This is a convenience derive to allow the user to easily derive all the different traits needed for a type to be used for
 /// communication with DustDDS. If the individual traits are manually derived then this derive should not be used.
 ///
@@ -341,4 +455,103 @@ mod tests {
         let result = serialize_rtps_classic_cdr_le(&vec![3_u8, 4]).unwrap();
         assert_eq!(result, vec![0, 1, 0, 0b_0000_0010, 2, 0, 0, 0, 3, 4, 0, 0])
     }
+
+    #[derive(Debug, PartialEq, CdrSerialize, CdrDeserialize, DdsSerialize, DdsDeserialize)]
+    struct Primitives {
+        a: u8,
+        b: i16,
+        c: u32,
+        d: f64,
+        e: bool,
+    }
+
+    #[test]
+    fn derived_struct_with_primitive_fields_round_trip() {
+        let data = Primitives {
+            a: 1,
+            b: -2,
+            c: 3,
+            d: 4.5,
+            e: true,
+        };
+        let serialized_data = data.serialize_data().unwrap();
+        // CDR_LE representation identifier
+        assert_eq!(&serialized_data[..2], &[0, 1]);
+        assert_eq!(
+            Primitives::deserialize_data(&serialized_data).unwrap(),
+            data
+        );
+    }
+
+    #[test]
+    fn derived_nested_struct_round_trip() {
+        #[derive(Debug, PartialEq, CdrSerialize, CdrDeserialize, DdsSerialize, DdsDeserialize)]
+        struct Nested {
+            id: u16,
+            inner: Primitives,
+        }
+
+        let data = Nested {
+            id: 7,
+            inner: Primitives {
+                a: 1,
+                b: 2,
+                c: 3,
+                d: 4.0,
+                e: false,
+            },
+        };
+        let serialized_data = data.serialize_data().unwrap();
+        assert_eq!(Nested::deserialize_data(&serialized_data).unwrap(), data);
+    }
+
+    #[test]
+    fn derived_struct_with_sequence_and_string_round_trip() {
+        #[derive(Debug, PartialEq, CdrSerialize, CdrDeserialize, DdsSerialize, DdsDeserialize)]
+        struct Sequences {
+            name: String,
+            values: Vec<u16>,
+            names: Vec<String>,
+        }
+
+        let data = Sequences {
+            name: String::from("dust"),
+            values: vec![1, 2, 3],
+            names: vec![String::from("a"), String::from("bc")],
+        };
+        let serialized_data = data.serialize_data().unwrap();
+        assert_eq!(Sequences::deserialize_data(&serialized_data).unwrap(), data);
+    }
+
+    #[test]
+    fn derived_struct_with_array_round_trip() {
+        #[derive(Debug, PartialEq, CdrSerialize, CdrDeserialize, DdsSerialize, DdsDeserialize)]
+        struct Arrays {
+            bytes: [u8; 3],
+            values: [u32; 2],
+        }
+
+        let data = Arrays {
+            bytes: [1, 2, 3],
+            values: [4, 5],
+        };
+        let serialized_data = data.serialize_data().unwrap();
+        assert_eq!(Arrays::deserialize_data(&serialized_data).unwrap(), data);
+    }
+
+    #[test]
+    fn derived_enum_round_trip() {
+        #[derive(Debug, PartialEq, CdrSerialize, CdrDeserialize, DdsSerialize, DdsDeserialize)]
+        #[repr(u32)]
+        enum Color {
+            Red,
+            Green = 10,
+            Blue,
+        }
+
+        for data in [Color::Red, Color::Green, Color::Blue] {
+            let serialized_data = data.serialize_data().unwrap();
+            assert_eq!(Color::deserialize_data(&serialized_data).unwrap(), data);
+        }
+    }
 }
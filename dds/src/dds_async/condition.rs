@@ -1,11 +1,16 @@
 use crate::{
     implementation::{
         actor::ActorAddress,
-        actors::status_condition_actor::{self, StatusConditionActor},
+        actors::{
+            data_reader_actor::{self, DataReaderActor},
+            status_condition_actor::{self, StatusConditionActor},
+        },
         runtime::{executor::ExecutorHandle, timer::TimerHandle},
     },
-    infrastructure::{error::DdsResult, status::StatusKind},
+    infrastructure::{error::DdsResult, instance::InstanceHandle, status::StatusKind},
+    subscription::sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
 };
+use std::ops::Deref;
 
 /// Async version of [`StatusCondition`](crate::infrastructure::condition::StatusCondition).
 #[derive(Clone)]
@@ -15,6 +20,12 @@ pub struct StatusConditionAsync {
     timer_handle: TimerHandle,
 }
 
+impl PartialEq for StatusConditionAsync {
+    fn eq(&self, other: &Self) -> bool {
+        self.address == other.address
+    }
+}
+
 impl StatusConditionAsync {
     pub(crate) fn new(
         address: ActorAddress<StatusConditionActor>,
@@ -83,3 +94,145 @@ impl StatusConditionAsync {
             .await)
     }
 }
+
+/// Async version of [`ReadCondition`](crate::infrastructure::condition::ReadCondition).
+#[derive(Clone)]
+pub struct ReadConditionAsync {
+    reader_address: ActorAddress<DataReaderActor>,
+    reader_handle: InstanceHandle,
+    condition_id: u32,
+    sample_state_mask: Vec<SampleStateKind>,
+    view_state_mask: Vec<ViewStateKind>,
+    instance_state_mask: Vec<InstanceStateKind>,
+    timer_handle: TimerHandle,
+}
+
+impl PartialEq for ReadConditionAsync {
+    fn eq(&self, other: &Self) -> bool {
+        self.reader_handle == other.reader_handle && self.condition_id == other.condition_id
+    }
+}
+
+impl ReadConditionAsync {
+    pub(crate) fn new(
+        reader_address: ActorAddress<DataReaderActor>,
+        reader_handle: InstanceHandle,
+        condition_id: u32,
+        sample_state_mask: Vec<SampleStateKind>,
+        view_state_mask: Vec<ViewStateKind>,
+        instance_state_mask: Vec<InstanceStateKind>,
+        timer_handle: TimerHandle,
+    ) -> Self {
+        Self {
+            reader_address,
+            reader_handle,
+            condition_id,
+            sample_state_mask,
+            view_state_mask,
+            instance_state_mask,
+            timer_handle,
+        }
+    }
+
+    pub(crate) fn timer_handle(&self) -> &TimerHandle {
+        &self.timer_handle
+    }
+
+    pub(crate) fn reader_handle(&self) -> InstanceHandle {
+        self.reader_handle
+    }
+
+    pub(crate) fn condition_id(&self) -> u32 {
+        self.condition_id
+    }
+}
+
+impl ReadConditionAsync {
+    /// Async version of [`get_sample_state_mask`](crate::infrastructure::condition::ReadCondition::get_sample_state_mask).
+    #[tracing::instrument(skip(self))]
+    pub fn get_sample_state_mask(&self) -> Vec<SampleStateKind> {
+        self.sample_state_mask.clone()
+    }
+
+    /// Async version of [`get_view_state_mask`](crate::infrastructure::condition::ReadCondition::get_view_state_mask).
+    #[tracing::instrument(skip(self))]
+    pub fn get_view_state_mask(&self) -> Vec<ViewStateKind> {
+        self.view_state_mask.clone()
+    }
+
+    /// Async version of [`get_instance_state_mask`](crate::infrastructure::condition::ReadCondition::get_instance_state_mask).
+    #[tracing::instrument(skip(self))]
+    pub fn get_instance_state_mask(&self) -> Vec<InstanceStateKind> {
+        self.instance_state_mask.clone()
+    }
+}
+
+impl ReadConditionAsync {
+    /// Async version of [`get_trigger_value`](crate::infrastructure::condition::ReadCondition::get_trigger_value).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_trigger_value(&self) -> DdsResult<bool> {
+        self.reader_address
+            .send_actor_mail(data_reader_actor::GetReadConditionTriggerValue {
+                condition_id: self.condition_id,
+            })?
+            .receive_reply()
+            .await
+    }
+}
+
+/// Async version of [`QueryCondition`](crate::infrastructure::condition::QueryCondition).
+#[derive(Clone, PartialEq)]
+pub struct QueryConditionAsync {
+    read_condition: ReadConditionAsync,
+    query_expression: String,
+}
+
+impl QueryConditionAsync {
+    pub(crate) fn new(read_condition: ReadConditionAsync, query_expression: String) -> Self {
+        Self {
+            read_condition,
+            query_expression,
+        }
+    }
+}
+
+impl Deref for QueryConditionAsync {
+    type Target = ReadConditionAsync;
+
+    fn deref(&self) -> &Self::Target {
+        &self.read_condition
+    }
+}
+
+impl QueryConditionAsync {
+    /// Async version of [`get_query_expression`](crate::infrastructure::condition::QueryCondition::get_query_expression).
+    #[tracing::instrument(skip(self))]
+    pub fn get_query_expression(&self) -> String {
+        self.query_expression.clone()
+    }
+
+    /// Async version of [`get_query_parameters`](crate::infrastructure::condition::QueryCondition::get_query_parameters).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_query_parameters(&self) -> DdsResult<Vec<String>> {
+        self.read_condition
+            .reader_address
+            .send_actor_mail(data_reader_actor::GetQueryParameters {
+                condition_id: self.read_condition.condition_id,
+            })?
+            .receive_reply()
+            .await
+    }
+
+    /// Async version of [`set_query_parameters`](crate::infrastructure::condition::QueryCondition::set_query_parameters).
+    #[tracing::instrument(skip(self))]
+    pub async fn set_query_parameters(&self, query_parameters: &[String]) -> DdsResult<()> {
+        self.read_condition
+            .reader_address
+            .send_actor_mail(data_reader_actor::SetQueryParameters {
+                condition_id: self.read_condition.condition_id,
+                query_parameters: query_parameters.to_vec(),
+            })?
+            .receive_reply()
+            .await
+    }
+}
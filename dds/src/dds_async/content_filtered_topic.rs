@@ -0,0 +1,87 @@
+use crate::{
+    implementation::actors::data_reader_actor::QueryFilter,
+    topic_definition::topic_description::TopicDescription,
+};
+
+use super::topic::TopicAsync;
+
+/// Async version of [`ContentFilteredTopic`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic).
+#[derive(Clone)]
+pub struct ContentFilteredTopicAsync {
+    topic_name: String,
+    related_topic: TopicAsync,
+    filter_expression: String,
+    expression_parameters: Vec<String>,
+    filter: QueryFilter,
+}
+
+impl ContentFilteredTopicAsync {
+    pub(crate) fn new(
+        topic_name: String,
+        related_topic: TopicAsync,
+        filter_expression: String,
+        expression_parameters: Vec<String>,
+        filter: QueryFilter,
+    ) -> Self {
+        Self {
+            topic_name,
+            related_topic,
+            filter_expression,
+            expression_parameters,
+            filter,
+        }
+    }
+}
+
+impl ContentFilteredTopicAsync {
+    /// Async version of [`get_filter_expression`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic::get_filter_expression).
+    #[tracing::instrument(skip(self))]
+    pub fn get_filter_expression(&self) -> String {
+        self.filter_expression.clone()
+    }
+
+    /// Async version of [`get_expression_parameters`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic::get_expression_parameters).
+    #[tracing::instrument(skip(self))]
+    pub fn get_expression_parameters(&self) -> Vec<String> {
+        self.expression_parameters.clone()
+    }
+
+    /// Async version of [`get_related_topic`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic::get_related_topic).
+    #[tracing::instrument(skip(self))]
+    pub fn get_related_topic(&self) -> TopicAsync {
+        self.related_topic.clone()
+    }
+}
+
+/// This implementation block represents the TopicDescription operations for the [`ContentFilteredTopicAsync`].
+impl ContentFilteredTopicAsync {
+    /// Async version of [`get_type_name`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic::get_type_name).
+    #[tracing::instrument(skip(self))]
+    pub fn get_type_name(&self) -> String {
+        self.related_topic.get_type_name()
+    }
+
+    /// Async version of [`get_name`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic::get_name).
+    #[tracing::instrument(skip(self))]
+    pub fn get_name(&self) -> String {
+        self.topic_name.clone()
+    }
+}
+
+impl TopicDescription for ContentFilteredTopicAsync {
+    fn get_type_name(&self) -> String {
+        self.get_type_name()
+    }
+
+    fn get_name(&self) -> String {
+        self.get_name()
+    }
+
+    fn related_topic_async(&self) -> &TopicAsync {
+        &self.related_topic
+    }
+
+    fn content_filter(&self) -> Option<QueryFilter> {
+        Some(self.filter.clone())
+    }
+}
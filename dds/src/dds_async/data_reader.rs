@@ -9,10 +9,11 @@ use crate::{
             any_data_reader_listener::AnyDataReaderListener,
             data_reader_actor::{self, DataReaderActor},
             domain_participant_actor::{self, DomainParticipantActor},
-            status_condition_actor::StatusConditionActor,
+            status_condition_actor::{self, StatusConditionActor},
             subscriber_actor::{self, SubscriberActor},
             topic_actor,
         },
+        query_expression::QueryExpression,
     },
     infrastructure::{
         error::{DdsError, DdsResult},
@@ -22,7 +23,7 @@ use crate::{
             LivelinessChangedStatus, RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus,
             SampleLostStatus, SampleRejectedStatus, StatusKind, SubscriptionMatchedStatus,
         },
-        time::Duration,
+        time::{Duration, Time},
     },
     subscription::{
         data_reader::Sample,
@@ -30,13 +31,18 @@ use crate::{
             InstanceStateKind, SampleStateKind, ViewStateKind, ANY_INSTANCE_STATE, ANY_VIEW_STATE,
         },
     },
+    topic_definition::type_support::{
+        deserialize_rtps_classic_cdr, DdsDeserialize, DdsGetField, DdsKey, DdsSerialize,
+    },
 };
 
 use std::marker::PhantomData;
 
 use super::{
-    condition::StatusConditionAsync, data_reader_listener::DataReaderListenerAsync,
-    subscriber::SubscriberAsync, topic::TopicAsync,
+    condition::{QueryConditionAsync, ReadConditionAsync, StatusConditionAsync},
+    data_reader_listener::DataReaderListenerAsync,
+    subscriber::SubscriberAsync,
+    topic::TopicAsync,
 };
 
 /// Async version of [`DataReader`](crate::subscription::data_reader::DataReader).
@@ -76,6 +82,41 @@ impl<Foo> DataReaderAsync<Foo> {
         &self.reader_address
     }
 
+    async fn check_condition_is_attached(&self, a_condition: &ReadConditionAsync) -> DdsResult<()> {
+        if a_condition.reader_handle() == self.get_instance_handle().await? {
+            Ok(())
+        } else {
+            Err(DdsError::PreconditionNotMet(
+                "Condition is not attached to the DataReader".to_string(),
+            ))
+        }
+    }
+
+    async fn get_current_time(&self) -> DdsResult<Time> {
+        Ok(self
+            .participant_address()
+            .send_actor_mail(domain_participant_actor::GetCurrentTime)?
+            .receive_reply()
+            .await)
+    }
+
+    fn add_samples_read(&self, number_of_samples: usize) -> DdsResult<()> {
+        self.participant_address()
+            .send_actor_mail(domain_participant_actor::AddSamplesRead { number_of_samples })?;
+        Ok(())
+    }
+
+    fn remove_data_on_readers_state(&self) -> DdsResult<()> {
+        // Reading or taking from any reader resets the DATA_ON_READERS status of its subscriber
+        self.subscriber
+            .get_statuscondition()
+            .address()
+            .send_actor_mail(status_condition_actor::RemoveCommunicationState {
+                state: StatusKind::DataOnReaders,
+            })?;
+        Ok(())
+    }
+
     async fn announce_reader(&self) -> DdsResult<()> {
         let builtin_publisher = self
             .get_subscriber()
@@ -157,6 +198,8 @@ impl<Foo> DataReaderAsync<Foo> {
         view_states: &[ViewStateKind],
         instance_states: &[InstanceStateKind],
     ) -> DdsResult<Vec<Sample<Foo>>> {
+        let now = self.get_current_time().await?;
+        self.remove_data_on_readers_state()?;
         let samples = self
             .reader_address
             .send_actor_mail(data_reader_actor::Read {
@@ -165,9 +208,11 @@ impl<Foo> DataReaderAsync<Foo> {
                 view_states: view_states.to_vec(),
                 instance_states: instance_states.to_vec(),
                 specific_instance_handle: None,
+                now,
             })?
             .receive_reply()
             .await?;
+        self.add_samples_read(samples.len())?;
 
         Ok(samples
             .into_iter()
@@ -184,6 +229,8 @@ impl<Foo> DataReaderAsync<Foo> {
         view_states: &[ViewStateKind],
         instance_states: &[InstanceStateKind],
     ) -> DdsResult<Vec<Sample<Foo>>> {
+        let now = self.get_current_time().await?;
+        self.remove_data_on_readers_state()?;
         let samples = self
             .reader_address
             .send_actor_mail(data_reader_actor::Take {
@@ -192,9 +239,65 @@ impl<Foo> DataReaderAsync<Foo> {
                 view_states: view_states.to_vec(),
                 instance_states: instance_states.to_vec(),
                 specific_instance_handle: None,
+                now,
             })?
             .receive_reply()
             .await?;
+        self.add_samples_read(samples.len())?;
+
+        Ok(samples
+            .into_iter()
+            .map(|(data, sample_info)| Sample::new(data, sample_info))
+            .collect())
+    }
+
+    /// Async version of [`read_w_condition`](crate::subscription::data_reader::DataReader::read_w_condition).
+    #[tracing::instrument(skip(self, a_condition))]
+    pub async fn read_w_condition(
+        &self,
+        max_samples: i32,
+        a_condition: &ReadConditionAsync,
+    ) -> DdsResult<Vec<Sample<Foo>>> {
+        self.check_condition_is_attached(a_condition).await?;
+        let now = self.get_current_time().await?;
+        self.remove_data_on_readers_state()?;
+        let samples = self
+            .reader_address
+            .send_actor_mail(data_reader_actor::ReadWCondition {
+                max_samples,
+                condition_id: a_condition.condition_id(),
+                now,
+            })?
+            .receive_reply()
+            .await?;
+        self.add_samples_read(samples.len())?;
+
+        Ok(samples
+            .into_iter()
+            .map(|(data, sample_info)| Sample::new(data, sample_info))
+            .collect())
+    }
+
+    /// Async version of [`take_w_condition`](crate::subscription::data_reader::DataReader::take_w_condition).
+    #[tracing::instrument(skip(self, a_condition))]
+    pub async fn take_w_condition(
+        &self,
+        max_samples: i32,
+        a_condition: &ReadConditionAsync,
+    ) -> DdsResult<Vec<Sample<Foo>>> {
+        self.check_condition_is_attached(a_condition).await?;
+        let now = self.get_current_time().await?;
+        self.remove_data_on_readers_state()?;
+        let samples = self
+            .reader_address
+            .send_actor_mail(data_reader_actor::TakeWCondition {
+                max_samples,
+                condition_id: a_condition.condition_id(),
+                now,
+            })?
+            .receive_reply()
+            .await?;
+        self.add_samples_read(samples.len())?;
 
         Ok(samples
             .into_iter()
@@ -205,6 +308,8 @@ impl<Foo> DataReaderAsync<Foo> {
     /// Async version of [`read_next_sample`](crate::subscription::data_reader::DataReader::read_next_sample).
     #[tracing::instrument(skip(self))]
     pub async fn read_next_sample(&self) -> DdsResult<Sample<Foo>> {
+        let now = self.get_current_time().await?;
+        self.remove_data_on_readers_state()?;
         let mut samples = self
             .reader_address
             .send_actor_mail(data_reader_actor::Read {
@@ -213,9 +318,11 @@ impl<Foo> DataReaderAsync<Foo> {
                 view_states: ANY_VIEW_STATE.to_vec(),
                 instance_states: ANY_INSTANCE_STATE.to_vec(),
                 specific_instance_handle: None,
+                now,
             })?
             .receive_reply()
             .await?;
+        self.add_samples_read(samples.len())?;
         let (data, sample_info) = samples.pop().expect("Would return NoData if empty");
         Ok(Sample::new(data, sample_info))
     }
@@ -223,6 +330,8 @@ impl<Foo> DataReaderAsync<Foo> {
     /// Async version of [`take_next_sample`](crate::subscription::data_reader::DataReader::take_next_sample).
     #[tracing::instrument(skip(self))]
     pub async fn take_next_sample(&self) -> DdsResult<Sample<Foo>> {
+        let now = self.get_current_time().await?;
+        self.remove_data_on_readers_state()?;
         let mut samples = self
             .reader_address
             .send_actor_mail(data_reader_actor::Take {
@@ -231,9 +340,11 @@ impl<Foo> DataReaderAsync<Foo> {
                 view_states: ANY_VIEW_STATE.to_vec(),
                 instance_states: ANY_INSTANCE_STATE.to_vec(),
                 specific_instance_handle: None,
+                now,
             })?
             .receive_reply()
             .await?;
+        self.add_samples_read(samples.len())?;
         let (data, sample_info) = samples.pop().expect("Would return NoData if empty");
         Ok(Sample::new(data, sample_info))
     }
@@ -248,6 +359,8 @@ impl<Foo> DataReaderAsync<Foo> {
         view_states: &[ViewStateKind],
         instance_states: &[InstanceStateKind],
     ) -> DdsResult<Vec<Sample<Foo>>> {
+        let now = self.get_current_time().await?;
+        self.remove_data_on_readers_state()?;
         let samples = self
             .reader_address
             .send_actor_mail(data_reader_actor::Read {
@@ -256,9 +369,11 @@ impl<Foo> DataReaderAsync<Foo> {
                 view_states: view_states.to_vec(),
                 instance_states: instance_states.to_vec(),
                 specific_instance_handle: Some(a_handle),
+                now,
             })?
             .receive_reply()
             .await?;
+        self.add_samples_read(samples.len())?;
         Ok(samples
             .into_iter()
             .map(|(data, sample_info)| Sample::new(data, sample_info))
@@ -275,6 +390,8 @@ impl<Foo> DataReaderAsync<Foo> {
         view_states: &[ViewStateKind],
         instance_states: &[InstanceStateKind],
     ) -> DdsResult<Vec<Sample<Foo>>> {
+        let now = self.get_current_time().await?;
+        self.remove_data_on_readers_state()?;
         let samples = self
             .reader_address
             .send_actor_mail(data_reader_actor::Take {
@@ -283,9 +400,11 @@ impl<Foo> DataReaderAsync<Foo> {
                 view_states: view_states.to_vec(),
                 instance_states: instance_states.to_vec(),
                 specific_instance_handle: Some(a_handle),
+                now,
             })?
             .receive_reply()
             .await?;
+        self.add_samples_read(samples.len())?;
 
         Ok(samples
             .into_iter()
@@ -303,6 +422,8 @@ impl<Foo> DataReaderAsync<Foo> {
         view_states: &[ViewStateKind],
         instance_states: &[InstanceStateKind],
     ) -> DdsResult<Vec<Sample<Foo>>> {
+        let now = self.get_current_time().await?;
+        self.remove_data_on_readers_state()?;
         let samples = self
             .reader_address
             .send_actor_mail(data_reader_actor::ReadNextInstance {
@@ -311,9 +432,11 @@ impl<Foo> DataReaderAsync<Foo> {
                 sample_states: sample_states.to_vec(),
                 view_states: view_states.to_vec(),
                 instance_states: instance_states.to_vec(),
+                now,
             })?
             .receive_reply()
             .await?;
+        self.add_samples_read(samples.len())?;
         Ok(samples
             .into_iter()
             .map(|(data, sample_info)| Sample::new(data, sample_info))
@@ -330,6 +453,8 @@ impl<Foo> DataReaderAsync<Foo> {
         view_states: &[ViewStateKind],
         instance_states: &[InstanceStateKind],
     ) -> DdsResult<Vec<Sample<Foo>>> {
+        let now = self.get_current_time().await?;
+        self.remove_data_on_readers_state()?;
         let samples = self
             .reader_address
             .send_actor_mail(data_reader_actor::TakeNextInstance {
@@ -338,9 +463,11 @@ impl<Foo> DataReaderAsync<Foo> {
                 sample_states: sample_states.to_vec(),
                 view_states: view_states.to_vec(),
                 instance_states: instance_states.to_vec(),
+                now,
             })?
             .receive_reply()
             .await?;
+        self.add_samples_read(samples.len())?;
         Ok(samples
             .into_iter()
             .map(|(data, sample_info)| Sample::new(data, sample_info))
@@ -348,19 +475,91 @@ impl<Foo> DataReaderAsync<Foo> {
     }
 
     /// Async version of [`get_key_value`](crate::subscription::data_reader::DataReader::get_key_value).
-    #[tracing::instrument(skip(self, _key_holder))]
-    pub async fn get_key_value(
-        &self,
-        _key_holder: &mut Foo,
-        _handle: InstanceHandle,
-    ) -> DdsResult<()> {
-        todo!()
+    #[tracing::instrument(skip(self, key_holder))]
+    pub async fn get_key_value(&self, key_holder: &mut Foo, handle: InstanceHandle) -> DdsResult<()>
+    where
+        Foo: DdsKey,
+    {
+        let serialized_key = self
+            .reader_address
+            .send_actor_mail(data_reader_actor::GetKeyValue { handle })?
+            .receive_reply()
+            .await?;
+        let key = deserialize_rtps_classic_cdr::<Foo::Key>(&mut serialized_key.as_slice())?;
+        key_holder.set_key_fields(key)
     }
 
     /// Async version of [`lookup_instance`](crate::subscription::data_reader::DataReader::lookup_instance).
-    #[tracing::instrument(skip(self, _instance))]
-    pub async fn lookup_instance(&self, _instance: &Foo) -> DdsResult<Option<InstanceHandle>> {
-        todo!()
+    #[tracing::instrument(skip(self, instance))]
+    pub async fn lookup_instance(&self, instance: &Foo) -> DdsResult<Option<InstanceHandle>>
+    where
+        Foo: DdsSerialize,
+    {
+        let type_support = self
+            .topic
+            .topic_address()
+            .send_actor_mail(topic_actor::GetTypeSupport)?
+            .receive_reply()
+            .await;
+
+        let serialized_foo = instance.serialize_data()?;
+        let instance_handle = type_support.instance_handle_from_serialized_foo(&serialized_foo)?;
+
+        self.reader_address
+            .send_actor_mail(data_reader_actor::LookupInstance { instance_handle })?
+            .receive_reply()
+            .await
+    }
+
+    /// Async version of [`create_readcondition`](crate::subscription::data_reader::DataReader::create_readcondition).
+    #[tracing::instrument(skip(self))]
+    pub async fn create_readcondition(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> DdsResult<ReadConditionAsync> {
+        let condition_id = self
+            .reader_address
+            .send_actor_mail(data_reader_actor::CreateReadCondition {
+                sample_states: sample_states.to_vec(),
+                view_states: view_states.to_vec(),
+                instance_states: instance_states.to_vec(),
+            })?
+            .receive_reply()
+            .await;
+
+        Ok(ReadConditionAsync::new(
+            self.reader_address.clone(),
+            self.get_instance_handle().await?,
+            condition_id,
+            sample_states.to_vec(),
+            view_states.to_vec(),
+            instance_states.to_vec(),
+            self.subscriber.get_participant().timer_handle().clone(),
+        ))
+    }
+
+    /// Async version of [`delete_readcondition`](crate::subscription::data_reader::DataReader::delete_readcondition).
+    #[tracing::instrument(skip(self, a_condition))]
+    pub async fn delete_readcondition(&self, a_condition: &ReadConditionAsync) -> DdsResult<()> {
+        self.check_condition_is_attached(a_condition).await?;
+        self.reader_address
+            .send_actor_mail(data_reader_actor::DeleteReadCondition {
+                condition_id: a_condition.condition_id(),
+            })?
+            .receive_reply()
+            .await
+    }
+
+    /// Async version of [`delete_contained_entities`](crate::subscription::data_reader::DataReader::delete_contained_entities).
+    #[tracing::instrument(skip(self))]
+    pub async fn delete_contained_entities(&self) -> DdsResult<()> {
+        self.reader_address
+            .send_actor_mail(data_reader_actor::DeleteContainedReadConditions)?
+            .receive_reply()
+            .await;
+        Ok(())
     }
 }
 
@@ -368,7 +567,11 @@ impl<Foo> DataReaderAsync<Foo> {
     /// Async version of [`get_liveliness_changed_status`](crate::subscription::data_reader::DataReader::get_liveliness_changed_status).
     #[tracing::instrument(skip(self))]
     pub async fn get_liveliness_changed_status(&self) -> DdsResult<LivelinessChangedStatus> {
-        todo!()
+        Ok(self
+            .reader_address
+            .send_actor_mail(data_reader_actor::GetLivelinessChangedStatus)?
+            .receive_reply()
+            .await)
     }
 
     /// Async version of [`get_requested_deadline_missed_status`](crate::subscription::data_reader::DataReader::get_requested_deadline_missed_status).
@@ -384,19 +587,31 @@ impl<Foo> DataReaderAsync<Foo> {
     pub async fn get_requested_incompatible_qos_status(
         &self,
     ) -> DdsResult<RequestedIncompatibleQosStatus> {
-        todo!()
+        Ok(self
+            .reader_address
+            .send_actor_mail(data_reader_actor::GetRequestedIncompatibleQosStatus)?
+            .receive_reply()
+            .await)
     }
 
     /// Async version of [`get_sample_lost_status`](crate::subscription::data_reader::DataReader::get_sample_lost_status).
     #[tracing::instrument(skip(self))]
     pub async fn get_sample_lost_status(&self) -> DdsResult<SampleLostStatus> {
-        todo!()
+        Ok(self
+            .reader_address
+            .send_actor_mail(data_reader_actor::GetSampleLostStatus)?
+            .receive_reply()
+            .await)
     }
 
     /// Async version of [`get_sample_rejected_status`](crate::subscription::data_reader::DataReader::get_sample_rejected_status).
     #[tracing::instrument(skip(self))]
     pub async fn get_sample_rejected_status(&self) -> DdsResult<SampleRejectedStatus> {
-        todo!()
+        Ok(self
+            .reader_address
+            .send_actor_mail(data_reader_actor::GetSampleRejectedStatus)?
+            .receive_reply()
+            .await)
     }
 
     /// Async version of [`get_subscription_matched_status`](crate::subscription::data_reader::DataReader::get_subscription_matched_status).
@@ -575,3 +790,55 @@ where
             .await
     }
 }
+
+impl<Foo> DataReaderAsync<Foo>
+where
+    Foo: DdsGetField + for<'de> DdsDeserialize<'de>,
+{
+    /// Async version of [`create_querycondition`](crate::subscription::data_reader::DataReader::create_querycondition).
+    #[tracing::instrument(skip(self))]
+    pub async fn create_querycondition(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+        query_expression: &str,
+        query_parameters: &[String],
+    ) -> DdsResult<QueryConditionAsync> {
+        let condition_id = self
+            .reader_address
+            .send_actor_mail(data_reader_actor::CreateQueryCondition {
+                sample_states: sample_states.to_vec(),
+                view_states: view_states.to_vec(),
+                instance_states: instance_states.to_vec(),
+                query_expression: query_expression.to_string(),
+                query_parameters: query_parameters.to_vec(),
+                evaluator: evaluate_query::<Foo>,
+            })?
+            .receive_reply()
+            .await?;
+
+        Ok(QueryConditionAsync::new(
+            ReadConditionAsync::new(
+                self.reader_address.clone(),
+                self.get_instance_handle().await?,
+                condition_id,
+                sample_states.to_vec(),
+                view_states.to_vec(),
+                instance_states.to_vec(),
+                self.subscriber.get_participant().timer_handle().clone(),
+            ),
+            query_expression.to_string(),
+        ))
+    }
+}
+
+pub(crate) fn evaluate_query<Foo>(query: &QueryExpression, serialized_data: &[u8]) -> bool
+where
+    Foo: DdsGetField + for<'de> DdsDeserialize<'de>,
+{
+    match Foo::deserialize_data(serialized_data) {
+        Ok(sample) => query.evaluate(&sample),
+        Err(_) => false,
+    }
+}
@@ -39,6 +39,7 @@ use crate::{
     rtps::{
         messages::submessage_elements::{Data, Parameter, ParameterList},
         types::ChangeKind,
+        writer_history_cache::RtpsWriterCacheChange,
     },
     serialized_payload::cdr::serialize::CdrSerialize,
     topic_definition::type_support::DdsSerialize,
@@ -98,6 +99,23 @@ impl<Foo> DataWriterAsync<Foo> {
         &self.writer_address
     }
 
+    async fn add_change(&self, add_change: data_writer_actor::AddChange) -> DdsResult<()> {
+        // While the publications are suspended or a coherent set is open the publisher holds
+        // on to the change and adds it to the writer history when they are resumed
+        if let Some(add_change) = self
+            .publisher_address()
+            .send_actor_mail(publisher_actor::StageChange { add_change })?
+            .receive_reply()
+            .await
+        {
+            self.writer_address
+                .send_actor_mail(add_change)?
+                .receive_reply()
+                .await;
+        }
+        Ok(())
+    }
+
     async fn announce_writer(&self) -> DdsResult<()> {
         let builtin_publisher = self
             .get_publisher()
@@ -319,15 +337,18 @@ where
             .receive_reply()
             .await;
 
+        self.add_change(data_writer_actor::AddChange {
+            change,
+            now,
+            message_sender_actor,
+            writer_address: self.writer_address.clone(),
+            executor_handle: self.publisher.get_participant().executor_handle().clone(),
+            participant: self.publisher.get_participant(),
+        })
+        .await?;
+
         self.writer_address
-            .send_actor_mail(data_writer_actor::AddChange {
-                change,
-                now,
-                message_sender_actor,
-                writer_address: self.writer_address.clone(),
-                executor_handle: self.publisher.get_participant().executor_handle().clone(),
-                timer_handle: self.publisher.get_participant().timer_handle().clone(),
-            })?
+            .send_actor_mail(data_writer_actor::UnregisterInstance { instance_handle })?
             .receive_reply()
             .await;
 
@@ -396,16 +417,53 @@ where
             .send_actor_mail(data_writer_actor::GetQos)?
             .receive_reply()
             .await;
-        let type_support = self
-            .topic
-            .topic_address()
-            .send_actor_mail(topic_actor::GetTypeSupport)?
+        let new_change = self.new_alive_change(data, handle, timestamp).await?;
+        let change = self
+            .writer_address
+            .send_actor_mail(new_change)?
             .receive_reply()
             .await;
 
-        let serialized_data = data.serialize_data()?;
-        let key = type_support.instance_handle_from_serialized_foo(&serialized_data)?;
+        self.write_change(change, &writer_qos).await
+    }
+
+    /// Async version of [`write_batch`](crate::publication::data_writer::DataWriter::write_batch).
+    #[tracing::instrument(skip(self, data))]
+    pub async fn write_batch(&self, data: &[Foo], timestamp: Option<Time>) -> DdsResult<()> {
+        if !self
+            .writer_address
+            .send_actor_mail(data_writer_actor::IsEnabled)?
+            .receive_reply()
+            .await
+        {
+            return Err(DdsError::NotEnabled);
+        }
 
+        let writer_qos = self
+            .writer_address
+            .send_actor_mail(data_writer_actor::GetQos)?
+            .receive_reply()
+            .await;
+        let timestamp = match timestamp {
+            Some(t) => t,
+            None => {
+                self.participant_address()
+                    .send_actor_mail(domain_participant_actor::GetCurrentTime)?
+                    .receive_reply()
+                    .await
+            }
+        };
+        let mut new_change_list = Vec::with_capacity(data.len());
+        for sample in data {
+            new_change_list.push(self.new_alive_change(sample, None, timestamp).await?);
+        }
+
+        // The changes are created with a single mail so that they get consecutive sequence numbers
+        let change_list = self
+            .writer_address
+            .send_actor_mail(data_writer_actor::NewChangeList { new_change_list })?
+            .receive_reply()
+            .await;
         let message_sender_actor = self
             .participant_address()
             .send_actor_mail(domain_participant_actor::GetMessageSender)?
@@ -417,27 +475,133 @@ where
             .receive_reply()
             .await;
 
-        let instance_handle = match handle {
-            Some(_) => todo!(),
-            None => self.register_instance_w_timestamp(data, timestamp),
+        // The whole batch is checked against the resource limits before any of its changes
+        // is added so that either all the samples are written or none of them
+        let instance_handle_list: Vec<InstanceHandle> = change_list
+            .iter()
+            .map(|change| change.instance_handle().into())
+            .collect();
+        if self
+            .writer_address
+            .send_actor_mail(data_writer_actor::IsResourcesLimitReachedForChangeList {
+                instance_handle_list: instance_handle_list.clone(),
+            })?
+            .receive_reply()
+            .await
+        {
+            return Err(DdsError::OutOfResources);
         }
-        .await?
-        .ok_or(DdsError::PreconditionNotMet(
-            "Failed to register instance".to_string(),
-        ))?;
+
+        self.wait_for_history_space(&instance_handle_list, &writer_qos)
+            .await?;
+
+        let number_of_samples = change_list.len();
+        let executor_handle = self.publisher.get_participant().executor_handle().clone();
+        let participant = self.publisher.get_participant();
+        let mut unstaged_change_list = Vec::with_capacity(change_list.len());
+        for change in change_list {
+            if let Some(add_change) = self
+                .publisher_address()
+                .send_actor_mail(publisher_actor::StageChange {
+                    add_change: data_writer_actor::AddChange {
+                        change,
+                        now,
+                        message_sender_actor: message_sender_actor.clone(),
+                        writer_address: self.writer_address.clone(),
+                        executor_handle: executor_handle.clone(),
+                        participant: participant.clone(),
+                    },
+                })?
+                .receive_reply()
+                .await
+            {
+                unstaged_change_list.push(add_change.change);
+            }
+        }
+        if !unstaged_change_list.is_empty() {
+            self.writer_address
+                .send_actor_mail(data_writer_actor::AddChangeList {
+                    change_list: unstaged_change_list,
+                    now,
+                    message_sender_actor,
+                    writer_address: self.writer_address.clone(),
+                    executor_handle,
+                    participant,
+                })?
+                .receive_reply()
+                .await;
+        }
+
+        // Only the samples which were actually written are counted
+        self.participant_address()
+            .send_actor_mail(domain_participant_actor::AddSamplesWritten { number_of_samples })?;
+
+        Ok(())
+    }
+
+    async fn new_alive_change(
+        &self,
+        data: &Foo,
+        handle: Option<InstanceHandle>,
+        timestamp: Time,
+    ) -> DdsResult<data_writer_actor::NewChange> {
+        let type_support = self
+            .topic
+            .topic_address()
+            .send_actor_mail(topic_actor::GetTypeSupport)?
+            .receive_reply()
+            .await;
+
+        let serialized_data = data.serialize_data()?;
+        let key = type_support.instance_handle_from_serialized_foo(&serialized_data)?;
+
+        let instance_handle = match handle {
+            Some(h) => {
+                if let Some(stored_handle) = self.lookup_instance(data).await? {
+                    if stored_handle == h {
+                        Ok(h)
+                    } else {
+                        Err(DdsError::PreconditionNotMet(
+                            "Handle does not match instance".to_string(),
+                        ))
+                    }
+                } else {
+                    Err(DdsError::BadParameter)
+                }
+            }
+            None => self
+                .register_instance_w_timestamp(data, timestamp)
+                .await?
+                .ok_or(DdsError::PreconditionNotMet(
+                    "Failed to register instance".to_string(),
+                )),
+        }?;
 
         let pid_key_hash = Parameter::new(PID_KEY_HASH, Arc::from(*instance_handle.as_ref()));
         let parameter_list = ParameterList::new(vec![pid_key_hash]);
 
-        let change = self
-            .writer_address
-            .send_actor_mail(data_writer_actor::NewChange {
-                kind: ChangeKind::Alive,
-                data: Data::from(serialized_data),
-                inline_qos: parameter_list,
-                handle: key,
-                timestamp,
-            })?
+        Ok(data_writer_actor::NewChange {
+            kind: ChangeKind::Alive,
+            data: Data::from(serialized_data),
+            inline_qos: parameter_list,
+            handle: key,
+            timestamp,
+        })
+    }
+
+    async fn write_change(
+        &self,
+        change: RtpsWriterCacheChange,
+        writer_qos: &DataWriterQos,
+    ) -> DdsResult<()> {
+        let message_sender_actor = self
+            .participant_address()
+            .send_actor_mail(domain_participant_actor::GetMessageSender)?
+            .receive_reply()
+            .await;
+        let now = self
+            .participant_address()
+            .send_actor_mail(domain_participant_actor::GetCurrentTime)?
             .receive_reply()
             .await;
 
@@ -452,44 +616,68 @@ where
             return Err(DdsError::OutOfResources);
         }
 
-        if writer_qos.reliability.kind == ReliabilityQosPolicyKind::Reliable {
-            let start = std::time::Instant::now();
-            let timer_handle = self.publisher.get_participant().timer_handle().clone();
-            loop {
-                if !self
-                    .writer_address
-                    .send_actor_mail(data_writer_actor::IsDataLostAfterAddingChange {
-                        instance_handle: change.instance_handle().into(),
-                    })?
-                    .receive_reply()
-                    .await
-                {
-                    break;
+        self.wait_for_history_space(&[change.instance_handle().into()], writer_qos)
+            .await?;
+
+        self.add_change(data_writer_actor::AddChange {
+            change,
+            now,
+            message_sender_actor,
+            writer_address: self.writer_address.clone(),
+            executor_handle: self.publisher.get_participant().executor_handle().clone(),
+            participant: self.publisher.get_participant(),
+        })
+        .await?;
+
+        // Only the samples which were actually written are counted
+        self.participant_address().send_actor_mail(
+            domain_participant_actor::AddSamplesWritten {
+                number_of_samples: 1,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    // The write blocks while the send queue of the writer is full or, for reliable writers,
+    // while adding a change would remove a sample not yet acknowledged by all readers
+    async fn wait_for_history_space(
+        &self,
+        instance_handle_list: &[InstanceHandle],
+        writer_qos: &DataWriterQos,
+    ) -> DdsResult<()> {
+        let start = std::time::Instant::now();
+        let timer_handle = self.publisher.get_participant().timer_handle().clone();
+        loop {
+            let is_send_queue_full = self
+                .writer_address
+                .send_actor_mail(data_writer_actor::IsSendQueueFull)?
+                .receive_reply()
+                .await;
+            let mut is_data_lost = false;
+            if writer_qos.reliability.kind == ReliabilityQosPolicyKind::Reliable {
+                for &instance_handle in instance_handle_list {
+                    is_data_lost |= self
+                        .writer_address
+                        .send_actor_mail(data_writer_actor::IsDataLostAfterAddingChange {
+                            instance_handle,
+                        })?
+                        .receive_reply()
+                        .await;
                 }
-                timer_handle
-                    .sleep(std::time::Duration::from_millis(20))
-                    .await;
-                if let DurationKind::Finite(timeout) = writer_qos.reliability.max_blocking_time {
-                    if std::time::Instant::now().duration_since(start) > timeout.into() {
-                        return Err(DdsError::Timeout);
-                    }
+            }
+            if !is_send_queue_full && !is_data_lost {
+                return Ok(());
+            }
+            timer_handle
+                .sleep(std::time::Duration::from_millis(20))
+                .await;
+            if let DurationKind::Finite(timeout) = writer_qos.reliability.max_blocking_time {
+                if std::time::Instant::now().duration_since(start) > timeout.into() {
+                    return Err(DdsError::Timeout);
                 }
             }
         }
-
-        self.writer_address
-            .send_actor_mail(data_writer_actor::AddChange {
-                change,
-                now,
-                message_sender_actor,
-                writer_address: self.writer_address.clone(),
-                executor_handle: self.publisher.get_participant().executor_handle().clone(),
-                timer_handle: self.publisher.get_participant().timer_handle().clone(),
-            })?
-            .receive_reply()
-            .await;
-
-        Ok(())
     }
 
     /// Async version of [`dispose`](crate::publication::data_writer::DataWriter::dispose).
@@ -589,17 +777,15 @@ where
             .receive_reply()
             .await;
 
-        self.writer_address
-            .send_actor_mail(data_writer_actor::AddChange {
-                change,
-                now,
-                message_sender_actor,
-                writer_address: self.writer_address.clone(),
-                executor_handle: self.publisher.get_participant().executor_handle().clone(),
-                timer_handle: self.publisher.get_participant().timer_handle().clone(),
-            })?
-            .receive_reply()
-            .await;
+        self.add_change(data_writer_actor::AddChange {
+            change,
+            now,
+            message_sender_actor,
+            writer_address: self.writer_address.clone(),
+            executor_handle: self.publisher.get_participant().executor_handle().clone(),
+            participant: self.publisher.get_participant(),
+        })
+        .await?;
 
         Ok(())
     }
@@ -609,26 +795,18 @@ impl<Foo> DataWriterAsync<Foo> {
     /// Async version of [`wait_for_acknowledgments`](crate::publication::data_writer::DataWriter::wait_for_acknowledgments).
     #[tracing::instrument(skip(self))]
     pub async fn wait_for_acknowledgments(&self, max_wait: Duration) -> DdsResult<()> {
-        let writer_address = self.writer_address.clone();
+        let acknowledgment = self
+            .writer_address
+            .send_actor_mail(data_writer_actor::WaitForAcknowledgments)?
+            .receive_reply()
+            .await;
         self.publisher
             .get_participant()
             .timer_handle()
-            .timeout(
-                max_wait.into(),
-                Box::pin(async move {
-                    loop {
-                        if writer_address
-                            .send_actor_mail(data_writer_actor::AreAllChangesAcknowledge)?
-                            .receive_reply()
-                            .await
-                        {
-                            return Ok(());
-                        }
-                    }
-                }),
-            )
+            .timeout(max_wait.into(), Box::pin(acknowledgment))
             .await
             .map_err(|_| DdsError::Timeout)?
+            .map_err(|_| DdsError::AlreadyDeleted)
     }
 
     /// Async version of [`get_liveliness_lost_status`](crate::publication::data_writer::DataWriter::get_liveliness_lost_status).
@@ -650,7 +828,11 @@ impl<Foo> DataWriterAsync<Foo> {
     pub async fn get_offered_incompatible_qos_status(
         &self,
     ) -> DdsResult<OfferedIncompatibleQosStatus> {
-        todo!()
+        Ok(self
+            .writer_address
+            .send_actor_mail(data_writer_actor::GetOfferedIncompatibleQosStatus)?
+            .receive_reply()
+            .await)
     }
 
     /// Async version of [`get_publication_matched_status`](crate::publication::data_writer::DataWriter::get_publication_matched_status).
@@ -678,7 +860,27 @@ impl<Foo> DataWriterAsync<Foo> {
     /// Async version of [`assert_liveliness`](crate::publication::data_writer::DataWriter::assert_liveliness).
     #[tracing::instrument(skip(self))]
     pub async fn assert_liveliness(&self) -> DdsResult<()> {
-        todo!()
+        if !self
+            .writer_address
+            .send_actor_mail(data_writer_actor::IsEnabled)?
+            .receive_reply()
+            .await
+        {
+            return Err(DdsError::NotEnabled);
+        }
+
+        let message_sender_actor = self
+            .participant_address()
+            .send_actor_mail(domain_participant_actor::GetMessageSender)?
+            .receive_reply()
+            .await;
+        self.writer_address
+            .send_actor_mail(data_writer_actor::AssertLiveliness {
+                message_sender_actor,
+            })?
+            .receive_reply()
+            .await;
+        Ok(())
     }
 
     /// Async version of [`get_matched_subscription_data`](crate::publication::data_writer::DataWriter::get_matched_subscription_data).
@@ -693,7 +895,6 @@ impl<Foo> DataWriterAsync<Foo> {
             })?
             .receive_reply()
             .await
-            .ok_or(DdsError::BadParameter)
     }
 
     /// Async version of [`get_matched_subscriptions`](crate::publication::data_writer::DataWriter::get_matched_subscriptions).
@@ -782,7 +983,7 @@ impl<Foo> DataWriterAsync<Foo> {
                     data_writer_address: writer.clone(),
                     message_sender_actor,
                     executor_handle: self.publisher.get_participant().executor_handle().clone(),
-                    timer_handle: self.publisher.get_participant().timer_handle().clone(),
+                    participant: self.publisher.get_participant(),
                 })?
                 .receive_reply()
                 .await;
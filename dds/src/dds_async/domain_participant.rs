@@ -1,43 +1,55 @@
-use std::sync::Arc;
+use std::sync::{atomic::Ordering, Arc};
 
 use crate::{
     builtin_topics::{ParticipantBuiltinTopicData, TopicBuiltinTopicData},
     data_representation_builtin_endpoints::{
-        discovered_topic_data::DCPS_TOPIC,
+        discovered_reader_data::{DiscoveredReaderData, DCPS_SUBSCRIPTION},
+        discovered_topic_data::{DiscoveredTopicData, DCPS_TOPIC},
+        discovered_writer_data::{DiscoveredWriterData, DCPS_PUBLICATION},
         spdp_discovered_participant_data::{SpdpDiscoveredParticipantData, DCPS_PARTICIPANT},
     },
-    domain::domain_participant_factory::DomainId,
+    domain::{domain_participant::DomainParticipantStats, domain_participant_factory::DomainId},
     implementation::{
         actor::{Actor, ActorAddress},
         actors::{
-            data_reader_actor, data_writer_actor,
+            data_reader_actor::{self, QueryFilter},
+            data_writer_actor,
             domain_participant_actor::{
                 self, DomainParticipantActor, FooTypeSupport, BUILT_IN_TOPIC_NAME_LIST,
             },
-            publisher_actor,
+            message_sender_actor, publisher_actor,
             status_condition_actor::StatusConditionActor,
             subscriber_actor::{self, SubscriberActor},
             topic_actor::{self, TopicActor},
         },
+        query_expression::QueryExpression,
         runtime::{executor::ExecutorHandle, timer::TimerHandle},
     },
     infrastructure::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DomainParticipantQos, PublisherQos, QosKind, SubscriberQos, TopicQos},
+        qos_policy::{LivelinessQosPolicyKind, TimeSourceQosPolicy},
         status::StatusKind,
         time::{Duration, Time},
     },
-    topic_definition::type_support::{DdsHasKey, DdsKey, DdsTypeXml, DynamicTypeInterface},
+    topic_definition::type_support::{
+        DdsDeserialize, DdsGetField, DdsHasKey, DdsKey, DdsTypeXml, DynamicTypeInterface,
+    },
 };
 
 use super::{
-    condition::StatusConditionAsync, domain_participant_listener::DomainParticipantListenerAsync,
+    condition::StatusConditionAsync, content_filtered_topic::ContentFilteredTopicAsync,
+    data_reader::evaluate_query, domain_participant_listener::DomainParticipantListenerAsync,
     publisher::PublisherAsync, publisher_listener::PublisherListenerAsync,
     subscriber::SubscriberAsync, subscriber_listener::SubscriberListenerAsync, topic::TopicAsync,
     topic_listener::TopicListenerAsync,
 };
 
+// The application can change the logical time at any moment without notifying the
+// participant so the timers following it check its value periodically
+const LOGICAL_TIME_POLL_PERIOD: std::time::Duration = std::time::Duration::from_millis(10);
+
 /// Async version of [`DomainParticipant`](crate::domain::domain_participant::DomainParticipant).
 #[derive(Clone)]
 pub struct DomainParticipantAsync {
@@ -83,6 +95,26 @@ impl DomainParticipantAsync {
         &self.timer_handle
     }
 
+    /// Waits until the duration has elapsed according to the time source of the participant.
+    /// Returns an error if the participant is deleted while waiting.
+    pub(crate) async fn sleep(&self, duration: std::time::Duration) -> DdsResult<()> {
+        match self.get_qos().await?.time_source {
+            TimeSourceQosPolicy::SystemTime => self.timer_handle.sleep(duration).await,
+            TimeSourceQosPolicy::Logical(logical_time) => {
+                let end = logical_time
+                    .load(Ordering::Acquire)
+                    .saturating_add(duration.as_nanos() as u64);
+                while logical_time.load(Ordering::Acquire) < end {
+                    if self.participant_address.is_closed() {
+                        return Err(DdsError::AlreadyDeleted);
+                    }
+                    self.timer_handle.sleep(LOGICAL_TIME_POLL_PERIOD).await;
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub(crate) async fn announce_participant(&self) -> DdsResult<()> {
         if self
             .participant_address
@@ -107,6 +139,193 @@ impl DomainParticipantAsync {
         Ok(())
     }
 
+    /// Changes the GUID prefix of the participant and of all its entities after another
+    /// participant with the same GUID prefix has been discovered
+    pub(crate) async fn regenerate_guid_prefix(&self) -> DdsResult<()> {
+        self.participant_address
+            .send_actor_mail(domain_participant_actor::RegenerateGuidPrefix {
+                participant: self.clone(),
+            })?
+            .receive_reply()
+            .await;
+
+        // The announcements made with the old GUID prefix must not be sent anymore
+        let builtin_publisher = self
+            .participant_address
+            .send_actor_mail(domain_participant_actor::GetBuiltinPublisher)?
+            .receive_reply()
+            .await;
+        for builtin_writer in builtin_publisher
+            .send_actor_mail(publisher_actor::GetDataWriterList)?
+            .receive_reply()
+            .await
+        {
+            builtin_writer
+                .send_actor_mail(data_writer_actor::ClearHistory)?
+                .receive_reply()
+                .await;
+        }
+
+        self.announce_participant().await?;
+        self.announce_entities().await
+    }
+
+    async fn announce_entities(&self) -> DdsResult<()> {
+        let builtin_publisher = self.get_builtin_publisher().await?;
+        let default_unicast_locator_list = self
+            .participant_address
+            .send_actor_mail(domain_participant_actor::GetDefaultUnicastLocatorList)?
+            .receive_reply()
+            .await;
+        let default_multicast_locator_list = self
+            .participant_address
+            .send_actor_mail(domain_participant_actor::GetDefaultMulticastLocatorList)?
+            .receive_reply()
+            .await;
+
+        if let Some(sedp_topics_announcer) = builtin_publisher
+            .lookup_datawriter::<DiscoveredTopicData>(DCPS_TOPIC)
+            .await?
+        {
+            for topic in self
+                .participant_address
+                .send_actor_mail(domain_participant_actor::GetTopicList)?
+                .receive_reply()
+                .await
+            {
+                if topic
+                    .send_actor_mail(topic_actor::IsEnabled)?
+                    .receive_reply()
+                    .await
+                {
+                    let discovered_topic_data = topic
+                        .send_actor_mail(topic_actor::AsDiscoveredTopicData)?
+                        .receive_reply()
+                        .await;
+                    sedp_topics_announcer
+                        .write(&discovered_topic_data, None)
+                        .await?;
+                }
+            }
+        }
+
+        if let Some(sedp_publications_announcer) = builtin_publisher
+            .lookup_datawriter::<DiscoveredWriterData>(DCPS_PUBLICATION)
+            .await?
+        {
+            for publisher in self
+                .participant_address
+                .send_actor_mail(domain_participant_actor::GetPublisherList)?
+                .receive_reply()
+                .await
+            {
+                let publisher_qos = publisher
+                    .send_actor_mail(publisher_actor::GetQos)?
+                    .receive_reply()
+                    .await;
+                for data_writer in publisher
+                    .send_actor_mail(publisher_actor::GetDataWriterList)?
+                    .receive_reply()
+                    .await
+                {
+                    if !data_writer
+                        .send_actor_mail(data_writer_actor::IsEnabled)?
+                        .receive_reply()
+                        .await
+                    {
+                        continue;
+                    }
+                    let topic = data_writer
+                        .send_actor_mail(data_writer_actor::GetTopicAddress)?
+                        .receive_reply()
+                        .await;
+                    let topic_data = topic
+                        .send_actor_mail(topic_actor::GetQos)?
+                        .receive_reply()
+                        .await
+                        .topic_data;
+                    let xml_type = topic
+                        .send_actor_mail(topic_actor::GetTypeSupport)?
+                        .receive_reply()
+                        .await
+                        .xml_type();
+                    let discovered_writer_data = data_writer
+                        .send_actor_mail(data_writer_actor::AsDiscoveredWriterData {
+                            publisher_qos: publisher_qos.clone(),
+                            default_unicast_locator_list: default_unicast_locator_list.clone(),
+                            default_multicast_locator_list: default_multicast_locator_list.clone(),
+                            topic_data,
+                            xml_type,
+                        })?
+                        .receive_reply()
+                        .await?;
+                    sedp_publications_announcer
+                        .write(&discovered_writer_data, None)
+                        .await?;
+                }
+            }
+        }
+
+        if let Some(sedp_subscriptions_announcer) = builtin_publisher
+            .lookup_datawriter::<DiscoveredReaderData>(DCPS_SUBSCRIPTION)
+            .await?
+        {
+            for subscriber in self
+                .participant_address
+                .send_actor_mail(domain_participant_actor::GetSubscriberList)?
+                .receive_reply()
+                .await
+            {
+                let subscriber_qos = subscriber
+                    .send_actor_mail(subscriber_actor::GetQos)?
+                    .receive_reply()
+                    .await;
+                for data_reader in subscriber
+                    .send_actor_mail(subscriber_actor::GetDataReaderList)?
+                    .receive_reply()
+                    .await
+                {
+                    if !data_reader
+                        .send_actor_mail(data_reader_actor::IsEnabled)?
+                        .receive_reply()
+                        .await
+                    {
+                        continue;
+                    }
+                    let topic = data_reader
+                        .send_actor_mail(data_reader_actor::GetTopicAddress)?
+                        .receive_reply()
+                        .await;
+                    let topic_data = topic
+                        .send_actor_mail(topic_actor::GetQos)?
+                        .receive_reply()
+                        .await
+                        .topic_data;
+                    let xml_type = topic
+                        .send_actor_mail(topic_actor::GetTypeSupport)?
+                        .receive_reply()
+                        .await
+                        .xml_type();
+                    let discovered_reader_data = data_reader
+                        .send_actor_mail(data_reader_actor::AsDiscoveredReaderData {
+                            subscriber_qos: subscriber_qos.clone(),
+                            default_unicast_locator_list: default_unicast_locator_list.clone(),
+                            default_multicast_locator_list: default_multicast_locator_list.clone(),
+                            topic_data,
+                            xml_type,
+                        })?
+                        .receive_reply()
+                        .await?;
+                    sedp_subscriptions_announcer
+                        .write(&discovered_reader_data, None)
+                        .await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     async fn announce_deleted_topic(&self, topic: Actor<TopicActor>) -> DdsResult<()> {
         let builtin_publisher = self.get_builtin_publisher().await?;
 
@@ -337,6 +556,29 @@ impl DomainParticipantAsync {
         Ok(topic)
     }
 
+    /// Async version of [`create_contentfilteredtopic`](crate::domain::domain_participant::DomainParticipant::create_contentfilteredtopic).
+    #[tracing::instrument(skip(self, related_topic))]
+    pub async fn create_contentfilteredtopic<Foo>(
+        &self,
+        name: &str,
+        related_topic: &TopicAsync,
+        filter_expression: &str,
+        expression_parameters: &[String],
+    ) -> DdsResult<ContentFilteredTopicAsync>
+    where
+        Foo: DdsGetField + for<'de> DdsDeserialize<'de>,
+    {
+        let query = QueryExpression::new(filter_expression, expression_parameters)?;
+
+        Ok(ContentFilteredTopicAsync::new(
+            name.to_string(),
+            related_topic.clone(),
+            filter_expression.to_string(),
+            expression_parameters.to_vec(),
+            QueryFilter::new(query, evaluate_query::<Foo>),
+        ))
+    }
+
     /// Async version of [`delete_topic`](crate::domain::domain_participant::DomainParticipant::delete_topic).
     #[tracing::instrument(skip(self, a_topic))]
     pub async fn delete_topic(&self, a_topic: &TopicAsync) -> DdsResult<()> {
@@ -422,42 +664,51 @@ impl DomainParticipantAsync {
     where
         Foo: DdsKey + DdsHasKey + DdsTypeXml,
     {
-        let participant_address = self.participant_address.clone();
-        let runtime_handle = self.executor_handle.clone();
         let type_support = Arc::new(FooTypeSupport::new::<Foo>());
-        let topic_name = topic_name.to_owned();
-        let participant = self.clone();
-        self.timer_handle
-            .timeout(
-                timeout.into(),
-                Box::pin(async move {
-                    loop {
-                        if let Some((topic_address, status_condition_address)) = participant_address
-                            .send_actor_mail(domain_participant_actor::FindTopic {
-                                topic_name: topic_name.clone(),
-                                type_support: type_support.clone(),
-                                executor_handle: runtime_handle.clone(),
-                            })?
-                            .receive_reply()
-                            .await?
-                        {
-                            let type_name = topic_address
-                                .send_actor_mail(topic_actor::GetTypeName)?
-                                .receive_reply()
-                                .await;
-                            return Ok(TopicAsync::new(
-                                topic_address,
-                                status_condition_address,
-                                type_name,
-                                topic_name.to_owned(),
-                                participant,
-                            ));
-                        }
-                    }
-                }),
-            )
-            .await
-            .map_err(|_| DdsError::Timeout)?
+        let timeout = std::time::Duration::from(timeout);
+        let start = std::time::Instant::now();
+        loop {
+            // Register for the notification before looking up the topic so that a topic
+            // created or discovered in between is not missed
+            let topic_notification = self
+                .participant_address
+                .send_actor_mail(domain_participant_actor::WaitForTopic)?
+                .receive_reply()
+                .await;
+            if let Some((topic_address, status_condition_address)) = self
+                .participant_address
+                .send_actor_mail(domain_participant_actor::FindTopic {
+                    topic_name: topic_name.to_owned(),
+                    type_support: type_support.clone(),
+                    executor_handle: self.executor_handle.clone(),
+                })?
+                .receive_reply()
+                .await?
+            {
+                let type_name = topic_address
+                    .send_actor_mail(topic_actor::GetTypeName)?
+                    .receive_reply()
+                    .await;
+                return Ok(TopicAsync::new(
+                    topic_address,
+                    status_condition_address,
+                    type_name,
+                    topic_name.to_owned(),
+                    self.clone(),
+                ));
+            }
+
+            // Wait for a topic to be created or discovered for at most the remaining time
+            let remaining = timeout.saturating_sub(start.elapsed());
+            if remaining.is_zero() {
+                return Err(DdsError::Timeout);
+            }
+            self.timer_handle
+                .timeout(remaining, Box::pin(topic_notification))
+                .await
+                .map_err(|_| DdsError::Timeout)?
+                .map_err(|_| DdsError::AlreadyDeleted)?;
+        }
     }
 
     /// Async version of [`lookup_topicdescription`](crate::domain::domain_participant::DomainParticipant::lookup_topicdescription).
@@ -590,7 +841,55 @@ impl DomainParticipantAsync {
     /// Async version of [`assert_liveliness`](crate::domain::domain_participant::DomainParticipant::assert_liveliness).
     #[tracing::instrument(skip(self))]
     pub async fn assert_liveliness(&self) -> DdsResult<()> {
-        todo!()
+        if !self
+            .participant_address
+            .send_actor_mail(domain_participant_actor::IsEnabled)?
+            .receive_reply()
+            .await
+        {
+            return Err(DdsError::NotEnabled);
+        }
+
+        self.participant_address
+            .send_actor_mail(domain_participant_actor::AssertLiveliness)?
+            .receive_reply()
+            .await;
+
+        let message_sender_actor = self
+            .participant_address
+            .send_actor_mail(domain_participant_actor::GetMessageSender)?
+            .receive_reply()
+            .await;
+        for publisher in self
+            .participant_address
+            .send_actor_mail(domain_participant_actor::GetPublisherList)?
+            .receive_reply()
+            .await
+        {
+            for data_writer in publisher
+                .send_actor_mail(publisher_actor::GetDataWriterList)?
+                .receive_reply()
+                .await
+            {
+                if data_writer
+                    .send_actor_mail(data_writer_actor::GetQos)?
+                    .receive_reply()
+                    .await
+                    .liveliness
+                    .kind
+                    == LivelinessQosPolicyKind::ManualByParticipant
+                {
+                    data_writer
+                        .send_actor_mail(data_writer_actor::AssertLiveliness {
+                            message_sender_actor: message_sender_actor.clone(),
+                        })?
+                        .receive_reply()
+                        .await;
+                }
+            }
+        }
+
+        self.announce_participant().await
     }
 
     /// Async version of [`set_default_publisher_qos`](crate::domain::domain_participant::DomainParticipant::set_default_publisher_qos).
@@ -698,8 +997,91 @@ impl DomainParticipantAsync {
 
     /// Async version of [`contains_entity`](crate::domain::domain_participant::DomainParticipant::contains_entity).
     #[tracing::instrument(skip(self))]
-    pub async fn contains_entity(&self, _a_handle: InstanceHandle) -> DdsResult<bool> {
-        todo!()
+    pub async fn contains_entity(&self, a_handle: InstanceHandle) -> DdsResult<bool> {
+        let mut publisher_list = self
+            .participant_address
+            .send_actor_mail(domain_participant_actor::GetPublisherList)?
+            .receive_reply()
+            .await;
+        publisher_list.push(
+            self.participant_address
+                .send_actor_mail(domain_participant_actor::GetBuiltinPublisher)?
+                .receive_reply()
+                .await,
+        );
+        for publisher in publisher_list {
+            if publisher
+                .send_actor_mail(publisher_actor::GetInstanceHandle)?
+                .receive_reply()
+                .await
+                == a_handle
+            {
+                return Ok(true);
+            }
+            for data_writer in publisher
+                .send_actor_mail(publisher_actor::GetDataWriterList)?
+                .receive_reply()
+                .await
+            {
+                if data_writer
+                    .send_actor_mail(data_writer_actor::GetInstanceHandle)?
+                    .receive_reply()
+                    .await
+                    == a_handle
+                {
+                    return Ok(true);
+                }
+            }
+        }
+
+        let mut subscriber_list = self
+            .participant_address
+            .send_actor_mail(domain_participant_actor::GetSubscriberList)?
+            .receive_reply()
+            .await;
+        subscriber_list.push(self.builtin_subscriber_address.clone());
+        for subscriber in subscriber_list {
+            if subscriber
+                .send_actor_mail(subscriber_actor::GetInstanceHandle)?
+                .receive_reply()
+                .await
+                == a_handle
+            {
+                return Ok(true);
+            }
+            for data_reader in subscriber
+                .send_actor_mail(subscriber_actor::GetDataReaderList)?
+                .receive_reply()
+                .await
+            {
+                if data_reader
+                    .send_actor_mail(data_reader_actor::GetInstanceHandle)?
+                    .receive_reply()
+                    .await
+                    == a_handle
+                {
+                    return Ok(true);
+                }
+            }
+        }
+
+        for topic in self
+            .participant_address
+            .send_actor_mail(domain_participant_actor::GetTopicList)?
+            .receive_reply()
+            .await
+        {
+            if topic
+                .send_actor_mail(topic_actor::GetInstanceHandle)?
+                .receive_reply()
+                .await
+                == a_handle
+            {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
     }
 
     /// Async version of [`get_current_time`](crate::domain::domain_participant::DomainParticipant::get_current_time).
@@ -711,6 +1093,30 @@ impl DomainParticipantAsync {
             .receive_reply()
             .await)
     }
+
+    /// Async version of [`get_stats`](crate::domain::domain_participant::DomainParticipant::get_stats).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_stats(&self) -> DdsResult<DomainParticipantStats> {
+        let stats = self
+            .participant_address
+            .send_actor_mail(domain_participant_actor::GetStats)?
+            .receive_reply()
+            .await;
+        let sent_stats = self
+            .participant_address
+            .send_actor_mail(domain_participant_actor::GetMessageSender)?
+            .receive_reply()
+            .await
+            .send_actor_mail(message_sender_actor::GetStats)?
+            .receive_reply()
+            .await;
+        Ok(DomainParticipantStats {
+            messages_sent: sent_stats.messages_sent,
+            bytes_sent: sent_stats.bytes_sent,
+            heartbeats_sent: sent_stats.heartbeats_sent,
+            ..stats
+        })
+    }
 }
 
 impl DomainParticipantAsync {
@@ -830,7 +1236,7 @@ impl DomainParticipantAsync {
                         data_writer_address: builtin_writer.clone(),
                         message_sender_actor,
                         executor_handle: self.executor_handle.clone(),
-                        timer_handle: self.timer_handle.clone(),
+                        participant: self.clone(),
                     })?
                     .receive_reply()
                     .await;
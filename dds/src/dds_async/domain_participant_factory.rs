@@ -20,6 +20,11 @@ use crate::{
     },
 };
 
+#[cfg(feature = "xml-profiles")]
+use crate::infrastructure::qos_profile::{parse_qos_profiles, QosProfile};
+#[cfg(feature = "xml-profiles")]
+use std::path::Path;
+
 use super::{
     domain_participant::DomainParticipantAsync,
     domain_participant_listener::DomainParticipantListenerAsync,
@@ -123,11 +128,11 @@ impl DomainParticipantFactoryAsync {
             .receive_reply()
             .await;
         if is_participant_empty {
-            let handle = participant.get_instance_handle().await?;
-
             let deleted_participant = self
                 .domain_participant_factory_actor
-                .send_actor_mail(domain_participant_factory_actor::DeleteParticipant { handle })
+                .send_actor_mail(domain_participant_factory_actor::DeleteParticipant {
+                    participant_address: participant.participant_address().clone(),
+                })
                 .receive_reply()
                 .await?;
             let builtin_publisher = participant.get_builtin_publisher().await?;
@@ -257,3 +262,68 @@ impl DomainParticipantFactoryAsync {
             .await)
     }
 }
+
+#[cfg(feature = "xml-profiles")]
+impl DomainParticipantFactoryAsync {
+    /// Async version of [`load_profiles_from_file`](crate::domain::domain_participant_factory::DomainParticipantFactory::load_profiles_from_file).
+    pub async fn load_profiles_from_file(&self, path: &Path) -> DdsResult<()> {
+        let xml = std::fs::read_to_string(path)?;
+        let qos_profiles = parse_qos_profiles(&xml)?;
+        self.domain_participant_factory_actor
+            .send_actor_mail(domain_participant_factory_actor::AddQosProfiles { qos_profiles })
+            .receive_reply()
+            .await;
+        Ok(())
+    }
+
+    /// Async version of [`get_qos_profile`](crate::domain::domain_participant_factory::DomainParticipantFactory::get_qos_profile).
+    pub async fn get_qos_profile(&self, profile_name: &str) -> DdsResult<QosProfile> {
+        self.domain_participant_factory_actor
+            .send_actor_mail(domain_participant_factory_actor::GetQosProfile {
+                profile_name: profile_name.to_string(),
+            })
+            .receive_reply()
+            .await
+    }
+
+    /// Async version of [`create_participant_with_profile`](crate::domain::domain_participant_factory::DomainParticipantFactory::create_participant_with_profile).
+    pub async fn create_participant_with_profile(
+        &self,
+        domain_id: DomainId,
+        profile_name: &str,
+    ) -> DdsResult<DomainParticipantAsync> {
+        let qos_profile = self.get_qos_profile(profile_name).await?;
+        let qos = match qos_profile.participant_qos {
+            Some(participant_qos) => QosKind::Specific(participant_qos),
+            None => QosKind::Default,
+        };
+        let participant = self.create_participant(domain_id, qos, None, &[]).await?;
+        let set_default_qos_result: DdsResult<()> = async {
+            if let Some(publisher_qos) = qos_profile.publisher_qos {
+                participant
+                    .set_default_publisher_qos(QosKind::Specific(publisher_qos))
+                    .await?;
+            }
+            if let Some(subscriber_qos) = qos_profile.subscriber_qos {
+                participant
+                    .set_default_subscriber_qos(QosKind::Specific(subscriber_qos))
+                    .await?;
+            }
+            if let Some(topic_qos) = qos_profile.topic_qos {
+                participant
+                    .set_default_topic_qos(QosKind::Specific(topic_qos))
+                    .await?;
+            }
+            Ok(())
+        }
+        .await;
+
+        // The participant is not returned to the application so it has to be removed
+        // from the factory, otherwise it would keep announcing itself
+        if let Err(e) = set_default_qos_result {
+            self.delete_participant(&participant).await?;
+            return Err(e);
+        }
+        Ok(participant)
+    }
+}
@@ -1,5 +1,7 @@
 /// Classes related to the async status conditions.
 pub mod condition;
+/// Classes related to the async content filtered topic.
+pub mod content_filtered_topic;
 /// Classes related to the async data reader.
 pub mod data_reader;
 /// Classes related to the async data reader listener.
@@ -15,6 +15,7 @@ use crate::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataWriterQos, PublisherQos, QosKind, TopicQos},
+        qos_policy::ReliabilityQosPolicyKind,
         status::StatusKind,
         time::Duration,
     },
@@ -285,31 +286,78 @@ impl PublisherAsync {
     /// Async version of [`delete_datawriter`](crate::publication::publisher::Publisher::suspend_publications).
     #[tracing::instrument(skip(self))]
     pub async fn suspend_publications(&self) -> DdsResult<()> {
-        todo!()
+        self.publisher_address
+            .send_actor_mail(publisher_actor::SuspendPublications)?
+            .receive_reply()
+            .await;
+        Ok(())
     }
 
     /// Async version of [`delete_datawriter`](crate::publication::publisher::Publisher::resume_publications).
     #[tracing::instrument(skip(self))]
     pub async fn resume_publications(&self) -> DdsResult<()> {
-        todo!()
+        self.publisher_address
+            .send_actor_mail(publisher_actor::ResumePublications)?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`delete_datawriter`](crate::publication::publisher::Publisher::begin_coherent_changes).
     #[tracing::instrument(skip(self))]
     pub async fn begin_coherent_changes(&self) -> DdsResult<()> {
-        todo!()
+        self.publisher_address
+            .send_actor_mail(publisher_actor::BeginCoherentChanges)?
+            .receive_reply()
+            .await;
+        Ok(())
     }
 
     /// Async version of [`delete_datawriter`](crate::publication::publisher::Publisher::end_coherent_changes).
     #[tracing::instrument(skip(self))]
     pub async fn end_coherent_changes(&self) -> DdsResult<()> {
-        todo!()
+        self.publisher_address
+            .send_actor_mail(publisher_actor::EndCoherentChanges)?
+            .receive_reply()
+            .await
     }
 
     /// Async version of [`delete_datawriter`](crate::publication::publisher::Publisher::wait_for_acknowledgments).
     #[tracing::instrument(skip(self))]
-    pub async fn wait_for_acknowledgments(&self, _max_wait: Duration) -> DdsResult<()> {
-        todo!()
+    pub async fn wait_for_acknowledgments(&self, max_wait: Duration) -> DdsResult<()> {
+        let mut acknowledgment_list = Vec::new();
+        for writer_address in self
+            .publisher_address
+            .send_actor_mail(publisher_actor::GetDataWriterList)?
+            .receive_reply()
+            .await
+        {
+            let writer_qos = writer_address
+                .send_actor_mail(data_writer_actor::GetQos)?
+                .receive_reply()
+                .await;
+            if writer_qos.reliability.kind == ReliabilityQosPolicyKind::Reliable {
+                acknowledgment_list.push(
+                    writer_address
+                        .send_actor_mail(data_writer_actor::WaitForAcknowledgments)?
+                        .receive_reply()
+                        .await,
+                );
+            }
+        }
+
+        self.participant
+            .timer_handle()
+            .timeout(
+                max_wait.into(),
+                Box::pin(async move {
+                    for acknowledgment in acknowledgment_list {
+                        acknowledgment.await.map_err(|_| DdsError::AlreadyDeleted)?;
+                    }
+                    Ok(())
+                }),
+            )
+            .await
+            .map_err(|_| DdsError::Timeout)?
     }
 
     /// Async version of [`get_participant`](crate::publication::publisher::Publisher::get_participant).
@@ -387,10 +435,21 @@ impl PublisherAsync {
     #[tracing::instrument(skip(self))]
     pub async fn copy_from_topic_qos(
         &self,
-        _a_datawriter_qos: &mut DataWriterQos,
-        _a_topic_qos: &TopicQos,
+        a_datawriter_qos: &mut DataWriterQos,
+        a_topic_qos: &TopicQos,
     ) -> DdsResult<()> {
-        todo!()
+        a_datawriter_qos.durability = a_topic_qos.durability.clone();
+        a_datawriter_qos.deadline = a_topic_qos.deadline.clone();
+        a_datawriter_qos.latency_budget = a_topic_qos.latency_budget.clone();
+        a_datawriter_qos.liveliness = a_topic_qos.liveliness.clone();
+        a_datawriter_qos.reliability = a_topic_qos.reliability.clone();
+        a_datawriter_qos.destination_order = a_topic_qos.destination_order.clone();
+        a_datawriter_qos.history = a_topic_qos.history.clone();
+        a_datawriter_qos.resource_limits = a_topic_qos.resource_limits.clone();
+        a_datawriter_qos.transport_priority = a_topic_qos.transport_priority.clone();
+        a_datawriter_qos.lifespan = a_topic_qos.lifespan.clone();
+        a_datawriter_qos.ownership = a_topic_qos.ownership.clone();
+        Ok(())
     }
 }
 
@@ -15,8 +15,11 @@ use crate::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataReaderQos, QosKind, SubscriberQos, TopicQos},
+        qos_policy::PresentationQosPolicyAccessScopeKind,
         status::{SampleLostStatus, StatusKind},
     },
+    subscription::sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
+    topic_definition::topic_description::TopicDescription,
 };
 
 use super::{
@@ -106,7 +109,7 @@ impl SubscriberAsync {
     #[tracing::instrument(skip(self, a_topic, a_listener))]
     pub async fn create_datareader<'a, 'b, Foo>(
         &'a self,
-        a_topic: &'a TopicAsync,
+        a_topic: &'a impl TopicDescription,
         qos: QosKind<DataReaderQos>,
         a_listener: Option<Box<(dyn DataReaderListenerAsync<'b, Foo = Foo> + Send + 'b)>>,
         mask: &'a [StatusKind],
@@ -128,11 +131,13 @@ impl SubscriberAsync {
             .receive_reply()
             .await;
 
-        let topic = a_topic.topic_address();
-        let topic_name = a_topic.get_name();
-        let type_name = a_topic.get_type_name();
-        let topic_status_condition = a_topic.get_statuscondition().address().clone();
-        let type_support = topic
+        // A content filtered topic is matched and discovered through its related topic
+        let related_topic = a_topic.related_topic_async();
+        let topic_name = related_topic.get_name();
+        let type_name = related_topic.get_type_name();
+        let topic_status_condition = related_topic.get_statuscondition().address().clone();
+        let type_support = related_topic
+            .topic_address()
             .send_actor_mail(topic_actor::GetTypeSupport)?
             .receive_reply()
             .await;
@@ -141,7 +146,7 @@ impl SubscriberAsync {
         let reader_address = self
             .subscriber_address
             .send_actor_mail(subscriber_actor::CreateDatareader {
-                topic_address: a_topic.topic_address().clone(),
+                topic_address: related_topic.topic_address().clone(),
                 topic_name,
                 type_name,
                 topic_status_condition,
@@ -152,6 +157,7 @@ impl SubscriberAsync {
                 mask: mask.to_vec(),
                 default_unicast_locator_list,
                 default_multicast_locator_list,
+                content_filter: a_topic.content_filter(),
                 executor_handle: self.participant.executor_handle().clone(),
             })?
             .receive_reply()
@@ -165,7 +171,7 @@ impl SubscriberAsync {
             reader_address,
             status_condition,
             self.clone(),
-            a_topic.clone(),
+            related_topic.clone(),
         );
 
         if self
@@ -278,10 +284,142 @@ impl SubscriberAsync {
         }
     }
 
+    /// Async version of [`begin_access`](crate::subscription::subscriber::Subscriber::begin_access).
+    #[tracing::instrument(skip(self))]
+    pub async fn begin_access(&self) -> DdsResult<()> {
+        self.subscriber_address
+            .send_actor_mail(subscriber_actor::BeginAccess)?
+            .receive_reply()
+            .await;
+        Ok(())
+    }
+
+    /// Async version of [`end_access`](crate::subscription::subscriber::Subscriber::end_access).
+    #[tracing::instrument(skip(self))]
+    pub async fn end_access(&self) -> DdsResult<()> {
+        self.subscriber_address
+            .send_actor_mail(subscriber_actor::EndAccess)?
+            .receive_reply()
+            .await
+    }
+
+    /// Async version of [`get_datareaders`](crate::subscription::subscriber::Subscriber::get_datareaders).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_datareaders(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> DdsResult<Vec<DataReaderAsync<()>>> {
+        let presentation = self.get_qos().await?.presentation;
+        let is_ordered_group_access = presentation.access_scope
+            == PresentationQosPolicyAccessScopeKind::Group
+            && presentation.ordered_access;
+        if is_ordered_group_access
+            && !self
+                .subscriber_address
+                .send_actor_mail(subscriber_actor::IsAccessActive)?
+                .receive_reply()
+                .await
+        {
+            return Err(DdsError::PreconditionNotMet(
+                "Ordered group access requires calling begin_access first".to_string(),
+            ));
+        }
+
+        let data_reader_list = self
+            .subscriber_address
+            .send_actor_mail(subscriber_actor::GetDataReaderList)?
+            .receive_reply()
+            .await;
+        let mut reader_list = Vec::new();
+        let mut sample_list = Vec::new();
+        for dr in data_reader_list {
+            let sample_sequence_number_list = dr
+                .send_actor_mail(data_reader_actor::GetSampleSequenceNumberList {
+                    sample_states: sample_states.to_vec(),
+                    view_states: view_states.to_vec(),
+                    instance_states: instance_states.to_vec(),
+                })?
+                .receive_reply()
+                .await;
+            if sample_sequence_number_list.is_empty() {
+                continue;
+            }
+
+            let topic_name = dr
+                .send_actor_mail(data_reader_actor::GetTopicName)?
+                .receive_reply()
+                .await?;
+            let type_name = dr
+                .send_actor_mail(data_reader_actor::GetTypeName)?
+                .receive_reply()
+                .await?;
+            let topic_address = dr
+                .send_actor_mail(data_reader_actor::GetTopicAddress)?
+                .receive_reply()
+                .await;
+            let topic_status_condition = topic_address
+                .send_actor_mail(topic_actor::GetStatuscondition)?
+                .receive_reply()
+                .await;
+            let topic = TopicAsync::new(
+                topic_address,
+                topic_status_condition,
+                type_name,
+                topic_name,
+                self.participant.clone(),
+            );
+            let status_condition = dr
+                .send_actor_mail(data_reader_actor::GetStatuscondition)?
+                .receive_reply()
+                .await;
+
+            sample_list.extend(
+                sample_sequence_number_list
+                    .into_iter()
+                    .map(|sequence_number| (sequence_number, reader_list.len())),
+            );
+            reader_list.push(DataReaderAsync::new(
+                dr,
+                status_condition,
+                self.clone(),
+                topic,
+            ));
+        }
+
+        if is_ordered_group_access {
+            // Each reader is listed once per sample in the order in which the samples
+            // should be accessed across the readers of the group. The samples are ordered
+            // by the group sequence number assigned by their publisher.
+            sample_list.sort_by_key(|(sequence_number, _)| *sequence_number);
+            Ok(sample_list
+                .into_iter()
+                .map(|(_, reader_index)| reader_list[reader_index].clone())
+                .collect())
+        } else {
+            Ok(reader_list)
+        }
+    }
+
     /// Async version of [`notify_datareaders`](crate::subscription::subscriber::Subscriber::notify_datareaders).
     #[tracing::instrument(skip(self))]
     pub async fn notify_datareaders(&self) -> DdsResult<()> {
-        todo!()
+        let data_reader_list = self
+            .subscriber_address
+            .send_actor_mail(subscriber_actor::GetDataReaderList)?
+            .receive_reply()
+            .await;
+        for data_reader_address in data_reader_list {
+            data_reader_address
+                .send_actor_mail(data_reader_actor::NotifyDataAvailable {
+                    data_reader_address: data_reader_address.clone(),
+                    subscriber: self.clone(),
+                })?
+                .receive_reply()
+                .await?;
+        }
+        Ok(())
     }
 
     /// Async version of [`get_participant`](crate::subscription::subscriber::Subscriber::get_participant).
@@ -351,10 +489,19 @@ impl SubscriberAsync {
     /// Async version of [`copy_from_topic_qos`](crate::subscription::subscriber::Subscriber::copy_from_topic_qos).
     #[tracing::instrument]
     pub async fn copy_from_topic_qos(
-        _a_datareader_qos: &mut DataReaderQos,
-        _a_topic_qos: &TopicQos,
+        a_datareader_qos: &mut DataReaderQos,
+        a_topic_qos: &TopicQos,
     ) -> DdsResult<()> {
-        todo!()
+        a_datareader_qos.durability = a_topic_qos.durability.clone();
+        a_datareader_qos.deadline = a_topic_qos.deadline.clone();
+        a_datareader_qos.latency_budget = a_topic_qos.latency_budget.clone();
+        a_datareader_qos.liveliness = a_topic_qos.liveliness.clone();
+        a_datareader_qos.reliability = a_topic_qos.reliability.clone();
+        a_datareader_qos.destination_order = a_topic_qos.destination_order.clone();
+        a_datareader_qos.history = a_topic_qos.history.clone();
+        a_datareader_qos.resource_limits = a_topic_qos.resource_limits.clone();
+        a_datareader_qos.ownership = a_topic_qos.ownership.clone();
+        Ok(())
     }
 
     /// Async version of [`set_qos`](crate::subscription::subscriber::Subscriber::set_qos).
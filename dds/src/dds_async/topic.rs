@@ -7,6 +7,7 @@ use crate::{
     implementation::{
         actor::ActorAddress,
         actors::{
+            data_reader_actor::QueryFilter,
             domain_participant_actor::{self, DomainParticipantActor},
             status_condition_actor::StatusConditionActor,
             topic_actor::{self, TopicActor},
@@ -18,7 +19,7 @@ use crate::{
         qos::{QosKind, TopicQos},
         status::{InconsistentTopicStatus, StatusKind},
     },
-    topic_definition::type_support::DynamicTypeInterface,
+    topic_definition::{topic_description::TopicDescription, type_support::DynamicTypeInterface},
 };
 
 use super::{
@@ -112,6 +113,24 @@ impl TopicAsync {
     }
 }
 
+impl TopicDescription for TopicAsync {
+    fn get_type_name(&self) -> String {
+        self.get_type_name()
+    }
+
+    fn get_name(&self) -> String {
+        self.get_name()
+    }
+
+    fn related_topic_async(&self) -> &TopicAsync {
+        self
+    }
+
+    fn content_filter(&self) -> Option<QueryFilter> {
+        None
+    }
+}
+
 impl TopicAsync {
     /// Async version of [`set_qos`](crate::topic_definition::topic::Topic::set_qos).
     #[tracing::instrument(skip(self))]
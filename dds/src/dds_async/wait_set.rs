@@ -6,19 +6,26 @@ use crate::{
     },
 };
 
-use super::condition::StatusConditionAsync;
+use super::condition::{QueryConditionAsync, ReadConditionAsync, StatusConditionAsync};
+use std::sync::Mutex;
 
 /// Async version of [`Condition`](crate::infrastructure::wait_set::Condition).
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub enum ConditionAsync {
     /// Status condition variant
     StatusCondition(StatusConditionAsync),
+    /// Read condition variant
+    ReadCondition(ReadConditionAsync),
+    /// Query condition variant
+    QueryCondition(QueryConditionAsync),
 }
 
 impl ConditionAsync {
     pub(crate) fn timer_handle(&self) -> &TimerHandle {
         match self {
             ConditionAsync::StatusCondition(s) => s.timer_handle(),
+            ConditionAsync::ReadCondition(r) => r.timer_handle(),
+            ConditionAsync::QueryCondition(q) => q.timer_handle(),
         }
     }
 }
@@ -29,6 +36,8 @@ impl ConditionAsync {
     pub async fn get_trigger_value(&self) -> DdsResult<bool> {
         match self {
             ConditionAsync::StatusCondition(c) => c.get_trigger_value().await,
+            ConditionAsync::ReadCondition(c) => c.get_trigger_value().await,
+            ConditionAsync::QueryCondition(c) => c.get_trigger_value().await,
         }
     }
 }
@@ -36,7 +45,7 @@ impl ConditionAsync {
 /// Async version of [`WaitSet`](crate::infrastructure::wait_set::WaitSet).
 #[derive(Default)]
 pub struct WaitSetAsync {
-    conditions: Vec<ConditionAsync>,
+    conditions: Mutex<Vec<ConditionAsync>>,
 }
 
 impl WaitSetAsync {
@@ -49,18 +58,32 @@ impl WaitSetAsync {
     /// Async version of [`wait`](crate::infrastructure::wait_set::WaitSet::wait).
     #[tracing::instrument(skip(self))]
     pub async fn wait(&self, timeout: Duration) -> DdsResult<Vec<ConditionAsync>> {
-        if self.conditions.is_empty() {
+        if self
+            .conditions
+            .lock()
+            .expect("Mutex shouldn't be poisoned")
+            .is_empty()
+        {
             return Err(DdsError::PreconditionNotMet(
                 "WaitSet has no attached conditions".to_string(),
             ));
         };
 
-        let timer_handle = self.conditions[0].timer_handle().clone();
         let start = std::time::Instant::now();
         while std::time::Instant::now().duration_since(start) < timeout.into() {
+            // The conditions are copied so that the lock is not held while waiting for
+            // their trigger values
+            let conditions = self.get_conditions().await?;
+            let timer_handle = conditions
+                .first()
+                .ok_or_else(|| {
+                    DdsError::PreconditionNotMet("WaitSet has no attached conditions".to_string())
+                })?
+                .timer_handle()
+                .clone();
             let mut finished = false;
             let mut trigger_conditions = Vec::new();
-            for condition in &self.conditions {
+            for condition in &conditions {
                 if condition.get_trigger_value().await? {
                     trigger_conditions.push(condition.clone());
                     finished = true;
@@ -81,19 +104,38 @@ impl WaitSetAsync {
     /// Async version of [`attach_condition`](crate::infrastructure::wait_set::WaitSet::attach_condition).
     #[tracing::instrument(skip(self, cond))]
     pub async fn attach_condition(&mut self, cond: ConditionAsync) -> DdsResult<()> {
-        self.conditions.push(cond);
+        let conditions = self
+            .conditions
+            .get_mut()
+            .expect("Mutex shouldn't be poisoned");
+        if !conditions.contains(&cond) {
+            conditions.push(cond);
+        }
         Ok(())
     }
 
     /// Async version of [`detach_condition`](crate::infrastructure::wait_set::WaitSet::detach_condition).
-    #[tracing::instrument(skip(self, _cond))]
-    pub async fn detach_condition(&self, _cond: ConditionAsync) -> DdsResult<()> {
-        todo!()
+    #[tracing::instrument(skip(self, cond))]
+    pub async fn detach_condition(&self, cond: ConditionAsync) -> DdsResult<()> {
+        let mut conditions = self.conditions.lock().expect("Mutex shouldn't be poisoned");
+        match conditions.iter().position(|c| c == &cond) {
+            Some(index) => {
+                conditions.remove(index);
+                Ok(())
+            }
+            None => Err(DdsError::PreconditionNotMet(
+                "Condition is not attached to the WaitSet".to_string(),
+            )),
+        }
     }
 
     /// Async version of [`get_conditions`](crate::infrastructure::wait_set::WaitSet::get_conditions).
     #[tracing::instrument(skip(self))]
     pub async fn get_conditions(&self) -> DdsResult<Vec<ConditionAsync>> {
-        Ok(self.conditions.clone())
+        Ok(self
+            .conditions
+            .lock()
+            .expect("Mutex shouldn't be poisoned")
+            .clone())
     }
 }
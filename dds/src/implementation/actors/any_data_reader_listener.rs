@@ -17,7 +17,7 @@ use super::{data_reader_actor::DataReaderActor, status_condition_actor::StatusCo
 pub enum DataReaderListenerOperation {
     DataAvailable,
     SampleRejected(SampleRejectedStatus),
-    _LivelinessChanged(LivelinessChangedStatus),
+    LivelinessChanged(LivelinessChangedStatus),
     RequestedDeadlineMissed(RequestedDeadlineMissedStatus),
     RequestedIncompatibleQos(RequestedIncompatibleQosStatus),
     SubscriptionMatched(SubscriptionMatchedStatus),
@@ -55,7 +55,7 @@ where
                 DataReaderListenerOperation::SampleRejected(status) => {
                     self.on_sample_rejected(reader, status).await
                 }
-                DataReaderListenerOperation::_LivelinessChanged(status) => {
+                DataReaderListenerOperation::LivelinessChanged(status) => {
                     self.on_liveliness_changed(reader, status).await
                 }
                 DataReaderListenerOperation::RequestedDeadlineMissed(status) => {
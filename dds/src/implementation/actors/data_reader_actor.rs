@@ -18,40 +18,41 @@ use crate::{
     implementation::{
         actor::{Actor, ActorAddress, Mail, MailHandler},
         data_representation_inline_qos::{
-            parameter_id_values::{PID_KEY_HASH, PID_STATUS_INFO},
+            parameter_id_values::{
+                PID_COHERENT_SET, PID_GROUP_SEQ_NUM, PID_KEY_HASH, PID_STATUS_INFO,
+            },
             types::{
-                StatusInfo, STATUS_INFO_DISPOSED, STATUS_INFO_DISPOSED_UNREGISTERED,
-                STATUS_INFO_UNREGISTERED,
+                CoherentSetParameter, GroupSequenceNumberParameter, StatusInfo, STATUS_INFO_ALIVE,
+                STATUS_INFO_DISPOSED, STATUS_INFO_DISPOSED_UNREGISTERED, STATUS_INFO_UNREGISTERED,
             },
         },
         payload_serializer_deserializer::{
             cdr_deserializer::ClassicCdrDeserializer, endianness::CdrEndianness,
         },
+        query_expression::QueryExpression,
         runtime::{
             executor::{block_on, ExecutorHandle, TaskHandle},
             mpsc::{mpsc_channel, MpscSender},
-            timer::TimerHandle,
         },
     },
     infrastructure::{
-        self,
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataReaderQos, SubscriberQos},
         qos_policy::{
             DestinationOrderQosPolicyKind, DurabilityQosPolicyKind, HistoryQosPolicyKind,
-            QosPolicyId, ReliabilityQosPolicyKind, TopicDataQosPolicy,
-            DATA_REPRESENTATION_QOS_POLICY_ID, DEADLINE_QOS_POLICY_ID,
-            DESTINATIONORDER_QOS_POLICY_ID, DURABILITY_QOS_POLICY_ID, LATENCYBUDGET_QOS_POLICY_ID,
-            LIVELINESS_QOS_POLICY_ID, OWNERSHIP_QOS_POLICY_ID, PRESENTATION_QOS_POLICY_ID,
-            RELIABILITY_QOS_POLICY_ID, XCDR_DATA_REPRESENTATION,
+            LivelinessQosPolicy, LivelinessQosPolicyKind, OwnershipQosPolicyKind, QosPolicyId,
+            ReliabilityQosPolicyKind, TopicDataQosPolicy, DATA_REPRESENTATION_QOS_POLICY_ID,
+            DEADLINE_QOS_POLICY_ID, DESTINATIONORDER_QOS_POLICY_ID, DURABILITY_QOS_POLICY_ID,
+            LATENCYBUDGET_QOS_POLICY_ID, LIVELINESS_QOS_POLICY_ID, OWNERSHIP_QOS_POLICY_ID,
+            PRESENTATION_QOS_POLICY_ID, RELIABILITY_QOS_POLICY_ID, XCDR_DATA_REPRESENTATION,
         },
         status::{
             LivelinessChangedStatus, QosPolicyCount, RequestedDeadlineMissedStatus,
             RequestedIncompatibleQosStatus, SampleLostStatus, SampleRejectedStatus,
             SampleRejectedStatusKind, StatusKind, SubscriptionMatchedStatus,
         },
-        time::DurationKind,
+        time::{DurationKind, Time},
     },
     rtps::{
         self,
@@ -64,7 +65,9 @@ use crate::{
             },
         },
         reader::RtpsReaderKind,
-        types::{ChangeKind, Guid, GuidPrefix, Locator, ENTITYID_UNKNOWN, GUID_UNKNOWN},
+        types::{
+            ChangeKind, Guid, GuidPrefix, Locator, SequenceNumber, ENTITYID_UNKNOWN, GUID_UNKNOWN,
+        },
         writer_proxy::RtpsWriterProxy,
     },
     serialized_payload::cdr::deserialize::CdrDeserialize,
@@ -72,7 +75,7 @@ use crate::{
     topic_definition::type_support::{DdsKey, DynamicTypeInterface},
 };
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{hash_map::Entry, HashMap, HashSet},
     sync::Arc,
     thread::JoinHandle,
 };
@@ -83,6 +86,7 @@ struct InstanceState {
     instance_state: InstanceStateKind,
     most_recent_disposed_generation_count: i32,
     most_recent_no_writers_generation_count: i32,
+    alive_writers: HashSet<InstanceHandle>,
 }
 
 impl InstanceState {
@@ -92,43 +96,62 @@ impl InstanceState {
             instance_state: InstanceStateKind::Alive,
             most_recent_disposed_generation_count: 0,
             most_recent_no_writers_generation_count: 0,
+            alive_writers: HashSet::new(),
         }
     }
 
-    pub fn update_state(&mut self, change_kind: ChangeKind) {
+    pub fn update_state(&mut self, change_kind: ChangeKind, writer_handle: InstanceHandle) {
+        match change_kind {
+            ChangeKind::Alive | ChangeKind::AliveFiltered => {
+                self.alive_writers.insert(writer_handle);
+            }
+            ChangeKind::NotAliveUnregistered | ChangeKind::NotAliveDisposedUnregistered => {
+                self.alive_writers.remove(&writer_handle);
+            }
+            ChangeKind::NotAliveDisposed => (),
+        }
+
         match self.instance_state {
             InstanceStateKind::Alive => {
                 if change_kind == ChangeKind::NotAliveDisposed
                     || change_kind == ChangeKind::NotAliveDisposedUnregistered
                 {
                     self.instance_state = InstanceStateKind::NotAliveDisposed;
-                } else if change_kind == ChangeKind::NotAliveUnregistered {
+                } else if change_kind == ChangeKind::NotAliveUnregistered
+                    && self.alive_writers.is_empty()
+                {
+                    // The instance only has no writers once all the writers which wrote it
+                    // have unregistered it
                     self.instance_state = InstanceStateKind::NotAliveNoWriters;
                 }
             }
+            // An instance which becomes alive again is reborn so its samples are
+            // presented as a new view even if the previous ones were already accessed
             InstanceStateKind::NotAliveDisposed => {
                 if change_kind == ChangeKind::Alive {
                     self.instance_state = InstanceStateKind::Alive;
+                    self.view_state = ViewStateKind::New;
                     self.most_recent_disposed_generation_count += 1;
                 }
             }
             InstanceStateKind::NotAliveNoWriters => {
                 if change_kind == ChangeKind::Alive {
                     self.instance_state = InstanceStateKind::Alive;
+                    self.view_state = ViewStateKind::New;
                     self.most_recent_no_writers_generation_count += 1;
                 }
             }
         }
+    }
 
-        match self.view_state {
-            ViewStateKind::New => (),
-            ViewStateKind::NotNew => {
-                if change_kind == ChangeKind::NotAliveDisposed
-                    || change_kind == ChangeKind::NotAliveUnregistered
-                {
-                    self.view_state = ViewStateKind::New;
-                }
-            }
+    pub fn remove_writer(&mut self, writer_handle: InstanceHandle) {
+        // A writer which is no longer matched or alive does not keep the instance alive
+        // even if it never unregistered it
+        if self.alive_writers.remove(&writer_handle)
+            && self.alive_writers.is_empty()
+            && self.instance_state == InstanceStateKind::Alive
+        {
+            self.instance_state = InstanceStateKind::NotAliveNoWriters;
         }
     }
 
@@ -140,6 +163,7 @@ impl InstanceState {
 #[derive(Debug)]
 struct ReaderCacheChange {
     rtps_cache_change: RtpsCacheChange,
+    sequence_number: SequenceNumber,
     sample_state: SampleStateKind,
     disposed_generation_count: i32,
     no_writers_generation_count: i32,
@@ -151,6 +175,106 @@ impl ReaderCacheChange {
     fn instance_handle(&self) -> InstanceHandle {
         self.rtps_cache_change.instance_handle.into()
     }
+
+    // Samples received without an INFO_TS submessage are ordered by their reception time
+    fn source_timestamp_or_reception(&self) -> rtps::messages::types::Time {
+        self.source_timestamp.unwrap_or(self.reception_timestamp)
+    }
+
+    fn is_in_access_snapshot(
+        &self,
+        access_snapshot: Option<&HashMap<InstanceHandle, SequenceNumber>>,
+    ) -> bool {
+        // Only the changes received before the application began accessing the
+        // subscriber are visible until the access ends
+        access_snapshot.is_none_or(|snapshot| {
+            snapshot
+                .get(&InstanceHandle::new(
+                    self.rtps_cache_change.writer_guid.into(),
+                ))
+                .is_some_and(|sequence_number| self.sequence_number <= *sequence_number)
+        })
+    }
+}
+
+pub type QueryEvaluator = fn(&QueryExpression, &[u8]) -> bool;
+
+#[derive(Clone)]
+pub struct QueryFilter {
+    query: QueryExpression,
+    evaluator: QueryEvaluator,
+}
+
+impl QueryFilter {
+    pub fn new(query: QueryExpression, evaluator: QueryEvaluator) -> Self {
+        Self { query, evaluator }
+    }
+
+    fn matches(&self, cache_change: &ReaderCacheChange) -> bool {
+        match cache_change.rtps_cache_change.kind {
+            ChangeKind::Alive | ChangeKind::AliveFiltered => (self.evaluator)(
+                &self.query,
+                cache_change.rtps_cache_change.data_value.as_ref(),
+            ),
+            ChangeKind::NotAliveDisposed
+            | ChangeKind::NotAliveUnregistered
+            | ChangeKind::NotAliveDisposedUnregistered => false,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct QueryConditionState {
+    query_expression: String,
+    query_parameters: Vec<String>,
+    filter: QueryFilter,
+}
+
+#[derive(Clone)]
+struct ReadConditionState {
+    sample_states: Vec<SampleStateKind>,
+    view_states: Vec<ViewStateKind>,
+    instance_states: Vec<InstanceStateKind>,
+    query: Option<QueryConditionState>,
+}
+
+/// Returns the sequence number identifying the coherent set of the change and whether the
+/// change is the last one of the set
+fn get_coherent_set_info(inline_qos: &ParameterList) -> Option<(SequenceNumber, bool)> {
+    let coherent_set = inline_qos
+        .parameter()
+        .iter()
+        .find(|&x| x.parameter_id() == PID_COHERENT_SET)?;
+    let mut deserializer =
+        ClassicCdrDeserializer::new(coherent_set.value(), CdrEndianness::LittleEndian);
+    let coherent_set_sequence_number =
+        SequenceNumber::from(CoherentSetParameter::deserialize(&mut deserializer).ok()?);
+
+    let is_coherent_set_end = inline_qos
+        .parameter()
+        .iter()
+        .find(|&x| x.parameter_id() == PID_STATUS_INFO)
+        .and_then(|p| {
+            let mut deserializer =
+                ClassicCdrDeserializer::new(p.value(), CdrEndianness::LittleEndian);
+            StatusInfo::deserialize(&mut deserializer).ok()
+        })
+        .is_some_and(|status_info| status_info.is_coherent_set_end());
+
+    Some((coherent_set_sequence_number, is_coherent_set_end))
+}
+
+/// Returns the sequence number of the change among the changes of all the writers of its publisher
+fn get_group_sequence_number(inline_qos: &ParameterList) -> Option<SequenceNumber> {
+    let group_sequence_number = inline_qos
+        .parameter()
+        .iter()
+        .find(|&x| x.parameter_id() == PID_GROUP_SEQ_NUM)?;
+    let mut deserializer =
+        ClassicCdrDeserializer::new(group_sequence_number.value(), CdrEndianness::LittleEndian);
+    Some(SequenceNumber::from(
+        GroupSequenceNumberParameter::deserialize(&mut deserializer).ok()?,
+    ))
 }
 
 fn build_instance_handle(
@@ -182,9 +306,9 @@ fn build_instance_handle(
 }
 
 impl SampleLostStatus {
-    fn increment(&mut self) {
-        self.total_count += 1;
-        self.total_count_change += 1;
+    fn increment(&mut self, lost_samples: i32) {
+        self.total_count += lost_samples;
+        self.total_count_change += lost_samples;
     }
 
     fn read_and_reset(&mut self) -> Self {
@@ -252,8 +376,113 @@ impl MailHandler<ReadRequestedDeadlineMissedStatus> for DataReaderActor {
     }
 }
 
+struct WriterLiveliness {
+    liveliness: LivelinessQosPolicy,
+    alive: bool,
+    lease_expired_task: Option<TaskHandle>,
+}
+
+pub struct ExpireWriterLiveliness {
+    pub writer_handle: InstanceHandle,
+    pub data_reader_address: ActorAddress<DataReaderActor>,
+    pub subscriber: SubscriberAsync,
+    pub subscriber_mask_listener: (
+        Option<MpscSender<SubscriberListenerMessage>>,
+        Vec<StatusKind>,
+    ),
+    pub participant_mask_listener: (
+        Option<MpscSender<ParticipantListenerMessage>>,
+        Vec<StatusKind>,
+    ),
+}
+impl Mail for ExpireWriterLiveliness {
+    type Result = ();
+}
+impl MailHandler<ExpireWriterLiveliness> for DataReaderActor {
+    fn handle(
+        &mut self,
+        message: ExpireWriterLiveliness,
+    ) -> <ExpireWriterLiveliness as Mail>::Result {
+        let mut is_writer_liveliness_lost = false;
+        if let Some(writer_liveliness) = self
+            .matched_writer_liveliness
+            .get_mut(&message.writer_handle)
+        {
+            if writer_liveliness.alive {
+                writer_liveliness.alive = false;
+                writer_liveliness.lease_expired_task = None;
+                is_writer_liveliness_lost = true;
+            }
+        }
+
+        if is_writer_liveliness_lost {
+            self.instance_ownership
+                .retain(|_, owner_handle| owner_handle != &message.writer_handle);
+            for instance in self.instances.values_mut() {
+                instance.remove_writer(message.writer_handle);
+            }
+            self.liveliness_changed_status
+                .on_writer_not_alive(message.writer_handle);
+            self.on_liveliness_changed(
+                &message.data_reader_address,
+                &message.subscriber,
+                &message.subscriber_mask_listener,
+                &message.participant_mask_listener,
+            )
+            .ok();
+        }
+    }
+}
+
+pub struct GetLivelinessChangedStatus;
+impl Mail for GetLivelinessChangedStatus {
+    type Result = LivelinessChangedStatus;
+}
+impl MailHandler<GetLivelinessChangedStatus> for DataReaderActor {
+    fn handle(
+        &mut self,
+        _: GetLivelinessChangedStatus,
+    ) -> <GetLivelinessChangedStatus as Mail>::Result {
+        self.status_condition
+            .send_actor_mail(status_condition_actor::RemoveCommunicationState {
+                state: StatusKind::LivelinessChanged,
+            });
+
+        self.liveliness_changed_status.read_and_reset()
+    }
+}
+
 impl LivelinessChangedStatus {
-    fn _read_and_reset(&mut self) -> Self {
+    fn on_writer_alive(&mut self, writer_handle: InstanceHandle, was_not_alive: bool) {
+        self.alive_count += 1;
+        self.alive_count_change += 1;
+        if was_not_alive {
+            self.not_alive_count -= 1;
+            self.not_alive_count_change -= 1;
+        }
+        self.last_publication_handle = writer_handle;
+    }
+
+    fn on_writer_not_alive(&mut self, writer_handle: InstanceHandle) {
+        self.alive_count -= 1;
+        self.alive_count_change -= 1;
+        self.not_alive_count += 1;
+        self.not_alive_count_change += 1;
+        self.last_publication_handle = writer_handle;
+    }
+
+    fn on_writer_removed(&mut self, writer_handle: InstanceHandle, was_alive: bool) {
+        if was_alive {
+            self.alive_count -= 1;
+            self.alive_count_change -= 1;
+        } else {
+            self.not_alive_count -= 1;
+            self.not_alive_count_change -= 1;
+        }
+        self.last_publication_handle = writer_handle;
+    }
+
+    fn read_and_reset(&mut self) -> Self {
         let status = self.clone();
 
         self.alive_count_change = 0;
@@ -292,21 +521,25 @@ impl RequestedIncompatibleQosStatus {
 }
 
 impl SubscriptionMatchedStatus {
-    fn increment(&mut self, instance_handle: InstanceHandle) {
+    fn on_writer_matched(&mut self, writer_handle: InstanceHandle) {
         self.total_count += 1;
         self.total_count_change += 1;
-        self.last_publication_handle = instance_handle;
+        self.last_publication_handle = writer_handle;
         self.current_count += 1;
         self.current_count_change += 1;
     }
 
-    fn read_and_reset(&mut self, current_count: i32) -> Self {
-        let last_current_count = self.current_count;
-        self.current_count = current_count;
-        self.current_count_change = current_count - last_current_count;
+    fn on_writer_unmatched(&mut self, writer_handle: InstanceHandle) {
+        self.current_count -= 1;
+        self.current_count_change -= 1;
+        self.last_publication_handle = writer_handle;
+    }
+
+    fn read_and_reset(&mut self) -> Self {
         let status = self.clone();
 
         self.total_count_change = 0;
+        self.current_count_change = 0;
 
         status
     }
@@ -371,13 +604,14 @@ pub struct DataReaderActor {
     type_name: String,
     topic_status_condition: ActorAddress<StatusConditionActor>,
     type_support: Arc<dyn DynamicTypeInterface + Send + Sync>,
-    _liveliness_changed_status: LivelinessChangedStatus,
+    liveliness_changed_status: LivelinessChangedStatus,
     requested_deadline_missed_status: ReaderRequestedDeadlineMissedStatus,
     requested_incompatible_qos_status: RequestedIncompatibleQosStatus,
     sample_lost_status: SampleLostStatus,
     sample_rejected_status: SampleRejectedStatus,
     subscription_matched_status: SubscriptionMatchedStatus,
     matched_publication_list: HashMap<InstanceHandle, PublicationBuiltinTopicData>,
+    matched_writer_liveliness: HashMap<InstanceHandle, WriterLiveliness>,
     enabled: bool,
     data_available_status_changed_flag: bool,
     incompatible_writer_list: HashSet<InstanceHandle>,
@@ -386,6 +620,15 @@ pub struct DataReaderActor {
     status_kind: Vec<StatusKind>,
     instances: HashMap<InstanceHandle, InstanceState>,
     instance_deadline_missed_task: HashMap<InstanceHandle, TaskHandle>,
+    instance_ownership: HashMap<InstanceHandle, InstanceHandle>,
+    instance_last_accepted_timestamp: HashMap<InstanceHandle, Time>,
+    instance_last_source_timestamp: HashMap<InstanceHandle, Time>,
+    instance_serialized_key: HashMap<InstanceHandle, Vec<u8>>,
+    read_conditions: HashMap<u32, ReadConditionState>,
+    read_condition_counter: u32,
+    content_filter: Option<QueryFilter>,
+    coherent_set_changes: HashMap<InstanceHandle, (SequenceNumber, Vec<ReaderCacheChange>)>,
+    access_snapshot: Option<HashMap<InstanceHandle, SequenceNumber>>,
 }
 
 impl DataReaderActor {
@@ -400,6 +643,7 @@ impl DataReaderActor {
         qos: DataReaderQos,
         listener: Option<Box<dyn AnyDataReaderListener + Send>>,
         status_kind: Vec<StatusKind>,
+        content_filter: Option<QueryFilter>,
         handle: &ExecutorHandle,
     ) -> Self {
         let status_condition = Actor::spawn(StatusConditionActor::default(), handle);
@@ -413,13 +657,14 @@ impl DataReaderActor {
             type_name,
             topic_status_condition,
             type_support,
-            _liveliness_changed_status: LivelinessChangedStatus::default(),
+            liveliness_changed_status: LivelinessChangedStatus::default(),
             requested_deadline_missed_status: ReaderRequestedDeadlineMissedStatus::default(),
             requested_incompatible_qos_status: RequestedIncompatibleQosStatus::default(),
             sample_lost_status: SampleLostStatus::default(),
             sample_rejected_status: SampleRejectedStatus::default(),
             subscription_matched_status: SubscriptionMatchedStatus::default(),
             matched_publication_list: HashMap::new(),
+            matched_writer_liveliness: HashMap::new(),
             enabled: false,
             data_available_status_changed_flag: false,
             incompatible_writer_list: HashSet::new(),
@@ -429,6 +674,15 @@ impl DataReaderActor {
             qos,
             instances: HashMap::new(),
             instance_deadline_missed_task: HashMap::new(),
+            instance_ownership: HashMap::new(),
+            instance_last_accepted_timestamp: HashMap::new(),
+            instance_last_source_timestamp: HashMap::new(),
+            instance_serialized_key: HashMap::new(),
+            read_conditions: HashMap::new(),
+            read_condition_counter: 0,
+            content_filter,
+            coherent_set_changes: HashMap::new(),
+            access_snapshot: None,
         }
     }
 
@@ -448,6 +702,7 @@ impl DataReaderActor {
         status
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn read(
         &mut self,
         max_samples: i32,
@@ -455,11 +710,15 @@ impl DataReaderActor {
         view_states: Vec<ViewStateKind>,
         instance_states: Vec<InstanceStateKind>,
         specific_instance_handle: Option<InstanceHandle>,
+        query_filter: Option<&QueryFilter>,
+        now: Time,
     ) -> DdsResult<Vec<(Option<Data>, SampleInfo)>> {
         if !self.enabled {
             return Err(DdsError::NotEnabled);
         }
 
+        self.remove_expired_changes(now);
+
         self.status_condition
             .send_actor_mail(status_condition_actor::RemoveCommunicationState {
                 state: StatusKind::DataAvailable,
@@ -471,6 +730,7 @@ impl DataReaderActor {
             &view_states,
             &instance_states,
             specific_instance_handle,
+            query_filter,
         )?;
 
         let change_index_list: Vec<usize>;
@@ -488,6 +748,7 @@ impl DataReaderActor {
         Ok(samples)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn take(
         &mut self,
         max_samples: i32,
@@ -495,17 +756,22 @@ impl DataReaderActor {
         view_states: Vec<ViewStateKind>,
         instance_states: Vec<InstanceStateKind>,
         specific_instance_handle: Option<InstanceHandle>,
+        query_filter: Option<&QueryFilter>,
+        now: Time,
     ) -> DdsResult<Vec<(Option<Data>, SampleInfo)>> {
         if !self.enabled {
             return Err(DdsError::NotEnabled);
         }
 
+        self.remove_expired_changes(now);
+
         let indexed_sample_list = self.create_indexed_sample_collection(
             max_samples,
             &sample_states,
             &view_states,
             &instance_states,
             specific_instance_handle,
+            query_filter,
         )?;
 
         self.status_condition
@@ -586,18 +852,6 @@ impl DataReaderActor {
                 })?;
             }
         }
-        subscriber
-            .get_statuscondition()
-            .address()
-            .send_actor_mail(AddCommunicationState {
-                state: StatusKind::DataOnReaders,
-            })?;
-
-        self.status_condition
-            .address()
-            .send_actor_mail(AddCommunicationState {
-                state: StatusKind::DataAvailable,
-            })?;
         Ok(())
     }
 
@@ -619,11 +873,17 @@ impl DataReaderActor {
             Vec<StatusKind>,
         ),
         executor_handle: &ExecutorHandle,
-        timer_handle: &TimerHandle,
     ) -> DdsResult<()> {
         let writer_guid = Guid::new(source_guid_prefix, data_submessage.writer_id());
         let sequence_number = data_submessage.writer_sn();
         let message_reader_id = data_submessage.reader_id();
+        self.on_writer_liveliness_asserted(
+            InstanceHandle::new(writer_guid.into()),
+            data_reader_address,
+            subscriber,
+            subscriber_mask_listener,
+            participant_mask_listener,
+        )?;
         match &mut self.rtps_reader {
             RtpsReaderKind::Stateful(r) => {
                 if let Some(writer_proxy) = r.matched_writer_lookup(writer_guid) {
@@ -635,7 +895,9 @@ impl DataReaderActor {
                                 writer_proxy.received_change_set(sequence_number);
                                 if sequence_number > expected_seq_num {
                                     writer_proxy.lost_changes_update(sequence_number);
+                                    // Every sequence number skipped by a best-effort writer is a lost sample
                                     self.on_sample_lost(
+                                        (sequence_number - expected_seq_num) as i32,
                                         data_reader_address,
                                         subscriber,
                                         subscriber_mask_listener,
@@ -644,6 +906,7 @@ impl DataReaderActor {
                                 }
                                 match self.convert_received_data_to_cache_change(
                                                 writer_guid,
+                                                sequence_number,
                                                 data_submessage.inline_qos().clone(),
                                                 data_submessage.serialized_payload().clone(),
                                                 source_timestamp,
@@ -657,7 +920,6 @@ impl DataReaderActor {
                                                         subscriber_mask_listener,
                                                         participant_mask_listener,
                                                         executor_handle,
-                                                        timer_handle,
                                                     )?;
                                                 }
                                                 Err(e) => debug!(
@@ -680,6 +942,7 @@ impl DataReaderActor {
                                 writer_proxy.received_change_set(sequence_number);
                                 match self.convert_received_data_to_cache_change(
                                                 writer_guid,
+                                                sequence_number,
                                                 data_submessage.inline_qos().clone(),
                                                 data_submessage.serialized_payload().clone(),
                                                 source_timestamp,
@@ -692,7 +955,7 @@ impl DataReaderActor {
                                                         subscriber,
                                                         subscriber_mask_listener,
                                                         participant_mask_listener,
-                                                        executor_handle,timer_handle,
+                                                        executor_handle,
                                                     )?;
                                                 }
                                                 Err(e) => debug!(
@@ -720,6 +983,7 @@ impl DataReaderActor {
                     // because all readers would get changes marked with ENTITYID_UNKNOWN
                     if let Ok(change) = self.convert_received_data_to_cache_change(
                         writer_guid,
+                        data_submessage.writer_sn(),
                         data_submessage.inline_qos().clone(),
                         data_submessage.serialized_payload().clone(),
                         source_timestamp,
@@ -732,7 +996,6 @@ impl DataReaderActor {
                             subscriber_mask_listener,
                             participant_mask_listener,
                             executor_handle,
-                            timer_handle,
                         )?;
                     }
                 }
@@ -760,7 +1023,6 @@ impl DataReaderActor {
             Vec<StatusKind>,
         ),
         executor_handle: &ExecutorHandle,
-        timer_handle: &TimerHandle,
     ) -> DdsResult<()> {
         let sequence_number = data_frag_submessage.writer_sn();
         let writer_guid = Guid::new(source_guid_prefix, data_frag_submessage.writer_id());
@@ -782,7 +1044,6 @@ impl DataReaderActor {
                             subscriber_mask_listener,
                             participant_mask_listener,
                             executor_handle,
-                            timer_handle,
                         )?;
                     }
                 }
@@ -793,12 +1054,24 @@ impl DataReaderActor {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn on_heartbeat_submessage_received(
         &mut self,
         heartbeat_submessage: &HeartbeatSubmessage,
         source_guid_prefix: GuidPrefix,
         message_sender_actor: &ActorAddress<MessageSenderActor>,
-    ) {
+        data_reader_address: &ActorAddress<DataReaderActor>,
+        subscriber: &SubscriberAsync,
+        subscriber_mask_listener: &(
+            Option<MpscSender<SubscriberListenerMessage>>,
+            Vec<StatusKind>,
+        ),
+        participant_mask_listener: &(
+            Option<MpscSender<ParticipantListenerMessage>>,
+            Vec<StatusKind>,
+        ),
+    ) -> DdsResult<()> {
+        let mut lost_samples = 0;
         if self.qos.reliability.kind == ReliabilityQosPolicyKind::Reliable {
             let writer_guid = Guid::new(source_guid_prefix, heartbeat_submessage.writer_id());
 
@@ -811,18 +1084,27 @@ impl DataReaderActor {
                             writer_proxy
                                 .set_last_received_heartbeat_count(heartbeat_submessage.count());
 
+                            // Changes that were never received and are no longer available in the
+                            // writer history are lost. Before anything is received from the writer
+                            // the changes below the first available one are of no interest.
+                            let available_changes_max = writer_proxy.available_changes_max();
+                            if available_changes_max > 0 {
+                                lost_samples =
+                                    (heartbeat_submessage.first_sn() - available_changes_max - 1)
+                                        .max(0) as i32;
+                            }
+
+                            writer_proxy.missing_changes_update(heartbeat_submessage.last_sn());
+                            writer_proxy.lost_changes_update(heartbeat_submessage.first_sn());
+
+                            // The missing changes are only known after the update with the
+                            // sequence numbers announced in the heartbeat
                             writer_proxy.set_must_send_acknacks(
                                 !heartbeat_submessage.final_flag()
                                     || (!heartbeat_submessage.liveliness_flag()
-                                        && !writer_proxy.missing_changes().count() == 0),
+                                        && writer_proxy.missing_changes().next().is_some()),
                             );
 
-                            if !heartbeat_submessage.final_flag() {
-                                writer_proxy.set_must_send_acknacks(true);
-                            }
-                            writer_proxy.missing_changes_update(heartbeat_submessage.last_sn());
-                            writer_proxy.lost_changes_update(heartbeat_submessage.first_sn());
-
                             self.send_message(message_sender_actor);
                         }
                     }
@@ -830,25 +1112,46 @@ impl DataReaderActor {
                 RtpsReaderKind::Stateless(_) => (),
             }
         }
+
+        if lost_samples > 0 {
+            self.on_sample_lost(
+                lost_samples,
+                data_reader_address,
+                subscriber,
+                subscriber_mask_listener,
+                participant_mask_listener,
+            )?;
+        }
+
+        Ok(())
     }
 
     fn on_heartbeat_frag_submessage_received(
         &mut self,
         heartbeat_frag_submessage: &HeartbeatFragSubmessage,
         source_guid_prefix: GuidPrefix,
+        message_sender_actor: &ActorAddress<MessageSenderActor>,
     ) {
         if self.qos.reliability.kind == ReliabilityQosPolicyKind::Reliable {
+            let reader_guid = self.rtps_reader.guid();
             let writer_guid = Guid::new(source_guid_prefix, heartbeat_frag_submessage.writer_id());
 
             match &mut self.rtps_reader {
                 RtpsReaderKind::Stateful(r) => {
                     if let Some(writer_proxy) = r.matched_writer_lookup(writer_guid) {
-                        if writer_proxy.last_received_heartbeat_count()
+                        if writer_proxy.last_received_heartbeat_frag_count()
                             < heartbeat_frag_submessage.count()
                         {
                             writer_proxy.set_last_received_heartbeat_frag_count(
                                 heartbeat_frag_submessage.count(),
                             );
+
+                            writer_proxy.send_nack_frag(
+                                &reader_guid,
+                                heartbeat_frag_submessage.writer_sn(),
+                                heartbeat_frag_submessage.last_fragment_num(),
+                                message_sender_actor,
+                            );
                         }
                     }
                 }
@@ -883,7 +1186,9 @@ impl DataReaderActor {
         if &self.qos.latency_budget > writer_info.latency_budget() {
             incompatible_qos_policy_list.push(LATENCYBUDGET_QOS_POLICY_ID);
         }
-        if &self.qos.liveliness > writer_info.liveliness() {
+        if self.qos.liveliness.kind > writer_info.liveliness().kind
+            || self.qos.liveliness.lease_duration < writer_info.liveliness().lease_duration
+        {
             incompatible_qos_policy_list.push(LIVELINESS_QOS_POLICY_ID);
         }
         if self.qos.reliability.kind > writer_info.reliability().kind {
@@ -942,6 +1247,7 @@ impl DataReaderActor {
 
     fn on_sample_lost(
         &mut self,
+        lost_samples: i32,
         data_reader_address: &ActorAddress<DataReaderActor>,
         subscriber: &SubscriberAsync,
         (subscriber_listener, subscriber_listener_mask): &(
@@ -953,7 +1259,7 @@ impl DataReaderActor {
             Vec<StatusKind>,
         ),
     ) -> DdsResult<()> {
-        self.sample_lost_status.increment();
+        self.sample_lost_status.increment(lost_samples);
 
         let topic_status_condition_address = self.topic_status_condition.clone();
         let type_name = self.type_name.clone();
@@ -1012,11 +1318,76 @@ impl DataReaderActor {
         Ok(())
     }
 
-    fn on_subscription_matched(
+    fn on_writer_liveliness_asserted(
         &mut self,
-        instance_handle: InstanceHandle,
-        data_reader_address: ActorAddress<DataReaderActor>,
-        subscriber: SubscriberAsync,
+        writer_handle: InstanceHandle,
+        data_reader_address: &ActorAddress<DataReaderActor>,
+        subscriber: &SubscriberAsync,
+        subscriber_mask_listener: &(
+            Option<MpscSender<SubscriberListenerMessage>>,
+            Vec<StatusKind>,
+        ),
+        participant_mask_listener: &(
+            Option<MpscSender<ParticipantListenerMessage>>,
+            Vec<StatusKind>,
+        ),
+    ) -> DdsResult<()> {
+        let mut is_writer_liveliness_regained = false;
+        if let Some(writer_liveliness) = self.matched_writer_liveliness.get_mut(&writer_handle) {
+            if let Some(t) = writer_liveliness.lease_expired_task.take() {
+                t.abort();
+            }
+
+            if let DurationKind::Finite(lease_duration) =
+                writer_liveliness.liveliness.lease_duration
+            {
+                let lease_duration = std::time::Duration::from(lease_duration);
+                let participant = subscriber.get_participant();
+                let data_reader_address = data_reader_address.clone();
+                let subscriber = subscriber.clone();
+                let subscriber_mask_listener = subscriber_mask_listener.clone();
+                let participant_mask_listener = participant_mask_listener.clone();
+                writer_liveliness.lease_expired_task =
+                    Some(participant.executor_handle().clone().spawn(async move {
+                        if participant.sleep(lease_duration).await.is_err() {
+                            return;
+                        }
+                        data_reader_address
+                            .send_actor_mail(ExpireWriterLiveliness {
+                                writer_handle,
+                                data_reader_address: data_reader_address.clone(),
+                                subscriber,
+                                subscriber_mask_listener,
+                                participant_mask_listener,
+                            })
+                            .ok();
+                    }));
+            }
+
+            if !writer_liveliness.alive {
+                writer_liveliness.alive = true;
+                is_writer_liveliness_regained = true;
+            }
+        }
+
+        if is_writer_liveliness_regained {
+            self.liveliness_changed_status
+                .on_writer_alive(writer_handle, true);
+            self.on_liveliness_changed(
+                data_reader_address,
+                subscriber,
+                subscriber_mask_listener,
+                participant_mask_listener,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn on_liveliness_changed(
+        &mut self,
+        data_reader_address: &ActorAddress<DataReaderActor>,
+        subscriber: &SubscriberAsync,
         (subscriber_listener, subscriber_listener_mask): &(
             Option<MpscSender<SubscriberListenerMessage>>,
             Vec<StatusKind>,
@@ -1026,16 +1397,12 @@ impl DataReaderActor {
             Vec<StatusKind>,
         ),
     ) -> DdsResult<()> {
-        self.subscription_matched_status.increment(instance_handle);
-
-        const SUBSCRIPTION_MATCHED_STATUS_KIND: &StatusKind = &StatusKind::SubscriptionMatched;
+        let topic_status_condition_address = self.topic_status_condition.clone();
         let type_name = self.type_name.clone();
         let topic_name = self.topic_name.clone();
         let reader_address = data_reader_address.clone();
         let status_condition_address = self.status_condition.address();
         let subscriber = subscriber.clone();
-
-        let topic_status_condition_address = self.topic_status_condition.clone();
         let topic = TopicAsync::new(
             self.topic_address.clone(),
             topic_status_condition_address.clone(),
@@ -1043,39 +1410,33 @@ impl DataReaderActor {
             topic_name.clone(),
             subscriber.get_participant(),
         );
-        if self.status_kind.contains(SUBSCRIPTION_MATCHED_STATUS_KIND) {
-            let status = self
-                .subscription_matched_status
-                .read_and_reset(self.matched_publication_list.len() as i32);
+        if self.status_kind.contains(&StatusKind::LivelinessChanged) {
+            let status = self.liveliness_changed_status.read_and_reset();
             if let Some(listener) = &self.data_reader_listener_thread {
                 listener.sender().send(DataReaderListenerMessage {
-                    listener_operation: DataReaderListenerOperation::SubscriptionMatched(status),
+                    listener_operation: DataReaderListenerOperation::LivelinessChanged(status),
                     reader_address,
                     status_condition_address,
                     subscriber,
                     topic,
                 })?;
             }
-        } else if subscriber_listener_mask.contains(SUBSCRIPTION_MATCHED_STATUS_KIND) {
-            let status = self
-                .subscription_matched_status
-                .read_and_reset(self.matched_publication_list.len() as i32);
+        } else if subscriber_listener_mask.contains(&StatusKind::LivelinessChanged) {
+            let status = self.liveliness_changed_status.read_and_reset();
             if let Some(listener) = subscriber_listener {
                 listener.send(SubscriberListenerMessage {
-                    listener_operation: SubscriberListenerOperation::SubscriptionMatched(status),
+                    listener_operation: SubscriberListenerOperation::LivelinessChanged(status),
                     reader_address,
                     status_condition_address,
                     subscriber,
                     topic,
                 })?;
             }
-        } else if participant_listener_mask.contains(SUBSCRIPTION_MATCHED_STATUS_KIND) {
-            let status = self
-                .subscription_matched_status
-                .read_and_reset(self.matched_publication_list.len() as i32);
+        } else if participant_listener_mask.contains(&StatusKind::LivelinessChanged) {
+            let status = self.liveliness_changed_status.read_and_reset();
             if let Some(listener) = participant_listener {
                 listener.send(ParticipantListenerMessage {
-                    listener_operation: ParticipantListenerOperation::SubscriptionMatched(status),
+                    listener_operation: ParticipantListenerOperation::LivelinessChanged(status),
                     listener_kind: ListenerKind::Reader {
                         reader_address,
                         status_condition_address,
@@ -1087,19 +1448,70 @@ impl DataReaderActor {
         }
         self.status_condition
             .send_actor_mail(AddCommunicationState {
-                state: StatusKind::SubscriptionMatched,
+                state: StatusKind::LivelinessChanged,
             });
 
         Ok(())
     }
 
-    #[allow(clippy::too_many_arguments)]
-    fn on_sample_rejected(
+    fn remove_matched_writer(
         &mut self,
-        instance_handle: InstanceHandle,
-        rejected_reason: SampleRejectedStatusKind,
+        discovered_writer_handle: InstanceHandle,
         data_reader_address: &ActorAddress<DataReaderActor>,
         subscriber: &SubscriberAsync,
+        subscriber_mask_listener: &(
+            Option<MpscSender<SubscriberListenerMessage>>,
+            Vec<StatusKind>,
+        ),
+        participant_mask_listener: &(
+            Option<MpscSender<ParticipantListenerMessage>>,
+            Vec<StatusKind>,
+        ),
+    ) -> DdsResult<bool> {
+        let Some(w) = self
+            .matched_publication_list
+            .remove(&discovered_writer_handle)
+        else {
+            return Ok(false);
+        };
+        self.coherent_set_changes.remove(&discovered_writer_handle);
+        match &mut self.rtps_reader {
+            RtpsReaderKind::Stateful(r) => r.matched_writer_remove(w.key().value.into()),
+            RtpsReaderKind::Stateless(_) => (),
+        }
+
+        self.instance_ownership
+            .retain(|_, owner_handle| owner_handle != &discovered_writer_handle);
+        for instance in self.instances.values_mut() {
+            instance.remove_writer(discovered_writer_handle);
+        }
+
+        if let Some(writer_liveliness) = self
+            .matched_writer_liveliness
+            .remove(&discovered_writer_handle)
+        {
+            if let Some(t) = writer_liveliness.lease_expired_task {
+                t.abort();
+            }
+            self.liveliness_changed_status
+                .on_writer_removed(discovered_writer_handle, writer_liveliness.alive);
+            self.on_liveliness_changed(
+                data_reader_address,
+                subscriber,
+                subscriber_mask_listener,
+                participant_mask_listener,
+            )?;
+        }
+
+        self.subscription_matched_status
+            .on_writer_unmatched(discovered_writer_handle);
+        Ok(true)
+    }
+
+    fn on_subscription_matched(
+        &mut self,
+        data_reader_address: ActorAddress<DataReaderActor>,
+        subscriber: SubscriberAsync,
         (subscriber_listener, subscriber_listener_mask): &(
             Option<MpscSender<SubscriberListenerMessage>>,
             Vec<StatusKind>,
@@ -1109,16 +1521,14 @@ impl DataReaderActor {
             Vec<StatusKind>,
         ),
     ) -> DdsResult<()> {
-        self.sample_rejected_status
-            .increment(instance_handle, rejected_reason);
-
+        const SUBSCRIPTION_MATCHED_STATUS_KIND: &StatusKind = &StatusKind::SubscriptionMatched;
         let type_name = self.type_name.clone();
         let topic_name = self.topic_name.clone();
-
-        let topic_status_condition_address = self.topic_status_condition.clone();
         let reader_address = data_reader_address.clone();
         let status_condition_address = self.status_condition.address();
         let subscriber = subscriber.clone();
+
+        let topic_status_condition_address = self.topic_status_condition.clone();
         let topic = TopicAsync::new(
             self.topic_address.clone(),
             topic_status_condition_address.clone(),
@@ -1126,33 +1536,33 @@ impl DataReaderActor {
             topic_name.clone(),
             subscriber.get_participant(),
         );
-        if self.status_kind.contains(&StatusKind::SampleRejected) {
-            let status = self.sample_rejected_status.read_and_reset();
+        if self.status_kind.contains(SUBSCRIPTION_MATCHED_STATUS_KIND) {
+            let status = self.subscription_matched_status.read_and_reset();
             if let Some(listener) = &self.data_reader_listener_thread {
                 listener.sender().send(DataReaderListenerMessage {
-                    listener_operation: DataReaderListenerOperation::SampleRejected(status),
+                    listener_operation: DataReaderListenerOperation::SubscriptionMatched(status),
                     reader_address,
                     status_condition_address,
                     subscriber,
                     topic,
                 })?;
             }
-        } else if subscriber_listener_mask.contains(&StatusKind::SampleRejected) {
-            let status = self.sample_rejected_status.read_and_reset();
+        } else if subscriber_listener_mask.contains(SUBSCRIPTION_MATCHED_STATUS_KIND) {
+            let status = self.subscription_matched_status.read_and_reset();
             if let Some(listener) = subscriber_listener {
                 listener.send(SubscriberListenerMessage {
-                    listener_operation: SubscriberListenerOperation::SampleRejected(status),
+                    listener_operation: SubscriberListenerOperation::SubscriptionMatched(status),
                     reader_address,
                     status_condition_address,
                     subscriber,
                     topic,
                 })?;
             }
-        } else if participant_listener_mask.contains(&StatusKind::SampleRejected) {
-            let status = self.sample_rejected_status.read_and_reset();
+        } else if participant_listener_mask.contains(SUBSCRIPTION_MATCHED_STATUS_KIND) {
+            let status = self.subscription_matched_status.read_and_reset();
             if let Some(listener) = participant_listener {
                 listener.send(ParticipantListenerMessage {
-                    listener_operation: ParticipantListenerOperation::SampleRejected(status),
+                    listener_operation: ParticipantListenerOperation::SubscriptionMatched(status),
                     listener_kind: ListenerKind::Reader {
                         reader_address,
                         status_condition_address,
@@ -1164,7 +1574,84 @@ impl DataReaderActor {
         }
         self.status_condition
             .send_actor_mail(AddCommunicationState {
-                state: StatusKind::SampleRejected,
+                state: StatusKind::SubscriptionMatched,
+            });
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn on_sample_rejected(
+        &mut self,
+        instance_handle: InstanceHandle,
+        rejected_reason: SampleRejectedStatusKind,
+        data_reader_address: &ActorAddress<DataReaderActor>,
+        subscriber: &SubscriberAsync,
+        (subscriber_listener, subscriber_listener_mask): &(
+            Option<MpscSender<SubscriberListenerMessage>>,
+            Vec<StatusKind>,
+        ),
+        (participant_listener, participant_listener_mask): &(
+            Option<MpscSender<ParticipantListenerMessage>>,
+            Vec<StatusKind>,
+        ),
+    ) -> DdsResult<()> {
+        self.sample_rejected_status
+            .increment(instance_handle, rejected_reason);
+
+        let type_name = self.type_name.clone();
+        let topic_name = self.topic_name.clone();
+
+        let topic_status_condition_address = self.topic_status_condition.clone();
+        let reader_address = data_reader_address.clone();
+        let status_condition_address = self.status_condition.address();
+        let subscriber = subscriber.clone();
+        let topic = TopicAsync::new(
+            self.topic_address.clone(),
+            topic_status_condition_address.clone(),
+            type_name.clone(),
+            topic_name.clone(),
+            subscriber.get_participant(),
+        );
+        if self.status_kind.contains(&StatusKind::SampleRejected) {
+            let status = self.sample_rejected_status.read_and_reset();
+            if let Some(listener) = &self.data_reader_listener_thread {
+                listener.sender().send(DataReaderListenerMessage {
+                    listener_operation: DataReaderListenerOperation::SampleRejected(status),
+                    reader_address,
+                    status_condition_address,
+                    subscriber,
+                    topic,
+                })?;
+            }
+        } else if subscriber_listener_mask.contains(&StatusKind::SampleRejected) {
+            let status = self.sample_rejected_status.read_and_reset();
+            if let Some(listener) = subscriber_listener {
+                listener.send(SubscriberListenerMessage {
+                    listener_operation: SubscriberListenerOperation::SampleRejected(status),
+                    reader_address,
+                    status_condition_address,
+                    subscriber,
+                    topic,
+                })?;
+            }
+        } else if participant_listener_mask.contains(&StatusKind::SampleRejected) {
+            let status = self.sample_rejected_status.read_and_reset();
+            if let Some(listener) = participant_listener {
+                listener.send(ParticipantListenerMessage {
+                    listener_operation: ParticipantListenerOperation::SampleRejected(status),
+                    listener_kind: ListenerKind::Reader {
+                        reader_address,
+                        status_condition_address,
+                        subscriber,
+                        topic,
+                    },
+                })?;
+            }
+        }
+        self.status_condition
+            .send_actor_mail(AddCommunicationState {
+                state: StatusKind::SampleRejected,
             });
 
         Ok(())
@@ -1256,6 +1743,7 @@ impl DataReaderActor {
     fn convert_received_data_to_cache_change(
         &mut self,
         writer_guid: Guid,
+        sequence_number: SequenceNumber,
         inline_qos: ParameterList,
         data: Data,
         source_timestamp: Option<rtps::messages::types::Time>,
@@ -1269,7 +1757,8 @@ impl DataReaderActor {
             let mut deserializer =
                 ClassicCdrDeserializer::new(p.value(), CdrEndianness::LittleEndian);
             let status_info: StatusInfo = CdrDeserialize::deserialize(&mut deserializer).unwrap();
-            match status_info {
+            match status_info.without_coherent_set_end() {
+                STATUS_INFO_ALIVE => Ok(ChangeKind::Alive),
                 STATUS_INFO_DISPOSED => Ok(ChangeKind::NotAliveDisposed),
                 STATUS_INFO_UNREGISTERED => Ok(ChangeKind::NotAliveUnregistered),
                 STATUS_INFO_DISPOSED_UNREGISTERED => Ok(ChangeKind::NotAliveDisposedUnregistered),
@@ -1291,7 +1780,16 @@ impl DataReaderActor {
                 self.instances
                     .entry(instance_handle)
                     .or_insert_with(InstanceState::new)
-                    .update_state(change_kind);
+                    .update_state(change_kind, InstanceHandle::new(writer_guid.into()));
+                // The key of an instance never changes so it only needs to be stored once. It is kept
+                // since later NOT_ALIVE changes might only carry the key hash.
+                if !self.instance_serialized_key.contains_key(&instance_handle) {
+                    let serialized_key = self
+                        .type_support
+                        .get_serialized_key_from_serialized_foo(data.as_ref())?;
+                    self.instance_serialized_key
+                        .insert(instance_handle, serialized_key);
+                }
                 Ok(())
             }
             ChangeKind::NotAliveDisposed
@@ -1299,7 +1797,7 @@ impl DataReaderActor {
             | ChangeKind::NotAliveDisposedUnregistered => {
                 match self.instances.get_mut(&instance_handle) {
                     Some(instance) => {
-                        instance.update_state(change_kind);
+                        instance.update_state(change_kind, InstanceHandle::new(writer_guid.into()));
                         Ok(())
                     }
                     None => Err(DdsError::Error(
@@ -1317,6 +1815,7 @@ impl DataReaderActor {
                 data_value: data,
                 inline_qos,
             },
+            sequence_number,
             sample_state: SampleStateKind::NotRead,
             disposed_generation_count: self.instances[&instance_handle]
                 .most_recent_disposed_generation_count,
@@ -1342,9 +1841,83 @@ impl DataReaderActor {
             Vec<StatusKind>,
         ),
         executor_handle: &ExecutorHandle,
-        timer_handle: &TimerHandle,
     ) -> DdsResult<()> {
-        if self.is_sample_of_interest_based_on_time(&change) {
+        let Some((coherent_set_sequence_number, is_coherent_set_end)) =
+            get_coherent_set_info(&change.rtps_cache_change.inline_qos)
+        else {
+            return self.add_change_to_history(
+                change,
+                data_reader_address,
+                subscriber,
+                subscriber_mask_listener,
+                participant_mask_listener,
+                executor_handle,
+            );
+        };
+
+        // The changes of a coherent set are held back until the end of the set is received
+        // so that they all become available to the application at once
+        let writer_handle = InstanceHandle::new(change.rtps_cache_change.writer_guid.into());
+        let (sequence_number, coherent_set) = self
+            .coherent_set_changes
+            .entry(writer_handle)
+            .or_insert_with(|| (coherent_set_sequence_number, Vec::new()));
+        if *sequence_number != coherent_set_sequence_number {
+            // A set for which the end was never received is incomplete and is discarded
+            *sequence_number = coherent_set_sequence_number;
+            coherent_set.clear();
+        }
+        coherent_set.push(change);
+
+        if is_coherent_set_end {
+            if let Some((_, coherent_set)) = self.coherent_set_changes.remove(&writer_handle) {
+                for change in coherent_set {
+                    self.add_change_to_history(
+                        change,
+                        data_reader_address,
+                        subscriber,
+                        subscriber_mask_listener,
+                        participant_mask_listener,
+                        executor_handle,
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn add_change_to_history(
+        &mut self,
+        change: ReaderCacheChange,
+        data_reader_address: &ActorAddress<DataReaderActor>,
+        subscriber: &SubscriberAsync,
+        subscriber_mask_listener: &(
+            Option<MpscSender<SubscriberListenerMessage>>,
+            Vec<StatusKind>,
+        ),
+        participant_mask_listener: &(
+            Option<MpscSender<ParticipantListenerMessage>>,
+            Vec<StatusKind>,
+        ),
+        executor_handle: &ExecutorHandle,
+    ) -> DdsResult<()> {
+        // Samples filtered out by the content filter are not considered lost or rejected
+        if !self.is_sample_of_interest_based_on_content_filter(&change) {
+            return Ok(());
+        }
+
+        if !self.is_sample_of_interest_based_on_time(&change) {
+            self.on_sample_lost(
+                1,
+                data_reader_address,
+                subscriber,
+                subscriber_mask_listener,
+                participant_mask_listener,
+            )?;
+        } else if self.is_sample_of_interest_based_on_ownership(&change)
+            && self.is_sample_of_interest_based_on_destination_order(&change)
+        {
             if self.is_max_samples_limit_reached(&change) {
                 self.on_sample_rejected(
                     change.instance_handle(),
@@ -1403,9 +1976,39 @@ impl DataReaderActor {
                     subscriber_mask_listener,
                     participant_mask_listener,
                     executor_handle,
-                    timer_handle,
                 )?;
 
+                if self.qos.ownership.kind == OwnershipQosPolicyKind::Exclusive {
+                    match change.rtps_cache_change.kind {
+                        ChangeKind::NotAliveUnregistered
+                        | ChangeKind::NotAliveDisposedUnregistered => {
+                            self.instance_ownership.remove(&change.instance_handle());
+                        }
+                        _ => {
+                            self.instance_ownership.insert(
+                                change.instance_handle(),
+                                InstanceHandle::new(change.rtps_cache_change.writer_guid.into()),
+                            );
+                        }
+                    }
+                }
+
+                self.instance_last_source_timestamp.insert(
+                    change.instance_handle(),
+                    Time::from(change.source_timestamp_or_reception()),
+                );
+
+                if change.rtps_cache_change.kind == ChangeKind::Alive {
+                    self.instance_last_accepted_timestamp.insert(
+                        change.instance_handle(),
+                        Time::from(
+                            change
+                                .source_timestamp
+                                .unwrap_or(change.reception_timestamp),
+                        ),
+                    );
+                }
+
                 tracing::debug!(cache_change = ?change, "Adding change to data reader history cache");
                 self.changes.push(change);
                 self.data_available_status_changed_flag = true;
@@ -1413,14 +2016,8 @@ impl DataReaderActor {
                 match self.qos.destination_order.kind {
                     DestinationOrderQosPolicyKind::BySourceTimestamp => {
                         self.changes.sort_by(|a, b| {
-                            a.source_timestamp
-                                .as_ref()
-                                .expect("Missing source timestamp")
-                                .cmp(
-                                    b.source_timestamp
-                                        .as_ref()
-                                        .expect("Missing source timestamp"),
-                                )
+                            a.source_timestamp_or_reception()
+                                .cmp(&b.source_timestamp_or_reception())
                         });
                     }
                     DestinationOrderQosPolicyKind::ByReceptionTimestamp => self
@@ -1435,37 +2032,119 @@ impl DataReaderActor {
         Ok(())
     }
 
+    fn is_sample_of_interest_based_on_content_filter(&self, change: &ReaderCacheChange) -> bool {
+        match &self.content_filter {
+            Some(filter) if change.rtps_cache_change.kind == ChangeKind::Alive => {
+                filter.matches(change)
+            }
+            _ => true,
+        }
+    }
+
     fn is_sample_of_interest_based_on_time(&self, change: &ReaderCacheChange) -> bool {
-        let closest_timestamp_before_received_sample = self
+        if change.rtps_cache_change.kind != ChangeKind::Alive {
+            return true;
+        }
+
+        match self
+            .instance_last_accepted_timestamp
+            .get(&change.instance_handle())
+        {
+            Some(last_accepted_timestamp) => {
+                let sample_timestamp = Time::from(
+                    change
+                        .source_timestamp
+                        .unwrap_or(change.reception_timestamp),
+                );
+                sample_timestamp < *last_accepted_timestamp
+                    || DurationKind::Finite(sample_timestamp - *last_accepted_timestamp)
+                        >= self.qos.time_based_filter.minimum_separation
+            }
+            None => true,
+        }
+    }
+
+    fn is_sample_of_interest_based_on_destination_order(&self, change: &ReaderCacheChange) -> bool {
+        if self.qos.destination_order.kind != DestinationOrderQosPolicyKind::BySourceTimestamp {
+            return true;
+        }
+
+        match self
+            .instance_last_source_timestamp
+            .get(&change.instance_handle())
+        {
+            Some(last_source_timestamp) => {
+                Time::from(change.source_timestamp_or_reception()) >= *last_source_timestamp
+            }
+            None => true,
+        }
+    }
+
+    fn is_sample_of_interest_based_on_ownership(&self, change: &ReaderCacheChange) -> bool {
+        if self.qos.ownership.kind != OwnershipQosPolicyKind::Exclusive {
+            return true;
+        }
+
+        let writer_handle = InstanceHandle::new(change.rtps_cache_change.writer_guid.into());
+        match self.instance_ownership.get(&change.instance_handle()) {
+            Some(owner_handle) if owner_handle != &writer_handle => {
+                let ownership_strength = |h: &InstanceHandle| {
+                    self.matched_publication_list
+                        .get(h)
+                        .map(|p| p.ownership_strength().value)
+                };
+                // Ties in strength are resolved in favour of the lowest writer handle so that
+                // all readers choose the same owner
+                match (
+                    ownership_strength(&writer_handle),
+                    ownership_strength(owner_handle),
+                ) {
+                    (Some(writer_strength), Some(owner_strength)) => {
+                        writer_strength > owner_strength
+                            || (writer_strength == owner_strength && &writer_handle < owner_handle)
+                    }
+                    (_, None) => true,
+                    (None, Some(_)) => false,
+                }
+            }
+            _ => true,
+        }
+    }
+
+    fn num_alive_samples_to_keep(&self, change: &ReaderCacheChange) -> usize {
+        let num_alive_samples_of_instance = self
             .changes
             .iter()
-            .filter(|cc| cc.instance_handle() == change.instance_handle())
-            .filter(|cc| cc.source_timestamp <= change.source_timestamp)
-            .map(|cc| cc.source_timestamp)
-            .max();
-
-        if let Some(Some(t)) = closest_timestamp_before_received_sample {
-            if let Some(sample_source_time) = change.source_timestamp {
-                let sample_separation = infrastructure::time::Duration::from(sample_source_time)
-                    - infrastructure::time::Duration::from(t);
-                DurationKind::Finite(sample_separation)
-                    >= self.qos.time_based_filter.minimum_separation
-            } else {
-                true
+            .filter(|cc| {
+                cc.instance_handle() == change.instance_handle()
+                    && cc.rtps_cache_change.kind == ChangeKind::Alive
+            })
+            .count();
+
+        // If the History QoS would remove one of the samples of the instance then it
+        // doesn't count towards the resource limits
+        match self.qos.history.kind {
+            HistoryQosPolicyKind::KeepLast(depth)
+                if depth as usize == num_alive_samples_of_instance =>
+            {
+                num_alive_samples_of_instance - 1
             }
-        } else {
-            true
+            _ => num_alive_samples_of_instance,
         }
     }
 
-    fn is_max_samples_limit_reached(&self, _change: &ReaderCacheChange) -> bool {
+    fn is_max_samples_limit_reached(&self, change: &ReaderCacheChange) -> bool {
         let total_samples = self
             .changes
             .iter()
-            .filter(|cc| cc.rtps_cache_change.kind == ChangeKind::Alive)
-            .count();
+            .filter(|cc| {
+                cc.instance_handle() != change.instance_handle()
+                    && cc.rtps_cache_change.kind == ChangeKind::Alive
+            })
+            .count()
+            + self.num_alive_samples_to_keep(change);
 
-        total_samples == self.qos.resource_limits.max_samples
+        self.qos.resource_limits.max_samples <= total_samples
     }
 
     fn is_max_instances_limit_reached(&self, change: &ReaderCacheChange) -> bool {
@@ -1480,13 +2159,24 @@ impl DataReaderActor {
     }
 
     fn is_max_samples_per_instance_limit_reached(&self, change: &ReaderCacheChange) -> bool {
-        let total_samples_of_instance = self
-            .changes
-            .iter()
-            .filter(|cc| cc.instance_handle() == change.instance_handle())
-            .count();
+        self.qos.resource_limits.max_samples_per_instance <= self.num_alive_samples_to_keep(change)
+    }
 
-        total_samples_of_instance == self.qos.resource_limits.max_samples_per_instance
+    fn remove_expired_changes(&mut self, now: Time) {
+        let matched_publication_list = &self.matched_publication_list;
+        self.changes.retain(|cc| {
+            let writer_handle = InstanceHandle::new(cc.rtps_cache_change.writer_guid.into());
+            match matched_publication_list
+                .get(&writer_handle)
+                .map(|p| p.lifespan().duration)
+            {
+                Some(DurationKind::Finite(lifespan)) => {
+                    let source_timestamp = cc.source_timestamp.unwrap_or(cc.reception_timestamp);
+                    now - Time::from(source_timestamp) < lifespan
+                }
+                _ => true,
+            }
+        });
     }
 
     fn create_indexed_sample_collection(
@@ -1496,6 +2186,7 @@ impl DataReaderActor {
         view_states: &[ViewStateKind],
         instance_states: &[InstanceStateKind],
         specific_instance_handle: Option<InstanceHandle>,
+        query_filter: Option<&QueryFilter>,
     ) -> DdsResult<Vec<IndexedSample>> {
         if let Some(h) = specific_instance_handle {
             if !self.instances.contains_key(&h) {
@@ -1506,7 +2197,8 @@ impl DataReaderActor {
         let mut indexed_samples = Vec::new();
 
         let instances = &self.instances;
-        let mut instances_in_collection = HashMap::new();
+        let access_snapshot = self.access_snapshot.as_ref();
+        let mut instances_in_collection = HashSet::new();
         for (index, cache_change) in self
             .changes
             .iter()
@@ -1520,29 +2212,24 @@ impl DataReaderActor {
                     } else {
                         true
                     }
+                    && query_filter.is_none_or(|f| f.matches(cc))
+                    && cc.is_in_access_snapshot(access_snapshot)
             })
             .take(max_samples as usize)
         {
-            instances_in_collection
-                .entry(cache_change.instance_handle())
-                .or_insert_with(InstanceState::new);
-
-            instances_in_collection
-                .get_mut(&cache_change.instance_handle())
-                .unwrap()
-                .update_state(cache_change.rtps_cache_change.kind);
+            instances_in_collection.insert(cache_change.instance_handle());
             let sample_state = cache_change.sample_state;
             let view_state = self.instances[&cache_change.instance_handle()].view_state;
             let instance_state = self.instances[&cache_change.instance_handle()].instance_state;
 
+            // Number of generations between the sample and the most recent sample of the
+            // instance in the reader, whether or not it is part of the collection
             let absolute_generation_rank = (self.instances[&cache_change.instance_handle()]
                 .most_recent_disposed_generation_count
                 + self.instances[&cache_change.instance_handle()]
                     .most_recent_no_writers_generation_count)
-                - (instances_in_collection[&cache_change.instance_handle()]
-                    .most_recent_disposed_generation_count
-                    + instances_in_collection[&cache_change.instance_handle()]
-                        .most_recent_no_writers_generation_count);
+                - (cache_change.disposed_generation_count
+                    + cache_change.no_writers_generation_count);
 
             let (data, valid_data) = match cache_change.rtps_cache_change.kind {
                 ChangeKind::Alive | ChangeKind::AliveFiltered => (
@@ -1577,7 +2264,7 @@ impl DataReaderActor {
         }
 
         // After the collection is created, update the relative generation rank values and mark the read instances as viewed
-        for handle in instances_in_collection.into_keys() {
+        for handle in instances_in_collection {
             let most_recent_sample_absolute_generation_rank = indexed_samples
                 .iter()
                 .filter(
@@ -1634,6 +2321,13 @@ impl DataReaderActor {
         }
     }
 
+    fn add_read_condition(&mut self, read_condition: ReadConditionState) -> u32 {
+        let condition_id = self.read_condition_counter;
+        self.read_condition_counter += 1;
+        self.read_conditions.insert(condition_id, read_condition);
+        condition_id
+    }
+
     fn next_instance(&self, previous_handle: Option<InstanceHandle>) -> Option<InstanceHandle> {
         match previous_handle {
             Some(p) => self.instances.keys().filter(|&h| h > &p).min().cloned(),
@@ -1656,7 +2350,6 @@ impl DataReaderActor {
             Vec<StatusKind>,
         ),
         executor_handle: &ExecutorHandle,
-        timer_handle: &TimerHandle,
     ) -> DdsResult<()> {
         if let Some(t) = self
             .instance_deadline_missed_task
@@ -1685,10 +2378,12 @@ impl DataReaderActor {
             let topic_name = self.topic_name.clone();
             let status_condition_address = self.status_condition.address();
             let topic_status_condition_address = self.topic_status_condition.clone();
-            let timer_handle = timer_handle.clone();
+            let participant = subscriber.get_participant();
             let deadline_missed_task = executor_handle.spawn(async move {
                 loop {
-                    timer_handle.sleep(deadline_missed_interval).await;
+                    if participant.sleep(deadline_missed_interval).await.is_err() {
+                        break;
+                    }
                     let subscriber_listener = subscriber_listener.clone();
                     let participant_listener = participant_listener.clone();
                     let r: DdsResult<()> = async {
@@ -1795,6 +2490,10 @@ impl DataReaderActor {
     }
 
     fn send_message(&mut self, message_sender_actor: &ActorAddress<MessageSenderActor>) {
+        // No traffic is generated for a reader which has not been enabled yet
+        if !self.enabled {
+            return;
+        }
         match &mut self.rtps_reader {
             RtpsReaderKind::Stateful(r) => r.send_message(message_sender_actor),
             RtpsReaderKind::Stateless(_) => (),
@@ -1808,6 +2507,7 @@ pub struct Read {
     pub view_states: Vec<ViewStateKind>,
     pub instance_states: Vec<InstanceStateKind>,
     pub specific_instance_handle: Option<InstanceHandle>,
+    pub now: Time,
 }
 impl Mail for Read {
     type Result = DdsResult<Vec<(Option<Data>, SampleInfo)>>;
@@ -1820,6 +2520,8 @@ impl MailHandler<Read> for DataReaderActor {
             message.view_states,
             message.instance_states,
             message.specific_instance_handle,
+            None,
+            message.now,
         )
     }
 }
@@ -1830,6 +2532,7 @@ pub struct Take {
     pub view_states: Vec<ViewStateKind>,
     pub instance_states: Vec<InstanceStateKind>,
     pub specific_instance_handle: Option<InstanceHandle>,
+    pub now: Time,
 }
 impl Mail for Take {
     type Result = DdsResult<Vec<(Option<Data>, SampleInfo)>>;
@@ -1842,6 +2545,277 @@ impl MailHandler<Take> for DataReaderActor {
             message.view_states,
             message.instance_states,
             message.specific_instance_handle,
+            None,
+            message.now,
+        )
+    }
+}
+
+pub struct GetSampleSequenceNumberList {
+    pub sample_states: Vec<SampleStateKind>,
+    pub view_states: Vec<ViewStateKind>,
+    pub instance_states: Vec<InstanceStateKind>,
+}
+impl Mail for GetSampleSequenceNumberList {
+    type Result = Vec<SequenceNumber>;
+}
+impl MailHandler<GetSampleSequenceNumberList> for DataReaderActor {
+    fn handle(
+        &mut self,
+        message: GetSampleSequenceNumberList,
+    ) -> <GetSampleSequenceNumberList as Mail>::Result {
+        self.changes
+            .iter()
+            .filter(|cc| {
+                message.sample_states.contains(&cc.sample_state)
+                    && message
+                        .view_states
+                        .contains(&self.instances[&cc.instance_handle()].view_state)
+                    && message
+                        .instance_states
+                        .contains(&self.instances[&cc.instance_handle()].instance_state)
+                    && cc.is_in_access_snapshot(self.access_snapshot.as_ref())
+            })
+            .map(|cc| {
+                get_group_sequence_number(&cc.rtps_cache_change.inline_qos)
+                    .unwrap_or(cc.sequence_number)
+            })
+            .collect()
+    }
+}
+
+pub struct BeginAccess;
+impl Mail for BeginAccess {
+    type Result = ();
+}
+impl MailHandler<BeginAccess> for DataReaderActor {
+    fn handle(&mut self, _: BeginAccess) -> <BeginAccess as Mail>::Result {
+        let mut access_snapshot = HashMap::new();
+        for cc in &self.changes {
+            let sequence_number = access_snapshot
+                .entry(InstanceHandle::new(cc.rtps_cache_change.writer_guid.into()))
+                .or_insert(cc.sequence_number);
+            *sequence_number = (*sequence_number).max(cc.sequence_number);
+        }
+        self.access_snapshot = Some(access_snapshot);
+    }
+}
+
+pub struct EndAccess;
+impl Mail for EndAccess {
+    type Result = ();
+}
+impl MailHandler<EndAccess> for DataReaderActor {
+    fn handle(&mut self, _: EndAccess) -> <EndAccess as Mail>::Result {
+        self.access_snapshot = None;
+    }
+}
+
+pub struct CreateReadCondition {
+    pub sample_states: Vec<SampleStateKind>,
+    pub view_states: Vec<ViewStateKind>,
+    pub instance_states: Vec<InstanceStateKind>,
+}
+impl Mail for CreateReadCondition {
+    type Result = u32;
+}
+impl MailHandler<CreateReadCondition> for DataReaderActor {
+    fn handle(&mut self, message: CreateReadCondition) -> <CreateReadCondition as Mail>::Result {
+        self.add_read_condition(ReadConditionState {
+            sample_states: message.sample_states,
+            view_states: message.view_states,
+            instance_states: message.instance_states,
+            query: None,
+        })
+    }
+}
+
+pub struct CreateQueryCondition {
+    pub sample_states: Vec<SampleStateKind>,
+    pub view_states: Vec<ViewStateKind>,
+    pub instance_states: Vec<InstanceStateKind>,
+    pub query_expression: String,
+    pub query_parameters: Vec<String>,
+    pub evaluator: QueryEvaluator,
+}
+impl Mail for CreateQueryCondition {
+    type Result = DdsResult<u32>;
+}
+impl MailHandler<CreateQueryCondition> for DataReaderActor {
+    fn handle(&mut self, message: CreateQueryCondition) -> <CreateQueryCondition as Mail>::Result {
+        let query = QueryExpression::new(&message.query_expression, &message.query_parameters)?;
+        Ok(self.add_read_condition(ReadConditionState {
+            sample_states: message.sample_states,
+            view_states: message.view_states,
+            instance_states: message.instance_states,
+            query: Some(QueryConditionState {
+                query_expression: message.query_expression,
+                query_parameters: message.query_parameters,
+                filter: QueryFilter::new(query, message.evaluator),
+            }),
+        }))
+    }
+}
+
+pub struct DeleteReadCondition {
+    pub condition_id: u32,
+}
+impl Mail for DeleteReadCondition {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<DeleteReadCondition> for DataReaderActor {
+    fn handle(&mut self, message: DeleteReadCondition) -> <DeleteReadCondition as Mail>::Result {
+        match self.read_conditions.remove(&message.condition_id) {
+            Some(_) => Ok(()),
+            None => Err(DdsError::PreconditionNotMet(
+                "Condition is not attached to the DataReader".to_string(),
+            )),
+        }
+    }
+}
+
+pub struct DeleteContainedReadConditions;
+impl Mail for DeleteContainedReadConditions {
+    type Result = ();
+}
+impl MailHandler<DeleteContainedReadConditions> for DataReaderActor {
+    fn handle(
+        &mut self,
+        _: DeleteContainedReadConditions,
+    ) -> <DeleteContainedReadConditions as Mail>::Result {
+        self.read_conditions.clear();
+    }
+}
+
+pub struct GetQueryParameters {
+    pub condition_id: u32,
+}
+impl Mail for GetQueryParameters {
+    type Result = DdsResult<Vec<String>>;
+}
+impl MailHandler<GetQueryParameters> for DataReaderActor {
+    fn handle(&mut self, message: GetQueryParameters) -> <GetQueryParameters as Mail>::Result {
+        Ok(self
+            .read_conditions
+            .get(&message.condition_id)
+            .and_then(|c| c.query.as_ref())
+            .ok_or(DdsError::AlreadyDeleted)?
+            .query_parameters
+            .clone())
+    }
+}
+
+pub struct SetQueryParameters {
+    pub condition_id: u32,
+    pub query_parameters: Vec<String>,
+}
+impl Mail for SetQueryParameters {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<SetQueryParameters> for DataReaderActor {
+    fn handle(&mut self, message: SetQueryParameters) -> <SetQueryParameters as Mail>::Result {
+        let query_condition = self
+            .read_conditions
+            .get_mut(&message.condition_id)
+            .and_then(|c| c.query.as_mut())
+            .ok_or(DdsError::AlreadyDeleted)?;
+        query_condition.filter.query =
+            QueryExpression::new(&query_condition.query_expression, &message.query_parameters)?;
+        query_condition.query_parameters = message.query_parameters;
+        Ok(())
+    }
+}
+
+pub struct GetReadConditionTriggerValue {
+    pub condition_id: u32,
+}
+impl Mail for GetReadConditionTriggerValue {
+    type Result = DdsResult<bool>;
+}
+impl MailHandler<GetReadConditionTriggerValue> for DataReaderActor {
+    fn handle(
+        &mut self,
+        message: GetReadConditionTriggerValue,
+    ) -> <GetReadConditionTriggerValue as Mail>::Result {
+        let read_condition = self
+            .read_conditions
+            .get(&message.condition_id)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        Ok(self.changes.iter().any(|cc| {
+            read_condition.sample_states.contains(&cc.sample_state)
+                && read_condition
+                    .view_states
+                    .contains(&self.instances[&cc.instance_handle()].view_state)
+                && read_condition
+                    .instance_states
+                    .contains(&self.instances[&cc.instance_handle()].instance_state)
+                && read_condition
+                    .query
+                    .as_ref()
+                    .is_none_or(|q| q.filter.matches(cc))
+                && cc.is_in_access_snapshot(self.access_snapshot.as_ref())
+        }))
+    }
+}
+
+pub struct ReadWCondition {
+    pub max_samples: i32,
+    pub condition_id: u32,
+    pub now: Time,
+}
+impl Mail for ReadWCondition {
+    type Result = DdsResult<Vec<(Option<Data>, SampleInfo)>>;
+}
+impl MailHandler<ReadWCondition> for DataReaderActor {
+    fn handle(&mut self, message: ReadWCondition) -> <ReadWCondition as Mail>::Result {
+        let read_condition = self
+            .read_conditions
+            .get(&message.condition_id)
+            .ok_or_else(|| {
+                DdsError::PreconditionNotMet(
+                    "Condition is not attached to the DataReader".to_string(),
+                )
+            })?
+            .clone();
+        self.read(
+            message.max_samples,
+            read_condition.sample_states,
+            read_condition.view_states,
+            read_condition.instance_states,
+            None,
+            read_condition.query.as_ref().map(|q| &q.filter),
+            message.now,
+        )
+    }
+}
+
+pub struct TakeWCondition {
+    pub max_samples: i32,
+    pub condition_id: u32,
+    pub now: Time,
+}
+impl Mail for TakeWCondition {
+    type Result = DdsResult<Vec<(Option<Data>, SampleInfo)>>;
+}
+impl MailHandler<TakeWCondition> for DataReaderActor {
+    fn handle(&mut self, message: TakeWCondition) -> <TakeWCondition as Mail>::Result {
+        let read_condition = self
+            .read_conditions
+            .get(&message.condition_id)
+            .ok_or_else(|| {
+                DdsError::PreconditionNotMet(
+                    "Condition is not attached to the DataReader".to_string(),
+                )
+            })?
+            .clone();
+        self.take(
+            message.max_samples,
+            read_condition.sample_states,
+            read_condition.view_states,
+            read_condition.instance_states,
+            None,
+            read_condition.query.as_ref().map(|q| &q.filter),
+            message.now,
         )
     }
 }
@@ -1940,6 +2914,18 @@ impl MailHandler<GetInstanceHandle> for DataReaderActor {
     }
 }
 
+pub struct SetGuidPrefix {
+    pub guid_prefix: GuidPrefix,
+}
+impl Mail for SetGuidPrefix {
+    type Result = ();
+}
+impl MailHandler<SetGuidPrefix> for DataReaderActor {
+    fn handle(&mut self, message: SetGuidPrefix) -> <SetGuidPrefix as Mail>::Result {
+        self.rtps_reader.set_guid_prefix(message.guid_prefix);
+    }
+}
+
 pub struct SetQos {
     pub qos: DataReaderQos,
 }
@@ -2009,6 +2995,41 @@ impl MailHandler<Enable> for DataReaderActor {
     }
 }
 
+pub struct NotifyDataAvailable {
+    pub data_reader_address: ActorAddress<DataReaderActor>,
+    pub subscriber: SubscriberAsync,
+}
+impl Mail for NotifyDataAvailable {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<NotifyDataAvailable> for DataReaderActor {
+    fn handle(&mut self, message: NotifyDataAvailable) -> <NotifyDataAvailable as Mail>::Result {
+        let has_unread_data = self
+            .changes
+            .iter()
+            .any(|cc| cc.sample_state == SampleStateKind::NotRead);
+        if has_unread_data {
+            if let Some(listener) = &self.data_reader_listener_thread {
+                let topic = TopicAsync::new(
+                    self.topic_address.clone(),
+                    self.topic_status_condition.clone(),
+                    self.type_name.clone(),
+                    self.topic_name.clone(),
+                    message.subscriber.get_participant(),
+                );
+                listener.sender().send(DataReaderListenerMessage {
+                    listener_operation: DataReaderListenerOperation::DataAvailable,
+                    reader_address: message.data_reader_address,
+                    status_condition_address: self.status_condition.address(),
+                    subscriber: message.subscriber,
+                    topic,
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
 pub struct GetStatuscondition;
 impl Mail for GetStatuscondition {
     type Result = ActorAddress<StatusConditionActor>;
@@ -2038,6 +3059,7 @@ pub struct TakeNextInstance {
     pub sample_states: Vec<SampleStateKind>,
     pub view_states: Vec<ViewStateKind>,
     pub instance_states: Vec<InstanceStateKind>,
+    pub now: Time,
 }
 impl Mail for TakeNextInstance {
     type Result = DdsResult<Vec<(Option<Data>, SampleInfo)>>;
@@ -2048,16 +3070,71 @@ impl MailHandler<TakeNextInstance> for DataReaderActor {
             return Err(DdsError::NotEnabled);
         }
 
-        match self.next_instance(message.previous_handle) {
-            Some(next_handle) => self.take(
+        // Instances without samples matching the masks are skipped
+        let mut previous_handle = message.previous_handle;
+        while let Some(next_handle) = self.next_instance(previous_handle) {
+            match self.take(
                 message.max_samples,
-                message.sample_states,
-                message.view_states,
-                message.instance_states,
+                message.sample_states.clone(),
+                message.view_states.clone(),
+                message.instance_states.clone(),
                 Some(next_handle),
-            ),
-            None => Err(DdsError::NoData),
+                None,
+                message.now,
+            ) {
+                Err(DdsError::NoData) => previous_handle = Some(next_handle),
+                r => return r,
+            }
         }
+        Err(DdsError::NoData)
+    }
+}
+
+pub struct GetRequestedIncompatibleQosStatus;
+impl Mail for GetRequestedIncompatibleQosStatus {
+    type Result = RequestedIncompatibleQosStatus;
+}
+impl MailHandler<GetRequestedIncompatibleQosStatus> for DataReaderActor {
+    fn handle(
+        &mut self,
+        _: GetRequestedIncompatibleQosStatus,
+    ) -> <GetRequestedIncompatibleQosStatus as Mail>::Result {
+        self.status_condition
+            .send_actor_mail(status_condition_actor::RemoveCommunicationState {
+                state: StatusKind::RequestedIncompatibleQos,
+            });
+
+        self.requested_incompatible_qos_status.read_and_reset()
+    }
+}
+
+pub struct GetSampleLostStatus;
+impl Mail for GetSampleLostStatus {
+    type Result = SampleLostStatus;
+}
+impl MailHandler<GetSampleLostStatus> for DataReaderActor {
+    fn handle(&mut self, _: GetSampleLostStatus) -> <GetSampleLostStatus as Mail>::Result {
+        self.status_condition
+            .send_actor_mail(status_condition_actor::RemoveCommunicationState {
+                state: StatusKind::SampleLost,
+            });
+
+        self.sample_lost_status.read_and_reset()
+    }
+}
+
+pub struct GetSampleRejectedStatus;
+impl Mail for GetSampleRejectedStatus {
+    type Result = SampleRejectedStatus;
+}
+impl MailHandler<GetSampleRejectedStatus> for DataReaderActor {
+    fn handle(&mut self, _: GetSampleRejectedStatus) -> <GetSampleRejectedStatus as Mail>::Result {
+        self.status_condition
+            .send_actor_mail(status_condition_actor::RemoveCommunicationState {
+                state: StatusKind::SampleRejected,
+            });
+
+        self.sample_rejected_status.read_and_reset()
     }
 }
 
@@ -2075,8 +3152,7 @@ impl MailHandler<GetSubscriptionMatchedStatus> for DataReaderActor {
                 state: StatusKind::SubscriptionMatched,
             });
 
-        self.subscription_matched_status
-            .read_and_reset(self.matched_publication_list.len() as i32)
+        self.subscription_matched_status.read_and_reset()
     }
 }
 
@@ -2086,6 +3162,7 @@ pub struct ReadNextInstance {
     pub sample_states: Vec<SampleStateKind>,
     pub view_states: Vec<ViewStateKind>,
     pub instance_states: Vec<InstanceStateKind>,
+    pub now: Time,
 }
 impl Mail for ReadNextInstance {
     type Result = DdsResult<Vec<(Option<Data>, SampleInfo)>>;
@@ -2096,16 +3173,62 @@ impl MailHandler<ReadNextInstance> for DataReaderActor {
             return Err(DdsError::NotEnabled);
         }
 
-        match self.next_instance(message.previous_handle) {
-            Some(next_handle) => self.read(
+        // Instances without samples matching the masks are skipped
+        let mut previous_handle = message.previous_handle;
+        while let Some(next_handle) = self.next_instance(previous_handle) {
+            match self.read(
                 message.max_samples,
-                message.sample_states,
-                message.view_states,
-                message.instance_states,
+                message.sample_states.clone(),
+                message.view_states.clone(),
+                message.instance_states.clone(),
                 Some(next_handle),
-            ),
-            None => Err(DdsError::NoData),
+                None,
+                message.now,
+            ) {
+                Err(DdsError::NoData) => previous_handle = Some(next_handle),
+                r => return r,
+            }
         }
+        Err(DdsError::NoData)
+    }
+}
+
+pub struct GetKeyValue {
+    pub handle: InstanceHandle,
+}
+impl Mail for GetKeyValue {
+    type Result = DdsResult<Vec<u8>>;
+}
+impl MailHandler<GetKeyValue> for DataReaderActor {
+    fn handle(&mut self, message: GetKeyValue) -> <GetKeyValue as Mail>::Result {
+        if !self.enabled {
+            return Err(DdsError::NotEnabled);
+        }
+
+        self.instance_serialized_key
+            .get(&message.handle)
+            .cloned()
+            .ok_or(DdsError::BadParameter)
+    }
+}
+
+pub struct LookupInstance {
+    pub instance_handle: InstanceHandle,
+}
+impl Mail for LookupInstance {
+    type Result = DdsResult<Option<InstanceHandle>>;
+}
+impl MailHandler<LookupInstance> for DataReaderActor {
+    fn handle(&mut self, message: LookupInstance) -> <LookupInstance as Mail>::Result {
+        if !self.enabled {
+            return Err(DdsError::NotEnabled);
+        }
+
+        Ok(if self.instances.contains_key(&message.instance_handle) {
+            Some(message.instance_handle)
+        } else {
+            None
+        })
     }
 }
 
@@ -2206,26 +3329,58 @@ impl MailHandler<AddMatchedWriter> for DataReaderActor {
                     .matched_publication_list
                     .insert(instance_handle, publication_builtin_topic_data.clone());
                 match insert_matched_publication_result {
+                    // Updates of an already matched writer are announced without counting
+                    // as a new match
                     Some(value) if &value != publication_builtin_topic_data => {
                         self.on_subscription_matched(
-                            instance_handle,
-                            message.data_reader_address,
-                            message.subscriber,
+                            message.data_reader_address.clone(),
+                            message.subscriber.clone(),
                             &message.subscriber_mask_listener,
                             &message.participant_mask_listener,
                         )?;
                     }
                     None => {
+                        self.subscription_matched_status
+                            .on_writer_matched(instance_handle);
                         self.on_subscription_matched(
-                            instance_handle,
-                            message.data_reader_address,
-                            message.subscriber,
+                            message.data_reader_address.clone(),
+                            message.subscriber.clone(),
                             &message.subscriber_mask_listener,
                             &message.participant_mask_listener,
                         )?;
                     }
                     _ => (),
                 }
+
+                // A newly matched writer is considered alive until its lease expires
+                match self.matched_writer_liveliness.entry(instance_handle) {
+                    Entry::Occupied(mut e) => {
+                        e.get_mut().liveliness =
+                            publication_builtin_topic_data.liveliness().clone();
+                    }
+                    Entry::Vacant(e) => {
+                        e.insert(WriterLiveliness {
+                            liveliness: publication_builtin_topic_data.liveliness().clone(),
+                            alive: true,
+                            lease_expired_task: None,
+                        });
+                        self.liveliness_changed_status
+                            .on_writer_alive(instance_handle, false);
+                        self.on_liveliness_changed(
+                            &message.data_reader_address,
+                            &message.subscriber,
+                            &message.subscriber_mask_listener,
+                            &message.participant_mask_listener,
+                        )?;
+                    }
+                }
+                self.on_writer_liveliness_asserted(
+                    instance_handle,
+                    &message.data_reader_address,
+                    &message.subscriber,
+                    &message.subscriber_mask_listener,
+                    &message.participant_mask_listener,
+                )?;
             } else if !self.incompatible_writer_list.contains(&instance_handle) {
                 self.incompatible_writer_list.insert(instance_handle);
                 self.on_requested_incompatible_qos(
@@ -2260,17 +3415,66 @@ impl Mail for RemoveMatchedWriter {
 }
 impl MailHandler<RemoveMatchedWriter> for DataReaderActor {
     fn handle(&mut self, message: RemoveMatchedWriter) -> <RemoveMatchedWriter as Mail>::Result {
-        let matched_publication = self
-            .matched_publication_list
-            .remove(&message.discovered_writer_handle);
-        if let Some(w) = matched_publication {
-            match &mut self.rtps_reader {
-                RtpsReaderKind::Stateful(r) => r.matched_writer_remove(w.key().value.into()),
-                RtpsReaderKind::Stateless(_) => (),
-            }
+        if self.remove_matched_writer(
+            message.discovered_writer_handle,
+            &message.data_reader_address,
+            &message.subscriber,
+            &message.subscriber_mask_listener,
+            &message.participant_mask_listener,
+        )? {
+            self.on_subscription_matched(
+                message.data_reader_address,
+                message.subscriber,
+                &message.subscriber_mask_listener,
+                &message.participant_mask_listener,
+            )?;
+        }
+        Ok(())
+    }
+}
 
+pub struct RemoveMatchedParticipant {
+    pub participant_handle: InstanceHandle,
+    pub data_reader_address: ActorAddress<DataReaderActor>,
+    pub subscriber: SubscriberAsync,
+    pub subscriber_mask_listener: (
+        Option<MpscSender<SubscriberListenerMessage>>,
+        Vec<StatusKind>,
+    ),
+    pub participant_mask_listener: (
+        Option<MpscSender<ParticipantListenerMessage>>,
+        Vec<StatusKind>,
+    ),
+}
+impl Mail for RemoveMatchedParticipant {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<RemoveMatchedParticipant> for DataReaderActor {
+    fn handle(
+        &mut self,
+        message: RemoveMatchedParticipant,
+    ) -> <RemoveMatchedParticipant as Mail>::Result {
+        // The handle of an endpoint starts with the GUID prefix of the participant it belongs to
+        let participant_guid_prefix = &message.participant_handle.as_ref()[..12];
+        let removed_writer_handle_list: Vec<InstanceHandle> = self
+            .matched_publication_list
+            .keys()
+            .filter(|h| &h.as_ref()[..12] == participant_guid_prefix)
+            .cloned()
+            .collect();
+
+        let mut is_any_writer_removed = false;
+        for discovered_writer_handle in removed_writer_handle_list {
+            is_any_writer_removed |= self.remove_matched_writer(
+                discovered_writer_handle,
+                &message.data_reader_address,
+                &message.subscriber,
+                &message.subscriber_mask_listener,
+                &message.participant_mask_listener,
+            )?;
+        }
+        if is_any_writer_removed {
             self.on_subscription_matched(
-                message.discovered_writer_handle,
                 message.data_reader_address,
                 message.subscriber,
                 &message.subscriber_mask_listener,
@@ -2317,7 +3521,6 @@ pub struct ProcessDataSubmessage {
         Vec<StatusKind>,
     ),
     pub executor_handle: ExecutorHandle,
-    pub timer_handle: TimerHandle,
 }
 impl Mail for ProcessDataSubmessage {
     type Result = ();
@@ -2337,7 +3540,6 @@ impl MailHandler<ProcessDataSubmessage> for DataReaderActor {
             &message.subscriber_mask_listener,
             &message.participant_mask_listener,
             &message.executor_handle,
-            &message.timer_handle,
         )
         .ok();
     }
@@ -2359,7 +3561,6 @@ pub struct ProcessDataFragSubmessage {
         Vec<StatusKind>,
     ),
     pub executor_handle: ExecutorHandle,
-    pub timer_handle: TimerHandle,
 }
 impl Mail for ProcessDataFragSubmessage {
     type Result = ();
@@ -2379,7 +3580,6 @@ impl MailHandler<ProcessDataFragSubmessage> for DataReaderActor {
             &message.subscriber_mask_listener,
             &message.participant_mask_listener,
             &message.executor_handle,
-            &message.timer_handle,
         )
         .ok();
     }
@@ -2402,6 +3602,16 @@ pub struct ProcessHeartbeatSubmessage {
     pub heartbeat_submessage: HeartbeatSubmessage,
     pub source_guid_prefix: GuidPrefix,
     pub message_sender_actor: ActorAddress<MessageSenderActor>,
+    pub data_reader_address: ActorAddress<DataReaderActor>,
+    pub subscriber: SubscriberAsync,
+    pub subscriber_mask_listener: (
+        Option<MpscSender<SubscriberListenerMessage>>,
+        Vec<StatusKind>,
+    ),
+    pub participant_mask_listener: (
+        Option<MpscSender<ParticipantListenerMessage>>,
+        Vec<StatusKind>,
+    ),
 }
 impl Mail for ProcessHeartbeatSubmessage {
     type Result = ();
@@ -2411,17 +3621,50 @@ impl MailHandler<ProcessHeartbeatSubmessage> for DataReaderActor {
         &mut self,
         message: ProcessHeartbeatSubmessage,
     ) -> <ProcessHeartbeatSubmessage as Mail>::Result {
+        // Writers with automatic liveliness are asserted by any heartbeat while the manual
+        // liveliness kinds require the liveliness flag to be set
+        let writer_handle = InstanceHandle::new(
+            Guid::new(
+                message.source_guid_prefix,
+                message.heartbeat_submessage.writer_id(),
+            )
+            .into(),
+        );
+        let is_liveliness_asserted = self
+            .matched_writer_liveliness
+            .get(&writer_handle)
+            .is_some_and(|w| {
+                message.heartbeat_submessage.liveliness_flag()
+                    || w.liveliness.kind == LivelinessQosPolicyKind::Automatic
+            });
+        if is_liveliness_asserted {
+            self.on_writer_liveliness_asserted(
+                writer_handle,
+                &message.data_reader_address,
+                &message.subscriber,
+                &message.subscriber_mask_listener,
+                &message.participant_mask_listener,
+            )
+            .ok();
+        }
+
         self.on_heartbeat_submessage_received(
             &message.heartbeat_submessage,
             message.source_guid_prefix,
             &message.message_sender_actor,
-        );
+            &message.data_reader_address,
+            &message.subscriber,
+            &message.subscriber_mask_listener,
+            &message.participant_mask_listener,
+        )
+        .ok();
     }
 }
 
 pub struct ProcessHeartbeatFragSubmessage {
     pub heartbeat_frag_submessage: HeartbeatFragSubmessage,
     pub source_guid_prefix: GuidPrefix,
+    pub message_sender_actor: ActorAddress<MessageSenderActor>,
 }
 impl Mail for ProcessHeartbeatFragSubmessage {
     type Result = ();
@@ -2434,6 +3677,7 @@ impl MailHandler<ProcessHeartbeatFragSubmessage> for DataReaderActor {
         self.on_heartbeat_frag_submessage_received(
             &message.heartbeat_frag_submessage,
             message.source_guid_prefix,
+            &message.message_sender_actor,
         )
     }
 }
@@ -4,26 +4,31 @@ use crate::{
         discovered_reader_data::DiscoveredReaderData,
         discovered_writer_data::{DiscoveredWriterData, WriterProxy},
     },
-    dds_async::{publisher::PublisherAsync, topic::TopicAsync},
+    dds_async::{
+        domain_participant::DomainParticipantAsync, publisher::PublisherAsync, topic::TopicAsync,
+    },
     implementation::{
         actor::{Actor, ActorAddress, Mail, MailHandler},
+        flow_controller::FlowController,
         runtime::{
             executor::{block_on, ExecutorHandle},
             mpsc::{mpsc_channel, MpscSender},
-            timer::TimerHandle,
+            oneshot::{oneshot, OneshotReceiver, OneshotSender},
         },
+        submessage_packer::SubmessagePacker,
+        writer_send_queue::{SendPriority, WriterSendQueue},
     },
     infrastructure::{
         error::{DdsError, DdsResult},
         instance::{InstanceHandle, HANDLE_NIL},
         qos::{DataWriterQos, PublisherQos},
         qos_policy::{
-            DurabilityQosPolicyKind, HistoryQosPolicyKind, Length, QosPolicyId,
-            ReliabilityQosPolicyKind, TopicDataQosPolicy, DATA_REPRESENTATION_QOS_POLICY_ID,
-            DEADLINE_QOS_POLICY_ID, DESTINATIONORDER_QOS_POLICY_ID, DURABILITY_QOS_POLICY_ID,
-            INVALID_QOS_POLICY_ID, LATENCYBUDGET_QOS_POLICY_ID, LIVELINESS_QOS_POLICY_ID,
-            OWNERSHIP_QOS_POLICY_ID, PRESENTATION_QOS_POLICY_ID, RELIABILITY_QOS_POLICY_ID,
-            XCDR_DATA_REPRESENTATION,
+            DurabilityQosPolicyKind, HistoryQosPolicyKind, Length, LivelinessQosPolicyKind,
+            QosPolicyId, ReliabilityQosPolicyKind, TopicDataQosPolicy,
+            DATA_REPRESENTATION_QOS_POLICY_ID, DEADLINE_QOS_POLICY_ID,
+            DESTINATIONORDER_QOS_POLICY_ID, DURABILITY_QOS_POLICY_ID, INVALID_QOS_POLICY_ID,
+            LATENCYBUDGET_QOS_POLICY_ID, LIVELINESS_QOS_POLICY_ID, OWNERSHIP_QOS_POLICY_ID,
+            PRESENTATION_QOS_POLICY_ID, RELIABILITY_QOS_POLICY_ID, XCDR_DATA_REPRESENTATION,
         },
         status::{
             OfferedIncompatibleQosStatus, PublicationMatchedStatus, QosPolicyCount, StatusKind,
@@ -32,12 +37,13 @@ use crate::{
     },
     rtps::{
         messages::{
-            submessage_elements::{Data, ParameterList, SequenceNumberSet, SerializedDataFragment},
+            submessage_elements::{Data, ParameterList, SequenceNumberSet},
             submessages::{
-                ack_nack::AckNackSubmessage, data_frag::DataFragSubmessage, gap::GapSubmessage,
+                ack_nack::AckNackSubmessage, gap::GapSubmessage,
                 info_destination::InfoDestinationSubmessage,
                 info_timestamp::InfoTimestampSubmessage, nack_frag::NackFragSubmessage,
             },
+            types::FragmentNumber,
         },
         reader_locator::RtpsReaderLocator,
         reader_proxy::RtpsReaderProxy,
@@ -263,6 +269,11 @@ pub struct DataWriterActor {
     max_seq_num: Option<SequenceNumber>,
     qos: DataWriterQos,
     registered_instance_list: HashSet<InstanceHandle>,
+    flow_controller: FlowController,
+    flow_controller_refill_sender: Option<MpscSender<()>>,
+    is_flow_controller_refill_scheduled: bool,
+    send_queue: WriterSendQueue,
+    acknowledgment_waiters: Vec<OneshotSender<()>>,
 }
 
 impl DataWriterActor {
@@ -280,6 +291,7 @@ impl DataWriterActor {
     ) -> Self {
         let status_condition = Actor::spawn(StatusConditionActor::default(), handle);
         let data_writer_listener_thread = listener.map(DataWriterListenerThread::new);
+        let flow_controller = FlowController::new(qos.flow_controller.max_bytes_per_second);
 
         DataWriterActor {
             rtps_writer,
@@ -299,6 +311,11 @@ impl DataWriterActor {
             max_seq_num: None,
             qos,
             registered_instance_list: HashSet::new(),
+            flow_controller,
+            flow_controller_refill_sender: None,
+            is_flow_controller_refill_scheduled: false,
+            send_queue: WriterSendQueue::new(WriterSendQueue::DEFAULT_CAPACITY),
+            acknowledgment_waiters: Vec::new(),
         }
     }
 
@@ -316,22 +333,172 @@ impl DataWriterActor {
     }
 
     fn send_message(&mut self, message_sender_actor: ActorAddress<MessageSenderActor>) {
-        self.send_message_to_reader_locators(&message_sender_actor);
-        self.send_message_to_reader_proxies(&message_sender_actor);
+        // No traffic is generated for a writer which has not been enabled yet
+        if !self.enabled {
+            return;
+        }
+        loop {
+            self.send_message_to_reader_locators();
+            self.send_message_to_reader_proxies();
+            let is_send_queue_full = self.send_queue.is_full();
+            self.flush_send_queue(&message_sender_actor);
+            // More messages are generated only if the previous ones were limited by the
+            // send queue and all of them could be sent
+            if !is_send_queue_full || !self.send_queue.is_empty() {
+                break;
+            }
+        }
+    }
+
+    fn remove_expired_changes(&mut self, now: Time) {
+        if let DurationKind::Finite(lifespan) = self.qos.lifespan.duration {
+            for changes_of_instance in self.changes.values_mut() {
+                changes_of_instance.retain(|cc| now - Time::from(cc.timestamp()) < lifespan);
+            }
+        }
+    }
+
+    fn flush_send_queue(&mut self, message_sender_actor: &ActorAddress<MessageSenderActor>) {
+        // Messages held back by the flow controller are sent once the rate allows it
+        let mut packer = SubmessagePacker::new(self.rtps_writer.data_max_size_serialized());
+        while self.flow_controller.is_ready() {
+            match self.send_queue.pop() {
+                Some((message, payload_size)) => {
+                    self.flow_controller.consume(payload_size);
+                    packer.add(message);
+                }
+                None => break,
+            }
+        }
+        for message in packer.into_messages() {
+            message_sender_actor.send_actor_mail(message).ok();
+        }
+
+        // The refill is only scheduled while there are messages waiting for tokens
+        if !self.send_queue.is_empty() && !self.is_flow_controller_refill_scheduled {
+            if let Some(refill_sender) = &self.flow_controller_refill_sender {
+                self.is_flow_controller_refill_scheduled = refill_sender.send(()).is_ok();
+            }
+        }
+    }
+
+    fn send_liveliness_heartbeat(
+        &mut self,
+        message_sender_actor: &ActorAddress<MessageSenderActor>,
+    ) {
+        let writer_id = self.rtps_writer.guid().entity_id();
+        let first_sn = self
+            .changes
+            .values()
+            .flatten()
+            .map(|cc| cc.sequence_number())
+            .min()
+            .unwrap_or(1);
+        let last_sn = self.max_seq_num.unwrap_or(0);
+        for reader_proxy in &mut self.matched_readers {
+            let heartbeat_submessage = Box::new(
+                reader_proxy
+                    .heartbeat_machine()
+                    .generate_new_liveliness_heartbeat(writer_id, first_sn, last_sn),
+            );
+            self.send_queue.push(
+                SendPriority::Heartbeat,
+                message_sender_actor::WriteMessage {
+                    submessages: vec![heartbeat_submessage],
+                    destination_locator_list: reader_proxy.unicast_locator_list().to_vec(),
+                },
+                0,
+            );
+        }
+        self.flush_send_queue(message_sender_actor);
     }
 
     fn matched_reader_remove(&mut self, a_reader_guid: Guid) {
         self.matched_readers
-            .retain(|x| x.remote_reader_guid() != a_reader_guid)
+            .retain(|x| x.remote_reader_guid() != a_reader_guid);
+        // The removed reader might be the last one which had not yet acknowledged the changes
+        self.notify_acknowledgment_waiters();
+    }
+
+    fn are_all_changes_acknowledged(&self) -> bool {
+        !self
+            .matched_readers
+            .iter()
+            .any(|rp| rp.unacked_changes(self.max_seq_num))
+    }
+
+    fn instance_change_kinds(
+        &self,
+    ) -> HashMap<crate::rtps::behavior_types::InstanceHandle, VecDeque<ChangeKind>> {
+        self.changes
+            .iter()
+            .map(|(instance, changes)| (*instance, changes.iter().map(|cc| cc.kind()).collect()))
+            .collect()
+    }
+
+    fn add_change(
+        &mut self,
+        change: RtpsWriterCacheChange,
+        now: Time,
+        writer_address: &ActorAddress<DataWriterActor>,
+        executor_handle: &ExecutorHandle,
+        participant: &DomainParticipantAsync,
+    ) {
+        let instance_changes = self.changes.entry(change.instance_handle()).or_default();
+
+        if let HistoryQosPolicyKind::KeepLast(depth) = self.qos.history.kind {
+            if instance_changes.len() == depth as usize {
+                instance_changes.pop_front();
+            }
+        }
+
+        let change_timestamp = change.timestamp();
+        let seq_num = change.sequence_number();
+
+        if seq_num > self.max_seq_num.unwrap_or(0) {
+            self.max_seq_num = Some(seq_num)
+        }
+
+        if let DurationKind::Finite(lifespan) = self.qos.lifespan.duration {
+            let change_lifespan =
+                crate::infrastructure::time::Time::from(change_timestamp) - now + lifespan;
+            if change_lifespan > Duration::new(0, 0) {
+                instance_changes.push_back(change);
+                let writer_address = writer_address.clone();
+                let participant = participant.clone();
+                executor_handle.spawn(async move {
+                    if participant.sleep(change_lifespan.into()).await.is_ok() {
+                        writer_address
+                            .send_actor_mail(RemoveChange { seq_num })
+                            .ok();
+                    }
+                });
+            }
+        } else {
+            instance_changes.push_back(change);
+        }
+    }
+
+    fn notify_acknowledgment_waiters(&mut self) {
+        if self.are_all_changes_acknowledged() {
+            for waiter in self.acknowledgment_waiters.drain(..) {
+                waiter.send(());
+            }
+        }
     }
 
     fn on_acknack_submessage_received(
         &mut self,
         acknack_submessage: &AckNackSubmessage,
         source_guid_prefix: GuidPrefix,
+        reply_locator_list: Vec<Locator>,
         message_sender_actor: ActorAddress<MessageSenderActor>,
     ) {
-        if self.qos.reliability.kind == ReliabilityQosPolicyKind::Reliable {
+        // The AckNack is dispatched to all the writers of the participant so it is only
+        // processed by the writer to which it is addressed
+        if self.qos.reliability.kind == ReliabilityQosPolicyKind::Reliable
+            && acknack_submessage.writer_id() == &self.rtps_writer.guid().entity_id()
+        {
             let reader_guid = Guid::new(source_guid_prefix, *acknack_submessage.reader_id());
 
             if let Some(reader_proxy) = self
@@ -347,10 +514,12 @@ impl DataWriterActor {
                                 .acked_changes_set(acknack_submessage.reader_sn_state().base() - 1);
                             reader_proxy
                                 .requested_changes_set(acknack_submessage.reader_sn_state().set());
+                            reader_proxy.set_reply_locator_list(reply_locator_list);
 
                             reader_proxy
                                 .set_last_received_acknack_count(acknack_submessage.count());
 
+                            self.notify_acknowledgment_waiters();
                             self.send_message(message_sender_actor);
                         }
                     }
@@ -359,16 +528,17 @@ impl DataWriterActor {
         }
     }
 
-    fn send_message_to_reader_locators(
-        &mut self,
-        message_sender_actor: &ActorAddress<MessageSenderActor>,
-    ) {
+    fn send_message_to_reader_locators(&mut self) {
         for reader_locator in &mut self.reader_locators {
             match &self.qos.reliability.kind {
                 ReliabilityQosPolicyKind::BestEffort => {
                     while let Some(unsent_change_seq_num) =
                         reader_locator.next_unsent_change(self.changes.values().flatten())
                     {
+                        // Changes which do not fit in the send queue are sent in a later round
+                        if self.send_queue.is_full() {
+                            break;
+                        }
                         // The post-condition:
                         // "( a_change BELONGS-TO the_reader_locator.unsent_changes() ) == FALSE"
                         // should be full-filled by next_unsent_change()
@@ -386,12 +556,14 @@ impl DataWriterActor {
                             let data_submessage =
                                 Box::new(cache_change.as_data_submessage(ENTITYID_UNKNOWN));
 
-                            message_sender_actor
-                                .send_actor_mail(message_sender_actor::WriteMessage {
+                            self.send_queue.push(
+                                SendPriority::NewData,
+                                message_sender_actor::WriteMessage {
                                     submessages: vec![info_ts_submessage, data_submessage],
                                     destination_locator_list: vec![reader_locator.locator()],
-                                })
-                                .ok();
+                                },
+                                cache_change.data_value().len(),
+                            );
                         } else {
                             let gap_submessage = Box::new(GapSubmessage::new(
                                 ENTITYID_UNKNOWN,
@@ -400,12 +572,14 @@ impl DataWriterActor {
                                 SequenceNumberSet::new(unsent_change_seq_num + 1, []),
                             ));
 
-                            message_sender_actor
-                                .send_actor_mail(message_sender_actor::WriteMessage {
+                            self.send_queue.push(
+                                SendPriority::NewData,
+                                message_sender_actor::WriteMessage {
                                     submessages: vec![gap_submessage],
                                     destination_locator_list: vec![reader_locator.locator()],
-                                })
-                                .ok();
+                                },
+                                0,
+                            );
                         }
                         reader_locator.set_highest_sent_change_sn(unsent_change_seq_num);
                     }
@@ -417,10 +591,34 @@ impl DataWriterActor {
         }
     }
 
-    fn send_message_to_reader_proxies(
-        &mut self,
-        message_sender_actor: &ActorAddress<MessageSenderActor>,
-    ) {
+    fn send_message_to_reader_proxies(&mut self) {
+        let seq_num_min = self
+            .changes
+            .values()
+            .flatten()
+            .map(|cc| cc.sequence_number())
+            .min();
+
+        // The changes requested by all the reliable readers are queued before any new change
+        // so that retransmissions are not delayed by new data
+        if self.qos.reliability.kind == ReliabilityQosPolicyKind::Reliable {
+            for reader_proxy in self
+                .matched_readers
+                .iter_mut()
+                .filter(|rp| rp.reliability() == ReliabilityKind::Reliable)
+            {
+                send_requested_changes_reader_proxy_reliable(
+                    reader_proxy,
+                    self.rtps_writer.guid().entity_id(),
+                    &self.changes,
+                    seq_num_min,
+                    self.max_seq_num,
+                    self.rtps_writer.data_max_size_serialized(),
+                    &mut self.send_queue,
+                );
+            }
+        }
+
         for reader_proxy in &mut self.matched_readers {
             match (&self.qos.reliability.kind, reader_proxy.reliability()) {
                 (ReliabilityQosPolicyKind::BestEffort, ReliabilityKind::BestEffort)
@@ -430,7 +628,7 @@ impl DataWriterActor {
                         self.rtps_writer.guid().entity_id(),
                         &self.changes,
                         self.rtps_writer.data_max_size_serialized(),
-                        message_sender_actor,
+                        &mut self.send_queue,
                     )
                 }
                 (ReliabilityQosPolicyKind::Reliable, ReliabilityKind::Reliable) => {
@@ -438,15 +636,11 @@ impl DataWriterActor {
                         reader_proxy,
                         self.rtps_writer.guid().entity_id(),
                         &self.changes,
-                        self.changes
-                            .values()
-                            .flatten()
-                            .map(|cc| cc.sequence_number())
-                            .min(),
+                        seq_num_min,
                         self.max_seq_num,
                         self.rtps_writer.data_max_size_serialized(),
                         self.rtps_writer.heartbeat_period().into(),
-                        message_sender_actor,
+                        &mut self.send_queue,
                     )
                 }
                 (ReliabilityQosPolicyKind::BestEffort, ReliabilityKind::Reliable) => {
@@ -472,12 +666,14 @@ impl DataWriterActor {
                 match reader_proxy.reliability() {
                     ReliabilityKind::BestEffort => (),
                     ReliabilityKind::Reliable => {
-                        if nackfrag_submessage.count()
-                            > reader_proxy.last_received_nack_frag_count()
+                        if nackfrag_submessage.writer_id() == self.rtps_writer.guid().entity_id()
+                            && nackfrag_submessage.count()
+                                > reader_proxy.last_received_nack_frag_count()
                         {
-                            reader_proxy.requested_changes_set(std::iter::once(
+                            reader_proxy.requested_fragments_set(
                                 nackfrag_submessage.writer_sn(),
-                            ));
+                                nackfrag_submessage.fragment_number_state().set(),
+                            );
                             reader_proxy
                                 .set_last_received_nack_frag_count(nackfrag_submessage.count());
                         }
@@ -695,16 +891,21 @@ pub struct GetMatchedSubscriptionData {
     pub handle: InstanceHandle,
 }
 impl Mail for GetMatchedSubscriptionData {
-    type Result = Option<SubscriptionBuiltinTopicData>;
+    type Result = DdsResult<SubscriptionBuiltinTopicData>;
 }
 impl MailHandler<GetMatchedSubscriptionData> for DataWriterActor {
     fn handle(
         &mut self,
         message: GetMatchedSubscriptionData,
     ) -> <GetMatchedSubscriptionData as Mail>::Result {
+        if !self.enabled {
+            return Err(DdsError::NotEnabled);
+        }
+
         self.matched_subscriptions
             .get_matched_subscription_data(message.handle)
             .cloned()
+            .ok_or(DdsError::BadParameter)
     }
 }
 
@@ -740,7 +941,7 @@ pub struct Enable {
     pub data_writer_address: ActorAddress<DataWriterActor>,
     pub message_sender_actor: ActorAddress<MessageSenderActor>,
     pub executor_handle: ExecutorHandle,
-    pub timer_handle: TimerHandle,
+    pub participant: DomainParticipantAsync,
 }
 impl Mail for Enable {
     type Result = ();
@@ -752,9 +953,9 @@ impl MailHandler<Enable> for DataWriterActor {
         if self.qos.reliability.kind == ReliabilityQosPolicyKind::Reliable {
             let half_heartbeat_period =
                 std::time::Duration::from(Duration::from(self.rtps_writer.heartbeat_period())) / 2;
-            let message_sender_actor = message.message_sender_actor;
-            let data_writer_address = message.data_writer_address;
-            let timer_handle = message.timer_handle;
+            let message_sender_actor = message.message_sender_actor.clone();
+            let data_writer_address = message.data_writer_address.clone();
+            let timer_handle = message.participant.timer_handle().clone();
             message.executor_handle.spawn(async move {
                 loop {
                     timer_handle.sleep(half_heartbeat_period).await;
@@ -768,6 +969,66 @@ impl MailHandler<Enable> for DataWriterActor {
                 }
             });
         }
+
+        // Messages held back by the flow controller are sent after a refill period
+        // each time the writer requests it
+        if self.qos.flow_controller.max_bytes_per_second.is_some() {
+            let (refill_sender, refill_receiver) = mpsc_channel();
+            self.flow_controller_refill_sender = Some(refill_sender);
+            let message_sender_actor = message.message_sender_actor.clone();
+            let data_writer_address = message.data_writer_address.clone();
+            let timer_handle = message.participant.timer_handle().clone();
+            message.executor_handle.spawn(async move {
+                while refill_receiver.recv().await.is_some() {
+                    timer_handle.sleep(FlowController::REFILL_PERIOD).await;
+
+                    let r = data_writer_address.send_actor_mail(RefillFlowController {
+                        message_sender_actor: message_sender_actor.clone(),
+                    });
+                    if r.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        // Automatic liveliness is asserted by the service at a rate which guarantees
+        // that the remote readers receive it before the lease duration expires
+        if let (LivelinessQosPolicyKind::Automatic, DurationKind::Finite(lease_duration)) = (
+            &self.qos.liveliness.kind,
+            self.qos.liveliness.lease_duration,
+        ) {
+            let half_lease_duration = std::time::Duration::from(lease_duration) / 2;
+            let message_sender_actor = message.message_sender_actor;
+            let data_writer_address = message.data_writer_address;
+            let participant = message.participant;
+            message.executor_handle.spawn(async move {
+                loop {
+                    if participant.sleep(half_lease_duration).await.is_err() {
+                        break;
+                    }
+
+                    let r = data_writer_address.send_actor_mail(AssertLiveliness {
+                        message_sender_actor: message_sender_actor.clone(),
+                    });
+                    if r.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    }
+}
+
+pub struct AssertLiveliness {
+    pub message_sender_actor: ActorAddress<MessageSenderActor>,
+}
+impl Mail for AssertLiveliness {
+    type Result = ();
+}
+impl MailHandler<AssertLiveliness> for DataWriterActor {
+    fn handle(&mut self, message: AssertLiveliness) -> <AssertLiveliness as Mail>::Result {
+        self.send_liveliness_heartbeat(&message.message_sender_actor)
     }
 }
 
@@ -801,6 +1062,28 @@ impl MailHandler<GetGuid> for DataWriterActor {
     }
 }
 
+pub struct SetGuidPrefix {
+    pub guid_prefix: GuidPrefix,
+}
+impl Mail for SetGuidPrefix {
+    type Result = ();
+}
+impl MailHandler<SetGuidPrefix> for DataWriterActor {
+    fn handle(&mut self, message: SetGuidPrefix) -> <SetGuidPrefix as Mail>::Result {
+        self.rtps_writer.set_guid_prefix(message.guid_prefix);
+    }
+}
+
+pub struct ClearHistory;
+impl Mail for ClearHistory {
+    type Result = ();
+}
+impl MailHandler<ClearHistory> for DataWriterActor {
+    fn handle(&mut self, _: ClearHistory) -> <ClearHistory as Mail>::Result {
+        self.changes.clear();
+    }
+}
+
 pub struct GetQos;
 impl Mail for GetQos {
     type Result = DataWriterQos;
@@ -854,6 +1137,19 @@ impl MailHandler<RegisterInstanceWTimestamp> for DataWriterActor {
     }
 }
 
+pub struct UnregisterInstance {
+    pub instance_handle: InstanceHandle,
+}
+impl Mail for UnregisterInstance {
+    type Result = ();
+}
+impl MailHandler<UnregisterInstance> for DataWriterActor {
+    fn handle(&mut self, message: UnregisterInstance) -> <UnregisterInstance as Mail>::Result {
+        self.registered_instance_list
+            .remove(&message.instance_handle);
+    }
+}
+
 pub struct LookupInstance {
     pub instance_handle: InstanceHandle,
 }
@@ -879,19 +1175,16 @@ impl MailHandler<LookupInstance> for DataWriterActor {
     }
 }
 
-pub struct AreAllChangesAcknowledge;
-impl Mail for AreAllChangesAcknowledge {
-    type Result = bool;
+pub struct WaitForAcknowledgments;
+impl Mail for WaitForAcknowledgments {
+    type Result = OneshotReceiver<()>;
 }
-impl MailHandler<AreAllChangesAcknowledge> for DataWriterActor {
-    fn handle(
-        &mut self,
-        _: AreAllChangesAcknowledge,
-    ) -> <AreAllChangesAcknowledge as Mail>::Result {
-        !self
-            .matched_readers
-            .iter()
-            .any(|rp| rp.unacked_changes(self.max_seq_num))
+impl MailHandler<WaitForAcknowledgments> for DataWriterActor {
+    fn handle(&mut self, _: WaitForAcknowledgments) -> <WaitForAcknowledgments as Mail>::Result {
+        let (sender, receiver) = oneshot();
+        self.acknowledgment_waiters.push(sender);
+        self.notify_acknowledgment_waiters();
+        receiver
     }
 }
 
@@ -1006,6 +1299,7 @@ pub struct AddMatchedReader {
         Vec<StatusKind>,
     ),
     pub message_sender_actor: ActorAddress<MessageSenderActor>,
+    pub now: Time,
 }
 impl Mail for AddMatchedReader {
     type Result = DdsResult<()>;
@@ -1151,6 +1445,7 @@ impl MailHandler<AddMatchedReader> for DataWriterActor {
                     )?;
                 }
 
+                self.remove_expired_changes(message.now);
                 self.send_message(message.message_sender_actor);
             } else if !self.incompatible_subscriptions.contains(&instance_handle) {
                 self.incompatible_subscriptions
@@ -1206,9 +1501,59 @@ impl MailHandler<RemoveMatchedReader> for DataWriterActor {
     }
 }
 
+pub struct RemoveMatchedParticipant {
+    pub participant_handle: InstanceHandle,
+    pub data_writer_address: ActorAddress<DataWriterActor>,
+    pub publisher: PublisherAsync,
+    pub publisher_mask_listener: (
+        Option<MpscSender<PublisherListenerMessage>>,
+        Vec<StatusKind>,
+    ),
+    pub participant_mask_listener: (
+        Option<MpscSender<ParticipantListenerMessage>>,
+        Vec<StatusKind>,
+    ),
+}
+impl Mail for RemoveMatchedParticipant {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<RemoveMatchedParticipant> for DataWriterActor {
+    fn handle(
+        &mut self,
+        message: RemoveMatchedParticipant,
+    ) -> <RemoveMatchedParticipant as Mail>::Result {
+        // The handle of an endpoint starts with the GUID prefix of the participant it belongs to
+        let participant_guid_prefix = &message.participant_handle.as_ref()[..12];
+        let removed_reader_handle_list: Vec<InstanceHandle> = self
+            .matched_subscriptions
+            .get_matched_subscriptions()
+            .into_iter()
+            .filter(|h| &h.as_ref()[..12] == participant_guid_prefix)
+            .collect();
+
+        if !removed_reader_handle_list.is_empty() {
+            for discovered_reader_handle in removed_reader_handle_list {
+                self.matched_reader_remove(<[u8; 16]>::from(discovered_reader_handle).into());
+                self.matched_subscriptions
+                    .remove_matched_subscription(discovered_reader_handle);
+            }
+
+            self.on_publication_matched(
+                message.data_writer_address,
+                message.publisher,
+                message.publisher_mask_listener,
+                message.participant_mask_listener,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
 pub struct ProcessAckNackSubmessage {
     pub acknack_submessage: AckNackSubmessage,
     pub source_guid_prefix: GuidPrefix,
+    pub reply_locator_list: Vec<Locator>,
     pub message_sender_actor: ActorAddress<MessageSenderActor>,
 }
 impl Mail for ProcessAckNackSubmessage {
@@ -1222,6 +1567,7 @@ impl MailHandler<ProcessAckNackSubmessage> for DataWriterActor {
         self.on_acknack_submessage_received(
             &message.acknack_submessage,
             message.source_guid_prefix,
+            message.reply_locator_list,
             message.message_sender_actor,
         )
     }
@@ -1258,6 +1604,19 @@ impl MailHandler<SendMessage> for DataWriterActor {
     }
 }
 
+pub struct RefillFlowController {
+    pub message_sender_actor: ActorAddress<MessageSenderActor>,
+}
+impl Mail for RefillFlowController {
+    type Result = ();
+}
+impl MailHandler<RefillFlowController> for DataWriterActor {
+    fn handle(&mut self, message: RefillFlowController) -> <RefillFlowController as Mail>::Result {
+        self.is_flow_controller_refill_scheduled = false;
+        self.send_message(message.message_sender_actor)
+    }
+}
+
 pub struct SetListener {
     pub listener: Option<Box<dyn AnyDataWriterListener + Send>>,
     pub status_kind: Vec<StatusKind>,
@@ -1299,55 +1658,72 @@ impl MailHandler<NewChange> for DataWriterActor {
     }
 }
 
+pub struct NewChangeList {
+    pub new_change_list: Vec<NewChange>,
+}
+impl Mail for NewChangeList {
+    type Result = Vec<RtpsWriterCacheChange>;
+}
+impl MailHandler<NewChangeList> for DataWriterActor {
+    fn handle(&mut self, message: NewChangeList) -> <NewChangeList as Mail>::Result {
+        message
+            .new_change_list
+            .into_iter()
+            .map(|new_change| self.handle(new_change))
+            .collect()
+    }
+}
+
 pub struct AddChange {
     pub change: RtpsWriterCacheChange,
     pub now: Time,
     pub message_sender_actor: ActorAddress<MessageSenderActor>,
     pub writer_address: ActorAddress<DataWriterActor>,
     pub executor_handle: ExecutorHandle,
-    pub timer_handle: TimerHandle,
+    pub participant: DomainParticipantAsync,
 }
 impl Mail for AddChange {
     type Result = ();
 }
 impl MailHandler<AddChange> for DataWriterActor {
     fn handle(&mut self, message: AddChange) -> <AddChange as Mail>::Result {
-        let instance_changes = self
-            .changes
-            .entry(message.change.instance_handle())
-            .or_default();
-
-        if let HistoryQosPolicyKind::KeepLast(depth) = self.qos.history.kind {
-            if instance_changes.len() == depth as usize {
-                instance_changes.pop_front();
-            }
-        }
-
-        let change_timestamp = message.change.timestamp();
-        let seq_num = message.change.sequence_number();
-
-        if seq_num > self.max_seq_num.unwrap_or(0) {
-            self.max_seq_num = Some(seq_num)
-        }
+        self.add_change(
+            message.change,
+            message.now,
+            &message.writer_address,
+            &message.executor_handle,
+            &message.participant,
+        );
 
-        if let DurationKind::Finite(lifespan) = self.qos.lifespan.duration {
-            let change_lifespan =
-                crate::infrastructure::time::Time::from(change_timestamp) - message.now + lifespan;
-            if change_lifespan > Duration::new(0, 0) {
-                instance_changes.push_back(message.change);
-                message.executor_handle.spawn(async move {
-                    message.timer_handle.sleep(change_lifespan.into()).await;
+        self.remove_expired_changes(message.now);
+        self.send_message(message.message_sender_actor);
+    }
+}
 
-                    message
-                        .writer_address
-                        .send_actor_mail(RemoveChange { seq_num })
-                        .ok();
-                });
-            }
-        } else {
-            instance_changes.push_back(message.change);
+pub struct AddChangeList {
+    pub change_list: Vec<RtpsWriterCacheChange>,
+    pub now: Time,
+    pub message_sender_actor: ActorAddress<MessageSenderActor>,
+    pub writer_address: ActorAddress<DataWriterActor>,
+    pub executor_handle: ExecutorHandle,
+    pub participant: DomainParticipantAsync,
+}
+impl Mail for AddChangeList {
+    type Result = ();
+}
+impl MailHandler<AddChangeList> for DataWriterActor {
+    fn handle(&mut self, message: AddChangeList) -> <AddChangeList as Mail>::Result {
+        for change in message.change_list {
+            self.add_change(
+                change,
+                message.now,
+                &message.writer_address,
+                &message.executor_handle,
+                &message.participant,
+            );
         }
 
+        self.remove_expired_changes(message.now);
         self.send_message(message.message_sender_actor);
     }
 }
@@ -1387,61 +1763,55 @@ impl MailHandler<IsResourcesLimitReached> for DataWriterActor {
         &mut self,
         message: IsResourcesLimitReached,
     ) -> <IsResourcesLimitReached as Mail>::Result {
-        if let Length::Limited(max_instances) = self.qos.resource_limits.max_instances {
-            if !self.changes.contains_key(&message.instance_handle.into())
-                && self.changes.len() == max_instances as usize
-            {
-                return true;
-            }
-        }
+        is_resources_limit_reached(
+            &self.qos,
+            &self.instance_change_kinds(),
+            message.instance_handle.into(),
+        )
+    }
+}
 
-        if let Length::Limited(max_samples_per_instance) =
-            self.qos.resource_limits.max_samples_per_instance
-        {
-            // If the history Qos guarantess that the number of samples
-            // is below the limit there is no need to check
-            match self.qos.history.kind {
-                HistoryQosPolicyKind::KeepLast(depth) if depth <= max_samples_per_instance => {}
-                _ => {
-                    if let Some(changes) = self.changes.get(&message.instance_handle.into()) {
-                        // Only Alive changes count towards the resource limits
-                        if changes
-                            .iter()
-                            .filter(|cc| cc.kind() == ChangeKind::Alive)
-                            .count()
-                            >= max_samples_per_instance as usize
-                        {
-                            return true;
-                        }
-                    }
-                }
+pub struct IsResourcesLimitReachedForChangeList {
+    pub instance_handle_list: Vec<InstanceHandle>,
+}
+impl Mail for IsResourcesLimitReachedForChangeList {
+    type Result = bool;
+}
+impl MailHandler<IsResourcesLimitReachedForChangeList> for DataWriterActor {
+    fn handle(
+        &mut self,
+        message: IsResourcesLimitReachedForChangeList,
+    ) -> <IsResourcesLimitReachedForChangeList as Mail>::Result {
+        // The changes of the list are checked as if each of them was added to the history
+        // after the previous ones
+        let mut instance_change_kinds = self.instance_change_kinds();
+        for instance_handle in message.instance_handle_list {
+            let instance_handle = instance_handle.into();
+            if is_resources_limit_reached(&self.qos, &instance_change_kinds, instance_handle) {
+                return true;
             }
-        }
-
-        if let Length::Limited(max_samples) = self.qos.resource_limits.max_samples {
-            let total_samples = self.changes.iter().fold(0, |acc, (instance, s)| {
-                let mut total_instance_samples =
-                    s.iter().filter(|cc| cc.kind() == ChangeKind::Alive).count();
-                // If the History QoS would remove one of the samples then the limit shouldn't
-                // be reached
-                if InstanceHandle::from(*instance) == message.instance_handle {
-                    if let HistoryQosPolicyKind::KeepLast(depth) = self.qos.history.kind {
-                        if depth as usize == total_instance_samples {
-                            total_instance_samples -= 1;
-                        }
-                    }
+            let change_kinds = instance_change_kinds.entry(instance_handle).or_default();
+            if let HistoryQosPolicyKind::KeepLast(depth) = self.qos.history.kind {
+                if change_kinds.len() == depth as usize {
+                    change_kinds.pop_front();
                 }
-                acc + total_instance_samples
-            });
-            if total_samples >= max_samples as usize {
-                return true;
             }
+            change_kinds.push_back(ChangeKind::Alive);
         }
-
         false
     }
 }
 
+pub struct IsSendQueueFull;
+impl Mail for IsSendQueueFull {
+    type Result = bool;
+}
+impl MailHandler<IsSendQueueFull> for DataWriterActor {
+    fn handle(&mut self, _: IsSendQueueFull) -> <IsSendQueueFull as Mail>::Result {
+        self.send_queue.is_full()
+    }
+}
+
 pub struct IsDataLostAfterAddingChange {
     pub instance_handle: InstanceHandle,
 }
@@ -1495,7 +1865,9 @@ fn get_discovered_reader_incompatible_qos_policy_list(
     if &writer_qos.latency_budget < discovered_reader_data.latency_budget() {
         incompatible_qos_policy_list.push(LATENCYBUDGET_QOS_POLICY_ID);
     }
-    if &writer_qos.liveliness < discovered_reader_data.liveliness() {
+    if writer_qos.liveliness.kind < discovered_reader_data.liveliness().kind
+        || writer_qos.liveliness.lease_duration > discovered_reader_data.liveliness().lease_duration
+    {
         incompatible_qos_policy_list.push(LIVELINESS_QOS_POLICY_ID);
     }
     if writer_qos.reliability.kind < discovered_reader_data.reliability().kind {
@@ -1526,12 +1898,73 @@ fn get_discovered_reader_incompatible_qos_policy_list(
     incompatible_qos_policy_list
 }
 
+fn is_resources_limit_reached(
+    qos: &DataWriterQos,
+    instance_change_kinds: &HashMap<
+        crate::rtps::behavior_types::InstanceHandle,
+        VecDeque<ChangeKind>,
+    >,
+    instance_handle: crate::rtps::behavior_types::InstanceHandle,
+) -> bool {
+    if let Length::Limited(max_instances) = qos.resource_limits.max_instances {
+        if !instance_change_kinds.contains_key(&instance_handle)
+            && instance_change_kinds.len() == max_instances as usize
+        {
+            return true;
+        }
+    }
+
+    if let Length::Limited(max_samples_per_instance) = qos.resource_limits.max_samples_per_instance
+    {
+        // If the history Qos guarantess that the number of samples
+        // is below the limit there is no need to check
+        match qos.history.kind {
+            HistoryQosPolicyKind::KeepLast(depth) if depth <= max_samples_per_instance => {}
+            _ => {
+                if let Some(changes) = instance_change_kinds.get(&instance_handle) {
+                    // Only Alive changes count towards the resource limits
+                    if changes
+                        .iter()
+                        .filter(|&&kind| kind == ChangeKind::Alive)
+                        .count()
+                        >= max_samples_per_instance as usize
+                    {
+                        return true;
+                    }
+                }
+            }
+        }
+    }
+
+    if let Length::Limited(max_samples) = qos.resource_limits.max_samples {
+        let total_samples = instance_change_kinds.iter().fold(0, |acc, (instance, s)| {
+            let mut total_instance_samples =
+                s.iter().filter(|&&kind| kind == ChangeKind::Alive).count();
+            // If the History QoS would remove one of the samples then the limit shouldn't
+            // be reached
+            if *instance == instance_handle {
+                if let HistoryQosPolicyKind::KeepLast(depth) = qos.history.kind {
+                    if depth as usize == total_instance_samples {
+                        total_instance_samples -= 1;
+                    }
+                }
+            }
+            acc + total_instance_samples
+        });
+        if total_samples >= max_samples as usize {
+            return true;
+        }
+    }
+
+    false
+}
+
 fn send_message_to_reader_proxy_best_effort(
     reader_proxy: &mut RtpsReaderProxy,
     writer_id: EntityId,
     changes: &HashMap<crate::rtps::behavior_types::InstanceHandle, VecDeque<RtpsWriterCacheChange>>,
     data_max_size_serialized: usize,
-    message_sender_actor: &ActorAddress<MessageSenderActor>,
+    send_queue: &mut WriterSendQueue,
 ) {
     // a_change_seq_num := the_reader_proxy.next_unsent_change();
     // if ( a_change_seq_num > the_reader_proxy.higuest_sent_seq_num +1 ) {
@@ -1560,6 +1993,10 @@ fn send_message_to_reader_proxy_best_effort(
     while let Some(next_unsent_change_seq_num) =
         reader_proxy.next_unsent_change(changes.values().flatten())
     {
+        // Changes which do not fit in the send queue are sent in a later round
+        if send_queue.is_full() {
+            break;
+        }
         if next_unsent_change_seq_num > reader_proxy.highest_sent_seq_num() + 1 {
             let gap_start_sequence_number = reader_proxy.highest_sent_seq_num() + 1;
             let gap_end_sequence_number = next_unsent_change_seq_num - 1;
@@ -1570,15 +2007,18 @@ fn send_message_to_reader_proxy_best_effort(
                 SequenceNumberSet::new(gap_end_sequence_number + 1, []),
             ));
 
-            message_sender_actor
-                .send_actor_mail(message_sender_actor::WriteMessage {
+            send_queue.push(
+                SendPriority::NewData,
+                message_sender_actor::WriteMessage {
                     submessages: vec![gap_submessage],
                     destination_locator_list: reader_proxy.unicast_locator_list().to_vec(),
-                })
-                .ok();
+                },
+                0,
+            );
+        }
 
-            reader_proxy.set_highest_sent_seq_num(next_unsent_change_seq_num);
-        } else if let Some(cache_change) = changes
+        // The change following the gap is sent in the same round as the gap
+        if let Some(cache_change) = changes
             .values()
             .flatten()
             .find(|cc| cc.sequence_number() == next_unsent_change_seq_num)
@@ -1590,7 +2030,10 @@ fn send_message_to_reader_proxy_best_effort(
 
             // Either send a DATAFRAG submessages or send a single DATA submessage
             if number_of_fragments > 1 {
-                for frag_index in 0..number_of_fragments {
+                for data_frag in cache_change.as_data_frag_submessages(
+                    reader_proxy.remote_reader_guid().entity_id(),
+                    data_max_size_serialized,
+                ) {
                     let info_dst = Box::new(InfoDestinationSubmessage::new(
                         reader_proxy.remote_reader_guid().prefix(),
                     ));
@@ -1600,52 +2043,15 @@ fn send_message_to_reader_proxy_best_effort(
                         cache_change.timestamp(),
                     ));
 
-                    let inline_qos_flag = true;
-                    let key_flag = match cache_change.kind() {
-                        ChangeKind::Alive => false,
-                        ChangeKind::NotAliveDisposed | ChangeKind::NotAliveUnregistered => true,
-                        _ => todo!(),
-                    };
-                    let non_standard_payload_flag = false;
-                    let reader_id = reader_proxy.remote_reader_guid().entity_id();
-                    let writer_id = cache_change.writer_guid().entity_id();
-                    let writer_sn = cache_change.sequence_number();
-                    let fragment_starting_num = (frag_index + 1) as u32;
-                    let fragments_in_submessage = 1;
-                    let fragment_size = data_max_size_serialized as u16;
-                    let data_size = cache_change.data_value().len() as u32;
-                    let inline_qos = cache_change.inline_qos().clone();
-
-                    let start = frag_index * data_max_size_serialized;
-                    let end = std::cmp::min(
-                        (frag_index + 1) * data_max_size_serialized,
-                        cache_change.data_value().len(),
-                    );
-
-                    let serialized_payload =
-                        SerializedDataFragment::new(cache_change.data_value().clone(), start..end);
-
-                    let data_frag = Box::new(DataFragSubmessage::new(
-                        inline_qos_flag,
-                        non_standard_payload_flag,
-                        key_flag,
-                        reader_id,
-                        writer_id,
-                        writer_sn,
-                        fragment_starting_num,
-                        fragments_in_submessage,
-                        fragment_size,
-                        data_size,
-                        inline_qos,
-                        serialized_payload,
-                    ));
-
-                    message_sender_actor
-                        .send_actor_mail(message_sender_actor::WriteMessage {
-                            submessages: vec![info_dst, info_timestamp, data_frag],
+                    let payload_size = data_frag.serialized_payload().as_ref().len();
+                    send_queue.push(
+                        SendPriority::NewData,
+                        message_sender_actor::WriteMessage {
+                            submessages: vec![info_dst, info_timestamp, Box::new(data_frag)],
                             destination_locator_list: reader_proxy.unicast_locator_list().to_vec(),
-                        })
-                        .ok();
+                        },
+                        payload_size,
+                    );
                 }
             } else {
                 let info_dst = Box::new(InfoDestinationSubmessage::new(
@@ -1661,16 +2067,19 @@ fn send_message_to_reader_proxy_best_effort(
                     cache_change.as_data_submessage(reader_proxy.remote_reader_guid().entity_id()),
                 );
 
-                message_sender_actor
-                    .send_actor_mail(message_sender_actor::WriteMessage {
+                send_queue.push(
+                    SendPriority::NewData,
+                    message_sender_actor::WriteMessage {
                         submessages: vec![info_dst, info_timestamp, data_submessage],
                         destination_locator_list: reader_proxy.unicast_locator_list().to_vec(),
-                    })
-                    .ok();
+                    },
+                    cache_change.data_value().len(),
+                );
             }
         } else {
-            message_sender_actor
-                .send_actor_mail(message_sender_actor::WriteMessage {
+            send_queue.push(
+                SendPriority::NewData,
+                message_sender_actor::WriteMessage {
                     submessages: vec![Box::new(GapSubmessage::new(
                         ENTITYID_UNKNOWN,
                         writer_id,
@@ -1678,8 +2087,9 @@ fn send_message_to_reader_proxy_best_effort(
                         SequenceNumberSet::new(next_unsent_change_seq_num + 1, []),
                     ))],
                     destination_locator_list: reader_proxy.unicast_locator_list().to_vec(),
-                })
-                .ok();
+                },
+                0,
+            );
         }
 
         reader_proxy.set_highest_sent_seq_num(next_unsent_change_seq_num);
@@ -1695,13 +2105,17 @@ fn send_message_to_reader_proxy_reliable(
     seq_num_max: Option<SequenceNumber>,
     data_max_size_serialized: usize,
     heartbeat_period: Duration,
-    message_sender_actor: &ActorAddress<MessageSenderActor>,
+    send_queue: &mut WriterSendQueue,
 ) {
     // Top part of the state machine - Figure 8.19 RTPS standard
     if reader_proxy.unsent_changes(changes.values().flatten()) {
         while let Some(next_unsent_change_seq_num) =
             reader_proxy.next_unsent_change(changes.values().flatten())
         {
+            // Changes which do not fit in the send queue are sent in a later round
+            if send_queue.is_full() {
+                break;
+            }
             if next_unsent_change_seq_num > reader_proxy.highest_sent_seq_num() + 1 {
                 let gap_start_sequence_number = reader_proxy.highest_sent_seq_num() + 1;
                 let gap_end_sequence_number = next_unsent_change_seq_num - 1;
@@ -1711,38 +2125,38 @@ fn send_message_to_reader_proxy_reliable(
                     gap_start_sequence_number,
                     SequenceNumberSet::new(gap_end_sequence_number + 1, []),
                 ));
-                let first_sn = seq_num_min.unwrap_or(1);
-                let last_sn = seq_num_max.unwrap_or(0);
-                let heartbeat_submessage = Box::new(
-                    reader_proxy
-                        .heartbeat_machine()
-                        .generate_new_heartbeat(writer_id, first_sn, last_sn),
-                );
-                message_sender_actor
-                    .send_actor_mail(message_sender_actor::WriteMessage {
-                        submessages: vec![gap_submessage, heartbeat_submessage],
+                send_queue.push(
+                    SendPriority::NewData,
+                    message_sender_actor::WriteMessage {
+                        submessages: vec![gap_submessage],
                         destination_locator_list: reader_proxy.unicast_locator_list().to_vec(),
-                    })
-                    .ok();
-            } else {
-                send_change_message_reader_proxy_reliable(
-                    reader_proxy,
-                    writer_id,
-                    changes,
-                    seq_num_min,
-                    seq_num_max,
-                    data_max_size_serialized,
-                    next_unsent_change_seq_num,
-                    message_sender_actor,
+                    },
+                    0,
                 );
             }
+
+            // The change following the gap is sent in the same round as the gap
+            let destination_locator_list = reader_proxy.unicast_locator_list().to_vec();
+            send_change_message_reader_proxy_reliable(
+                reader_proxy,
+                writer_id,
+                changes,
+                seq_num_min,
+                seq_num_max,
+                data_max_size_serialized,
+                next_unsent_change_seq_num,
+                &destination_locator_list,
+                SendPriority::NewData,
+                send_queue,
+            );
             reader_proxy.set_highest_sent_seq_num(next_unsent_change_seq_num);
         }
     } else if !reader_proxy.unacked_changes(seq_num_max) {
         // Idle
-    } else if reader_proxy
-        .heartbeat_machine()
-        .is_time_for_heartbeat(heartbeat_period.into())
+    } else if !send_queue.is_full()
+        && reader_proxy
+            .heartbeat_machine()
+            .is_time_for_heartbeat(heartbeat_period.into())
     {
         let first_sn = seq_num_min.unwrap_or(1);
         let last_sn = seq_num_max.unwrap_or(0);
@@ -1752,22 +2166,40 @@ fn send_message_to_reader_proxy_reliable(
                 .generate_new_heartbeat(writer_id, first_sn, last_sn),
         );
 
-        message_sender_actor
-            .send_actor_mail(message_sender_actor::WriteMessage {
+        send_queue.push(
+            SendPriority::Heartbeat,
+            message_sender_actor::WriteMessage {
                 submessages: vec![heartbeat_submessage],
                 destination_locator_list: reader_proxy.unicast_locator_list().to_vec(),
-            })
-            .ok();
+            },
+            0,
+        );
     }
+}
 
+#[allow(clippy::too_many_arguments)]
+fn send_requested_changes_reader_proxy_reliable(
+    reader_proxy: &mut RtpsReaderProxy,
+    writer_id: EntityId,
+    changes: &HashMap<crate::rtps::behavior_types::InstanceHandle, VecDeque<RtpsWriterCacheChange>>,
+    seq_num_min: Option<SequenceNumber>,
+    seq_num_max: Option<SequenceNumber>,
+    data_max_size_serialized: usize,
+    send_queue: &mut WriterSendQueue,
+) {
     // Middle-part of the state-machine - Figure 8.19 RTPS standard
+    // Requested changes which do not fit in the send queue stay requested for a later round
     if !reader_proxy.requested_changes().is_empty() {
-        while let Some(next_requested_change_seq_num) = reader_proxy.next_requested_change() {
+        while !send_queue.is_full() {
+            let Some(next_requested_change_seq_num) = reader_proxy.next_requested_change() else {
+                break;
+            };
             // "a_change.status := UNDERWAY;" should be done by next_requested_change() as
             // it's not done here to avoid the change being a mutable reference
             // Also the post-condition:
             // a_change BELONGS-TO the_reader_proxy.requested_changes() ) == FALSE
             // should be full-filled by next_requested_change()
+            let destination_locator_list = reader_proxy.reply_locator_list().to_vec();
             send_change_message_reader_proxy_reliable(
                 reader_proxy,
                 writer_id,
@@ -1776,7 +2208,120 @@ fn send_message_to_reader_proxy_reliable(
                 seq_num_max,
                 data_max_size_serialized,
                 next_requested_change_seq_num,
-                message_sender_actor,
+                &destination_locator_list,
+                SendPriority::Retransmit,
+                send_queue,
+            );
+        }
+    }
+
+    // Fragments requested through NackFrag are re-sent individually instead of the whole change
+    while !send_queue.is_full() {
+        let Some((requested_seq_num, requested_fragments)) =
+            reader_proxy.next_requested_fragments()
+        else {
+            break;
+        };
+        let destination_locator_list = reader_proxy.reply_locator_list().to_vec();
+        send_requested_fragments_reader_proxy_reliable(
+            reader_proxy,
+            writer_id,
+            changes,
+            data_max_size_serialized,
+            requested_seq_num,
+            &requested_fragments,
+            &destination_locator_list,
+            send_queue,
+        );
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn send_requested_fragments_reader_proxy_reliable(
+    reader_proxy: &mut RtpsReaderProxy,
+    writer_id: EntityId,
+    changes: &HashMap<crate::rtps::behavior_types::InstanceHandle, VecDeque<RtpsWriterCacheChange>>,
+    data_max_size_serialized: usize,
+    change_seq_num: SequenceNumber,
+    requested_fragments: &[FragmentNumber],
+    destination_locator_list: &[Locator],
+    send_queue: &mut WriterSendQueue,
+) {
+    match changes
+        .values()
+        .flatten()
+        .find(|cc| cc.sequence_number() == change_seq_num)
+    {
+        Some(cache_change) if change_seq_num > reader_proxy.first_relevant_sample_seq_num() => {
+            let number_of_fragments = cache_change
+                .data_value()
+                .len()
+                .div_ceil(data_max_size_serialized);
+
+            for data_frag in cache_change
+                .as_data_frag_submessages(
+                    reader_proxy.remote_reader_guid().entity_id(),
+                    data_max_size_serialized,
+                )
+                .into_iter()
+                .filter(|df| requested_fragments.contains(&df.fragment_starting_num()))
+            {
+                let info_dst = Box::new(InfoDestinationSubmessage::new(
+                    reader_proxy.remote_reader_guid().prefix(),
+                ));
+
+                let info_timestamp = Box::new(InfoTimestampSubmessage::new(
+                    false,
+                    cache_change.timestamp(),
+                ));
+
+                let payload_size = data_frag.serialized_payload().as_ref().len();
+                send_queue.push(
+                    SendPriority::Retransmit,
+                    message_sender_actor::WriteMessage {
+                        submessages: vec![info_dst, info_timestamp, Box::new(data_frag)],
+                        destination_locator_list: destination_locator_list.to_vec(),
+                    },
+                    payload_size,
+                );
+            }
+
+            let info_dst = Box::new(InfoDestinationSubmessage::new(
+                reader_proxy.remote_reader_guid().prefix(),
+            ));
+            let heartbeat_frag = Box::new(reader_proxy.heartbeat_frag_machine().submessage(
+                writer_id,
+                change_seq_num,
+                number_of_fragments as u32,
+            ));
+            send_queue.push(
+                SendPriority::Retransmit,
+                message_sender_actor::WriteMessage {
+                    submessages: vec![info_dst, heartbeat_frag],
+                    destination_locator_list: destination_locator_list.to_vec(),
+                },
+                0,
+            );
+        }
+        _ => {
+            let info_dst = Box::new(InfoDestinationSubmessage::new(
+                reader_proxy.remote_reader_guid().prefix(),
+            ));
+
+            let gap_submessage = Box::new(GapSubmessage::new(
+                ENTITYID_UNKNOWN,
+                writer_id,
+                change_seq_num,
+                SequenceNumberSet::new(change_seq_num + 1, []),
+            ));
+
+            send_queue.push(
+                SendPriority::Retransmit,
+                message_sender_actor::WriteMessage {
+                    submessages: vec![info_dst, gap_submessage],
+                    destination_locator_list: destination_locator_list.to_vec(),
+                },
+                0,
             );
         }
     }
@@ -1791,7 +2336,9 @@ fn send_change_message_reader_proxy_reliable(
     seq_num_max: Option<SequenceNumber>,
     data_max_size_serialized: usize,
     change_seq_num: SequenceNumber,
-    message_sender_actor: &ActorAddress<MessageSenderActor>,
+    destination_locator_list: &[Locator],
+    priority: SendPriority,
+    send_queue: &mut WriterSendQueue,
 ) {
     match changes
         .values()
@@ -1806,7 +2353,10 @@ fn send_change_message_reader_proxy_reliable(
 
             // Either send a DATAFRAG submessages or send a single DATA submessage
             if number_of_fragments > 1 {
-                for frag_index in 0..number_of_fragments {
+                for data_frag in cache_change.as_data_frag_submessages(
+                    reader_proxy.remote_reader_guid().entity_id(),
+                    data_max_size_serialized,
+                ) {
                     let info_dst = Box::new(InfoDestinationSubmessage::new(
                         reader_proxy.remote_reader_guid().prefix(),
                     ));
@@ -1816,53 +2366,33 @@ fn send_change_message_reader_proxy_reliable(
                         cache_change.timestamp(),
                     ));
 
-                    let inline_qos_flag = true;
-                    let key_flag = match cache_change.kind() {
-                        ChangeKind::Alive => false,
-                        ChangeKind::NotAliveDisposed | ChangeKind::NotAliveUnregistered => true,
-                        _ => todo!(),
-                    };
-                    let non_standard_payload_flag = false;
-                    let reader_id = reader_proxy.remote_reader_guid().entity_id();
-                    let writer_id = cache_change.writer_guid().entity_id();
-                    let writer_sn = cache_change.sequence_number();
-                    let fragment_starting_num = (frag_index + 1) as u32;
-                    let fragments_in_submessage = 1;
-                    let fragment_size = data_max_size_serialized as u16;
-                    let data_size = cache_change.data_value().len() as u32;
-                    let inline_qos = cache_change.inline_qos().clone();
-
-                    let start = frag_index * data_max_size_serialized;
-                    let end = std::cmp::min(
-                        (frag_index + 1) * data_max_size_serialized,
-                        cache_change.data_value().len(),
+                    let payload_size = data_frag.serialized_payload().as_ref().len();
+                    send_queue.push(
+                        priority,
+                        message_sender_actor::WriteMessage {
+                            submessages: vec![info_dst, info_timestamp, Box::new(data_frag)],
+                            destination_locator_list: destination_locator_list.to_vec(),
+                        },
+                        payload_size,
                     );
-
-                    let serialized_payload =
-                        SerializedDataFragment::new(cache_change.data_value().clone(), start..end);
-
-                    let data_frag = Box::new(DataFragSubmessage::new(
-                        inline_qos_flag,
-                        non_standard_payload_flag,
-                        key_flag,
-                        reader_id,
-                        writer_id,
-                        writer_sn,
-                        fragment_starting_num,
-                        fragments_in_submessage,
-                        fragment_size,
-                        data_size,
-                        inline_qos,
-                        serialized_payload,
-                    ));
-
-                    message_sender_actor
-                        .send_actor_mail(message_sender_actor::WriteMessage {
-                            submessages: vec![info_dst, info_timestamp, data_frag],
-                            destination_locator_list: reader_proxy.unicast_locator_list().to_vec(),
-                        })
-                        .ok();
                 }
+
+                let info_dst = Box::new(InfoDestinationSubmessage::new(
+                    reader_proxy.remote_reader_guid().prefix(),
+                ));
+                let heartbeat_frag = Box::new(reader_proxy.heartbeat_frag_machine().submessage(
+                    writer_id,
+                    change_seq_num,
+                    number_of_fragments as u32,
+                ));
+                send_queue.push(
+                    priority,
+                    message_sender_actor::WriteMessage {
+                        submessages: vec![info_dst, heartbeat_frag],
+                        destination_locator_list: destination_locator_list.to_vec(),
+                    },
+                    0,
+                );
             } else {
                 let info_dst = Box::new(InfoDestinationSubmessage::new(
                     reader_proxy.remote_reader_guid().prefix(),
@@ -1878,19 +2408,26 @@ fn send_change_message_reader_proxy_reliable(
                 );
 
                 let first_sn = seq_num_min.unwrap_or(1);
-                let last_sn = seq_num_max.unwrap_or(0);
+                // New data only announces the changes up to the one being sent. Otherwise the
+                // reader would request the changes still waiting in the send queue.
+                let last_sn = match priority {
+                    SendPriority::NewData => change_seq_num,
+                    _ => seq_num_max.unwrap_or(0),
+                };
                 let heartbeat = Box::new(
                     reader_proxy
                         .heartbeat_machine()
                         .generate_new_heartbeat(writer_id, first_sn, last_sn),
                 );
 
-                message_sender_actor
-                    .send_actor_mail(message_sender_actor::WriteMessage {
+                send_queue.push(
+                    priority,
+                    message_sender_actor::WriteMessage {
                         submessages: vec![info_dst, info_timestamp, data_submessage, heartbeat],
-                        destination_locator_list: reader_proxy.unicast_locator_list().to_vec(),
-                    })
-                    .ok();
+                        destination_locator_list: destination_locator_list.to_vec(),
+                    },
+                    cache_change.data_value().len(),
+                );
             }
         }
         _ => {
@@ -1905,12 +2442,14 @@ fn send_change_message_reader_proxy_reliable(
                 SequenceNumberSet::new(change_seq_num + 1, []),
             ));
 
-            message_sender_actor
-                .send_actor_mail(message_sender_actor::WriteMessage {
+            send_queue.push(
+                priority,
+                message_sender_actor::WriteMessage {
                     submessages: vec![info_dst, gap_submessage],
-                    destination_locator_list: reader_proxy.unicast_locator_list().to_vec(),
-                })
-                .ok();
+                    destination_locator_list: destination_locator_list.to_vec(),
+                },
+                0,
+            );
         }
     }
 }
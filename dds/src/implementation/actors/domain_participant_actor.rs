@@ -22,7 +22,7 @@ use crate::{
         subscriber_listener::SubscriberListenerAsync, topic::TopicAsync,
         topic_listener::TopicListenerAsync,
     },
-    domain::domain_participant_factory::DomainId,
+    domain::{domain_participant::DomainParticipantStats, domain_participant_factory::DomainId},
     implementation::{
         actor::{Actor, ActorAddress, Mail, MailHandler},
         actors::{
@@ -30,8 +30,9 @@ use crate::{
             topic_actor::TopicActor,
         },
         runtime::{
-            executor::{block_on, Executor, ExecutorHandle},
+            executor::{block_on, Executor, ExecutorHandle, TaskHandle},
             mpsc::{mpsc_channel, MpscSender},
+            oneshot::{oneshot, OneshotReceiver, OneshotSender},
             timer::{TimerDriver, TimerHandle},
         },
     },
@@ -40,8 +41,8 @@ use crate::{
         instance::InstanceHandle,
         qos::{DomainParticipantQos, PublisherQos, QosKind, SubscriberQos, TopicQos},
         qos_policy::{
-            HistoryQosPolicy, LifespanQosPolicy, ResourceLimitsQosPolicy, TopicDataQosPolicy,
-            TransportPriorityQosPolicy,
+            HistoryQosPolicy, LifespanQosPolicy, ResourceLimitsQosPolicy, TimeSourceQosPolicy,
+            TopicDataQosPolicy, TransportPriorityQosPolicy,
         },
         status::{
             LivelinessChangedStatus, LivelinessLostStatus, OfferedDeadlineMissedStatus,
@@ -67,7 +68,7 @@ use crate::{
         },
         participant::RtpsParticipant,
         types::{
-            EntityId, Guid, Locator, BUILT_IN_READER_GROUP, BUILT_IN_WRITER_GROUP,
+            EntityId, Guid, GuidPrefix, Locator, BUILT_IN_READER_GROUP, BUILT_IN_WRITER_GROUP,
             ENTITYID_PARTICIPANT, ENTITYID_UNKNOWN, USER_DEFINED_READER_GROUP, USER_DEFINED_TOPIC,
             USER_DEFINED_WRITER_GROUP,
         },
@@ -82,8 +83,12 @@ use crate::{
 };
 
 use std::{
-    collections::{hash_map::Entry, HashMap, HashSet},
-    sync::Arc,
+    collections::{
+        hash_map::{Entry, RandomState},
+        HashMap, HashSet,
+    },
+    hash::{BuildHasher, Hasher},
+    sync::{atomic::Ordering, Arc},
     thread::JoinHandle,
     time::{SystemTime, UNIX_EPOCH},
 };
@@ -91,12 +96,17 @@ use std::{
 use super::{
     data_writer_actor::DataWriterActor,
     domain_participant_factory_actor::{sedp_data_reader_qos, sedp_data_writer_qos},
-    message_sender_actor::MessageSenderActor,
+    message_sender_actor::{self, MessageSenderActor},
     publisher_actor::{self, PublisherActor},
     status_condition_actor::StatusConditionActor,
     subscriber_actor, topic_actor,
 };
 
+// Period after enabling the participant during which the discovered participants are checked
+// for having the same GUID prefix as the local one
+const GUID_PREFIX_COLLISION_DETECTION_PERIOD: std::time::Duration =
+    std::time::Duration::from_secs(2);
+
 pub const BUILT_IN_TOPIC_NAME_LIST: [&str; 4] = [
     DCPS_PARTICIPANT,
     DCPS_TOPIC,
@@ -203,7 +213,7 @@ pub enum ListenerKind {
 pub enum ParticipantListenerOperation {
     _DataAvailable,
     SampleRejected(SampleRejectedStatus),
-    _LivenessChanged(LivelinessChangedStatus),
+    LivelinessChanged(LivelinessChangedStatus),
     RequestedDeadlineMissed(RequestedDeadlineMissedStatus),
     RequestedIncompatibleQos(RequestedIncompatibleQosStatus),
     SubscriptionMatched(SubscriptionMatchedStatus),
@@ -269,7 +279,7 @@ impl ParticipantListenerThread {
                             };
                             listener.on_sample_rejected(data_reader, status).await
                         }
-                        ParticipantListenerOperation::_LivenessChanged(status) => {
+                        ParticipantListenerOperation::LivelinessChanged(status) => {
                             let data_reader = match m.listener_kind {
                                 ListenerKind::Reader {
                                     reader_address,
@@ -485,8 +495,11 @@ pub struct DomainParticipantActor {
     manual_liveliness_count: Count,
     lease_duration: Duration,
     discovered_participant_list: HashMap<InstanceHandle, SpdpDiscoveredParticipantData>,
+    discovered_participant_lease_task_list: HashMap<InstanceHandle, TaskHandle>,
     discovered_topic_list: HashMap<InstanceHandle, TopicBuiltinTopicData>,
+    topic_waiters: Vec<OneshotSender<()>>,
     enabled: bool,
+    enable_time: Option<std::time::Instant>,
     ignored_participants: HashSet<InstanceHandle>,
     ignored_publications: HashSet<InstanceHandle>,
     ignored_subcriptions: HashSet<InstanceHandle>,
@@ -498,6 +511,7 @@ pub struct DomainParticipantActor {
     message_sender_actor: Actor<MessageSenderActor>,
     executor: Executor,
     timer_driver: TimerDriver,
+    stats: DomainParticipantStats,
 }
 
 impl DomainParticipantActor {
@@ -507,6 +521,7 @@ impl DomainParticipantActor {
         domain_id: DomainId,
         domain_tag: String,
         domain_participant_qos: DomainParticipantQos,
+        lease_duration: Duration,
         data_max_size_serialized: usize,
         listener: Option<Box<dyn DomainParticipantListenerAsync + Send>>,
         status_kind: Vec<StatusKind>,
@@ -522,7 +537,6 @@ impl DomainParticipantActor {
         ActorAddress<SubscriberActor>,
         ActorAddress<StatusConditionActor>,
     ) {
-        let lease_duration = Duration::new(100, 0);
         let guid_prefix = rtps_participant.guid().prefix();
         let executor_handle = executor.handle();
 
@@ -578,8 +592,11 @@ impl DomainParticipantActor {
                 manual_liveliness_count: 0,
                 lease_duration,
                 discovered_participant_list: HashMap::new(),
+                discovered_participant_lease_task_list: HashMap::new(),
                 discovered_topic_list: HashMap::new(),
+                topic_waiters: Vec::new(),
                 enabled: false,
+                enable_time: None,
                 ignored_participants: HashSet::new(),
                 ignored_publications: HashSet::new(),
                 ignored_subcriptions: HashSet::new(),
@@ -591,6 +608,7 @@ impl DomainParticipantActor {
                 message_sender_actor: Actor::spawn(message_sender_actor, &executor_handle),
                 executor,
                 timer_driver,
+                stats: DomainParticipantStats::default(),
             },
             status_condition_address,
             builtin_subscriber_address,
@@ -598,6 +616,12 @@ impl DomainParticipantActor {
         )
     }
 
+    fn notify_topic_waiters(&mut self) {
+        for waiter in self.topic_waiters.drain(..) {
+            waiter.send(());
+        }
+    }
+
     fn lookup_discovered_topic(
         &mut self,
         topic_name: String,
@@ -692,12 +716,102 @@ impl DomainParticipantActor {
     }
 
     fn get_current_time(&self) -> infrastructure::time::Time {
-        let now_system_time = SystemTime::now();
-        let unix_time = now_system_time
-            .duration_since(UNIX_EPOCH)
-            .expect("Clock time is before Unix epoch start");
+        let unix_time = match &self.qos.time_source {
+            TimeSourceQosPolicy::SystemTime => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Clock time is before Unix epoch start"),
+            TimeSourceQosPolicy::Logical(logical_time) => {
+                std::time::Duration::from_nanos(logical_time.load(Ordering::Acquire))
+            }
+        };
         infrastructure::time::Time::new(unix_time.as_secs() as i32, unix_time.subsec_nanos())
     }
+
+    fn restart_discovered_participant_lease(
+        &mut self,
+        discovered_participant_handle: InstanceHandle,
+        lease_duration: Duration,
+        participant: DomainParticipantAsync,
+    ) {
+        if let Some(t) = self
+            .discovered_participant_lease_task_list
+            .remove(&discovered_participant_handle)
+        {
+            t.abort();
+        }
+
+        let lease_duration = std::time::Duration::from(lease_duration);
+        let timer_handle = self.timer_driver.handle();
+        let lease_task = self.executor.handle().spawn(async move {
+            timer_handle.sleep(lease_duration).await;
+            if let Ok(r) =
+                participant
+                    .participant_address()
+                    .send_actor_mail(RemoveDiscoveredParticipant {
+                        handle: discovered_participant_handle,
+                        participant: participant.clone(),
+                    })
+            {
+                r.receive_reply().await;
+            }
+        });
+        self.discovered_participant_lease_task_list
+            .insert(discovered_participant_handle, lease_task);
+    }
+
+    fn remove_discovered_participant(
+        &mut self,
+        handle: InstanceHandle,
+        participant: &DomainParticipantAsync,
+    ) {
+        if let Some(t) = self.discovered_participant_lease_task_list.remove(&handle) {
+            t.abort();
+        }
+        if self.discovered_participant_list.remove(&handle).is_some() {
+            self.stats.discovery_events += 1;
+
+            // Remove the proxies of all the built-in and user-defined endpoints of the participant
+            // since they won't be disposed if the participant lease expired
+            let participant_mask_listener = (
+                self.participant_listener_thread
+                    .as_ref()
+                    .map(|l| l.sender().clone()),
+                self.status_kind.clone(),
+            );
+            for publisher in std::iter::once(&self.builtin_publisher)
+                .chain(self.user_defined_publisher_list.values())
+            {
+                publisher.send_actor_mail(publisher_actor::RemoveMatchedParticipant {
+                    participant_handle: handle,
+                    publisher_address: publisher.address(),
+                    participant: participant.clone(),
+                    participant_mask_listener: participant_mask_listener.clone(),
+                });
+            }
+            for subscriber in std::iter::once(&self.builtin_subscriber)
+                .chain(self.user_defined_subscriber_list.values())
+            {
+                subscriber.send_actor_mail(subscriber_actor::RemoveMatchedParticipant {
+                    participant_handle: handle,
+                    subscriber_address: subscriber.address(),
+                    participant: participant.clone(),
+                    participant_mask_listener: participant_mask_listener.clone(),
+                });
+            }
+        }
+    }
+
+    fn has_local_locators(&self, participant_proxy: &ParticipantProxy) -> bool {
+        participant_proxy.metatraffic_unicast_locator_list()
+            == self.rtps_participant.metatraffic_unicast_locator_list()
+            && participant_proxy.default_unicast_locator_list()
+                == self.rtps_participant.default_unicast_locator_list()
+    }
+
+    fn on_message_received(&mut self, rtps_message: &RtpsMessageRead) {
+        self.stats.messages_received += 1;
+        self.stats.bytes_received += rtps_message.length() as u64;
+    }
 }
 
 pub struct CreateUserDefinedPublisher {
@@ -867,7 +981,7 @@ impl MailHandler<CreateUserDefinedTopic> for DomainParticipantActor {
         &mut self,
         message: CreateUserDefinedTopic,
     ) -> <CreateUserDefinedTopic as Mail>::Result {
-        self.create_user_defined_topic(
+        let topic = self.create_user_defined_topic(
             message.topic_name,
             message.type_name,
             message.qos,
@@ -875,7 +989,9 @@ impl MailHandler<CreateUserDefinedTopic> for DomainParticipantActor {
             message.mask,
             message.type_support,
             message.executor_handle,
-        )
+        )?;
+        self.notify_topic_waiters();
+        Ok(topic)
     }
 }
 
@@ -894,6 +1010,16 @@ impl MailHandler<DeleteUserDefinedTopic> for DomainParticipantActor {
     }
 }
 
+pub struct GetTopicList;
+impl Mail for GetTopicList {
+    type Result = Vec<ActorAddress<TopicActor>>;
+}
+impl MailHandler<GetTopicList> for DomainParticipantActor {
+    fn handle(&mut self, _: GetTopicList) -> <GetTopicList as Mail>::Result {
+        self.topic_list.values().map(|(t, _)| t.address()).collect()
+    }
+}
+
 pub struct FindTopic {
     pub topic_name: String,
     pub type_support: Arc<dyn DynamicTypeInterface + Send + Sync>,
@@ -916,6 +1042,18 @@ impl MailHandler<FindTopic> for DomainParticipantActor {
     }
 }
 
+pub struct WaitForTopic;
+impl Mail for WaitForTopic {
+    type Result = OneshotReceiver<()>;
+}
+impl MailHandler<WaitForTopic> for DomainParticipantActor {
+    fn handle(&mut self, _: WaitForTopic) -> <WaitForTopic as Mail>::Result {
+        let (sender, receiver) = oneshot();
+        self.topic_waiters.push(sender);
+        receiver
+    }
+}
+
 pub struct LookupTopicdescription {
     pub topic_name: String,
 }
@@ -948,11 +1086,22 @@ impl Mail for Enable {
 impl MailHandler<Enable> for DomainParticipantActor {
     fn handle(&mut self, _: Enable) -> <Enable as Mail>::Result {
         self.enabled = true;
+        self.enable_time = Some(std::time::Instant::now());
 
         Ok(())
     }
 }
 
+pub struct AssertLiveliness;
+impl Mail for AssertLiveliness {
+    type Result = ();
+}
+impl MailHandler<AssertLiveliness> for DomainParticipantActor {
+    fn handle(&mut self, _: AssertLiveliness) -> <AssertLiveliness as Mail>::Result {
+        self.manual_liveliness_count = self.manual_liveliness_count.wrapping_add(1);
+    }
+}
+
 pub struct IsEnabled;
 impl Mail for IsEnabled {
     type Result = bool;
@@ -1423,6 +1572,40 @@ impl MailHandler<GetCurrentTime> for DomainParticipantActor {
     }
 }
 
+pub struct GetStats;
+impl Mail for GetStats {
+    type Result = DomainParticipantStats;
+}
+impl MailHandler<GetStats> for DomainParticipantActor {
+    fn handle(&mut self, _: GetStats) -> <GetStats as Mail>::Result {
+        self.stats
+    }
+}
+
+pub struct AddSamplesWritten {
+    pub number_of_samples: usize,
+}
+impl Mail for AddSamplesWritten {
+    type Result = ();
+}
+impl MailHandler<AddSamplesWritten> for DomainParticipantActor {
+    fn handle(&mut self, message: AddSamplesWritten) -> <AddSamplesWritten as Mail>::Result {
+        self.stats.samples_written += message.number_of_samples as u64;
+    }
+}
+
+pub struct AddSamplesRead {
+    pub number_of_samples: usize,
+}
+impl Mail for AddSamplesRead {
+    type Result = ();
+}
+impl MailHandler<AddSamplesRead> for DomainParticipantActor {
+    fn handle(&mut self, message: AddSamplesRead) -> <AddSamplesRead as Mail>::Result {
+        self.stats.samples_read += message.number_of_samples as u64;
+    }
+}
+
 pub struct GetBuiltinPublisher;
 impl Mail for GetBuiltinPublisher {
     type Result = ActorAddress<PublisherActor>;
@@ -1450,8 +1633,10 @@ impl MailHandler<ProcessMetatrafficRtpsMessage> for DomainParticipantActor {
             rtps_message = ?message.rtps_message,
             "Received metatraffic RTPS message"
         );
+        self.on_message_received(&message.rtps_message);
         let reception_timestamp = self.get_current_time().into();
-        let mut message_receiver = MessageReceiver::new(message.rtps_message);
+        let mut message_receiver =
+            MessageReceiver::new(message.rtps_message, self.rtps_participant.guid().prefix());
         while let Some(submessage) = message_receiver.next() {
             match submessage {
                 RtpsSubmessageReadKind::Data(data_submessage) => {
@@ -1471,7 +1656,6 @@ impl MailHandler<ProcessMetatrafficRtpsMessage> for DomainParticipantActor {
                             participant: message.participant.clone(),
                             participant_mask_listener,
                             executor_handle: message.executor_handle.clone(),
-                            timer_handle: self.timer_driver.handle(),
                         },
                     );
                 }
@@ -1492,7 +1676,6 @@ impl MailHandler<ProcessMetatrafficRtpsMessage> for DomainParticipantActor {
                             participant: message.participant.clone(),
                             participant_mask_listener,
                             executor_handle: message.executor_handle.clone(),
-                            timer_handle: self.timer_driver.handle(),
                         },
                     );
                 }
@@ -1505,11 +1688,20 @@ impl MailHandler<ProcessMetatrafficRtpsMessage> for DomainParticipantActor {
                     );
                 }
                 RtpsSubmessageReadKind::Heartbeat(heartbeat_submessage) => {
+                    let participant_mask_listener = (
+                        self.participant_listener_thread
+                            .as_ref()
+                            .map(|l| l.sender().clone()),
+                        self.status_kind.clone(),
+                    );
                     self.builtin_subscriber.send_actor_mail(
                         subscriber_actor::ProcessHeartbeatSubmessage {
                             heartbeat_submessage,
                             source_guid_prefix: message_receiver.source_guid_prefix(),
                             message_sender_actor: self.message_sender_actor.address(),
+                            subscriber_address: self.builtin_subscriber.address(),
+                            participant: message.participant.clone(),
+                            participant_mask_listener,
                         },
                     );
                 }
@@ -1518,6 +1710,7 @@ impl MailHandler<ProcessMetatrafficRtpsMessage> for DomainParticipantActor {
                         subscriber_actor::ProcessHeartbeatFragSubmessage {
                             heartbeat_frag_submessage,
                             source_guid_prefix: message_receiver.source_guid_prefix(),
+                            message_sender_actor: self.message_sender_actor.address(),
                         },
                     );
                 }
@@ -1526,6 +1719,9 @@ impl MailHandler<ProcessMetatrafficRtpsMessage> for DomainParticipantActor {
                         publisher_actor::ProcessAckNackSubmessage {
                             acknack_submessage,
                             source_guid_prefix: message_receiver.source_guid_prefix(),
+                            reply_locator_list: message_receiver
+                                .unicast_reply_locator_list()
+                                .to_vec(),
                             message_sender_actor: self.message_sender_actor.address(),
                         },
                     );
@@ -1565,8 +1761,10 @@ impl MailHandler<ProcessUserDefinedRtpsMessage> for DomainParticipantActor {
         &mut self,
         message: ProcessUserDefinedRtpsMessage,
     ) -> <ProcessUserDefinedRtpsMessage as Mail>::Result {
+        self.on_message_received(&message.rtps_message);
         let reception_timestamp = self.get_current_time().into();
-        let mut message_receiver = MessageReceiver::new(message.rtps_message);
+        let mut message_receiver =
+            MessageReceiver::new(message.rtps_message, self.rtps_participant.guid().prefix());
         while let Some(submessage) = message_receiver.next() {
             match submessage {
                 RtpsSubmessageReadKind::Data(data_submessage) => {
@@ -1588,7 +1786,6 @@ impl MailHandler<ProcessUserDefinedRtpsMessage> for DomainParticipantActor {
                                 participant: message.participant.clone(),
                                 participant_mask_listener,
                                 executor_handle: message.executor_handle.clone(),
-                                timer_handle: self.timer_driver.handle(),
                             },
                         );
                     }
@@ -1612,7 +1809,6 @@ impl MailHandler<ProcessUserDefinedRtpsMessage> for DomainParticipantActor {
                                 participant: message.participant.clone(),
                                 participant_mask_listener,
                                 executor_handle: message.executor_handle.clone(),
-                                timer_handle: self.timer_driver.handle(),
                             },
                         );
                     }
@@ -1631,11 +1827,20 @@ impl MailHandler<ProcessUserDefinedRtpsMessage> for DomainParticipantActor {
                 RtpsSubmessageReadKind::Heartbeat(heartbeat_submessage) => {
                     for user_defined_subscriber_actor in self.user_defined_subscriber_list.values()
                     {
+                        let participant_mask_listener = (
+                            self.participant_listener_thread
+                                .as_ref()
+                                .map(|l| l.sender().clone()),
+                            self.status_kind.clone(),
+                        );
                         user_defined_subscriber_actor.send_actor_mail(
                             subscriber_actor::ProcessHeartbeatSubmessage {
                                 heartbeat_submessage: heartbeat_submessage.clone(),
                                 source_guid_prefix: message_receiver.source_guid_prefix(),
                                 message_sender_actor: self.message_sender_actor.address(),
+                                subscriber_address: user_defined_subscriber_actor.address(),
+                                participant: message.participant.clone(),
+                                participant_mask_listener,
                             },
                         );
                     }
@@ -1647,6 +1852,7 @@ impl MailHandler<ProcessUserDefinedRtpsMessage> for DomainParticipantActor {
                             subscriber_actor::ProcessHeartbeatFragSubmessage {
                                 heartbeat_frag_submessage: heartbeat_frag_submessage.clone(),
                                 source_guid_prefix: message_receiver.source_guid_prefix(),
+                                message_sender_actor: self.message_sender_actor.address(),
                             },
                         );
                     }
@@ -1657,6 +1863,9 @@ impl MailHandler<ProcessUserDefinedRtpsMessage> for DomainParticipantActor {
                             publisher_actor::ProcessAckNackSubmessage {
                                 acknack_submessage: acknack_submessage.clone(),
                                 source_guid_prefix: message_receiver.source_guid_prefix(),
+                                reply_locator_list: message_receiver
+                                    .unicast_reply_locator_list()
+                                    .to_vec(),
                                 message_sender_actor: self.message_sender_actor.address(),
                             },
                         );
@@ -1807,6 +2016,17 @@ impl MailHandler<AddDiscoveredParticipant> for DomainParticipantActor {
             .domain_id()
             .unwrap_or(self.domain_id)
             == self.domain_id;
+        if !is_domain_id_matching {
+            self.stats.domain_isolation_violations += 1;
+            tracing::warn!(
+                domain_id = self.domain_id,
+                discovered_domain_id = ?message
+                    .discovered_participant_data
+                    .participant_proxy()
+                    .domain_id(),
+                "Ignoring announcement of participant from a different domain"
+            );
+        }
         let is_domain_tag_matching = message
             .discovered_participant_data
             .participant_proxy()
@@ -1819,16 +2039,25 @@ impl MailHandler<AddDiscoveredParticipant> for DomainParticipantActor {
                 .key()
                 .value,
         );
+        let lease_duration = *message.discovered_participant_data.lease_duration();
         let is_participant_ignored = self
             .ignored_participants
             .contains(&discovered_participant_handle);
         let is_participant_discovered = self
             .discovered_participant_list
             .contains_key(&discovered_participant_handle);
+        // Announcements of the participant itself sent before its GUID prefix was changed
+        let is_previous_local_participant = message
+            .discovered_participant_data
+            .participant_proxy()
+            .guid_prefix()
+            != self.rtps_participant.guid().prefix()
+            && self.has_local_locators(message.discovered_participant_data.participant_proxy());
         if is_domain_id_matching
             && is_domain_tag_matching
             && !is_participant_ignored
             && !is_participant_discovered
+            && !is_previous_local_participant
         {
             self.add_matched_publications_detector(
                 &message.discovered_participant_data,
@@ -1855,6 +2084,7 @@ impl MailHandler<AddDiscoveredParticipant> for DomainParticipantActor {
                 message.participant.clone(),
             )?;
 
+            self.stats.discovery_events += 1;
             self.discovered_participant_list.insert(
                 InstanceHandle::new(
                     message
@@ -1865,6 +2095,24 @@ impl MailHandler<AddDiscoveredParticipant> for DomainParticipantActor {
                 ),
                 message.discovered_participant_data,
             );
+        } else if is_participant_discovered {
+            // Already discovered participants re-announce themselves when their QoS changes
+            self.discovered_participant_list.insert(
+                discovered_participant_handle,
+                message.discovered_participant_data,
+            );
+        }
+
+        // Every announcement of a discovered participant renews its lease
+        if self
+            .discovered_participant_list
+            .contains_key(&discovered_participant_handle)
+        {
+            self.restart_discovered_participant_lease(
+                discovered_participant_handle,
+                lease_duration,
+                message.participant,
+            );
         }
         Ok(())
     }
@@ -1872,6 +2120,7 @@ impl MailHandler<AddDiscoveredParticipant> for DomainParticipantActor {
 
 pub struct RemoveDiscoveredParticipant {
     pub handle: InstanceHandle,
+    pub participant: DomainParticipantAsync,
 }
 impl Mail for RemoveDiscoveredParticipant {
     type Result = ();
@@ -1881,10 +2130,108 @@ impl MailHandler<RemoveDiscoveredParticipant> for DomainParticipantActor {
         &mut self,
         message: RemoveDiscoveredParticipant,
     ) -> <RemoveDiscoveredParticipant as Mail>::Result {
-        self.discovered_participant_list.remove(&message.handle);
+        self.remove_discovered_participant(message.handle, &message.participant)
     }
 }
 
+pub struct IsGuidPrefixCollision {
+    pub discovered_participant_data: SpdpDiscoveredParticipantData,
+}
+impl Mail for IsGuidPrefixCollision {
+    type Result = bool;
+}
+impl MailHandler<IsGuidPrefixCollision> for DomainParticipantActor {
+    fn handle(
+        &mut self,
+        message: IsGuidPrefixCollision,
+    ) -> <IsGuidPrefixCollision as Mail>::Result {
+        let is_detection_period = self
+            .enable_time
+            .is_some_and(|t| t.elapsed() < GUID_PREFIX_COLLISION_DETECTION_PERIOD);
+        let participant_proxy = message.discovered_participant_data.participant_proxy();
+        // The announcements of the participant itself are also received but those are
+        // sent with the locators of the local participant
+        is_detection_period
+            && participant_proxy.guid_prefix() == self.rtps_participant.guid().prefix()
+            && !self.has_local_locators(participant_proxy)
+    }
+}
+
+pub struct RegenerateGuidPrefix {
+    pub participant: DomainParticipantAsync,
+}
+impl Mail for RegenerateGuidPrefix {
+    type Result = ();
+}
+impl MailHandler<RegenerateGuidPrefix> for DomainParticipantActor {
+    fn handle(&mut self, message: RegenerateGuidPrefix) -> <RegenerateGuidPrefix as Mail>::Result {
+        // All the matches were made while using the old GUID prefix so they are removed and
+        // done again once the participant announces itself with the new one
+        let discovered_participant_handle_list: Vec<_> =
+            self.discovered_participant_list.keys().cloned().collect();
+        for discovered_participant_handle in discovered_participant_handle_list {
+            self.remove_discovered_participant(discovered_participant_handle, &message.participant);
+        }
+
+        let guid_prefix = create_random_guid_prefix(self.rtps_participant.guid().prefix());
+        warn!(
+            "GUID prefix collision detected. Changing participant GUID prefix to {:?}",
+            guid_prefix
+        );
+        self.rtps_participant.set_guid_prefix(guid_prefix);
+        self.message_sender_actor
+            .send_actor_mail(message_sender_actor::SetGuidPrefix { guid_prefix });
+        for publisher in std::iter::once(&self.builtin_publisher)
+            .chain(self.user_defined_publisher_list.values())
+        {
+            publisher.send_actor_mail(publisher_actor::SetGuidPrefix { guid_prefix });
+        }
+        for subscriber in std::iter::once(&self.builtin_subscriber)
+            .chain(self.user_defined_subscriber_list.values())
+        {
+            subscriber.send_actor_mail(subscriber_actor::SetGuidPrefix { guid_prefix });
+        }
+        for (topic, _) in self.topic_list.values() {
+            topic.send_actor_mail(topic_actor::SetGuidPrefix { guid_prefix });
+        }
+
+        // The entities are indexed by their handle which is derived from their GUID
+        self.user_defined_publisher_list = self
+            .user_defined_publisher_list
+            .drain()
+            .map(|(handle, publisher)| (replace_guid_prefix(handle, guid_prefix), publisher))
+            .collect();
+        self.user_defined_subscriber_list = self
+            .user_defined_subscriber_list
+            .drain()
+            .map(|(handle, subscriber)| (replace_guid_prefix(handle, guid_prefix), subscriber))
+            .collect();
+    }
+}
+
+fn replace_guid_prefix(handle: InstanceHandle, guid_prefix: GuidPrefix) -> InstanceHandle {
+    let entity_id = Guid::from(<[u8; 16]>::from(handle)).entity_id();
+    InstanceHandle::new(Guid::new(guid_prefix, entity_id).into())
+}
+
+fn create_random_guid_prefix(guid_prefix: GuidPrefix) -> GuidPrefix {
+    // The hasher of the standard library is randomly seeded so it is used as a source
+    // of random bytes
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    let random_bytes = hasher.finish().to_ne_bytes();
+
+    // The host ID is kept and the app ID and instance ID are replaced by the random bytes
+    let mut new_guid_prefix = guid_prefix;
+    new_guid_prefix[4..].copy_from_slice(&random_bytes);
+    new_guid_prefix
+}
+
 pub struct AddMatchedWriter {
     pub discovered_writer_data: DiscoveredWriterData,
     pub participant: DomainParticipantAsync,
@@ -1913,6 +2260,7 @@ impl MailHandler<AddMatchedWriter> for DomainParticipantActor {
                 .value,
         ));
         if !is_publication_ignored && !is_participant_ignored {
+            self.stats.discovery_events += 1;
             if let Some(discovered_participant_data) =
                 self.discovered_participant_list.get(&InstanceHandle::new(
                     Guid::new(
@@ -2030,6 +2378,7 @@ impl MailHandler<AddMatchedWriter> for DomainParticipantActor {
                 );
                 self.discovered_topic_list
                     .insert(topic_instance_handle, writer_topic);
+                self.notify_topic_waiters();
             }
         }
         Ok(())
@@ -2045,6 +2394,7 @@ impl Mail for RemoveMatchedWriter {
 }
 impl MailHandler<RemoveMatchedWriter> for DomainParticipantActor {
     fn handle(&mut self, message: RemoveMatchedWriter) -> <RemoveMatchedWriter as Mail>::Result {
+        self.stats.discovery_events += 1;
         for subscriber in self.user_defined_subscriber_list.values() {
             let subscriber_address = subscriber.address();
             let participant_mask_listener = (
@@ -2092,6 +2442,7 @@ impl MailHandler<AddMatchedReader> for DomainParticipantActor {
                 .value,
         ));
         if !is_subscription_ignored && !is_participant_ignored {
+            self.stats.discovery_events += 1;
             if let Some(discovered_participant_data) =
                 self.discovered_participant_list.get(&InstanceHandle::new(
                     Guid::new(
@@ -2131,6 +2482,7 @@ impl MailHandler<AddMatchedReader> for DomainParticipantActor {
                         participant: message.participant.clone(),
                         participant_mask_listener,
                         message_sender_actor: self.message_sender_actor.address(),
+                        now: self.get_current_time(),
                     });
                 }
 
@@ -2208,6 +2560,7 @@ impl MailHandler<AddMatchedReader> for DomainParticipantActor {
                 );
                 self.discovered_topic_list
                     .insert(topic_instance_handle, reader_topic);
+                self.notify_topic_waiters();
             }
         }
         Ok(())
@@ -2223,6 +2576,7 @@ impl Mail for RemoveMatchedReader {
 }
 impl MailHandler<RemoveMatchedReader> for DomainParticipantActor {
     fn handle(&mut self, message: RemoveMatchedReader) -> <RemoveMatchedReader as Mail>::Result {
+        self.stats.discovery_events += 1;
         for publisher in self.user_defined_publisher_list.values() {
             let publisher_address = publisher.address();
             let participant_mask_listener = (
@@ -2259,6 +2613,7 @@ impl MailHandler<AddMatchedTopic> for DomainParticipantActor {
         );
         let is_topic_ignored = self.ignored_topic_list.contains(&handle);
         if !is_topic_ignored {
+            self.stats.discovery_events += 1;
             for (topic, _) in self.topic_list.values() {
                 topic.send_actor_mail(topic_actor::ProcessDiscoveredTopic {
                     discovered_topic_data: message.discovered_topic_data.clone(),
@@ -2271,6 +2626,7 @@ impl MailHandler<AddMatchedTopic> for DomainParticipantActor {
                     .topic_builtin_topic_data()
                     .clone(),
             );
+            self.notify_topic_waiters();
         }
     }
 }
@@ -2362,6 +2718,7 @@ impl DomainParticipantActor {
                     participant,
                     participant_mask_listener,
                     message_sender_actor: self.message_sender_actor.address(),
+                    now: self.get_current_time(),
                 });
         }
         Ok(())
@@ -2490,6 +2847,7 @@ impl DomainParticipantActor {
                         self.status_kind.clone(),
                     ),
                     message_sender_actor: self.message_sender_actor.address(),
+                    now: self.get_current_time(),
                 });
         }
         Ok(())
@@ -2619,6 +2977,7 @@ impl DomainParticipantActor {
                         self.status_kind.clone(),
                     ),
                     message_sender_actor: self.message_sender_actor.address(),
+                    now: self.get_current_time(),
                 });
         }
         Ok(())
@@ -2718,14 +3077,26 @@ async fn process_spdp_participant_discovery(participant: &DomainParticipantAsync
                         if let Ok(discovered_participant_data) =
                             discovered_participant_sample.data()
                         {
-                            participant
+                            if participant
                                 .participant_address()
-                                .send_actor_mail(AddDiscoveredParticipant {
-                                    discovered_participant_data,
-                                    participant: participant.clone(),
+                                .send_actor_mail(IsGuidPrefixCollision {
+                                    discovered_participant_data: discovered_participant_data
+                                        .clone(),
                                 })?
                                 .receive_reply()
-                                .await?;
+                                .await
+                            {
+                                participant.regenerate_guid_prefix().await?;
+                            } else {
+                                participant
+                                    .participant_address()
+                                    .send_actor_mail(AddDiscoveredParticipant {
+                                        discovered_participant_data,
+                                        participant: participant.clone(),
+                                    })?
+                                    .receive_reply()
+                                    .await?;
+                            }
                         }
                     }
                     InstanceStateKind::NotAliveDisposed | InstanceStateKind::NotAliveNoWriters => {
@@ -2733,6 +3104,7 @@ async fn process_spdp_participant_discovery(participant: &DomainParticipantAsync
                             .participant_address()
                             .send_actor_mail(RemoveDiscoveredParticipant {
                                 handle: discovered_participant_sample.sample_info().instance_handle,
+                                participant: participant.clone(),
                             })?
                             .receive_reply()
                             .await;
@@ -2779,7 +3151,7 @@ async fn process_sedp_publications_discovery(
                             e
                         ),
                     },
-                    InstanceStateKind::NotAliveDisposed => {
+                    InstanceStateKind::NotAliveDisposed | InstanceStateKind::NotAliveNoWriters => {
                         participant
                             .participant_address()
                             .send_actor_mail(RemoveMatchedWriter {
@@ -2789,9 +3161,6 @@ async fn process_sedp_publications_discovery(
                                 participant: participant.clone(),
                             })?;
                     }
-                    InstanceStateKind::NotAliveNoWriters => {
-                        todo!()
-                    }
                 }
             }
         }
@@ -2833,7 +3202,7 @@ async fn process_sedp_subscriptions_discovery(
                             e
                         ),
                     },
-                    InstanceStateKind::NotAliveDisposed => {
+                    InstanceStateKind::NotAliveDisposed | InstanceStateKind::NotAliveNoWriters => {
                         participant
                             .participant_address()
                             .send_actor_mail(RemoveMatchedReader {
@@ -2843,9 +3212,6 @@ async fn process_sedp_subscriptions_discovery(
                                 participant: participant.clone(),
                             })?;
                     }
-                    InstanceStateKind::NotAliveNoWriters => {
-                        todo!()
-                    }
                 }
             }
         }
@@ -6,8 +6,10 @@ use super::{
     status_condition_actor::StatusConditionActor,
     topic_actor::TopicActor,
 };
+#[cfg(feature = "xml-profiles")]
+use crate::infrastructure::qos_profile::QosProfile;
 use crate::{
-    configuration::DustDdsConfiguration,
+    configuration::{DustDdsConfiguration, TransportKind},
     data_representation_builtin_endpoints::{
         discovered_reader_data::{DiscoveredReaderData, DCPS_SUBSCRIPTION},
         discovered_topic_data::{DiscoveredTopicData, DCPS_TOPIC},
@@ -22,6 +24,7 @@ use crate::{
     implementation::{
         actor::{Actor, ActorAddress, Mail, MailHandler},
         actors::domain_participant_actor::DomainParticipantActor,
+        rtps_tcp_psm::{read_frame, tcp_v4_locator},
         runtime::{
             executor::{Executor, ExecutorHandle},
             timer::TimerDriver,
@@ -29,7 +32,6 @@ use crate::{
     },
     infrastructure::{
         error::{DdsError, DdsResult},
-        instance::InstanceHandle,
         qos::{
             DataReaderQos, DataWriterQos, DomainParticipantFactoryQos, DomainParticipantQos,
             QosKind, TopicQos,
@@ -77,12 +79,21 @@ use tracing::{info, warn};
 
 const MAX_DATAGRAM_SIZE: usize = 65507;
 
+enum UserDefinedDataReceiver {
+    Udp(std::net::UdpSocket),
+    Tcp(std::net::TcpListener),
+}
+
 #[derive(Default)]
 pub struct DomainParticipantFactoryActor {
-    domain_participant_list: HashMap<InstanceHandle, Actor<DomainParticipantActor>>,
+    // Participants are not keyed by their instance handle since the handle changes
+    // when the GUID prefix of the participant is regenerated.
+    domain_participant_list: Vec<Actor<DomainParticipantActor>>,
     qos: DomainParticipantFactoryQos,
     default_participant_qos: DomainParticipantQos,
     configuration: DustDdsConfiguration,
+    #[cfg(feature = "xml-profiles")]
+    qos_profiles: HashMap<String, QosProfile>,
 }
 
 impl DomainParticipantFactoryActor {
@@ -263,6 +274,7 @@ impl DomainParticipantFactoryActor {
             spdp_reader_qos,
             None,
             vec![],
+            None,
             handle,
         );
 
@@ -278,6 +290,7 @@ impl DomainParticipantFactoryActor {
             sedp_data_reader_qos(),
             None,
             vec![],
+            None,
             handle,
         );
 
@@ -293,6 +306,7 @@ impl DomainParticipantFactoryActor {
             sedp_data_reader_qos(),
             None,
             vec![],
+            None,
             handle,
         );
 
@@ -308,6 +322,7 @@ impl DomainParticipantFactoryActor {
             sedp_data_reader_qos(),
             None,
             vec![],
+            None,
             handle,
         );
 
@@ -356,11 +371,27 @@ impl DomainParticipantFactoryActor {
             handle,
         );
 
-        let spdp_discovery_locator_list = [Locator::new(
+        let mut spdp_discovery_locator_list = vec![Locator::new(
             LOCATOR_KIND_UDP_V4,
             port_builtin_multicast(domain_id) as u32,
             DEFAULT_MULTICAST_LOCATOR_ADDRESS,
         )];
+        for initial_peer in self.configuration.initial_peers() {
+            match initial_peer {
+                SocketAddr::V4(peer) => {
+                    let mut address = [0; 16];
+                    address[12..].copy_from_slice(&peer.ip().octets());
+                    spdp_discovery_locator_list.push(Locator::new(
+                        LOCATOR_KIND_UDP_V4,
+                        peer.port() as u32,
+                        address,
+                    ));
+                }
+                SocketAddr::V6(_) => {
+                    warn!("Ignoring unsupported IPv6 initial peer {:?}", initial_peer)
+                }
+            }
+        }
 
         for reader_locator in spdp_discovery_locator_list
             .iter()
@@ -453,9 +484,19 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
 
         let guid_prefix = self.create_new_guid_prefix();
 
-        let socket = std::net::UdpSocket::bind("0.0.0.0:0000")?;
-        let message_sender_actor =
-            MessageSenderActor::new(socket, PROTOCOLVERSION, VENDOR_ID_S2E, guid_prefix);
+        // Unicast sockets are bound to the selected interface so that the traffic does not egress
+        // on a different one in multi-homed hosts
+        let interface_bind_address =
+            get_interface_bind_address(self.configuration.interface_name());
+
+        let socket = std::net::UdpSocket::bind(SocketAddr::from((interface_bind_address, 0)))?;
+        let message_sender_actor = MessageSenderActor::new(
+            socket,
+            self.configuration.interface_name().cloned(),
+            PROTOCOLVERSION,
+            VENDOR_ID_S2E,
+            guid_prefix,
+        );
 
         let mut rtps_participant = RtpsParticipant::new(
             guid_prefix,
@@ -466,8 +507,6 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
             PROTOCOLVERSION,
             VENDOR_ID_S2E,
         );
-        let participant_guid = rtps_participant.guid();
-
         let topic_list = self.create_builtin_topics(guid_prefix, &executor.handle());
         let builtin_data_writer_list = self.create_builtin_writers(
             guid_prefix,
@@ -495,26 +534,57 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
                     Addr::V4(_) => true,
                     _ => false,
                 })
+            })
+            .filter(move |a| {
+                interface_bind_address.is_unspecified()
+                    || a.ip() == IpAddr::V4(interface_bind_address)
             });
 
-        let default_unicast_socket =
-            socket2::Socket::new(socket2::Domain::IPV4, socket2::Type::DGRAM, None)?;
-        default_unicast_socket.bind(&SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)).into())?;
-        default_unicast_socket.set_nonblocking(false)?;
-        if let Some(buffer_size) = self.configuration.udp_receive_buffer_size() {
-            default_unicast_socket.set_recv_buffer_size(buffer_size)?;
-        }
-        let mut default_unicast_socket = std::net::UdpSocket::from(default_unicast_socket);
-        let user_defined_unicast_port = default_unicast_socket.local_addr()?.port().into();
-        let default_unicast_locator_list: Vec<Locator> = interface_address_list
-            .clone()
-            .map(|a| Locator::from_ip_and_port(&a, user_defined_unicast_port))
-            .collect();
+        let (user_defined_data_receiver, default_unicast_locator_list) =
+            match self.configuration.transport() {
+                TransportKind::Udp => {
+                    let default_unicast_socket =
+                        socket2::Socket::new(socket2::Domain::IPV4, socket2::Type::DGRAM, None)?;
+                    default_unicast_socket
+                        .bind(&SocketAddr::from((interface_bind_address, 0)).into())?;
+                    default_unicast_socket.set_nonblocking(false)?;
+                    if let Some(buffer_size) = self.configuration.udp_receive_buffer_size() {
+                        default_unicast_socket.set_recv_buffer_size(buffer_size)?;
+                    }
+                    let default_unicast_socket = std::net::UdpSocket::from(default_unicast_socket);
+                    let user_defined_unicast_port =
+                        default_unicast_socket.local_addr()?.port().into();
+                    let default_unicast_locator_list: Vec<Locator> = interface_address_list
+                        .clone()
+                        .map(|a| Locator::from_ip_and_port(&a, user_defined_unicast_port))
+                        .collect();
+                    (
+                        UserDefinedDataReceiver::Udp(default_unicast_socket),
+                        default_unicast_locator_list,
+                    )
+                }
+                TransportKind::Tcp => {
+                    let listener =
+                        std::net::TcpListener::bind(SocketAddr::from((interface_bind_address, 0)))?;
+                    let user_defined_unicast_port = listener.local_addr()?.port();
+                    let default_unicast_locator_list: Vec<Locator> = interface_address_list
+                        .clone()
+                        .filter_map(|a| match a {
+                            Addr::V4(v4) => Some(tcp_v4_locator(v4.ip, user_defined_unicast_port)),
+                            _ => None,
+                        })
+                        .collect();
+                    (
+                        UserDefinedDataReceiver::Tcp(listener),
+                        default_unicast_locator_list,
+                    )
+                }
+            };
         rtps_participant.set_default_unicast_locator_list(default_unicast_locator_list);
 
         // Open socket for unicast metatraffic data
         let mut metatrafic_unicast_socket =
-            std::net::UdpSocket::bind(SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)))?;
+            std::net::UdpSocket::bind(SocketAddr::from((interface_bind_address, 0)))?;
         metatrafic_unicast_socket.set_nonblocking(false)?;
         let metattrafic_unicast_locator_port =
             metatrafic_unicast_socket.local_addr()?.port().into();
@@ -545,6 +615,7 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
             message.domain_id,
             self.configuration.domain_tag().to_string(),
             domain_participant_qos,
+            self.configuration.participant_lease_duration().into(),
             self.configuration.fragment_size(),
             message.listener,
             message.status_kind,
@@ -569,23 +640,57 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
         let participant_address_clone = participant_actor.address();
         let participant_clone = participant.clone();
 
-        std::thread::spawn(move || {
-            let mut buf = Box::new([0; MAX_DATAGRAM_SIZE]);
-            loop {
-                if let Ok(message) = read_message(&mut default_unicast_socket, buf.as_mut_slice()) {
-                    let r = participant_address_clone.send_actor_mail(
-                        domain_participant_actor::ProcessUserDefinedRtpsMessage {
-                            rtps_message: message,
-                            participant: participant_clone.clone(),
-                            executor_handle: participant_clone.executor_handle().clone(),
-                        },
-                    );
-                    if r.is_err() {
-                        break;
+        match user_defined_data_receiver {
+            UserDefinedDataReceiver::Udp(mut default_unicast_socket) => {
+                std::thread::spawn(move || {
+                    let mut buf = Box::new([0; MAX_DATAGRAM_SIZE]);
+                    loop {
+                        if let Ok(message) =
+                            read_message(&mut default_unicast_socket, buf.as_mut_slice())
+                        {
+                            let r = participant_address_clone.send_actor_mail(
+                                domain_participant_actor::ProcessUserDefinedRtpsMessage {
+                                    rtps_message: message,
+                                    participant: participant_clone.clone(),
+                                    executor_handle: participant_clone.executor_handle().clone(),
+                                },
+                            );
+                            if r.is_err() {
+                                break;
+                            }
+                        }
                     }
-                }
+                });
             }
-        });
+            UserDefinedDataReceiver::Tcp(listener) => {
+                std::thread::spawn(move || {
+                    for mut stream in listener.incoming().flatten() {
+                        let participant_address_clone = participant_address_clone.clone();
+                        let participant_clone = participant_clone.clone();
+                        // Each connection is read on its own thread until the remote closes it
+                        std::thread::spawn(move || {
+                            let mut buf = Box::new([0; MAX_DATAGRAM_SIZE]);
+                            while let Ok(length) = read_frame(&mut stream, buf.as_mut_slice()) {
+                                if let Ok(message) = RtpsMessageRead::try_from(&buf[0..length]) {
+                                    let r = participant_address_clone.send_actor_mail(
+                                        domain_participant_actor::ProcessUserDefinedRtpsMessage {
+                                            rtps_message: message,
+                                            participant: participant_clone.clone(),
+                                            executor_handle: participant_clone
+                                                .executor_handle()
+                                                .clone(),
+                                        },
+                                    );
+                                    if r.is_err() {
+                                        break;
+                                    }
+                                }
+                            }
+                        });
+                    }
+                });
+            }
+        }
 
         // Start the regular participant announcement task
         let participant_clone = participant.clone();
@@ -654,24 +759,25 @@ impl MailHandler<CreateParticipant> for DomainParticipantFactoryActor {
         });
 
         let participant_address = participant_actor.address();
-        self.domain_participant_list.insert(
-            InstanceHandle::new(participant_guid.into()),
-            participant_actor,
-        );
+        self.domain_participant_list.push(participant_actor);
         Ok(participant_address)
     }
 }
 
 pub struct DeleteParticipant {
-    pub handle: InstanceHandle,
+    pub participant_address: ActorAddress<DomainParticipantActor>,
 }
 impl Mail for DeleteParticipant {
     type Result = DdsResult<Actor<DomainParticipantActor>>;
 }
 impl MailHandler<DeleteParticipant> for DomainParticipantFactoryActor {
     fn handle(&mut self, message: DeleteParticipant) -> <DeleteParticipant as Mail>::Result {
-        self.domain_participant_list
-            .remove(&message.handle)
+        let index = self
+            .domain_participant_list
+            .iter()
+            .position(|p| p.address() == message.participant_address);
+        index
+            .map(|i| self.domain_participant_list.remove(i))
             .ok_or(DdsError::PreconditionNotMet(
                 "Participant can only be deleted from its parent domain participant factory"
                     .to_string(),
@@ -686,7 +792,7 @@ impl Mail for GetParticipantList {
 impl MailHandler<GetParticipantList> for DomainParticipantFactoryActor {
     fn handle(&mut self, _: GetParticipantList) -> <GetParticipantList as Mail>::Result {
         self.domain_participant_list
-            .values()
+            .iter()
             .map(|a| a.address())
             .collect()
     }
@@ -778,6 +884,44 @@ impl MailHandler<GetConfiguration> for DomainParticipantFactoryActor {
     }
 }
 
+#[cfg(feature = "xml-profiles")]
+pub struct AddQosProfiles {
+    pub qos_profiles: HashMap<String, QosProfile>,
+}
+#[cfg(feature = "xml-profiles")]
+impl Mail for AddQosProfiles {
+    type Result = ();
+}
+#[cfg(feature = "xml-profiles")]
+impl MailHandler<AddQosProfiles> for DomainParticipantFactoryActor {
+    fn handle(&mut self, message: AddQosProfiles) -> <AddQosProfiles as Mail>::Result {
+        self.qos_profiles.extend(message.qos_profiles);
+    }
+}
+
+#[cfg(feature = "xml-profiles")]
+pub struct GetQosProfile {
+    pub profile_name: String,
+}
+#[cfg(feature = "xml-profiles")]
+impl Mail for GetQosProfile {
+    type Result = DdsResult<QosProfile>;
+}
+#[cfg(feature = "xml-profiles")]
+impl MailHandler<GetQosProfile> for DomainParticipantFactoryActor {
+    fn handle(&mut self, message: GetQosProfile) -> <GetQosProfile as Mail>::Result {
+        self.qos_profiles
+            .get(&message.profile_name)
+            .cloned()
+            .ok_or_else(|| {
+                DdsError::PreconditionNotMet(format!(
+                    "QoS profile {} not found",
+                    message.profile_name
+                ))
+            })
+    }
+}
+
 type LocatorAddress = [u8; 16];
 // As of 9.6.1.4.1  Default multicast address
 const DEFAULT_MULTICAST_LOCATOR_ADDRESS: LocatorAddress =
@@ -796,6 +940,28 @@ fn port_builtin_multicast(domain_id: DomainId) -> u16 {
     (PB + DG * domain_id + d0) as u16
 }
 
+fn get_interface_bind_address(interface_name: Option<&String>) -> Ipv4Addr {
+    if let Some(if_name) = interface_name {
+        let interface_address = NetworkInterface::show()
+            .expect("Could not scan interfaces")
+            .into_iter()
+            .filter(|x| &x.name == if_name)
+            .flat_map(|i| i.addr)
+            .find_map(|a| match a {
+                Addr::V4(v4) => Some(v4.ip),
+                Addr::V6(_) => None,
+            });
+        if let Some(address) = interface_address {
+            return address;
+        }
+        warn!(
+            "Interface {} has no IPv4 address. Binding to all interfaces instead",
+            if_name
+        );
+    }
+    Ipv4Addr::UNSPECIFIED
+}
+
 fn get_multicast_socket(
     multicast_address: LocatorAddress,
     port: u16,
@@ -966,3 +1132,32 @@ pub fn sedp_data_writer_qos() -> DataWriterQos {
         ..Default::default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interface_bind_address_defaults_to_all_interfaces() {
+        assert_eq!(get_interface_bind_address(None), Ipv4Addr::UNSPECIFIED);
+        assert_eq!(
+            get_interface_bind_address(Some(&"non-existing-interface".to_string())),
+            Ipv4Addr::UNSPECIFIED
+        );
+    }
+
+    #[test]
+    fn interface_bind_address_is_resolved_from_interface_name() {
+        let loopback_interface = NetworkInterface::show().unwrap().into_iter().find(|i| {
+            i.addr
+                .iter()
+                .any(|a| a.ip() == IpAddr::V4(Ipv4Addr::LOCALHOST))
+        });
+        if let Some(loopback_interface) = loopback_interface {
+            assert_eq!(
+                get_interface_bind_address(Some(&loopback_interface.name)),
+                Ipv4Addr::LOCALHOST
+            );
+        }
+    }
+}
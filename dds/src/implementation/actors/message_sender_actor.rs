@@ -1,36 +1,121 @@
 use network_interface::{Addr, NetworkInterface, NetworkInterfaceConfig};
-use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, ToSocketAddrs};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, TcpStream, ToSocketAddrs},
+};
 
 use crate::{
-    implementation::actor::{Mail, MailHandler},
+    domain::domain_participant::DomainParticipantStats,
+    implementation::{
+        actor::{Mail, MailHandler},
+        rtps_tcp_psm::{tcp_locator_socket_address, write_frame},
+    },
     rtps::{
         messages::overall_structure::{RtpsMessageHeader, RtpsMessageWrite, Submessage},
         types::{
-            GuidPrefix, Locator, ProtocolVersion, VendorId, LOCATOR_KIND_UDP_V4,
-            LOCATOR_KIND_UDP_V6,
+            GuidPrefix, Locator, ProtocolVersion, VendorId, LOCATOR_KIND_TCP_V4,
+            LOCATOR_KIND_UDP_V4, LOCATOR_KIND_UDP_V6,
         },
     },
 };
 
+const TCP_CONNECT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(1);
+const TCP_WRITE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(1);
+const TCP_RECONNECT_INITIAL_BACKOFF: std::time::Duration = std::time::Duration::from_secs(1);
+const TCP_RECONNECT_MAX_BACKOFF: std::time::Duration = std::time::Duration::from_secs(30);
+
+// The sender is shared by all the traffic of the participant so a destination which can not
+// be reached is only retried after a backoff which doubles with every failed attempt
+struct TcpReconnectBackoff {
+    next_attempt: std::time::Instant,
+    backoff: std::time::Duration,
+}
+
 pub struct MessageSenderActor {
     socket: std::net::UdpSocket,
+    tcp_connections: HashMap<SocketAddr, TcpStream>,
+    tcp_reconnect_backoff: HashMap<SocketAddr, TcpReconnectBackoff>,
+    interface_name: Option<String>,
     protocol_version: ProtocolVersion,
     vendor_id: VendorId,
     guid_prefix: GuidPrefix,
+    stats: DomainParticipantStats,
 }
 
 impl MessageSenderActor {
     pub fn new(
         socket: std::net::UdpSocket,
+        interface_name: Option<String>,
         protocol_version: ProtocolVersion,
         vendor_id: VendorId,
         guid_prefix: GuidPrefix,
     ) -> Self {
         Self {
             socket,
+            tcp_connections: HashMap::new(),
+            tcp_reconnect_backoff: HashMap::new(),
+            interface_name,
             protocol_version,
             vendor_id,
             guid_prefix,
+            stats: DomainParticipantStats::default(),
+        }
+    }
+
+    fn on_message_sent(&mut self, bytes: usize, heartbeats: usize) {
+        self.stats.messages_sent += 1;
+        self.stats.bytes_sent += bytes as u64;
+        self.stats.heartbeats_sent += heartbeats as u64;
+    }
+
+    fn on_tcp_connection_failed(&mut self, destination: SocketAddr) {
+        let backoff = match self.tcp_reconnect_backoff.get(&destination) {
+            Some(b) => (b.backoff * 2).min(TCP_RECONNECT_MAX_BACKOFF),
+            None => TCP_RECONNECT_INITIAL_BACKOFF,
+        };
+        self.tcp_reconnect_backoff.insert(
+            destination,
+            TcpReconnectBackoff {
+                next_attempt: std::time::Instant::now() + backoff,
+                backoff,
+            },
+        );
+    }
+
+    fn send_tcp(&mut self, buf: &[u8], destination: SocketAddr) -> bool {
+        let stream = match self.tcp_connections.entry(destination) {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => {
+                if let Some(b) = self.tcp_reconnect_backoff.get(&destination) {
+                    if std::time::Instant::now() < b.next_attempt {
+                        return false;
+                    }
+                }
+                match TcpStream::connect_timeout(&destination, TCP_CONNECT_TIMEOUT) {
+                    Ok(stream) => {
+                        stream.set_nodelay(true).ok();
+                        // A peer which stops reading must not block the sender indefinitely
+                        stream.set_write_timeout(Some(TCP_WRITE_TIMEOUT)).ok();
+                        self.tcp_reconnect_backoff.remove(&destination);
+                        e.insert(stream)
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to connect to {:?}. Error {:?}", destination, e);
+                        self.on_tcp_connection_failed(destination);
+                        return false;
+                    }
+                }
+            }
+        };
+
+        if let Err(e) = write_frame(stream, buf) {
+            // The connection is re-established on a later message to this destination
+            tracing::warn!("Failed to send message to {:?}. Error {:?}", destination, e);
+            self.tcp_connections.remove(&destination);
+            self.on_tcp_connection_failed(destination);
+            false
+        } else {
+            true
         }
     }
 }
@@ -48,14 +133,32 @@ impl MailHandler<WriteMessage> for MessageSenderActor {
             RtpsMessageHeader::new(self.protocol_version, self.vendor_id, self.guid_prefix);
         let rtpmessage = RtpsMessageWrite::new(&header, &message.submessages);
         let buf = rtpmessage.buffer();
+        let heartbeats = message
+            .submessages
+            .iter()
+            .filter(|s| s.is_heartbeat())
+            .count();
 
         for destination_locator in message.destination_locator_list {
-            if UdpLocator(destination_locator).is_multicast() {
+            if destination_locator.kind() == LOCATOR_KIND_TCP_V4 {
+                if let Some(destination) = tcp_locator_socket_address(&destination_locator) {
+                    if self.send_tcp(buf, destination) {
+                        self.on_message_sent(buf.len(), heartbeats);
+                    }
+                }
+            } else if UdpLocator(destination_locator).is_multicast() {
                 let socket2: socket2::Socket = self.socket.try_clone().unwrap().into();
                 let interface_addresses = NetworkInterface::show();
                 let interface_addresses: Vec<_> = interface_addresses
                     .expect("Could not scan interfaces")
                     .into_iter()
+                    .filter(|i| {
+                        if let Some(if_name) = &self.interface_name {
+                            &i.name == if_name
+                        } else {
+                            true
+                        }
+                    })
                     .flat_map(|i| {
                         i.addr.into_iter().filter_map(|a| match a {
                             Addr::V4(v4) => Some(v4.ip),
@@ -64,21 +167,48 @@ impl MailHandler<WriteMessage> for MessageSenderActor {
                     })
                     .collect();
                 for address in interface_addresses {
-                    if socket2.set_multicast_if_v4(&address).is_ok() {
-                        self.socket
+                    if socket2.set_multicast_if_v4(&address).is_ok()
+                        && self
+                            .socket
                             .send_to(buf, UdpLocator(destination_locator))
-                            .ok();
+                            .is_ok()
+                    {
+                        self.on_message_sent(buf.len(), heartbeats);
                     }
                 }
-            } else {
-                self.socket
-                    .send_to(buf, UdpLocator(destination_locator))
-                    .ok();
+            } else if self
+                .socket
+                .send_to(buf, UdpLocator(destination_locator))
+                .is_ok()
+            {
+                self.on_message_sent(buf.len(), heartbeats);
             }
         }
     }
 }
 
+pub struct SetGuidPrefix {
+    pub guid_prefix: GuidPrefix,
+}
+impl Mail for SetGuidPrefix {
+    type Result = ();
+}
+impl MailHandler<SetGuidPrefix> for MessageSenderActor {
+    fn handle(&mut self, message: SetGuidPrefix) -> <SetGuidPrefix as Mail>::Result {
+        self.guid_prefix = message.guid_prefix;
+    }
+}
+
+pub struct GetStats;
+impl Mail for GetStats {
+    type Result = DomainParticipantStats;
+}
+impl MailHandler<GetStats> for MessageSenderActor {
+    fn handle(&mut self, _: GetStats) -> <GetStats as Mail>::Result {
+        self.stats
+    }
+}
+
 struct UdpLocator(Locator);
 
 impl ToSocketAddrs for UdpLocator {
@@ -121,3 +251,62 @@ impl UdpLocator {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rtps::types::{PROTOCOLVERSION, VENDOR_ID_S2E};
+    use std::net::TcpListener;
+
+    fn message_sender_actor() -> MessageSenderActor {
+        MessageSenderActor::new(
+            std::net::UdpSocket::bind("127.0.0.1:0").unwrap(),
+            None,
+            PROTOCOLVERSION,
+            VENDOR_ID_S2E,
+            [1; 12],
+        )
+    }
+
+    #[test]
+    fn unreachable_tcp_destination_is_not_retried_before_backoff() {
+        let mut message_sender_actor = message_sender_actor();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let destination = listener.local_addr().unwrap();
+        drop(listener);
+
+        assert!(!message_sender_actor.send_tcp(&[1, 2, 3], destination));
+
+        // Even once the peer is listening no connection is attempted until the backoff expires
+        let listener = TcpListener::bind(destination).unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let start = std::time::Instant::now();
+        for _ in 0..100 {
+            assert!(!message_sender_actor.send_tcp(&[1, 2, 3], destination));
+        }
+        assert!(start.elapsed() < TCP_CONNECT_TIMEOUT);
+        assert_eq!(
+            listener.accept().unwrap_err().kind(),
+            std::io::ErrorKind::WouldBlock
+        );
+    }
+
+    #[test]
+    fn tcp_destination_which_stops_reading_does_not_block_sender() {
+        let mut message_sender_actor = message_sender_actor();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let destination = listener.local_addr().unwrap();
+
+        let message = vec![0; 1024 * 1024];
+        let start = std::time::Instant::now();
+        let mut is_send_failed = false;
+        for _ in 0..256 {
+            if !message_sender_actor.send_tcp(&message, destination) {
+                is_send_failed = true;
+                break;
+            }
+        }
+        assert!(is_send_failed);
+        assert!(start.elapsed() < std::time::Duration::from_secs(10));
+    }
+}
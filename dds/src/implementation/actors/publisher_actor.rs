@@ -1,4 +1,4 @@
-use std::{collections::HashMap, thread::JoinHandle};
+use std::{collections::HashMap, sync::Arc, thread::JoinHandle};
 
 use fnmatch_regex::glob_to_regex;
 use tracing::warn;
@@ -11,6 +11,16 @@ use crate::{
     },
     implementation::{
         actor::{Actor, ActorAddress, Mail, MailHandler},
+        data_representation_inline_qos::{
+            parameter_id_values::{PID_COHERENT_SET, PID_GROUP_SEQ_NUM, PID_STATUS_INFO},
+            types::{
+                CoherentSetParameter, GroupSequenceNumberParameter, StatusInfo, STATUS_INFO_ALIVE,
+            },
+        },
+        payload_serializer_deserializer::{
+            cdr_deserializer::ClassicCdrDeserializer, cdr_serializer::ClassicCdrSerializer,
+            endianness::CdrEndianness,
+        },
         runtime::{
             executor::{block_on, ExecutorHandle},
             mpsc::{mpsc_channel, MpscSender},
@@ -20,24 +30,28 @@ use crate::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataWriterQos, PublisherQos, QosKind},
-        qos_policy::PartitionQosPolicy,
+        qos_policy::{PartitionQosPolicy, PresentationQosPolicyAccessScopeKind},
         status::{
             LivelinessLostStatus, OfferedDeadlineMissedStatus, OfferedIncompatibleQosStatus,
             PublicationMatchedStatus, StatusKind,
         },
-        time::Duration,
+        time::{Duration, Time},
     },
     rtps::{
         behavior_types::DURATION_ZERO,
         endpoint::RtpsEndpoint,
         group::RtpsGroup,
-        messages::submessages::{ack_nack::AckNackSubmessage, nack_frag::NackFragSubmessage},
+        messages::{
+            submessage_elements::{Parameter, ParameterList},
+            submessages::{ack_nack::AckNackSubmessage, nack_frag::NackFragSubmessage},
+        },
         types::{
-            EntityId, Guid, GuidPrefix, Locator, TopicKind, USER_DEFINED_WRITER_NO_KEY,
-            USER_DEFINED_WRITER_WITH_KEY,
+            EntityId, Guid, GuidPrefix, Locator, SequenceNumber, TopicKind,
+            USER_DEFINED_WRITER_NO_KEY, USER_DEFINED_WRITER_WITH_KEY,
         },
         writer::RtpsWriter,
     },
+    serialized_payload::cdr::{deserialize::CdrDeserialize, serialize::CdrSerialize},
 };
 
 use super::{
@@ -126,6 +140,11 @@ pub struct PublisherActor {
     publisher_listener_thread: Option<PublisherListenerThread>,
     status_kind: Vec<StatusKind>,
     status_condition: Actor<StatusConditionActor>,
+    publications_suspended: bool,
+    coherent_changes_nesting: u32,
+    coherent_set_start: usize,
+    staged_changes: Vec<data_writer_actor::AddChange>,
+    group_sequence_number: SequenceNumber,
 }
 
 impl PublisherActor {
@@ -152,6 +171,53 @@ impl PublisherActor {
             publisher_listener_thread,
             status_kind,
             status_condition: Actor::spawn(StatusConditionActor::default(), handle),
+            publications_suspended: false,
+            coherent_changes_nesting: 0,
+            coherent_set_start: 0,
+            staged_changes: Vec::new(),
+            group_sequence_number: 0,
+        }
+    }
+
+    fn is_staging_changes(&self) -> bool {
+        self.publications_suspended || self.coherent_changes_nesting > 0
+    }
+
+    // Every change of the coherent set carries the sequence number of the first change of its
+    // writer in the set and the last change of each writer marks the end of the set
+    fn mark_coherent_set(&mut self) {
+        let coherent_set = &mut self.staged_changes[self.coherent_set_start..];
+        let mut writer_sequence_number_range: HashMap<
+            InstanceHandle,
+            (SequenceNumber, SequenceNumber),
+        > = HashMap::new();
+        for add_change in coherent_set.iter() {
+            let sequence_number = add_change.change.sequence_number();
+            writer_sequence_number_range
+                .entry(InstanceHandle::new(add_change.change.writer_guid().into()))
+                .and_modify(|(first, last)| {
+                    *first = (*first).min(sequence_number);
+                    *last = (*last).max(sequence_number);
+                })
+                .or_insert((sequence_number, sequence_number));
+        }
+
+        for add_change in coherent_set.iter_mut() {
+            let (first, last) = writer_sequence_number_range
+                [&InstanceHandle::new(add_change.change.writer_guid().into())];
+            let is_coherent_set_end = add_change.change.sequence_number() == last;
+            let inline_qos =
+                coherent_set_inline_qos(add_change.change.inline_qos(), first, is_coherent_set_end);
+            add_change.change.set_inline_qos(inline_qos);
+        }
+    }
+
+    fn flush_staged_changes(&mut self) {
+        if !self.is_staging_changes() {
+            for add_change in self.staged_changes.drain(..) {
+                let writer_address = add_change.writer_address.clone();
+                writer_address.send_actor_mail(add_change).ok();
+            }
         }
     }
 
@@ -327,6 +393,98 @@ impl MailHandler<IsEnabled> for PublisherActor {
     }
 }
 
+pub struct SuspendPublications;
+impl Mail for SuspendPublications {
+    type Result = ();
+}
+impl MailHandler<SuspendPublications> for PublisherActor {
+    fn handle(&mut self, _: SuspendPublications) -> <SuspendPublications as Mail>::Result {
+        self.publications_suspended = true;
+    }
+}
+
+pub struct ResumePublications;
+impl Mail for ResumePublications {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<ResumePublications> for PublisherActor {
+    fn handle(&mut self, _: ResumePublications) -> <ResumePublications as Mail>::Result {
+        if !self.publications_suspended {
+            return Err(DdsError::PreconditionNotMet(
+                "Publications are not suspended".to_string(),
+            ));
+        }
+        self.publications_suspended = false;
+        self.flush_staged_changes();
+        Ok(())
+    }
+}
+
+pub struct BeginCoherentChanges;
+impl Mail for BeginCoherentChanges {
+    type Result = ();
+}
+impl MailHandler<BeginCoherentChanges> for PublisherActor {
+    fn handle(&mut self, _: BeginCoherentChanges) -> <BeginCoherentChanges as Mail>::Result {
+        if self.coherent_changes_nesting == 0 {
+            self.coherent_set_start = self.staged_changes.len();
+        }
+        self.coherent_changes_nesting += 1;
+    }
+}
+
+pub struct EndCoherentChanges;
+impl Mail for EndCoherentChanges {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<EndCoherentChanges> for PublisherActor {
+    fn handle(&mut self, _: EndCoherentChanges) -> <EndCoherentChanges as Mail>::Result {
+        if self.coherent_changes_nesting == 0 {
+            return Err(DdsError::PreconditionNotMet(
+                "No matching call to begin_coherent_changes".to_string(),
+            ));
+        }
+        self.coherent_changes_nesting -= 1;
+        if self.coherent_changes_nesting == 0 && self.qos.presentation.coherent_access {
+            self.mark_coherent_set();
+        }
+        self.flush_staged_changes();
+        Ok(())
+    }
+}
+
+/// Returns the change back if it is not staged so that it can be added directly to the writer
+pub struct StageChange {
+    pub add_change: data_writer_actor::AddChange,
+}
+impl Mail for StageChange {
+    type Result = Option<data_writer_actor::AddChange>;
+}
+impl MailHandler<StageChange> for PublisherActor {
+    fn handle(&mut self, message: StageChange) -> <StageChange as Mail>::Result {
+        let mut add_change = message.add_change;
+        // With ordered group access the subscribers order the samples of the different
+        // writers of the publisher by the group sequence number
+        if self.qos.presentation.access_scope == PresentationQosPolicyAccessScopeKind::Group
+            && self.qos.presentation.ordered_access
+        {
+            self.group_sequence_number += 1;
+            let inline_qos = group_sequence_number_inline_qos(
+                add_change.change.inline_qos(),
+                self.group_sequence_number,
+            );
+            add_change.change.set_inline_qos(inline_qos);
+        }
+
+        if self.is_staging_changes() {
+            self.staged_changes.push(add_change);
+            None
+        } else {
+            Some(add_change)
+        }
+    }
+}
+
 pub struct IsEmpty;
 impl Mail for IsEmpty {
     type Result = bool;
@@ -405,6 +563,31 @@ impl MailHandler<GetGuid> for PublisherActor {
     }
 }
 
+pub struct SetGuidPrefix {
+    pub guid_prefix: GuidPrefix,
+}
+impl Mail for SetGuidPrefix {
+    type Result = ();
+}
+impl MailHandler<SetGuidPrefix> for PublisherActor {
+    fn handle(&mut self, message: SetGuidPrefix) -> <SetGuidPrefix as Mail>::Result {
+        self.rtps_group.set_guid_prefix(message.guid_prefix);
+        // The data writers are indexed by their handle which is derived from their GUID
+        self.data_writer_list = self
+            .data_writer_list
+            .drain()
+            .map(|(handle, data_writer)| {
+                data_writer.send_actor_mail(data_writer_actor::SetGuidPrefix {
+                    guid_prefix: message.guid_prefix,
+                });
+                let entity_id = Guid::from(<[u8; 16]>::from(handle)).entity_id();
+                let guid = Guid::new(message.guid_prefix, entity_id);
+                (InstanceHandle::new(guid.into()), data_writer)
+            })
+            .collect();
+    }
+}
+
 pub struct GetInstanceHandle;
 impl Mail for GetInstanceHandle {
     type Result = InstanceHandle;
@@ -451,6 +634,7 @@ impl MailHandler<GetDataWriterList> for PublisherActor {
 pub struct ProcessAckNackSubmessage {
     pub acknack_submessage: AckNackSubmessage,
     pub source_guid_prefix: GuidPrefix,
+    pub reply_locator_list: Vec<Locator>,
     pub message_sender_actor: ActorAddress<MessageSenderActor>,
 }
 impl Mail for ProcessAckNackSubmessage {
@@ -465,6 +649,7 @@ impl MailHandler<ProcessAckNackSubmessage> for PublisherActor {
             data_writer_actor.send_actor_mail(data_writer_actor::ProcessAckNackSubmessage {
                 acknack_submessage: message.acknack_submessage.clone(),
                 source_guid_prefix: message.source_guid_prefix,
+                reply_locator_list: message.reply_locator_list.clone(),
                 message_sender_actor: message.message_sender_actor.clone(),
             });
         }
@@ -503,6 +688,7 @@ pub struct AddMatchedReader {
         Vec<StatusKind>,
     ),
     pub message_sender_actor: ActorAddress<MessageSenderActor>,
+    pub now: Time,
 }
 impl Mail for AddMatchedReader {
     type Result = DdsResult<()>;
@@ -538,6 +724,7 @@ impl MailHandler<AddMatchedReader> for PublisherActor {
                     publisher_mask_listener,
                     participant_mask_listener: message.participant_mask_listener.clone(),
                     message_sender_actor: message.message_sender_actor.clone(),
+                    now: message.now,
                 });
             }
         }
@@ -583,6 +770,46 @@ impl MailHandler<RemoveMatchedReader> for PublisherActor {
     }
 }
 
+pub struct RemoveMatchedParticipant {
+    pub participant_handle: InstanceHandle,
+    pub publisher_address: ActorAddress<PublisherActor>,
+    pub participant: DomainParticipantAsync,
+    pub participant_mask_listener: (
+        Option<MpscSender<ParticipantListenerMessage>>,
+        Vec<StatusKind>,
+    ),
+}
+impl Mail for RemoveMatchedParticipant {
+    type Result = ();
+}
+impl MailHandler<RemoveMatchedParticipant> for PublisherActor {
+    fn handle(
+        &mut self,
+        message: RemoveMatchedParticipant,
+    ) -> <RemoveMatchedParticipant as Mail>::Result {
+        for data_writer in self.data_writer_list.values() {
+            let data_writer_address = data_writer.address();
+            let publisher_mask_listener = (
+                self.publisher_listener_thread
+                    .as_ref()
+                    .map(|l| l.sender().clone()),
+                self.status_kind.clone(),
+            );
+            data_writer.send_actor_mail(data_writer_actor::RemoveMatchedParticipant {
+                participant_handle: message.participant_handle,
+                data_writer_address,
+                publisher: PublisherAsync::new(
+                    message.publisher_address.clone(),
+                    self.status_condition.address(),
+                    message.participant.clone(),
+                ),
+                publisher_mask_listener,
+                participant_mask_listener: message.participant_mask_listener.clone(),
+            });
+        }
+    }
+}
+
 pub struct GetStatuscondition;
 impl Mail for GetStatuscondition {
     type Result = ActorAddress<StatusConditionActor>;
@@ -620,3 +847,76 @@ impl PublisherQos {
         }
     }
 }
+
+fn group_sequence_number_inline_qos(
+    inline_qos: &ParameterList,
+    group_sequence_number: SequenceNumber,
+) -> ParameterList {
+    let mut parameter_list = inline_qos.parameter().to_vec();
+    let mut serialized_group_sequence_number = Vec::new();
+    let mut serializer = ClassicCdrSerializer::new(
+        &mut serialized_group_sequence_number,
+        CdrEndianness::LittleEndian,
+    );
+    GroupSequenceNumberParameter::from(group_sequence_number)
+        .serialize(&mut serializer)
+        .expect("GroupSequenceNumberParameter serialization must succeed");
+    parameter_list.push(Parameter::new(
+        PID_GROUP_SEQ_NUM,
+        Arc::from(serialized_group_sequence_number),
+    ));
+    ParameterList::new(parameter_list)
+}
+
+fn coherent_set_inline_qos(
+    inline_qos: &ParameterList,
+    coherent_set_sequence_number: SequenceNumber,
+    is_coherent_set_end: bool,
+) -> ParameterList {
+    let mut status_info = None;
+    let mut parameter_list = Vec::new();
+    for parameter in inline_qos.parameter() {
+        if parameter.parameter_id() == PID_STATUS_INFO {
+            let mut deserializer =
+                ClassicCdrDeserializer::new(parameter.value(), CdrEndianness::LittleEndian);
+            status_info = StatusInfo::deserialize(&mut deserializer).ok();
+        } else {
+            parameter_list.push(parameter.clone());
+        }
+    }
+
+    let mut serialized_coherent_set = Vec::new();
+    let mut serializer =
+        ClassicCdrSerializer::new(&mut serialized_coherent_set, CdrEndianness::LittleEndian);
+    CoherentSetParameter::from(coherent_set_sequence_number)
+        .serialize(&mut serializer)
+        .expect("CoherentSetParameter serialization must succeed");
+    parameter_list.push(Parameter::new(
+        PID_COHERENT_SET,
+        Arc::from(serialized_coherent_set),
+    ));
+
+    let status_info = if is_coherent_set_end {
+        Some(
+            status_info
+                .unwrap_or(STATUS_INFO_ALIVE)
+                .with_coherent_set_end(),
+        )
+    } else {
+        status_info
+    };
+    if let Some(status_info) = status_info {
+        let mut serialized_status_info = Vec::new();
+        let mut serializer =
+            ClassicCdrSerializer::new(&mut serialized_status_info, CdrEndianness::LittleEndian);
+        status_info
+            .serialize(&mut serializer)
+            .expect("StatusInfo serialization must succeed");
+        parameter_list.push(Parameter::new(
+            PID_STATUS_INFO,
+            Arc::from(serialized_status_info),
+        ));
+    }
+
+    ParameterList::new(parameter_list)
+}
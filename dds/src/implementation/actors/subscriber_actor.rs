@@ -5,7 +5,7 @@ use tracing::warn;
 
 use super::{
     any_data_reader_listener::AnyDataReaderListener,
-    data_reader_actor::{self, DataReaderActor},
+    data_reader_actor::{self, DataReaderActor, QueryFilter},
     domain_participant_actor::ParticipantListenerMessage,
     message_sender_actor::MessageSenderActor,
     topic_actor::TopicActor,
@@ -23,7 +23,6 @@ use crate::{
         runtime::{
             executor::{block_on, ExecutorHandle},
             mpsc::{mpsc_channel, MpscSender},
-            timer::TimerHandle,
         },
     },
     infrastructure::{
@@ -58,7 +57,7 @@ pub enum SubscriberListenerOperation {
     DataOnReaders(SubscriberAsync),
     _DataAvailable,
     SampleRejected(SampleRejectedStatus),
-    _LivenessChanged(LivelinessChangedStatus),
+    LivelinessChanged(LivelinessChangedStatus),
     RequestedDeadlineMissed(RequestedDeadlineMissedStatus),
     RequestedIncompatibleQos(RequestedIncompatibleQosStatus),
     SubscriptionMatched(SubscriptionMatchedStatus),
@@ -100,7 +99,7 @@ impl SubscriberListenerThread {
                         SubscriberListenerOperation::SampleRejected(status) => {
                             listener.on_sample_rejected(data_reader, status).await
                         }
-                        SubscriberListenerOperation::_LivenessChanged(status) => {
+                        SubscriberListenerOperation::LivelinessChanged(status) => {
                             listener.on_liveliness_changed(data_reader, status).await
                         }
                         SubscriberListenerOperation::RequestedDeadlineMissed(status) => {
@@ -147,6 +146,7 @@ pub struct SubscriberActor {
     status_condition: Actor<StatusConditionActor>,
     subscriber_listener_thread: Option<SubscriberListenerThread>,
     status_kind: Vec<StatusKind>,
+    access_nesting: u32,
 }
 
 impl SubscriberActor {
@@ -176,6 +176,7 @@ impl SubscriberActor {
                 status_condition,
                 subscriber_listener_thread,
                 status_kind,
+                access_nesting: 0,
             },
             status_condition_address,
         )
@@ -249,6 +250,7 @@ pub struct CreateDatareader {
     pub mask: Vec<StatusKind>,
     pub default_unicast_locator_list: Vec<Locator>,
     pub default_multicast_locator_list: Vec<Locator>,
+    pub content_filter: Option<QueryFilter>,
     pub executor_handle: ExecutorHandle,
 }
 impl Mail for CreateDatareader {
@@ -307,6 +309,7 @@ impl MailHandler<CreateDatareader> for SubscriberActor {
             qos,
             message.a_listener,
             status_kind,
+            message.content_filter,
             &message.executor_handle,
         );
 
@@ -357,6 +360,31 @@ impl MailHandler<GetGuid> for SubscriberActor {
     }
 }
 
+pub struct SetGuidPrefix {
+    pub guid_prefix: GuidPrefix,
+}
+impl Mail for SetGuidPrefix {
+    type Result = ();
+}
+impl MailHandler<SetGuidPrefix> for SubscriberActor {
+    fn handle(&mut self, message: SetGuidPrefix) -> <SetGuidPrefix as Mail>::Result {
+        self.rtps_group.set_guid_prefix(message.guid_prefix);
+        // The data readers are indexed by their handle which is derived from their GUID
+        self.data_reader_list = self
+            .data_reader_list
+            .drain()
+            .map(|(handle, data_reader)| {
+                data_reader.send_actor_mail(data_reader_actor::SetGuidPrefix {
+                    guid_prefix: message.guid_prefix,
+                });
+                let entity_id = Guid::from(<[u8; 16]>::from(handle)).entity_id();
+                let guid = Guid::new(message.guid_prefix, entity_id);
+                (InstanceHandle::new(guid.into()), data_reader)
+            })
+            .collect();
+    }
+}
+
 pub struct IsEmpty;
 impl Mail for IsEmpty {
     type Result = bool;
@@ -377,6 +405,52 @@ impl MailHandler<IsEnabled> for SubscriberActor {
     }
 }
 
+pub struct BeginAccess;
+impl Mail for BeginAccess {
+    type Result = ();
+}
+impl MailHandler<BeginAccess> for SubscriberActor {
+    fn handle(&mut self, _: BeginAccess) -> <BeginAccess as Mail>::Result {
+        if self.access_nesting == 0 {
+            for data_reader_actor in self.data_reader_list.values() {
+                data_reader_actor.send_actor_mail(data_reader_actor::BeginAccess);
+            }
+        }
+        self.access_nesting += 1;
+    }
+}
+
+pub struct EndAccess;
+impl Mail for EndAccess {
+    type Result = DdsResult<()>;
+}
+impl MailHandler<EndAccess> for SubscriberActor {
+    fn handle(&mut self, _: EndAccess) -> <EndAccess as Mail>::Result {
+        if self.access_nesting == 0 {
+            return Err(DdsError::PreconditionNotMet(
+                "No matching call to begin_access".to_string(),
+            ));
+        }
+        self.access_nesting -= 1;
+        if self.access_nesting == 0 {
+            for data_reader_actor in self.data_reader_list.values() {
+                data_reader_actor.send_actor_mail(data_reader_actor::EndAccess);
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct IsAccessActive;
+impl Mail for IsAccessActive {
+    type Result = bool;
+}
+impl MailHandler<IsAccessActive> for SubscriberActor {
+    fn handle(&mut self, _: IsAccessActive) -> <IsAccessActive as Mail>::Result {
+        self.access_nesting > 0
+    }
+}
+
 pub struct SetDefaultDatareaderQos {
     pub qos: QosKind<DataReaderQos>,
 }
@@ -507,7 +581,6 @@ pub struct ProcessDataSubmessage {
         Vec<StatusKind>,
     ),
     pub executor_handle: ExecutorHandle,
-    pub timer_handle: TimerHandle,
 }
 impl Mail for ProcessDataSubmessage {
     type Result = ();
@@ -538,7 +611,6 @@ impl MailHandler<ProcessDataSubmessage> for SubscriberActor {
                 subscriber_mask_listener,
                 participant_mask_listener: message.participant_mask_listener.clone(),
                 executor_handle: message.executor_handle.clone(),
-                timer_handle: message.timer_handle.clone(),
             });
         }
     }
@@ -556,7 +628,6 @@ pub struct ProcessDataFragSubmessage {
         Vec<StatusKind>,
     ),
     pub executor_handle: ExecutorHandle,
-    pub timer_handle: TimerHandle,
 }
 impl Mail for ProcessDataFragSubmessage {
     type Result = ();
@@ -587,7 +658,6 @@ impl MailHandler<ProcessDataFragSubmessage> for SubscriberActor {
                 subscriber_mask_listener,
                 participant_mask_listener: message.participant_mask_listener.clone(),
                 executor_handle: message.executor_handle.clone(),
-                timer_handle: message.timer_handle.clone(),
             });
         }
     }
@@ -615,6 +685,12 @@ pub struct ProcessHeartbeatSubmessage {
     pub heartbeat_submessage: HeartbeatSubmessage,
     pub source_guid_prefix: GuidPrefix,
     pub message_sender_actor: ActorAddress<MessageSenderActor>,
+    pub subscriber_address: ActorAddress<SubscriberActor>,
+    pub participant: DomainParticipantAsync,
+    pub participant_mask_listener: (
+        Option<MpscSender<ParticipantListenerMessage>>,
+        Vec<StatusKind>,
+    ),
 }
 impl Mail for ProcessHeartbeatSubmessage {
     type Result = ();
@@ -625,10 +701,24 @@ impl MailHandler<ProcessHeartbeatSubmessage> for SubscriberActor {
         message: ProcessHeartbeatSubmessage,
     ) -> <ProcessHeartbeatSubmessage as Mail>::Result {
         for data_reader_actor in self.data_reader_list.values() {
+            let subscriber_mask_listener = (
+                self.subscriber_listener_thread
+                    .as_ref()
+                    .map(|l| l.sender().clone()),
+                self.status_kind.clone(),
+            );
             data_reader_actor.send_actor_mail(data_reader_actor::ProcessHeartbeatSubmessage {
                 heartbeat_submessage: message.heartbeat_submessage.clone(),
                 source_guid_prefix: message.source_guid_prefix,
                 message_sender_actor: message.message_sender_actor.clone(),
+                data_reader_address: data_reader_actor.address(),
+                subscriber: SubscriberAsync::new(
+                    message.subscriber_address.clone(),
+                    self.status_condition.address(),
+                    message.participant.clone(),
+                ),
+                subscriber_mask_listener,
+                participant_mask_listener: message.participant_mask_listener.clone(),
             });
         }
     }
@@ -637,6 +727,7 @@ impl MailHandler<ProcessHeartbeatSubmessage> for SubscriberActor {
 pub struct ProcessHeartbeatFragSubmessage {
     pub heartbeat_frag_submessage: HeartbeatFragSubmessage,
     pub source_guid_prefix: GuidPrefix,
+    pub message_sender_actor: ActorAddress<MessageSenderActor>,
 }
 impl Mail for ProcessHeartbeatFragSubmessage {
     type Result = ();
@@ -650,6 +741,7 @@ impl MailHandler<ProcessHeartbeatFragSubmessage> for SubscriberActor {
             data_reader_actor.send_actor_mail(data_reader_actor::ProcessHeartbeatFragSubmessage {
                 heartbeat_frag_submessage: message.heartbeat_frag_submessage.clone(),
                 source_guid_prefix: message.source_guid_prefix,
+                message_sender_actor: message.message_sender_actor.clone(),
             });
         }
     }
@@ -745,6 +837,46 @@ impl MailHandler<RemoveMatchedWriter> for SubscriberActor {
     }
 }
 
+pub struct RemoveMatchedParticipant {
+    pub participant_handle: InstanceHandle,
+    pub subscriber_address: ActorAddress<SubscriberActor>,
+    pub participant: DomainParticipantAsync,
+    pub participant_mask_listener: (
+        Option<MpscSender<ParticipantListenerMessage>>,
+        Vec<StatusKind>,
+    ),
+}
+impl Mail for RemoveMatchedParticipant {
+    type Result = ();
+}
+impl MailHandler<RemoveMatchedParticipant> for SubscriberActor {
+    fn handle(
+        &mut self,
+        message: RemoveMatchedParticipant,
+    ) -> <RemoveMatchedParticipant as Mail>::Result {
+        for data_reader in self.data_reader_list.values() {
+            let data_reader_address = data_reader.address();
+            let subscriber_mask_listener = (
+                self.subscriber_listener_thread
+                    .as_ref()
+                    .map(|l| l.sender().clone()),
+                self.status_kind.clone(),
+            );
+            data_reader.send_actor_mail(data_reader_actor::RemoveMatchedParticipant {
+                participant_handle: message.participant_handle,
+                data_reader_address,
+                subscriber: SubscriberAsync::new(
+                    message.subscriber_address.clone(),
+                    self.status_condition.address(),
+                    message.participant.clone(),
+                ),
+                subscriber_mask_listener,
+                participant_mask_listener: message.participant_mask_listener.clone(),
+            });
+        }
+    }
+}
+
 pub struct SetListener {
     pub listener: Option<Box<dyn SubscriberListenerAsync + Send>>,
     pub status_kind: Vec<StatusKind>,
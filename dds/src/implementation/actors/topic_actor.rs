@@ -17,7 +17,7 @@ use crate::{
         qos::TopicQos,
         status::{InconsistentTopicStatus, StatusKind},
     },
-    rtps::types::Guid,
+    rtps::types::{Guid, GuidPrefix},
     topic_definition::type_support::DynamicTypeInterface,
 };
 
@@ -147,6 +147,18 @@ impl MailHandler<GetGuid> for TopicActor {
     }
 }
 
+pub struct SetGuidPrefix {
+    pub guid_prefix: GuidPrefix,
+}
+impl Mail for SetGuidPrefix {
+    type Result = ();
+}
+impl MailHandler<SetGuidPrefix> for TopicActor {
+    fn handle(&mut self, message: SetGuidPrefix) -> <SetGuidPrefix as Mail>::Result {
+        self.guid = Guid::new(message.guid_prefix, self.guid.entity_id());
+    }
+}
+
 pub struct SetQos {
     pub qos: TopicQos,
 }
@@ -1,11 +1,85 @@
-use crate::serialized_payload::cdr::{deserialize::CdrDeserialize, serialize::CdrSerialize};
+use crate::{
+    rtps::types::SequenceNumber,
+    serialized_payload::cdr::{deserialize::CdrDeserialize, serialize::CdrSerialize},
+};
 
 #[derive(Clone, Copy, CdrSerialize, CdrDeserialize, PartialEq, Eq)]
 pub struct KeyHash(pub [u8; 16]);
 
 #[derive(Clone, Copy, CdrSerialize, CdrDeserialize, PartialEq, Eq)]
 pub struct StatusInfo([u8; 4]);
+pub const STATUS_INFO_ALIVE: StatusInfo = StatusInfo([0, 0, 0, 0]);
 pub const STATUS_INFO_DISPOSED: StatusInfo = StatusInfo([0, 0, 0, 0b00000001]);
 pub const STATUS_INFO_UNREGISTERED: StatusInfo = StatusInfo([0, 0, 0, 0b0000010]);
 pub const STATUS_INFO_DISPOSED_UNREGISTERED: StatusInfo = StatusInfo([0, 0, 0, 0b00000011]);
 pub const _STATUS_INFO_FILTERED: StatusInfo = StatusInfo([0, 0, 0, 0b0000100]);
+// Marks the last sample of a coherent set
+pub const STATUS_INFO_COHERENT_SET_END: StatusInfo = StatusInfo([0, 0, 0, 0b0001000]);
+
+impl StatusInfo {
+    pub fn is_coherent_set_end(&self) -> bool {
+        self.0[3] & STATUS_INFO_COHERENT_SET_END.0[3] != 0
+    }
+
+    pub fn with_coherent_set_end(self) -> Self {
+        Self([
+            self.0[0],
+            self.0[1],
+            self.0[2],
+            self.0[3] | STATUS_INFO_COHERENT_SET_END.0[3],
+        ])
+    }
+
+    pub fn without_coherent_set_end(self) -> Self {
+        Self([
+            self.0[0],
+            self.0[1],
+            self.0[2],
+            self.0[3] & !STATUS_INFO_COHERENT_SET_END.0[3],
+        ])
+    }
+}
+
+// Sequence number of the first sample of the coherent set a sample belongs to
+#[derive(Clone, Copy, CdrSerialize, CdrDeserialize, PartialEq, Eq)]
+pub struct CoherentSetParameter {
+    high: i32,
+    low: u32,
+}
+
+impl From<SequenceNumber> for CoherentSetParameter {
+    fn from(value: SequenceNumber) -> Self {
+        Self {
+            high: (value >> 32) as i32,
+            low: value as u32,
+        }
+    }
+}
+
+impl From<CoherentSetParameter> for SequenceNumber {
+    fn from(value: CoherentSetParameter) -> Self {
+        ((value.high as i64) << 32) + value.low as i64
+    }
+}
+
+// Sequence number of a sample among all the samples of the writers of its publisher
+#[derive(Clone, Copy, CdrSerialize, CdrDeserialize, PartialEq, Eq)]
+pub struct GroupSequenceNumberParameter {
+    high: i32,
+    low: u32,
+}
+
+impl From<SequenceNumber> for GroupSequenceNumberParameter {
+    fn from(value: SequenceNumber) -> Self {
+        Self {
+            high: (value >> 32) as i32,
+            low: value as u32,
+        }
+    }
+}
+
+impl From<GroupSequenceNumberParameter> for SequenceNumber {
+    fn from(value: GroupSequenceNumberParameter) -> Self {
+        ((value.high as i64) << 32) + value.low as i64
+    }
+}
@@ -0,0 +1,84 @@
+use std::time::{Duration, Instant};
+
+/// Token bucket limiting the number of bytes per second that a writer sends.
+/// Sending is allowed as long as the bucket is not in debt so that changes larger
+/// than the bucket are not held back forever.
+pub struct FlowController {
+    max_bytes_per_second: Option<u64>,
+    tokens: i64,
+    last_refill: Instant,
+}
+
+impl FlowController {
+    /// Period at which the writer retries sending the changes held back by the flow controller
+    pub const REFILL_PERIOD: Duration = Duration::from_millis(5);
+
+    pub fn new(max_bytes_per_second: Option<u64>) -> Self {
+        Self {
+            max_bytes_per_second,
+            tokens: 0,
+            last_refill: Instant::now(),
+        }
+    }
+
+    pub fn is_ready(&mut self) -> bool {
+        match self.max_bytes_per_second {
+            Some(max_bytes_per_second) => {
+                self.refill(max_bytes_per_second, Instant::now());
+                self.tokens >= 0
+            }
+            None => true,
+        }
+    }
+
+    pub fn consume(&mut self, bytes: usize) {
+        if self.max_bytes_per_second.is_some() {
+            self.tokens -= bytes as i64;
+        }
+    }
+
+    fn refill(&mut self, max_bytes_per_second: u64, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        let new_tokens = (elapsed.as_secs_f64() * max_bytes_per_second as f64) as i64;
+        if new_tokens > 0 {
+            // At most a tenth of a second worth of bytes is saved up while the writer is idle
+            let capacity = (max_bytes_per_second / 10).max(1) as i64;
+            self.tokens = (self.tokens + new_tokens).min(capacity);
+            // Only the time corresponding to whole tokens is accounted for to avoid drifting
+            self.last_refill +=
+                Duration::from_secs_f64(new_tokens as f64 / max_bytes_per_second as f64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlimited_flow_controller_is_always_ready() {
+        let mut flow_controller = FlowController::new(None);
+        flow_controller.consume(1_000_000);
+        assert!(flow_controller.is_ready());
+    }
+
+    #[test]
+    fn flow_controller_is_ready_again_after_refill() {
+        let mut flow_controller = FlowController::new(Some(1000));
+        assert!(flow_controller.is_ready());
+        flow_controller.consume(100);
+        assert!(!flow_controller.is_ready());
+
+        let now = flow_controller.last_refill + Duration::from_millis(100);
+        flow_controller.refill(1000, now);
+        assert!(flow_controller.tokens >= 0);
+    }
+
+    #[test]
+    fn flow_controller_saves_up_a_limited_amount_of_tokens() {
+        let mut flow_controller = FlowController::new(Some(1000));
+        let now = flow_controller.last_refill + Duration::from_secs(10);
+        flow_controller.refill(1000, now);
+        assert_eq!(flow_controller.tokens, 100);
+    }
+}
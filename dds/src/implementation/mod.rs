@@ -1,5 +1,10 @@
 pub mod actor;
 pub mod actors;
 pub mod data_representation_inline_qos;
+pub mod flow_controller;
 pub mod payload_serializer_deserializer;
+pub mod query_expression;
+pub mod rtps_tcp_psm;
 pub mod runtime;
+pub mod submessage_packer;
+pub mod writer_send_queue;
@@ -0,0 +1,598 @@
+use crate::{
+    infrastructure::error::{DdsError, DdsResult},
+    topic_definition::type_support::{DdsGetField, FieldValue},
+};
+use std::{cmp::Ordering, iter::Peekable, str::Chars};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Identifier(String),
+    Literal(Value),
+    Parameter(usize),
+    Operator(ComparisonOperator),
+    LeftParenthesis,
+    RightParenthesis,
+    And,
+    Or,
+    Between,
+    Like,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ComparisonOperator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl ComparisonOperator {
+    fn reversed(self) -> Self {
+        match self {
+            ComparisonOperator::Less => ComparisonOperator::Greater,
+            ComparisonOperator::LessOrEqual => ComparisonOperator::GreaterOrEqual,
+            ComparisonOperator::Greater => ComparisonOperator::Less,
+            ComparisonOperator::GreaterOrEqual => ComparisonOperator::LessOrEqual,
+            o => o,
+        }
+    }
+
+    fn is_satisfied_by(self, ordering: Option<Ordering>) -> bool {
+        match self {
+            ComparisonOperator::Equal => ordering == Some(Ordering::Equal),
+            ComparisonOperator::NotEqual => {
+                matches!(ordering, Some(Ordering::Less | Ordering::Greater))
+            }
+            ComparisonOperator::Less => ordering == Some(Ordering::Less),
+            ComparisonOperator::LessOrEqual => {
+                matches!(ordering, Some(Ordering::Less | Ordering::Equal))
+            }
+            ComparisonOperator::Greater => ordering == Some(Ordering::Greater),
+            ComparisonOperator::GreaterOrEqual => {
+                matches!(ordering, Some(Ordering::Greater | Ordering::Equal))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    Literal(Value),
+    Parameter(usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    Comparison {
+        field_name: String,
+        operator: ComparisonOperator,
+        operand: Operand,
+    },
+    Between {
+        field_name: String,
+        low: Operand,
+        high: Operand,
+    },
+    Like {
+        field_name: String,
+        pattern: Operand,
+    },
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+}
+
+fn tokenize_number(first: char, chars: &mut Peekable<Chars>) -> DdsResult<Value> {
+    let mut number = String::from(first);
+    while let Some(&c) = chars.peek() {
+        if c.is_ascii_digit() || c == '.' {
+            number.push(c);
+            chars.next();
+        } else {
+            break;
+        }
+    }
+    if number.contains('.') {
+        number
+            .parse()
+            .map(Value::Float)
+            .map_err(|_| DdsError::BadParameter)
+    } else {
+        number
+            .parse()
+            .map(Value::Integer)
+            .map_err(|_| DdsError::BadParameter)
+    }
+}
+
+fn tokenize(expression: &str) -> DdsResult<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LeftParenthesis,
+            ')' => Token::RightParenthesis,
+            '=' => Token::Operator(ComparisonOperator::Equal),
+            '<' => match chars.next_if(|&c| c == '=' || c == '>') {
+                Some('=') => Token::Operator(ComparisonOperator::LessOrEqual),
+                Some(_) => Token::Operator(ComparisonOperator::NotEqual),
+                None => Token::Operator(ComparisonOperator::Less),
+            },
+            '>' => match chars.next_if_eq(&'=') {
+                Some(_) => Token::Operator(ComparisonOperator::GreaterOrEqual),
+                None => Token::Operator(ComparisonOperator::Greater),
+            },
+            '!' => match chars.next_if_eq(&'=') {
+                Some(_) => Token::Operator(ComparisonOperator::NotEqual),
+                None => return Err(DdsError::BadParameter),
+            },
+            '\'' => {
+                let mut string = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => string.push(c),
+                        None => return Err(DdsError::BadParameter),
+                    }
+                }
+                Token::Literal(Value::String(string))
+            }
+            '%' => {
+                let mut index = String::new();
+                while let Some(c) = chars.next_if(char::is_ascii_digit) {
+                    index.push(c);
+                }
+                Token::Parameter(index.parse().map_err(|_| DdsError::BadParameter)?)
+            }
+            c if c.is_ascii_digit() || c == '-' || c == '+' => {
+                Token::Literal(tokenize_number(c, &mut chars)?)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut identifier = String::from(c);
+                while let Some(c) = chars.next_if(|&c| c.is_alphanumeric() || c == '_') {
+                    identifier.push(c);
+                }
+                match identifier.to_uppercase().as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "BETWEEN" => Token::Between,
+                    "LIKE" => Token::Like,
+                    "TRUE" => Token::Literal(Value::Boolean(true)),
+                    "FALSE" => Token::Literal(Value::Boolean(false)),
+                    _ => Token::Identifier(identifier),
+                }
+            }
+            _ => return Err(DdsError::BadParameter),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: std::vec::IntoIter<Token>,
+}
+
+impl Parser {
+    fn next(&mut self) -> DdsResult<Token> {
+        self.tokens.next().ok_or(DdsError::BadParameter)
+    }
+
+    fn next_if(&mut self, token: &Token) -> bool {
+        if self.tokens.as_slice().first() == Some(token) {
+            self.tokens.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token) -> DdsResult<()> {
+        if self.next_if(token) {
+            Ok(())
+        } else {
+            Err(DdsError::BadParameter)
+        }
+    }
+
+    fn parse_or(&mut self) -> DdsResult<Condition> {
+        let mut condition = self.parse_and()?;
+        while self.next_if(&Token::Or) {
+            condition = Condition::Or(Box::new(condition), Box::new(self.parse_and()?));
+        }
+        Ok(condition)
+    }
+
+    fn parse_and(&mut self) -> DdsResult<Condition> {
+        let mut condition = self.parse_primary()?;
+        while self.next_if(&Token::And) {
+            condition = Condition::And(Box::new(condition), Box::new(self.parse_primary()?));
+        }
+        Ok(condition)
+    }
+
+    fn parse_primary(&mut self) -> DdsResult<Condition> {
+        if self.next_if(&Token::LeftParenthesis) {
+            let condition = self.parse_or()?;
+            self.expect(&Token::RightParenthesis)?;
+            Ok(condition)
+        } else {
+            self.parse_predicate()
+        }
+    }
+
+    fn parse_operand(&mut self) -> DdsResult<Operand> {
+        match self.next()? {
+            Token::Literal(value) => Ok(Operand::Literal(value)),
+            Token::Parameter(index) => Ok(Operand::Parameter(index)),
+            _ => Err(DdsError::BadParameter),
+        }
+    }
+
+    fn parse_predicate(&mut self) -> DdsResult<Condition> {
+        match self.next()? {
+            Token::Identifier(field_name) => match self.next()? {
+                Token::Operator(operator) => Ok(Condition::Comparison {
+                    field_name,
+                    operator,
+                    operand: self.parse_operand()?,
+                }),
+                Token::Between => {
+                    let low = self.parse_operand()?;
+                    self.expect(&Token::And)?;
+                    let high = self.parse_operand()?;
+                    Ok(Condition::Between {
+                        field_name,
+                        low,
+                        high,
+                    })
+                }
+                Token::Like => Ok(Condition::Like {
+                    field_name,
+                    pattern: self.parse_operand()?,
+                }),
+                _ => Err(DdsError::BadParameter),
+            },
+            Token::Literal(value) => self.parse_reversed_comparison(Operand::Literal(value)),
+            Token::Parameter(index) => self.parse_reversed_comparison(Operand::Parameter(index)),
+            _ => Err(DdsError::BadParameter),
+        }
+    }
+
+    fn parse_reversed_comparison(&mut self, operand: Operand) -> DdsResult<Condition> {
+        match (self.next()?, self.next()?) {
+            (Token::Operator(operator), Token::Identifier(field_name)) => {
+                Ok(Condition::Comparison {
+                    field_name,
+                    operator: operator.reversed(),
+                    operand,
+                })
+            }
+            _ => Err(DdsError::BadParameter),
+        }
+    }
+}
+
+fn parse_parameter(parameter: &str) -> Value {
+    match tokenize(parameter).as_deref() {
+        Ok([Token::Literal(value)]) => value.clone(),
+        _ => Value::String(parameter.to_string()),
+    }
+}
+
+fn compare(field: &FieldValue, value: &Value) -> Option<Ordering> {
+    match (field, value) {
+        (FieldValue::Boolean(f), Value::Boolean(v)) => f.partial_cmp(v),
+        (FieldValue::Int(f), Value::Integer(v)) => f.partial_cmp(v),
+        (FieldValue::UInt(f), Value::Integer(v)) => (*f as i128).partial_cmp(&(*v as i128)),
+        (FieldValue::Float(f), Value::Integer(v)) => f.partial_cmp(&(*v as f64)),
+        (FieldValue::Int(f), Value::Float(v)) => (*f as f64).partial_cmp(v),
+        (FieldValue::UInt(f), Value::Float(v)) => (*f as f64).partial_cmp(v),
+        (FieldValue::Float(f), Value::Float(v)) => f.partial_cmp(v),
+        (FieldValue::String(f), Value::String(v)) => f.as_str().partial_cmp(v.as_str()),
+        (FieldValue::Char(f), Value::String(v)) => {
+            let mut chars = v.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => f.partial_cmp(&c),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// Matches the text against a LIKE pattern where '%' matches any sequence of
+// characters and '_' matches exactly one character. On a mismatch only the
+// last '%' is extended, which keeps the matching time proportional to the
+// product of the text and pattern lengths regardless of the number of '%'.
+fn is_like(text: &[char], pattern: &[char]) -> bool {
+    let mut text_index = 0;
+    let mut pattern_index = 0;
+    // Position of the last '%' in the pattern and of the text it matched up to
+    let mut last_wildcard: Option<(usize, usize)> = None;
+
+    while text_index < text.len() {
+        match pattern.get(pattern_index) {
+            Some('%') => {
+                last_wildcard = Some((pattern_index, text_index));
+                pattern_index += 1;
+            }
+            Some(&c) if c == '_' || c == text[text_index] => {
+                text_index += 1;
+                pattern_index += 1;
+            }
+            _ => match last_wildcard {
+                Some((wildcard_pattern_index, wildcard_text_index)) => {
+                    last_wildcard = Some((wildcard_pattern_index, wildcard_text_index + 1));
+                    pattern_index = wildcard_pattern_index + 1;
+                    text_index = wildcard_text_index + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[pattern_index..].iter().all(|&c| c == '%')
+}
+
+/// Parsed representation of the query expression of a QueryCondition.
+/// The supported grammar is the subset of the SQL WHERE clause defined by the DDS standard consisting of
+/// comparisons (=, <>, <, <=, >, >=), BETWEEN and LIKE predicates on the members of the type combined
+/// with AND, OR and parentheses. Parameters are referenced as %0, %1, ...
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryExpression {
+    condition: Condition,
+    parameters: Vec<Value>,
+}
+
+impl QueryExpression {
+    pub fn new(query_expression: &str, query_parameters: &[String]) -> DdsResult<Self> {
+        let mut parser = Parser {
+            tokens: tokenize(query_expression)?.into_iter(),
+        };
+        let condition = parser.parse_or()?;
+        if parser.tokens.len() != 0 {
+            return Err(DdsError::BadParameter);
+        }
+
+        let query_expression = Self {
+            condition,
+            parameters: query_parameters
+                .iter()
+                .map(|p| parse_parameter(p))
+                .collect(),
+        };
+        if query_expression.has_valid_parameters(&query_expression.condition) {
+            Ok(query_expression)
+        } else {
+            Err(DdsError::BadParameter)
+        }
+    }
+
+    pub fn evaluate(&self, sample: &impl DdsGetField) -> bool {
+        self.evaluate_condition(&self.condition, sample)
+    }
+
+    fn has_valid_parameters(&self, condition: &Condition) -> bool {
+        let is_valid = |operand: &Operand| match operand {
+            Operand::Literal(_) => true,
+            Operand::Parameter(index) => *index < self.parameters.len(),
+        };
+        match condition {
+            Condition::Comparison { operand, .. } => is_valid(operand),
+            Condition::Between { low, high, .. } => is_valid(low) && is_valid(high),
+            Condition::Like { pattern, .. } => is_valid(pattern),
+            Condition::And(a, b) | Condition::Or(a, b) => {
+                self.has_valid_parameters(a) && self.has_valid_parameters(b)
+            }
+        }
+    }
+
+    fn value<'a>(&'a self, operand: &'a Operand) -> &'a Value {
+        match operand {
+            Operand::Literal(value) => value,
+            Operand::Parameter(index) => &self.parameters[*index],
+        }
+    }
+
+    fn evaluate_condition(&self, condition: &Condition, sample: &impl DdsGetField) -> bool {
+        match condition {
+            Condition::Comparison {
+                field_name,
+                operator,
+                operand,
+            } => match sample.get_field(field_name) {
+                Some(field) => operator.is_satisfied_by(compare(&field, self.value(operand))),
+                None => false,
+            },
+            Condition::Between {
+                field_name,
+                low,
+                high,
+            } => match sample.get_field(field_name) {
+                Some(field) => {
+                    ComparisonOperator::GreaterOrEqual
+                        .is_satisfied_by(compare(&field, self.value(low)))
+                        && ComparisonOperator::LessOrEqual
+                            .is_satisfied_by(compare(&field, self.value(high)))
+                }
+                None => false,
+            },
+            Condition::Like {
+                field_name,
+                pattern,
+            } => match (sample.get_field(field_name), self.value(pattern)) {
+                (Some(FieldValue::String(text)), Value::String(pattern)) => is_like(
+                    &text.chars().collect::<Vec<_>>(),
+                    &pattern.chars().collect::<Vec<_>>(),
+                ),
+                _ => false,
+            },
+            Condition::And(a, b) => {
+                self.evaluate_condition(a, sample) && self.evaluate_condition(b, sample)
+            }
+            Condition::Or(a, b) => {
+                self.evaluate_condition(a, sample) || self.evaluate_condition(b, sample)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topic_definition::type_support::DdsGetField;
+
+    #[derive(DdsGetField)]
+    struct Shape {
+        color: String,
+        x: i32,
+        y: u16,
+        size: f64,
+        visible: bool,
+    }
+
+    fn shape() -> Shape {
+        Shape {
+            color: "BLUE".to_string(),
+            x: 10,
+            y: 200,
+            size: 2.5,
+            visible: true,
+        }
+    }
+
+    fn evaluate(query_expression: &str, query_parameters: &[&str]) -> bool {
+        let query_parameters: Vec<String> =
+            query_parameters.iter().map(|p| p.to_string()).collect();
+        QueryExpression::new(query_expression, &query_parameters)
+            .unwrap()
+            .evaluate(&shape())
+    }
+
+    #[test]
+    fn comparison_operators() {
+        assert!(evaluate("x = 10", &[]));
+        assert!(!evaluate("x = 11", &[]));
+        assert!(evaluate("x <> 11", &[]));
+        assert!(evaluate("x != 11", &[]));
+        assert!(evaluate("x < 11", &[]));
+        assert!(!evaluate("x < 10", &[]));
+        assert!(evaluate("x <= 10", &[]));
+        assert!(evaluate("x > -1", &[]));
+        assert!(evaluate("y >= 200", &[]));
+        assert!(evaluate("size > 2", &[]));
+        assert!(evaluate("size < 2.6", &[]));
+        assert!(evaluate("color = 'BLUE'", &[]));
+        assert!(evaluate("visible = TRUE", &[]));
+    }
+
+    #[test]
+    fn literal_on_the_left_side_of_the_comparison() {
+        assert!(evaluate("5 < x", &[]));
+        assert!(!evaluate("15 < x", &[]));
+    }
+
+    #[test]
+    fn between() {
+        assert!(evaluate("x BETWEEN 10 AND 20", &[]));
+        assert!(evaluate("x BETWEEN 0 AND 10", &[]));
+        assert!(!evaluate("x BETWEEN 11 AND 20", &[]));
+    }
+
+    #[test]
+    fn like() {
+        assert!(evaluate("color LIKE 'BLUE'", &[]));
+        assert!(evaluate("color LIKE 'B%'", &[]));
+        assert!(evaluate("color LIKE '%U%'", &[]));
+        assert!(evaluate("color LIKE 'B_UE'", &[]));
+        assert!(!evaluate("color LIKE 'B_E'", &[]));
+        assert!(!evaluate("color LIKE 'R%'", &[]));
+    }
+
+    #[test]
+    fn like_with_several_wildcards() {
+        let text: Vec<char> = "a".repeat(1000).chars().collect();
+        let pattern: Vec<char> = "%a%a%a%a%a%a%a%a%b".chars().collect();
+        assert!(!is_like(&text, &pattern));
+
+        let pattern: Vec<char> = "%a%a%a%a%a%a%a%a%".chars().collect();
+        assert!(is_like(&text, &pattern));
+        assert!(is_like(&['a', 'b', 'c'], &['%', '_', '%', 'c']));
+        assert!(!is_like(&['a', 'b', 'c'], &['%', 'b', '_', '_']));
+        assert!(is_like(&[], &['%', '%']));
+    }
+
+    #[test]
+    fn and_or_with_parentheses() {
+        assert!(evaluate("x = 10 AND color = 'BLUE'", &[]));
+        assert!(!evaluate("x = 10 AND color = 'RED'", &[]));
+        assert!(evaluate("x = 11 OR color = 'BLUE'", &[]));
+        assert!(evaluate("x = 11 AND y = 0 OR color = 'BLUE'", &[]));
+        assert!(!evaluate("x = 11 AND (y = 0 OR color = 'BLUE')", &[]));
+        assert!(evaluate("x BETWEEN 0 AND 20 AND color LIKE 'B%'", &[]));
+    }
+
+    #[test]
+    fn keywords_are_case_insensitive() {
+        assert!(evaluate("x between 0 and 20 or color like 'R%'", &[]));
+    }
+
+    #[test]
+    fn parameters() {
+        assert!(evaluate("x = %0 AND color = %1", &["10", "'BLUE'"]));
+        assert!(evaluate("color = %0", &["BLUE"]));
+        assert!(evaluate("x BETWEEN %1 AND %0", &["20", "5"]));
+        assert!(!evaluate("x > %0", &["10"]));
+    }
+
+    #[test]
+    fn unknown_fields_do_not_match() {
+        assert!(!evaluate("z = 10", &[]));
+    }
+
+    #[test]
+    fn mismatched_types_do_not_match() {
+        assert!(!evaluate("color = 10", &[]));
+        assert!(!evaluate("x = 'BLUE'", &[]));
+    }
+
+    #[test]
+    fn invalid_expressions() {
+        for query_expression in [
+            "",
+            "x =",
+            "x = 10 AND",
+            "(x = 10",
+            "x = 10)",
+            "x BETWEEN 1 20",
+            "color = 'BLUE",
+            "x = 10 y = 20",
+            "x # 10",
+        ] {
+            assert_eq!(
+                QueryExpression::new(query_expression, &[]),
+                Err(DdsError::BadParameter),
+                "{query_expression}"
+            );
+        }
+    }
+
+    #[test]
+    fn missing_parameter() {
+        assert_eq!(
+            QueryExpression::new("x = %1", &["10".to_string()]),
+            Err(DdsError::BadParameter)
+        );
+    }
+}
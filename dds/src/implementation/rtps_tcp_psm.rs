@@ -0,0 +1,121 @@
+use std::{
+    io::{Read, Write},
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+};
+
+use crate::rtps::types::{Locator, LOCATOR_KIND_TCP_V4};
+
+// RTPS messages sent over a TCP stream are delimited by a 4-byte big-endian length prefix
+const FRAME_HEADER_SIZE: usize = 4;
+
+pub fn write_frame(stream: &mut impl Write, message: &[u8]) -> std::io::Result<()> {
+    let length = u32::try_from(message.len())
+        .map_err(|_| std::io::Error::from(std::io::ErrorKind::InvalidInput))?;
+    let mut frame = Vec::with_capacity(FRAME_HEADER_SIZE + message.len());
+    frame.extend_from_slice(&length.to_be_bytes());
+    frame.extend_from_slice(message);
+    stream.write_all(&frame)
+}
+
+pub fn read_frame(stream: &mut impl Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut length = [0; FRAME_HEADER_SIZE];
+    stream.read_exact(&mut length)?;
+    let length = u32::from_be_bytes(length) as usize;
+    if length > buf.len() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Frame length {} exceeds maximum {}", length, buf.len()),
+        ));
+    }
+    stream.read_exact(&mut buf[..length])?;
+    Ok(length)
+}
+
+pub fn tcp_v4_locator(address: Ipv4Addr, port: u16) -> Locator {
+    let mut locator_address = [0; 16];
+    locator_address[12..].copy_from_slice(&address.octets());
+    Locator::new(LOCATOR_KIND_TCP_V4, port as u32, locator_address)
+}
+
+pub fn tcp_locator_socket_address(locator: &Locator) -> Option<SocketAddr> {
+    match locator.kind() {
+        LOCATOR_KIND_TCP_V4 => {
+            let address = locator.address();
+            Some(SocketAddr::V4(SocketAddrV4::new(
+                Ipv4Addr::new(address[12], address[13], address[14], address[15]),
+                locator.port() as u16,
+            )))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct OneByteReader<'a>(&'a [u8]);
+
+    impl Read for OneByteReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.0.is_empty() || buf.is_empty() {
+                return Ok(0);
+            }
+            buf[0] = self.0[0];
+            self.0 = &self.0[1..];
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn frames_are_read_in_written_order() {
+        let mut stream = Vec::new();
+        write_frame(&mut stream, &[1, 2, 3]).unwrap();
+        write_frame(&mut stream, &[4, 5]).unwrap();
+        assert_eq!(&stream[..7], &[0, 0, 0, 3, 1, 2, 3]);
+
+        let mut reader = stream.as_slice();
+        let mut buf = [0; 10];
+        assert_eq!(read_frame(&mut reader, &mut buf).unwrap(), 3);
+        assert_eq!(&buf[..3], &[1, 2, 3]);
+        assert_eq!(read_frame(&mut reader, &mut buf).unwrap(), 2);
+        assert_eq!(&buf[..2], &[4, 5]);
+        assert!(read_frame(&mut reader, &mut buf).is_err());
+    }
+
+    #[test]
+    fn frame_is_reassembled_from_partial_reads() {
+        let mut stream = Vec::new();
+        write_frame(&mut stream, &[7; 20]).unwrap();
+
+        let mut buf = [0; 20];
+        assert_eq!(
+            read_frame(&mut OneByteReader(&stream), &mut buf).unwrap(),
+            20
+        );
+        assert_eq!(buf, [7; 20]);
+    }
+
+    #[test]
+    fn frame_larger_than_buffer_is_rejected() {
+        let mut stream = Vec::new();
+        write_frame(&mut stream, &[1; 8]).unwrap();
+
+        let mut buf = [0; 4];
+        assert_eq!(
+            read_frame(&mut stream.as_slice(), &mut buf)
+                .unwrap_err()
+                .kind(),
+            std::io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn tcp_locator_to_socket_address() {
+        let locator = tcp_v4_locator(Ipv4Addr::new(192, 168, 1, 2), 7410);
+        assert_eq!(
+            tcp_locator_socket_address(&locator),
+            Some(SocketAddr::from(([192, 168, 1, 2], 7410)))
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use super::actors::message_sender_actor::WriteMessage;
+use crate::rtps::{
+    messages::{
+        overall_structure::{Submessage, RTPS_MESSAGE_HEADER_SIZE},
+        submessages::info_destination::InfoDestinationSubmessage,
+    },
+    types::GUIDPREFIX_UNKNOWN,
+};
+
+struct PackedMessage {
+    message: WriteMessage,
+    size: usize,
+    is_packable: bool,
+    has_info_destination: bool,
+}
+
+/// Combines the messages going to the same destination into a single RTPS message as long
+/// as the result fits in the maximum message size. Messages containing DATA_FRAG submessages
+/// are sent on their own so that they keep the size limit used for the fragmentation.
+pub struct SubmessagePacker {
+    max_message_size: usize,
+    messages: Vec<PackedMessage>,
+}
+
+impl SubmessagePacker {
+    pub fn new(max_message_size: usize) -> Self {
+        Self {
+            max_message_size,
+            messages: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, message: WriteMessage) {
+        let size: usize = message.submessages.iter().map(|s| s.size()).sum();
+        let is_packable = !message.submessages.iter().any(|s| s.is_data_frag());
+        let has_info_destination = message.submessages.iter().any(|s| s.is_info_destination());
+        let starts_with_info_destination = message
+            .submessages
+            .first()
+            .is_some_and(|s| s.is_info_destination());
+
+        // Only the last message sharing a destination with the new one can be extended and
+        // only if it goes to exactly the same destinations, otherwise the order of the
+        // submessages seen by one of the destinations would change
+        if let Some(packed) = self
+            .messages
+            .iter_mut()
+            .rev()
+            .find(|p| {
+                p.message
+                    .destination_locator_list
+                    .iter()
+                    .any(|l| message.destination_locator_list.contains(l))
+            })
+            .filter(|p| {
+                p.message.destination_locator_list == message.destination_locator_list
+                    && p.is_packable
+                    && is_packable
+            })
+        {
+            // The destination set by a previous INFO_DST applies to all the following
+            // submessages so it has to be reset when the new message does not set one
+            let info_destination_reset: Option<Box<dyn Submessage + Send>> =
+                if packed.has_info_destination && !starts_with_info_destination {
+                    Some(Box::new(InfoDestinationSubmessage::new(GUIDPREFIX_UNKNOWN)))
+                } else {
+                    None
+                };
+            let info_destination_reset_size = info_destination_reset
+                .as_ref()
+                .map(|s| s.size())
+                .unwrap_or(0);
+
+            if packed.size + info_destination_reset_size + size <= self.max_message_size {
+                packed.message.submessages.extend(info_destination_reset);
+                packed.message.submessages.extend(message.submessages);
+                packed.size += info_destination_reset_size + size;
+                packed.has_info_destination = has_info_destination;
+                return;
+            }
+        }
+
+        self.messages.push(PackedMessage {
+            message,
+            size: RTPS_MESSAGE_HEADER_SIZE + size,
+            is_packable,
+            has_info_destination,
+        });
+    }
+
+    pub fn into_messages(self) -> impl Iterator<Item = WriteMessage> {
+        self.messages.into_iter().map(|p| p.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rtps::{
+        messages::{
+            submessage_elements::{Data, ParameterList, SequenceNumberSet, SerializedDataFragment},
+            submessages::{
+                data::DataSubmessage, data_frag::DataFragSubmessage, gap::GapSubmessage,
+            },
+        },
+        types::{Locator, ENTITYID_UNKNOWN, LOCATOR_KIND_UDP_V4},
+    };
+
+    const MAX_MESSAGE_SIZE: usize = 1344;
+
+    fn locator(port: u32) -> Locator {
+        Locator::new(LOCATOR_KIND_UDP_V4, port, [0; 16])
+    }
+
+    fn data(sequence_number: i64, size: usize) -> Box<dyn Submessage + Send> {
+        Box::new(DataSubmessage::new(
+            false,
+            true,
+            false,
+            false,
+            ENTITYID_UNKNOWN,
+            ENTITYID_UNKNOWN,
+            sequence_number,
+            ParameterList::empty(),
+            Data::new(vec![0; size].into()),
+        ))
+    }
+
+    fn data_frag() -> Box<dyn Submessage + Send> {
+        Box::new(DataFragSubmessage::new(
+            false,
+            false,
+            false,
+            ENTITYID_UNKNOWN,
+            ENTITYID_UNKNOWN,
+            1,
+            1,
+            1,
+            4,
+            8,
+            ParameterList::empty(),
+            SerializedDataFragment::default(),
+        ))
+    }
+
+    fn gap() -> Box<dyn Submessage + Send> {
+        Box::new(GapSubmessage::new(
+            ENTITYID_UNKNOWN,
+            ENTITYID_UNKNOWN,
+            1,
+            SequenceNumberSet::new(2, []),
+        ))
+    }
+
+    fn info_destination() -> Box<dyn Submessage + Send> {
+        Box::new(InfoDestinationSubmessage::new([1; 12]))
+    }
+
+    fn write_message(
+        submessages: Vec<Box<dyn Submessage + Send>>,
+        destination: Locator,
+    ) -> WriteMessage {
+        WriteMessage {
+            submessages,
+            destination_locator_list: vec![destination],
+        }
+    }
+
+    #[test]
+    fn messages_to_same_destination_are_packed() {
+        let mut packer = SubmessagePacker::new(MAX_MESSAGE_SIZE);
+        packer.add(write_message(vec![data(1, 10)], locator(1)));
+        packer.add(write_message(vec![data(2, 10)], locator(2)));
+        packer.add(write_message(vec![data(3, 10), gap()], locator(1)));
+
+        let messages: Vec<_> = packer.into_messages().collect();
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].destination_locator_list, vec![locator(1)]);
+        assert_eq!(messages[0].submessages.len(), 3);
+        assert_eq!(messages[1].destination_locator_list, vec![locator(2)]);
+        assert_eq!(messages[1].submessages.len(), 1);
+    }
+
+    #[test]
+    fn messages_to_overlapping_destinations_keep_their_order() {
+        let mut packer = SubmessagePacker::new(MAX_MESSAGE_SIZE);
+        packer.add(write_message(vec![data(1, 10)], locator(1)));
+        packer.add(WriteMessage {
+            submessages: vec![data(2, 10)],
+            destination_locator_list: vec![locator(1), locator(2)],
+        });
+        packer.add(write_message(vec![data(3, 10)], locator(1)));
+
+        let messages: Vec<_> = packer.into_messages().collect();
+
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0].destination_locator_list, vec![locator(1)]);
+        assert_eq!(
+            messages[1].destination_locator_list,
+            vec![locator(1), locator(2)]
+        );
+        assert_eq!(messages[2].destination_locator_list, vec![locator(1)]);
+    }
+
+    #[test]
+    fn packed_message_does_not_exceed_max_message_size() {
+        let data_size = data(1, 100).size();
+        let mut packer = SubmessagePacker::new(RTPS_MESSAGE_HEADER_SIZE + 2 * data_size);
+        for sequence_number in 1..=5 {
+            packer.add(write_message(vec![data(sequence_number, 100)], locator(1)));
+        }
+
+        let number_of_submessages: Vec<_> = packer
+            .into_messages()
+            .map(|m| m.submessages.len())
+            .collect();
+
+        assert_eq!(number_of_submessages, vec![2, 2, 1]);
+    }
+
+    #[test]
+    fn messages_with_data_frag_are_not_packed() {
+        let mut packer = SubmessagePacker::new(MAX_MESSAGE_SIZE);
+        packer.add(write_message(vec![data(1, 10)], locator(1)));
+        packer.add(write_message(vec![data_frag()], locator(1)));
+        packer.add(write_message(vec![data(2, 10)], locator(1)));
+
+        let number_of_submessages: Vec<_> = packer
+            .into_messages()
+            .map(|m| m.submessages.len())
+            .collect();
+
+        assert_eq!(number_of_submessages, vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn info_destination_is_reset_for_message_without_destination() {
+        let mut packer = SubmessagePacker::new(MAX_MESSAGE_SIZE);
+        packer.add(write_message(
+            vec![info_destination(), data(1, 10)],
+            locator(1),
+        ));
+        packer.add(write_message(vec![gap()], locator(1)));
+        packer.add(write_message(
+            vec![info_destination(), data(2, 10)],
+            locator(1),
+        ));
+
+        let messages: Vec<_> = packer.into_messages().collect();
+
+        assert_eq!(messages.len(), 1);
+        let is_info_destination: Vec<_> = messages[0]
+            .submessages
+            .iter()
+            .map(|s| s.is_info_destination())
+            .collect();
+        assert_eq!(
+            is_info_destination,
+            vec![true, false, true, false, true, false]
+        );
+    }
+}
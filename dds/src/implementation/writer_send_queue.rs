@@ -0,0 +1,119 @@
+use std::collections::VecDeque;
+
+use super::actors::message_sender_actor::WriteMessage;
+
+/// Priority of the messages of a writer. Messages with a higher priority are sent first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendPriority {
+    Retransmit,
+    NewData,
+    Heartbeat,
+}
+
+struct QueuedMessage {
+    message: WriteMessage,
+    payload_size: usize,
+}
+
+/// Queue holding the messages of a writer until they are handed to the message sender.
+/// Reliable retransmits are sent before new samples and those before heartbeats.
+/// The queue is bounded: the writer stops generating messages once it is full and the
+/// write operation waits for it to have space.
+pub struct WriterSendQueue {
+    capacity: usize,
+    retransmits: VecDeque<QueuedMessage>,
+    new_data: VecDeque<QueuedMessage>,
+    heartbeats: VecDeque<QueuedMessage>,
+}
+
+impl WriterSendQueue {
+    pub const DEFAULT_CAPACITY: usize = 64;
+
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            retransmits: VecDeque::new(),
+            new_data: VecDeque::new(),
+            heartbeats: VecDeque::new(),
+        }
+    }
+
+    /// Adds a message to the queue. The payload size is the number of bytes of user data
+    /// in the message which is accounted for by the flow controller.
+    pub fn push(&mut self, priority: SendPriority, message: WriteMessage, payload_size: usize) {
+        let queued_message = QueuedMessage {
+            message,
+            payload_size,
+        };
+        match priority {
+            SendPriority::Retransmit => self.retransmits.push_back(queued_message),
+            SendPriority::NewData => self.new_data.push_back(queued_message),
+            SendPriority::Heartbeat => self.heartbeats.push_back(queued_message),
+        }
+    }
+
+    /// Removes the message with the highest priority from the queue returning it
+    /// together with its payload size
+    pub fn pop(&mut self) -> Option<(WriteMessage, usize)> {
+        self.retransmits
+            .pop_front()
+            .or_else(|| self.new_data.pop_front())
+            .or_else(|| self.heartbeats.pop_front())
+            .map(|m| (m.message, m.payload_size))
+    }
+
+    pub fn len(&self) -> usize {
+        self.retransmits.len() + self.new_data.len() + self.heartbeats.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() >= self.capacity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rtps::types::{Locator, LOCATOR_KIND_UDP_V4};
+
+    fn message(port: u32) -> WriteMessage {
+        WriteMessage {
+            submessages: vec![],
+            destination_locator_list: vec![Locator::new(LOCATOR_KIND_UDP_V4, port, [0; 16])],
+        }
+    }
+
+    fn port(message: &WriteMessage) -> u32 {
+        message.destination_locator_list[0].port()
+    }
+
+    #[test]
+    fn messages_are_sent_in_priority_order() {
+        let mut send_queue = WriterSendQueue::new(10);
+        send_queue.push(SendPriority::Heartbeat, message(1), 0);
+        send_queue.push(SendPriority::NewData, message(2), 10);
+        send_queue.push(SendPriority::Retransmit, message(3), 10);
+        send_queue.push(SendPriority::NewData, message(4), 10);
+
+        let order: Vec<_> = std::iter::from_fn(|| send_queue.pop())
+            .map(|(m, _)| port(&m))
+            .collect();
+        assert_eq!(order, vec![3, 2, 4, 1]);
+    }
+
+    #[test]
+    fn queue_is_full_when_capacity_is_reached() {
+        let mut send_queue = WriterSendQueue::new(2);
+        send_queue.push(SendPriority::NewData, message(1), 10);
+        assert!(!send_queue.is_full());
+        send_queue.push(SendPriority::Heartbeat, message(2), 0);
+        assert!(send_queue.is_full());
+
+        assert_eq!(send_queue.pop().map(|(_, size)| size), Some(10));
+        assert!(!send_queue.is_full());
+    }
+}
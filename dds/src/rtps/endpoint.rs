@@ -1,6 +1,6 @@
 use super::{
     entity::RtpsEntity,
-    types::{Guid, Locator, TopicKind},
+    types::{Guid, GuidPrefix, Locator, TopicKind},
 };
 
 pub struct RtpsEndpoint {
@@ -30,6 +30,10 @@ impl RtpsEndpoint {
     pub fn guid(&self) -> Guid {
         self.entity.guid()
     }
+
+    pub fn set_guid_prefix(&mut self, guid_prefix: GuidPrefix) {
+        self.entity.set_guid_prefix(guid_prefix)
+    }
 }
 
 impl RtpsEndpoint {
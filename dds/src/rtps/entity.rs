@@ -1,4 +1,4 @@
-use super::types::Guid;
+use super::types::{Guid, GuidPrefix};
 
 pub struct RtpsEntity {
     guid: Guid,
@@ -14,4 +14,8 @@ impl RtpsEntity {
     pub fn guid(&self) -> Guid {
         self.guid
     }
+
+    pub fn set_guid_prefix(&mut self, guid_prefix: GuidPrefix) {
+        self.guid = Guid::new(guid_prefix, self.guid.entity_id());
+    }
 }
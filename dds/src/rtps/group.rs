@@ -1,4 +1,7 @@
-use super::{entity::RtpsEntity, types::Guid};
+use super::{
+    entity::RtpsEntity,
+    types::{Guid, GuidPrefix},
+};
 
 pub struct RtpsGroup {
     entity: RtpsEntity,
@@ -16,4 +19,8 @@ impl RtpsGroup {
     pub fn guid(&self) -> Guid {
         self.entity.guid()
     }
+
+    pub fn set_guid_prefix(&mut self, guid_prefix: GuidPrefix) {
+        self.entity.set_guid_prefix(guid_prefix)
+    }
 }
@@ -12,8 +12,9 @@ pub struct MessageReceiver {
     source_vendor_id: VendorId,
     source_guid_prefix: GuidPrefix,
     dest_guid_prefix: GuidPrefix,
-    _unicast_reply_locator_list: Vec<Locator>,
-    _multicast_reply_locator_list: Vec<Locator>,
+    local_guid_prefix: GuidPrefix,
+    unicast_reply_locator_list: Vec<Locator>,
+    multicast_reply_locator_list: Vec<Locator>,
     have_timestamp: bool,
     timestamp: messages::types::Time,
     submessages: std::vec::IntoIter<RtpsSubmessageReadKind>,
@@ -31,12 +32,28 @@ impl Iterator for MessageReceiver {
                 | RtpsSubmessageReadKind::Gap(_)
                 | RtpsSubmessageReadKind::Heartbeat(_)
                 | RtpsSubmessageReadKind::HeartbeatFrag(_)
-                | RtpsSubmessageReadKind::NackFrag(_) => return Some(submessage),
+                | RtpsSubmessageReadKind::NackFrag(_) => {
+                    // Entity submessages following an InfoDestination for another
+                    // participant are not meant for this one and are dropped
+                    if self.dest_guid_prefix == GUIDPREFIX_UNKNOWN
+                        || self.dest_guid_prefix == self.local_guid_prefix
+                    {
+                        return Some(submessage);
+                    }
+                }
 
                 RtpsSubmessageReadKind::InfoDestination(m) => {
                     self.dest_guid_prefix = m.guid_prefix();
                 }
-                RtpsSubmessageReadKind::InfoReply(_) => todo!(),
+                RtpsSubmessageReadKind::InfoReply(m) => {
+                    self.unicast_reply_locator_list = m.unicast_locator_list().value().to_vec();
+                    if m.multicast_flag() {
+                        self.multicast_reply_locator_list =
+                            m.multicast_locator_list().value().to_vec();
+                    } else {
+                        self.multicast_reply_locator_list.clear();
+                    }
+                }
                 RtpsSubmessageReadKind::InfoSource(m) => {
                     self.source_vendor_id = m.vendor_id();
                     self.source_version = m.protocol_version();
@@ -59,15 +76,16 @@ impl Iterator for MessageReceiver {
 }
 
 impl MessageReceiver {
-    pub fn new(message: RtpsMessageRead) -> Self {
+    pub fn new(message: RtpsMessageRead, local_guid_prefix: GuidPrefix) -> Self {
         let header = message.header();
         Self {
             source_version: header.version(),
             source_vendor_id: header.vendor_id(),
             source_guid_prefix: header.guid_prefix(),
             dest_guid_prefix: GUIDPREFIX_UNKNOWN,
-            _unicast_reply_locator_list: Vec::new(),
-            _multicast_reply_locator_list: Vec::new(),
+            local_guid_prefix,
+            unicast_reply_locator_list: Vec::new(),
+            multicast_reply_locator_list: Vec::new(),
             have_timestamp: false,
             timestamp: TIME_INVALID,
             submessages: message.submessages().into_iter(),
@@ -90,12 +108,12 @@ impl MessageReceiver {
         self.dest_guid_prefix
     }
 
-    pub fn _unicast_reply_locator_list(&self) -> &[Locator] {
-        self._unicast_reply_locator_list.as_ref()
+    pub fn unicast_reply_locator_list(&self) -> &[Locator] {
+        self.unicast_reply_locator_list.as_ref()
     }
 
     pub fn _multicast_reply_locator_list(&self) -> &[Locator] {
-        self._multicast_reply_locator_list.as_ref()
+        self.multicast_reply_locator_list.as_ref()
     }
 
     pub fn source_timestamp(&self) -> Option<messages::types::Time> {
@@ -106,3 +124,173 @@ impl MessageReceiver {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rtps::{
+        messages::{
+            overall_structure::{RtpsMessageHeader, RtpsMessageWrite, Submessage},
+            submessage_elements::{Data, LocatorList, ParameterList},
+            submessages::{
+                data::DataSubmessage, info_destination::InfoDestinationSubmessage,
+                info_reply::InfoReplySubmessage, info_source::InfoSourceSubmessage,
+            },
+        },
+        types::{
+            EntityId, SequenceNumber, LOCATOR_KIND_UDP_V4, PROTOCOLVERSION, PROTOCOLVERSION_2_3,
+            USER_DEFINED_READER_NO_KEY, USER_DEFINED_WRITER_NO_KEY, VENDOR_ID_S2E,
+        },
+    };
+
+    const LOCAL_GUID_PREFIX: GuidPrefix = [1; 12];
+    const OTHER_GUID_PREFIX: GuidPrefix = [2; 12];
+    const HEADER_GUID_PREFIX: GuidPrefix = [3; 12];
+
+    fn data_submessage(writer_sn: SequenceNumber) -> Box<dyn Submessage + Send> {
+        Box::new(DataSubmessage::new(
+            false,
+            true,
+            false,
+            false,
+            EntityId::new([1, 2, 3], USER_DEFINED_READER_NO_KEY),
+            EntityId::new([6, 7, 8], USER_DEFINED_WRITER_NO_KEY),
+            writer_sn,
+            ParameterList::empty(),
+            Data::new(vec![].into()),
+        ))
+    }
+
+    fn message_receiver(submessages: &[Box<dyn Submessage + Send>]) -> MessageReceiver {
+        let header = RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, HEADER_GUID_PREFIX);
+        let message = RtpsMessageWrite::new(&header, submessages);
+        let message = RtpsMessageRead::try_from(message.buffer()).unwrap();
+        MessageReceiver::new(message, LOCAL_GUID_PREFIX)
+    }
+
+    fn received_data_sequence_numbers(
+        submessages: &[Box<dyn Submessage + Send>],
+    ) -> Vec<SequenceNumber> {
+        message_receiver(submessages)
+            .filter_map(|s| match s {
+                RtpsSubmessageReadKind::Data(d) => Some(d.writer_sn()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn data_after_info_destination_for_other_participant_is_dropped() {
+        let submessages = [
+            data_submessage(1),
+            Box::new(InfoDestinationSubmessage::new(OTHER_GUID_PREFIX)),
+            data_submessage(2),
+            Box::new(InfoDestinationSubmessage::new(LOCAL_GUID_PREFIX)),
+            data_submessage(3),
+        ];
+
+        assert_eq!(received_data_sequence_numbers(&submessages), vec![1, 3]);
+    }
+
+    #[test]
+    fn data_after_info_destination_with_unknown_prefix_is_delivered() {
+        let submessages = [
+            Box::new(InfoDestinationSubmessage::new(OTHER_GUID_PREFIX)),
+            data_submessage(1),
+            Box::new(InfoDestinationSubmessage::new(GUIDPREFIX_UNKNOWN)),
+            data_submessage(2),
+        ];
+
+        assert_eq!(received_data_sequence_numbers(&submessages), vec![2]);
+    }
+
+    #[test]
+    fn info_reply_sets_reply_locator_lists() {
+        let unicast_locator = Locator::new(LOCATOR_KIND_UDP_V4, 7400, [1; 16]);
+        let multicast_locator = Locator::new(LOCATOR_KIND_UDP_V4, 7401, [2; 16]);
+        let submessages = [
+            Box::new(InfoReplySubmessage::new(
+                true,
+                LocatorList::new(vec![unicast_locator]),
+                LocatorList::new(vec![multicast_locator]),
+            )),
+            data_submessage(1),
+        ];
+        let mut message_receiver = message_receiver(&submessages);
+
+        assert!(matches!(
+            message_receiver.next(),
+            Some(RtpsSubmessageReadKind::Data(_))
+        ));
+        assert_eq!(
+            message_receiver.unicast_reply_locator_list(),
+            &[unicast_locator]
+        );
+        assert_eq!(
+            message_receiver._multicast_reply_locator_list(),
+            &[multicast_locator]
+        );
+    }
+
+    #[test]
+    fn info_reply_without_multicast_flag_clears_multicast_reply_locator_list() {
+        let unicast_locator1 = Locator::new(LOCATOR_KIND_UDP_V4, 7400, [1; 16]);
+        let unicast_locator2 = Locator::new(LOCATOR_KIND_UDP_V4, 7402, [3; 16]);
+        let multicast_locator = Locator::new(LOCATOR_KIND_UDP_V4, 7401, [2; 16]);
+        let submessages = [
+            Box::new(InfoReplySubmessage::new(
+                true,
+                LocatorList::new(vec![unicast_locator1]),
+                LocatorList::new(vec![multicast_locator]),
+            )),
+            data_submessage(1),
+            Box::new(InfoReplySubmessage::new(
+                false,
+                LocatorList::new(vec![unicast_locator2]),
+                LocatorList::new(vec![]),
+            )),
+            data_submessage(2),
+        ];
+        let mut message_receiver = message_receiver(&submessages);
+        message_receiver.next();
+        message_receiver.next();
+
+        assert_eq!(
+            message_receiver.unicast_reply_locator_list(),
+            &[unicast_locator2]
+        );
+        assert!(message_receiver._multicast_reply_locator_list().is_empty());
+    }
+
+    #[test]
+    fn data_after_info_source_is_attributed_to_info_source_participant() {
+        let info_source_guid_prefix = [5; 12];
+        let info_source_vendor_id = [9, 9];
+        let submessages = [
+            data_submessage(1),
+            Box::new(InfoSourceSubmessage::_new(
+                PROTOCOLVERSION_2_3,
+                info_source_vendor_id,
+                info_source_guid_prefix,
+            )),
+            data_submessage(2),
+        ];
+        let mut message_receiver = message_receiver(&submessages);
+
+        message_receiver.next();
+        assert_eq!(message_receiver.source_guid_prefix(), HEADER_GUID_PREFIX);
+        assert_eq!(message_receiver._source_vendor_id(), VENDOR_ID_S2E);
+        assert_eq!(message_receiver._source_version(), PROTOCOLVERSION);
+
+        match message_receiver.next() {
+            Some(RtpsSubmessageReadKind::Data(d)) => assert_eq!(d.writer_sn(), 2),
+            _ => panic!("Expected Data submessage"),
+        }
+        assert_eq!(
+            message_receiver.source_guid_prefix(),
+            info_source_guid_prefix
+        );
+        assert_eq!(message_receiver._source_vendor_id(), info_source_vendor_id);
+        assert_eq!(message_receiver._source_version(), PROTOCOLVERSION_2_3);
+    }
+}
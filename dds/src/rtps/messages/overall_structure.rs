@@ -15,7 +15,7 @@ use super::{
                 INFO_SRC, INFO_TS, NACK_FRAG, PAD,
             },
         },
-        types::{GuidPrefix, ProtocolVersion, VendorId},
+        types::{GuidPrefix, ProtocolVersion, VendorId, PROTOCOLVERSION},
     },
     types::{ProtocolId, SubmessageFlag, SubmessageKind},
 };
@@ -49,9 +49,38 @@ pub trait WriteIntoBytes {
 pub trait Submessage {
     fn write_submessage_header_into_bytes(&self, octets_to_next_header: u16, buf: &mut dyn Write);
     fn write_submessage_elements_into_bytes(&self, buf: &mut dyn Write);
+    fn is_heartbeat(&self) -> bool {
+        false
+    }
+    fn is_info_destination(&self) -> bool {
+        false
+    }
+    fn is_data_frag(&self) -> bool {
+        false
+    }
+}
+
+struct ByteCounter(usize);
+
+impl Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
 }
 
 impl dyn Submessage + Send + '_ {
+    /// Number of bytes taken by the submessage in an RTPS message including its header
+    pub fn size(&self) -> usize {
+        let mut counter = ByteCounter(0);
+        self.write_submessage_elements_into_bytes(&mut counter);
+        SUBMESSAGE_HEADER_SIZE + counter.0
+    }
+
     fn write_submessage_into_bytes(&self, buf: &mut Cursor<Vec<u8>>) {
         let header_position: u64 = buf.position();
         let elements_position = header_position + 4;
@@ -65,6 +94,9 @@ impl dyn Submessage + Send + '_ {
     }
 }
 
+pub const RTPS_MESSAGE_HEADER_SIZE: usize = 20;
+const SUBMESSAGE_HEADER_SIZE: usize = 4;
+
 pub struct SubmessageHeaderRead {
     submessage_id: u8,
     flags: [SubmessageFlag; 8],
@@ -91,7 +123,13 @@ impl SubmessageHeaderRead {
                 true => Endianness::LittleEndian,
                 false => Endianness::BigEndian,
             };
-            let submessage_length = u16::try_read_from_bytes(&mut &data[2..], &endianness)?;
+            let mut submessage_length = u16::try_read_from_bytes(&mut &data[2..], &endianness)?;
+            // An octetsToNextHeader of 0 means that the submessage extends to the end of the
+            // message, except for PAD and INFO_TS which are allowed to have no elements
+            if submessage_length == 0 && submessage_id != PAD && submessage_id != INFO_TS {
+                submessage_length = u16::try_from(data.len() - 4)
+                    .map_err(|_| RtpsError::new(RtpsErrorKind::InvalidData, "Submessage length"))?;
+            }
             data.consume(4);
             Ok(Self {
                 submessage_id,
@@ -128,6 +166,7 @@ impl SubmessageHeaderRead {
 pub struct RtpsMessageRead {
     header: RtpsMessageHeader,
     submessages: Vec<RtpsSubmessageReadKind>,
+    length: usize,
 }
 
 impl RtpsMessageRead {
@@ -135,6 +174,10 @@ impl RtpsMessageRead {
         self.header
     }
 
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
     pub fn submessages(self) -> Vec<RtpsSubmessageReadKind> {
         self.submessages
     }
@@ -144,10 +187,19 @@ impl TryFrom<&[u8]> for RtpsMessageRead {
     type Error = RtpsError;
 
     fn try_from(mut v: &[u8]) -> RtpsResult<Self> {
+        let length = v.len();
         if v.len() >= 20 {
             if b"RTPS" == &[v[0], v[1], v[2], v[3]] {
                 let major = v[4];
                 let minor = v[5];
+                // Messages of a different major version can not be interpreted while any
+                // minor version is compatible
+                if major != PROTOCOLVERSION.major() {
+                    return Err(RtpsError::new(
+                        RtpsErrorKind::InvalidData,
+                        "Unsupported RTPS protocol version",
+                    ));
+                }
                 let version = ProtocolVersion::new(major, minor);
                 let vendor_id = [v[6], v[7]];
                 let guid_prefix = [
@@ -220,6 +272,7 @@ impl TryFrom<&[u8]> for RtpsMessageRead {
                 Ok(Self {
                     header,
                     submessages,
+                    length,
                 })
             } else {
                 Err(RtpsError::new(
@@ -369,7 +422,10 @@ mod tests {
     use crate::rtps::{
         messages::{
             submessage_elements::{Data, Parameter, ParameterList},
-            submessages::{data::DataSubmessage, info_timestamp::InfoTimestampSubmessage},
+            submessages::{
+                data::DataSubmessage, heartbeat::HeartbeatSubmessage,
+                info_timestamp::InfoTimestampSubmessage,
+            },
             types::Time,
         },
         types::{EntityId, USER_DEFINED_READER_GROUP, USER_DEFINED_READER_NO_KEY},
@@ -503,6 +559,82 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn submessage_size_matches_serialized_length() {
+        let header = RtpsMessageHeader {
+            version: ProtocolVersion::new(2, 3),
+            vendor_id: [9, 8],
+            guid_prefix: [3; 12],
+        };
+        let info_timestamp_submessage: Box<dyn Submessage + Send> =
+            Box::new(InfoTimestampSubmessage::new(false, Time::new(4, 0)));
+        let data_submessage: Box<dyn Submessage + Send> = Box::new(DataSubmessage::new(
+            false,
+            true,
+            false,
+            false,
+            EntityId::new([1, 2, 3], USER_DEFINED_READER_NO_KEY),
+            EntityId::new([6, 7, 8], USER_DEFINED_READER_GROUP),
+            5,
+            ParameterList::empty(),
+            Data::new(vec![1, 2, 3, 4, 5].into()),
+        ));
+        let size =
+            RTPS_MESSAGE_HEADER_SIZE + info_timestamp_submessage.size() + data_submessage.size();
+
+        let value = RtpsMessageWrite::new(&header, &[info_timestamp_submessage, data_submessage]);
+
+        assert_eq!(value.buffer().len(), size);
+    }
+
+    #[test]
+    fn rtps_message_write_and_read_round_trip() {
+        let header = RtpsMessageHeader::new(ProtocolVersion::new(2, 4), [9, 8], [3; 12]);
+        let data_submessage = DataSubmessage::new(
+            true,
+            true,
+            false,
+            false,
+            EntityId::new([1, 2, 3], USER_DEFINED_READER_NO_KEY),
+            EntityId::new([6, 7, 8], USER_DEFINED_READER_GROUP),
+            5,
+            ParameterList::new(vec![Parameter::new(6, vec![10, 11, 12, 13].into())]),
+            Data::new(vec![0, 1, 0, 0, 1, 2, 3, 4].into()),
+        );
+        let heartbeat_submessage = HeartbeatSubmessage::new(
+            false,
+            true,
+            EntityId::new([1, 2, 3], USER_DEFINED_READER_NO_KEY),
+            EntityId::new([6, 7, 8], USER_DEFINED_READER_GROUP),
+            1,
+            5,
+            2,
+        );
+
+        let message = RtpsMessageWrite::new(
+            &header,
+            &[
+                Box::new(InfoTimestampSubmessage::new(false, Time::new(4, 0))),
+                Box::new(data_submessage.clone()),
+                Box::new(heartbeat_submessage.clone()),
+            ],
+        );
+        let rtps_message = RtpsMessageRead::try_from(message.buffer()).unwrap();
+
+        assert_eq!(rtps_message.header(), header);
+        assert_eq!(
+            rtps_message.submessages(),
+            vec![
+                RtpsSubmessageReadKind::InfoTimestamp(InfoTimestampSubmessage::new(
+                    false,
+                    Time::new(4, 0)
+                )),
+                RtpsSubmessageReadKind::Data(data_submessage),
+                RtpsSubmessageReadKind::Heartbeat(heartbeat_submessage),
+            ]
+        );
+    }
+
     #[test]
     fn deserialize_rtps_message_no_submessage() {
         let header = RtpsMessageHeader {
@@ -631,4 +763,76 @@ mod tests {
         let rtps_message = RtpsMessageRead::try_from(&data[..]).unwrap();
         assert_eq!(expected_submessages, rtps_message.submessages());
     }
+
+    #[test]
+    fn deserialize_rtps_message_last_submessage_extends_to_end() {
+        #[rustfmt::skip]
+        let data = [
+            b'R', b'T', b'P', b'S', // Protocol
+            2, 3, 9, 8, // ProtocolVersion | VendorId
+            3, 3, 3, 3, // GuidPrefix
+            3, 3, 3, 3, // GuidPrefix
+            3, 3, 3, 3, // GuidPrefix
+            0x09, 0b_0000_0001, 8, 0, // Submessage header
+            4, 0, 0, 0, // Time
+            0, 0, 0, 0, // Time
+            0x15, 0b_0000_0011, 0, 0, // Submessage header
+            0, 0, 16, 0, // extraFlags, octetsToInlineQos
+            1, 2, 3, 4, // readerId: value[4]
+            6, 7, 8, 9, // writerId: value[4]
+            0, 0, 0, 0, // writerSN: high
+            5, 0, 0, 0, // writerSN: low
+            6, 0, 4, 0, // inlineQos: parameterId_1, length_1
+            10, 11, 12, 13, // inlineQos: value_1[length_1]
+            1, 0, 0, 0, // inlineQos: Sentinel
+        ];
+
+        let submessages = RtpsMessageRead::try_from(&data[..]).unwrap().submessages();
+        assert_eq!(submessages.len(), 2);
+        assert!(matches!(
+            submessages[0],
+            RtpsSubmessageReadKind::InfoTimestamp(..)
+        ));
+        assert_eq!(
+            submessages[1],
+            RtpsSubmessageReadKind::Data(DataSubmessage::new(
+                true,
+                false,
+                false,
+                false,
+                EntityId::new([1, 2, 3], 4),
+                EntityId::new([6, 7, 8], 9),
+                5,
+                ParameterList::new(vec![Parameter::new(6, vec![10, 11, 12, 13].into())]),
+                Data::default(),
+            ))
+        );
+    }
+
+    #[test]
+    fn deserialize_rtps_message_with_newer_minor_version() {
+        #[rustfmt::skip]
+        let data = [
+            b'R', b'T', b'P', b'S', // Protocol
+            2, 9, 9, 8, // ProtocolVersion | VendorId
+            3, 3, 3, 3, // GuidPrefix
+            3, 3, 3, 3, // GuidPrefix
+            3, 3, 3, 3, // GuidPrefix
+        ];
+        let rtps_message = RtpsMessageRead::try_from(&data[..]).unwrap();
+        assert_eq!(rtps_message.header().version(), ProtocolVersion::new(2, 9));
+    }
+
+    #[test]
+    fn deserialize_rtps_message_with_unsupported_major_version() {
+        #[rustfmt::skip]
+        let data = [
+            b'R', b'T', b'P', b'S', // Protocol
+            3, 0, 9, 8, // ProtocolVersion | VendorId
+            3, 3, 3, 3, // GuidPrefix
+            3, 3, 3, 3, // GuidPrefix
+            3, 3, 3, 3, // GuidPrefix
+        ];
+        assert!(RtpsMessageRead::try_from(&data[..]).is_err());
+    }
 }
@@ -129,9 +129,19 @@ impl FragmentNumberSet {
         }
     }
 
+    pub fn set(&self) -> impl Iterator<Item = FragmentNumber> + '_ {
+        self.set.iter().copied()
+    }
+
     pub fn try_read_from_bytes(data: &mut &[u8], endianness: &Endianness) -> RtpsResult<Self> {
         let base = FragmentNumber::try_read_from_bytes(data, endianness)?;
         let num_bits = u32::try_read_from_bytes(data, endianness)?;
+        if num_bits > 256 {
+            return Err(RtpsError::new(
+                RtpsErrorKind::InvalidData,
+                "Maximum number of bits in FragmentNumberSet is 256",
+            ));
+        }
         let number_of_bitmap_elements = ((num_bits + 31) / 32) as usize; //In standard referred to as "M"
         let mut bitmap = [0; 8];
 
@@ -466,6 +476,29 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn serialize_fragment_number_set_empty() {
+        let fragment_number_set = FragmentNumberSet::new(2, []);
+        #[rustfmt::skip]
+        assert_eq!(write_into_bytes_vec(fragment_number_set), vec![
+            2, 0, 0, 0, // bitmapBase: (unsigned long)
+            0, 0, 0, 0, // numBits (unsigned long)
+        ]);
+    }
+
+    #[test]
+    fn serialize_fragment_number_set_multiple_bitmap_words() {
+        let fragment_number_set = FragmentNumberSet::new(10, [10, 50, 100]);
+        #[rustfmt::skip]
+        assert_eq!(write_into_bytes_vec(fragment_number_set), vec![
+            10, 0, 0, 0, // bitmapBase: (unsigned long)
+            91, 0, 0, 0, // numBits (unsigned long)
+            0b000_0000, 0b_0000_0000, 0b_0000_0000, 0b_1000_0000, // bitmap[0] (long)
+            0b000_0000, 0b_0000_0000, 0b_1000_0000, 0b_0000_0000, // bitmap[1] (long)
+            0b010_0000, 0b_0000_0000, 0b_0000_0000, 0b_0000_0000, // bitmap[2] (long)
+        ]);
+    }
+
     #[test]
     fn serialize_locator_list() {
         let locator_1 = Locator::new(1, 2, [3; 16]);
@@ -567,6 +600,41 @@ mod tests {
         assert_eq!(expected, result);
     }
 
+    #[test]
+    fn deserialize_fragment_number_set_empty() {
+        let expected = FragmentNumberSet::new(2, []);
+        #[rustfmt::skip]
+        let result = FragmentNumberSet::try_read_from_bytes(&mut &[
+            2, 0, 0, 0, // bitmapBase: (unsigned long)
+            0, 0, 0, 0, // numBits (unsigned long)
+        ][..], &Endianness::LittleEndian).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn deserialize_fragment_number_set_multiple_bitmap_words() {
+        let expected = FragmentNumberSet::new(10, [10, 50, 100]);
+        #[rustfmt::skip]
+        let result = FragmentNumberSet::try_read_from_bytes(&mut &[
+            10, 0, 0, 0, // bitmapBase: (unsigned long)
+            91, 0, 0, 0, // numBits (unsigned long)
+            0b000_0000, 0b_0000_0000, 0b_0000_0000, 0b_1000_0000, // bitmap[0] (long)
+            0b000_0000, 0b_0000_0000, 0b_1000_0000, 0b_0000_0000, // bitmap[1] (long)
+            0b010_0000, 0b_0000_0000, 0b_0000_0000, 0b_0000_0000, // bitmap[2] (long)
+        ][..], &Endianness::LittleEndian).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn deserialize_fragment_number_set_too_many_bits() {
+        #[rustfmt::skip]
+        let result = FragmentNumberSet::try_read_from_bytes(&mut &[
+            2, 0, 0, 0, // bitmapBase: (unsigned long)
+            0xff, 0xff, 0xff, 0xff, // numBits (unsigned long)
+        ][..], &Endianness::LittleEndian);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn deserialize_guid_prefix() {
         let expected = [1; 12];
@@ -213,6 +213,10 @@ impl Submessage for DataFragSubmessage {
         }
         self.serialized_payload.write_into_bytes(buf);
     }
+
+    fn is_data_frag(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
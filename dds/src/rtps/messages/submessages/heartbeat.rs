@@ -107,6 +107,10 @@ impl Submessage for HeartbeatSubmessage {
         self.last_sn.write_into_bytes(buf);
         self.count.write_into_bytes(buf);
     }
+
+    fn is_heartbeat(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
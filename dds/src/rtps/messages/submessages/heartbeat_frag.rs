@@ -43,11 +43,11 @@ impl HeartbeatFragSubmessage {
         self.writer_id
     }
 
-    pub fn _writer_sn(&self) -> SequenceNumber {
+    pub fn writer_sn(&self) -> SequenceNumber {
         self.writer_sn
     }
 
-    pub fn _last_fragment_num(&self) -> FragmentNumber {
+    pub fn last_fragment_num(&self) -> FragmentNumber {
         self.last_fragment_num
     }
 
@@ -57,7 +57,7 @@ impl HeartbeatFragSubmessage {
 }
 
 impl HeartbeatFragSubmessage {
-    pub fn _new(
+    pub fn new(
         reader_id: EntityId,
         writer_id: EntityId,
         writer_sn: SequenceNumber,
@@ -99,7 +99,7 @@ mod tests {
 
     #[test]
     fn serialize_heart_beat() {
-        let submessage = HeartbeatFragSubmessage::_new(
+        let submessage = HeartbeatFragSubmessage::new(
             EntityId::new([1, 2, 3], USER_DEFINED_READER_NO_KEY),
             EntityId::new([6, 7, 8], USER_DEFINED_READER_GROUP),
             5,
@@ -142,8 +142,8 @@ mod tests {
 
         assert_eq!(expected_reader_id, submessage._reader_id());
         assert_eq!(expected_writer_id, submessage.writer_id());
-        assert_eq!(expected_writer_sn, submessage._writer_sn());
-        assert_eq!(expected_last_fragment_num, submessage._last_fragment_num());
+        assert_eq!(expected_writer_sn, submessage.writer_sn());
+        assert_eq!(expected_last_fragment_num, submessage.last_fragment_num());
         assert_eq!(expected_count, submessage.count());
     }
 }
@@ -49,6 +49,10 @@ impl Submessage for InfoDestinationSubmessage {
     fn write_submessage_elements_into_bytes(&self, buf: &mut dyn Write) {
         self.guid_prefix.write_into_bytes(buf);
     }
+
+    fn is_info_destination(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
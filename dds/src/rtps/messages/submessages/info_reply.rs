@@ -38,23 +38,27 @@ impl InfoReplySubmessage {
         })
     }
 
-    pub fn _multicast_flag(&self) -> bool {
+    pub fn multicast_flag(&self) -> bool {
         self.multicast_flag
     }
 
-    pub fn _unicast_locator_list(&self) -> &LocatorList {
+    pub fn unicast_locator_list(&self) -> &LocatorList {
         &self.unicast_locator_list
     }
 
-    pub fn _multicast_locator_list(&self) -> &LocatorList {
+    pub fn multicast_locator_list(&self) -> &LocatorList {
         &self.multicast_locator_list
     }
 }
 
 impl Submessage for InfoReplySubmessage {
     fn write_submessage_header_into_bytes(&self, octets_to_next_header: u16, buf: &mut dyn Write) {
-        SubmessageHeaderWrite::new(SubmessageKind::INFO_REPLY, &[], octets_to_next_header)
-            .write_into_bytes(buf);
+        SubmessageHeaderWrite::new(
+            SubmessageKind::INFO_REPLY,
+            &[self.multicast_flag],
+            octets_to_next_header,
+        )
+        .write_into_bytes(buf);
     }
 
     fn write_submessage_elements_into_bytes(&self, buf: &mut dyn Write) {
@@ -66,7 +70,7 @@ impl Submessage for InfoReplySubmessage {
 }
 
 impl InfoReplySubmessage {
-    pub fn _new(
+    pub fn new(
         multicast_flag: SubmessageFlag,
         unicast_locator_list: LocatorList,
         multicast_locator_list: LocatorList,
@@ -89,7 +93,7 @@ mod tests {
     #[test]
     fn serialize_info_reply() {
         let locator = Locator::new(11, 12, [1; 16]);
-        let submessage = InfoReplySubmessage::_new(
+        let submessage = InfoReplySubmessage::new(
             false,
             LocatorList::new(vec![locator]),
             LocatorList::new(vec![]),
@@ -108,6 +112,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn serialize_info_reply_with_multicast() {
+        let locator1 = Locator::new(11, 12, [1; 16]);
+        let locator2 = Locator::new(11, 12, [2; 16]);
+        let submessage = InfoReplySubmessage::new(
+            true,
+            LocatorList::new(vec![locator1]),
+            LocatorList::new(vec![locator2]),
+        );
+        #[rustfmt::skip]
+        assert_eq!(write_submessage_into_bytes_vec(&submessage), vec![
+                0x0f, 0b_0000_0011, 56, 0, // Submessage header
+                1, 0, 0, 0, //numLocators
+                11, 0, 0, 0, //kind
+                12, 0, 0, 0, //port
+                1, 1, 1, 1, //address
+                1, 1, 1, 1, //address
+                1, 1, 1, 1, //address
+                1, 1, 1, 1, //address
+                1, 0, 0, 0, //numLocators
+                11, 0, 0, 0, //kind
+                12, 0, 0, 0, //port
+                2, 2, 2, 2, //address
+                2, 2, 2, 2, //address
+                2, 2, 2, 2, //address
+                2, 2, 2, 2, //address
+            ]
+        );
+    }
+
     #[test]
     fn deserialize_info_reply() {
         #[rustfmt::skip]
@@ -128,14 +162,14 @@ mod tests {
         let expected_unicast_locator_list = LocatorList::new(vec![locator]);
         let expected_multicast_locator_list = LocatorList::new(vec![]);
 
-        assert_eq!(expected_multicast_flag, submessage._multicast_flag());
+        assert_eq!(expected_multicast_flag, submessage.multicast_flag());
         assert_eq!(
             &expected_unicast_locator_list,
-            submessage._unicast_locator_list()
+            submessage.unicast_locator_list()
         );
         assert_eq!(
             &expected_multicast_locator_list,
-            submessage._multicast_locator_list()
+            submessage.multicast_locator_list()
         );
     }
 
@@ -167,14 +201,14 @@ mod tests {
         let expected_unicast_locator_list = LocatorList::new(vec![]);
         let expected_multicast_locator_list = LocatorList::new(vec![locator1, locator2]);
 
-        assert_eq!(expected_multicast_flag, submessage._multicast_flag());
+        assert_eq!(expected_multicast_flag, submessage.multicast_flag());
         assert_eq!(
             &expected_unicast_locator_list,
-            submessage._unicast_locator_list()
+            submessage.unicast_locator_list()
         );
         assert_eq!(
             &expected_multicast_locator_list,
-            submessage._multicast_locator_list()
+            submessage.multicast_locator_list()
         );
     }
 }
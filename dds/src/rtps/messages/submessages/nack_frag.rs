@@ -40,7 +40,7 @@ impl NackFragSubmessage {
         self.reader_id
     }
 
-    pub fn _writer_id(&self) -> EntityId {
+    pub fn writer_id(&self) -> EntityId {
         self.writer_id
     }
 
@@ -48,7 +48,7 @@ impl NackFragSubmessage {
         self.writer_sn
     }
 
-    pub fn _fragment_number_state(&self) -> &FragmentNumberSet {
+    pub fn fragment_number_state(&self) -> &FragmentNumberSet {
         &self.fragment_number_state
     }
 
@@ -144,11 +144,11 @@ mod tests {
         let expected_count = 6;
 
         assert_eq!(expected_reader_id, submessage.reader_id());
-        assert_eq!(expected_writer_id, submessage._writer_id());
+        assert_eq!(expected_writer_id, submessage.writer_id());
         assert_eq!(expected_writer_sn, submessage.writer_sn());
         assert_eq!(
             &expected_fragment_number_state,
-            submessage._fragment_number_state()
+            submessage.fragment_number_state()
         );
         assert_eq!(expected_count, submessage.count());
     }
@@ -217,3 +217,42 @@ pub type UExtension4 = [Octet; 4];
 /// Type used to hold an undefined 8-byte value. It is intended to be used in future revisions of the specification.
 #[allow(dead_code)]
 pub type WExtension8 = [Octet; 8];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rtps::messages::overall_structure::write_into_bytes_vec;
+
+    #[test]
+    fn count_round_trip() {
+        let count: Count = 0x1234_5678;
+        let data = write_into_bytes_vec(count);
+        assert_eq!(data, vec![0x78, 0x56, 0x34, 0x12]);
+        assert_eq!(
+            Count::try_read_from_bytes(&mut data.as_slice(), &Endianness::LittleEndian).unwrap(),
+            count
+        );
+        assert_eq!(
+            Count::try_read_from_bytes(&mut &[0x12, 0x34, 0x56, 0x78][..], &Endianness::BigEndian)
+                .unwrap(),
+            count
+        );
+    }
+
+    #[test]
+    fn time_round_trip() {
+        let time = Time::new(4, 0x8000_0000);
+        let data = write_into_bytes_vec(time);
+        assert_eq!(
+            data,
+            vec![
+                4, 0, 0, 0, // seconds
+                0, 0, 0, 0x80, // fraction
+            ]
+        );
+        assert_eq!(
+            Time::try_read_from_bytes(&mut data.as_slice(), &Endianness::LittleEndian).unwrap(),
+            time
+        );
+    }
+}
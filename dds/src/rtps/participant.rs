@@ -38,6 +38,10 @@ impl RtpsParticipant {
         self.entity.guid()
     }
 
+    pub fn set_guid_prefix(&mut self, guid_prefix: GuidPrefix) {
+        self.entity.set_guid_prefix(guid_prefix)
+    }
+
     pub fn protocol_version(&self) -> ProtocolVersion {
         self.protocol_version
     }
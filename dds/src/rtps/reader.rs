@@ -1,7 +1,7 @@
 use super::{
     behavior_types::Duration,
     endpoint::RtpsEndpoint,
-    types::{Guid, Locator},
+    types::{Guid, GuidPrefix, Locator},
     writer_proxy::RtpsWriterProxy,
 };
 use crate::implementation::{
@@ -34,6 +34,10 @@ impl RtpsReader {
         self.endpoint.guid()
     }
 
+    pub fn set_guid_prefix(&mut self, guid_prefix: GuidPrefix) {
+        self.endpoint.set_guid_prefix(guid_prefix)
+    }
+
     pub fn unicast_locator_list(&self) -> &[Locator] {
         self.endpoint.unicast_locator_list()
     }
@@ -121,6 +125,13 @@ impl RtpsReaderKind {
         }
     }
 
+    pub fn set_guid_prefix(&mut self, guid_prefix: GuidPrefix) {
+        match self {
+            RtpsReaderKind::Stateful(r) => r.rtps_reader.set_guid_prefix(guid_prefix),
+            RtpsReaderKind::Stateless(r) => r.rtps_reader.set_guid_prefix(guid_prefix),
+        }
+    }
+
     pub fn unicast_locator_list(&self) -> &[Locator] {
         match self {
             RtpsReaderKind::Stateful(r) => r.rtps_reader.unicast_locator_list(),
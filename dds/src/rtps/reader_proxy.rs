@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use super::{
     messages::{
         submessages::{heartbeat::HeartbeatSubmessage, heartbeat_frag::HeartbeatFragSubmessage},
@@ -42,6 +44,23 @@ impl HeartbeatMachine {
             self.count,
         )
     }
+    pub fn generate_new_liveliness_heartbeat(
+        &mut self,
+        writer_id: EntityId,
+        first_sn: SequenceNumber,
+        last_sn: SequenceNumber,
+    ) -> HeartbeatSubmessage {
+        self.count = self.count.wrapping_add(1);
+        HeartbeatSubmessage::new(
+            true,
+            true,
+            self.reader_id,
+            writer_id,
+            first_sn,
+            last_sn,
+            self.count,
+        )
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -57,14 +76,14 @@ impl HeartbeatFragMachine {
             reader_id,
         }
     }
-    pub fn _submessage(
+    pub fn submessage(
         &mut self,
         writer_id: EntityId,
         writer_sn: SequenceNumber,
         last_fragment_num: FragmentNumber,
     ) -> HeartbeatFragSubmessage {
         self.count = self.count.wrapping_add(1);
-        HeartbeatFragSubmessage::_new(
+        HeartbeatFragSubmessage::new(
             self.reader_id,
             writer_id,
             writer_sn,
@@ -80,9 +99,11 @@ pub struct RtpsReaderProxy {
     remote_group_entity_id: EntityId,
     unicast_locator_list: Vec<Locator>,
     multicast_locator_list: Vec<Locator>,
+    reply_locator_list: Vec<Locator>,
     highest_sent_seq_num: SequenceNumber,
     highest_acked_seq_num: SequenceNumber,
     requested_changes: Vec<SequenceNumber>,
+    requested_fragments: BTreeMap<SequenceNumber, Vec<FragmentNumber>>,
     expects_inline_qos: bool,
     is_active: bool,
     last_received_acknack_count: Count,
@@ -112,9 +133,11 @@ impl RtpsReaderProxy {
             remote_group_entity_id,
             unicast_locator_list: unicast_locator_list.to_vec(),
             multicast_locator_list: multicast_locator_list.to_vec(),
+            reply_locator_list: Vec::new(),
             highest_sent_seq_num: 0,
             highest_acked_seq_num: 0,
             requested_changes: Vec::new(),
+            requested_fragments: BTreeMap::new(),
             expects_inline_qos,
             is_active,
             last_received_acknack_count: 0,
@@ -134,6 +157,18 @@ impl RtpsReaderProxy {
         self.unicast_locator_list.as_slice()
     }
 
+    pub fn reply_locator_list(&self) -> &[Locator] {
+        if self.reply_locator_list.is_empty() {
+            self.unicast_locator_list.as_slice()
+        } else {
+            self.reply_locator_list.as_slice()
+        }
+    }
+
+    pub fn set_reply_locator_list(&mut self, reply_locator_list: Vec<Locator>) {
+        self.reply_locator_list = reply_locator_list;
+    }
+
     pub fn reliability(&self) -> ReliabilityKind {
         self.reliability
     }
@@ -142,7 +177,7 @@ impl RtpsReaderProxy {
         &mut self.heartbeat_machine
     }
 
-    pub fn _heartbeat_frag_machine(&mut self) -> &mut HeartbeatFragMachine {
+    pub fn heartbeat_frag_machine(&mut self) -> &mut HeartbeatFragMachine {
         &mut self.heartbeat_frag_machine
     }
 
@@ -204,6 +239,28 @@ impl RtpsReaderProxy {
         }
     }
 
+    pub fn requested_fragments_set(
+        &mut self,
+        seq_num: SequenceNumber,
+        req_fragment_num_set: impl Iterator<Item = FragmentNumber>,
+    ) {
+        let requested_fragments = self.requested_fragments.entry(seq_num).or_default();
+        for fragment_num in req_fragment_num_set {
+            if !requested_fragments.contains(&fragment_num) {
+                requested_fragments.push(fragment_num);
+            }
+        }
+        if requested_fragments.is_empty() {
+            self.requested_fragments.remove(&seq_num);
+        }
+    }
+
+    pub fn next_requested_fragments(&mut self) -> Option<(SequenceNumber, Vec<FragmentNumber>)> {
+        let (seq_num, mut fragment_nums) = self.requested_fragments.pop_first()?;
+        fragment_nums.sort();
+        Some((seq_num, fragment_nums))
+    }
+
     pub fn unacked_changes(&self, highest_available_seq_num: Option<SequenceNumber>) -> bool {
         // highest_available_seq_num := MAX { change.sequenceNumber }
         // highest_acked_seq_num := MAX { this.acknowledged_changes }
@@ -251,3 +308,99 @@ impl RtpsReaderProxy {
         self.last_received_nack_frag_count = count;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rtps::types::{ENTITYID_UNKNOWN, GUID_UNKNOWN};
+
+    fn reader_proxy() -> RtpsReaderProxy {
+        RtpsReaderProxy::new(
+            GUID_UNKNOWN,
+            ENTITYID_UNKNOWN,
+            &[],
+            &[],
+            false,
+            true,
+            ReliabilityKind::Reliable,
+            0,
+        )
+    }
+
+    #[test]
+    fn next_requested_fragments_returns_lowest_sequence_number_first() {
+        let mut reader_proxy = reader_proxy();
+        reader_proxy.requested_fragments_set(3, [5, 2].into_iter());
+        reader_proxy.requested_fragments_set(1, [4].into_iter());
+        reader_proxy.requested_fragments_set(3, [2, 1].into_iter());
+
+        assert_eq!(reader_proxy.next_requested_fragments(), Some((1, vec![4])));
+        assert_eq!(
+            reader_proxy.next_requested_fragments(),
+            Some((3, vec![1, 2, 5]))
+        );
+        assert_eq!(reader_proxy.next_requested_fragments(), None);
+    }
+
+    #[test]
+    fn empty_fragment_request_is_not_queued() {
+        let mut reader_proxy = reader_proxy();
+        reader_proxy.requested_fragments_set(1, std::iter::empty());
+
+        assert_eq!(reader_proxy.next_requested_fragments(), None);
+    }
+
+    #[test]
+    fn reply_locator_list_defaults_to_unicast_locator_list() {
+        let unicast_locator = Locator::new(1, 7400, [1; 16]);
+        let reply_locator = Locator::new(1, 7410, [2; 16]);
+        let mut reader_proxy = RtpsReaderProxy::new(
+            GUID_UNKNOWN,
+            ENTITYID_UNKNOWN,
+            &[unicast_locator],
+            &[],
+            false,
+            true,
+            ReliabilityKind::Reliable,
+            0,
+        );
+        assert_eq!(reader_proxy.reply_locator_list(), &[unicast_locator]);
+
+        reader_proxy.set_reply_locator_list(vec![reply_locator]);
+        assert_eq!(reader_proxy.reply_locator_list(), &[reply_locator]);
+
+        reader_proxy.set_reply_locator_list(vec![]);
+        assert_eq!(reader_proxy.reply_locator_list(), &[unicast_locator]);
+    }
+
+    #[test]
+    fn heartbeat_is_due_once_per_heartbeat_period() {
+        let heartbeat_period = std::time::Duration::from_millis(50);
+        let mut reader_proxy = reader_proxy();
+
+        let heartbeat =
+            reader_proxy
+                .heartbeat_machine()
+                .generate_new_heartbeat(ENTITYID_UNKNOWN, 2, 5);
+        assert_eq!(heartbeat.first_sn(), 2);
+        assert_eq!(heartbeat.last_sn(), 5);
+        assert_eq!(heartbeat.count(), 1);
+        assert!(!reader_proxy
+            .heartbeat_machine()
+            .is_time_for_heartbeat(heartbeat_period));
+
+        std::thread::sleep(heartbeat_period);
+        assert!(reader_proxy
+            .heartbeat_machine()
+            .is_time_for_heartbeat(heartbeat_period));
+
+        let heartbeat =
+            reader_proxy
+                .heartbeat_machine()
+                .generate_new_heartbeat(ENTITYID_UNKNOWN, 2, 6);
+        assert_eq!(heartbeat.count(), 2);
+        assert!(!reader_proxy
+            .heartbeat_machine()
+            .is_time_for_heartbeat(heartbeat_period));
+    }
+}
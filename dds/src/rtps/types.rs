@@ -281,6 +281,7 @@ pub const LOCATOR_KIND_INVALID: Long = -1;
 pub const LOCATOR_KIND_RESERVED: Long = 0;
 pub const LOCATOR_KIND_UDP_V4: Long = 1;
 pub const LOCATOR_KIND_UDP_V6: Long = 2;
+pub const LOCATOR_KIND_TCP_V4: Long = 4;
 pub const LOCATOR_PORT_INVALID: UnsignedLong = 0;
 pub const LOCATOR_ADDRESS_INVALID: [Octet; 16] = [0; 16];
 
@@ -432,7 +433,7 @@ impl ProtocolVersion {
             bytes: [major, minor],
         }
     }
-    pub const fn _major(&self) -> Octet {
+    pub const fn major(&self) -> Octet {
         self.bytes[0]
     }
     pub const fn _minor(&self) -> Octet {
@@ -494,4 +495,16 @@ mod tests {
         ]
         );
     }
+
+    #[test]
+    fn entity_id_round_trip_preserves_every_entity_kind() {
+        for entity_kind in Octet::MIN..=Octet::MAX {
+            let entity_id = EntityId::new([1, 2, 3], entity_kind);
+            let data = write_into_bytes_vec(entity_id);
+            let result =
+                EntityId::try_read_from_bytes(&mut data.as_slice(), &Endianness::LittleEndian)
+                    .unwrap();
+            assert_eq!(result, entity_id);
+        }
+    }
 }
@@ -5,7 +5,7 @@ use super::{
         self,
         submessage_elements::{Data, ParameterList},
     },
-    types::{ChangeKind, Guid, Locator, SequenceNumber},
+    types::{ChangeKind, Guid, GuidPrefix, Locator, SequenceNumber},
     writer_history_cache::RtpsWriterCacheChange,
 };
 
@@ -43,6 +43,10 @@ impl RtpsWriter {
         self.endpoint.guid()
     }
 
+    pub fn set_guid_prefix(&mut self, guid_prefix: GuidPrefix) {
+        self.endpoint.set_guid_prefix(guid_prefix)
+    }
+
     pub fn unicast_locator_list(&self) -> &[Locator] {
         self.endpoint.unicast_locator_list()
     }
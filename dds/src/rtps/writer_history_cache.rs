@@ -2,8 +2,8 @@ use super::{
     behavior_types::InstanceHandle,
     messages::{
         self,
-        submessage_elements::{Data, ParameterList},
-        submessages::data::DataSubmessage,
+        submessage_elements::{Data, ParameterList, SerializedDataFragment},
+        submessages::{data::DataSubmessage, data_frag::DataFragSubmessage},
     },
     types::{ChangeKind, EntityId, Guid, SequenceNumber},
 };
@@ -20,15 +20,11 @@ pub struct RtpsWriterCacheChange {
 
 impl RtpsWriterCacheChange {
     pub fn as_data_submessage(&self, reader_id: EntityId) -> DataSubmessage {
-        let (data_flag, key_flag) = match self.kind() {
-            ChangeKind::Alive => (true, false),
-            ChangeKind::NotAliveDisposed | ChangeKind::NotAliveUnregistered => (false, true),
-            _ => todo!(),
-        };
+        let key_flag = self.is_key_payload();
 
         DataSubmessage::new(
             true,
-            data_flag,
+            !key_flag,
             key_flag,
             false,
             reader_id,
@@ -39,9 +35,51 @@ impl RtpsWriterCacheChange {
         )
     }
 
+    pub fn as_data_frag_submessages(
+        &self,
+        reader_id: EntityId,
+        fragment_size: usize,
+    ) -> Vec<DataFragSubmessage> {
+        let data_size = self.data_value.len();
+        let number_of_fragments = data_size.div_ceil(fragment_size);
+
+        (0..number_of_fragments)
+            .map(|frag_index| {
+                // The last fragment contains whatever remains of the data which
+                // might be less than the fragment size
+                let start = frag_index * fragment_size;
+                let end = std::cmp::min(start + fragment_size, data_size);
+
+                DataFragSubmessage::new(
+                    true,
+                    false,
+                    self.is_key_payload(),
+                    reader_id,
+                    self.writer_guid().entity_id(),
+                    self.sequence_number(),
+                    (frag_index + 1) as u32,
+                    1,
+                    fragment_size as u16,
+                    data_size as u32,
+                    self.inline_qos.clone(),
+                    SerializedDataFragment::new(self.data_value.clone(), start..end),
+                )
+            })
+            .collect()
+    }
+
     pub fn instance_handle(&self) -> InstanceHandle {
         self.instance_handle
     }
+
+    fn is_key_payload(&self) -> bool {
+        match self.kind() {
+            ChangeKind::Alive | ChangeKind::AliveFiltered => false,
+            ChangeKind::NotAliveDisposed
+            | ChangeKind::NotAliveUnregistered
+            | ChangeKind::NotAliveDisposedUnregistered => true,
+        }
+    }
 }
 
 impl RtpsWriterCacheChange {
@@ -90,4 +128,77 @@ impl RtpsWriterCacheChange {
     pub fn inline_qos(&self) -> &ParameterList {
         &self.inline_qos
     }
+
+    pub fn set_inline_qos(&mut self, inline_qos: ParameterList) {
+        self.inline_qos = inline_qos;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rtps::types::{ENTITYID_UNKNOWN, USER_DEFINED_WRITER_WITH_KEY};
+    use std::sync::Arc;
+
+    #[test]
+    fn data_frag_submessages_last_fragment_smaller_than_fragment_size() {
+        let writer_guid = Guid::new(
+            [1; 12],
+            EntityId::new([1, 2, 3], USER_DEFINED_WRITER_WITH_KEY),
+        );
+        let data: Vec<u8> = (0..10).collect();
+        let cache_change = RtpsWriterCacheChange::new(
+            ChangeKind::Alive,
+            writer_guid,
+            InstanceHandle([3; 16]),
+            7,
+            messages::types::Time::new(0, 0),
+            Data::new(Arc::from(data.as_slice())),
+            ParameterList::empty(),
+        );
+
+        let data_frag_list = cache_change.as_data_frag_submessages(ENTITYID_UNKNOWN, 4);
+
+        assert_eq!(data_frag_list.len(), 3);
+        for (index, data_frag) in data_frag_list.iter().enumerate() {
+            assert_eq!(data_frag.writer_id(), writer_guid.entity_id());
+            assert_eq!(data_frag.writer_sn(), 7);
+            assert_eq!(data_frag.fragment_starting_num(), index as u32 + 1);
+            assert_eq!(data_frag.fragments_in_submessage(), 1);
+            assert_eq!(data_frag.fragment_size(), 4);
+            assert_eq!(data_frag.data_size(), 10);
+            assert!(!data_frag.key_flag());
+        }
+        assert_eq!(
+            data_frag_list[0].serialized_payload().as_ref(),
+            &[0, 1, 2, 3]
+        );
+        assert_eq!(
+            data_frag_list[1].serialized_payload().as_ref(),
+            &[4, 5, 6, 7]
+        );
+        assert_eq!(data_frag_list[2].serialized_payload().as_ref(), &[8, 9]);
+    }
+
+    #[test]
+    fn data_frag_submessages_of_disposed_change_have_key_flag() {
+        let writer_guid = Guid::new(
+            [1; 12],
+            EntityId::new([1, 2, 3], USER_DEFINED_WRITER_WITH_KEY),
+        );
+        let cache_change = RtpsWriterCacheChange::new(
+            ChangeKind::NotAliveDisposedUnregistered,
+            writer_guid,
+            InstanceHandle([3; 16]),
+            1,
+            messages::types::Time::new(0, 0),
+            Data::new(Arc::from([1, 2, 3, 4, 5, 6, 7, 8].as_slice())),
+            ParameterList::empty(),
+        );
+
+        let data_frag_list = cache_change.as_data_frag_submessages(ENTITYID_UNKNOWN, 4);
+
+        assert_eq!(data_frag_list.len(), 2);
+        assert!(data_frag_list.iter().all(|d| d.key_flag()));
+    }
 }
@@ -6,7 +6,7 @@ use super::{
             ack_nack::AckNackSubmessage, data::DataSubmessage, data_frag::DataFragSubmessage,
             info_destination::InfoDestinationSubmessage, nack_frag::NackFragSubmessage,
         },
-        types::Count,
+        types::{Count, FragmentNumber},
     },
     types::{EntityId, Guid, Locator, SequenceNumber},
 };
@@ -68,6 +68,16 @@ impl RtpsWriterProxy {
     }
 
     pub fn push_data_frag(&mut self, submessage: DataFragSubmessage) {
+        // A fragment size of zero is invalid and would make it impossible
+        // to know how many fragments are expected for the data
+        if submessage.fragment_size() == 0 {
+            return;
+        }
+        // Fragments of a change which is already available are re-sent fragments which are
+        // not needed. Buffering them would make the change look incomplete again.
+        if submessage.writer_sn() <= self.available_changes_max() {
+            return;
+        }
         let frag_bug_seq_num = self.frag_buffer.entry(submessage.writer_sn()).or_default();
         if !frag_bug_seq_num.contains(&submessage) {
             frag_bug_seq_num.push(submessage);
@@ -211,6 +221,10 @@ impl RtpsWriterProxy {
         self.last_received_heartbeat_count = last_received_heartbeat_count;
     }
 
+    pub fn last_received_heartbeat_frag_count(&self) -> Count {
+        self.last_received_heartbeat_frag_count
+    }
+
     pub fn set_last_received_heartbeat_frag_count(
         &mut self,
         last_received_heartbeat_frag_count: Count,
@@ -231,7 +245,7 @@ impl RtpsWriterProxy {
         reader_guid: &Guid,
         message_sender_actor: &ActorAddress<MessageSenderActor>,
     ) {
-        if self.must_send_acknacks() || !self.missing_changes().count() == 0 {
+        if self.must_send_acknacks() {
             self.set_must_send_acknacks(false);
             self.increment_acknack_count();
 
@@ -252,29 +266,18 @@ impl RtpsWriterProxy {
             let mut submessages: Vec<Box<dyn Submessage + Send>> =
                 vec![Box::new(info_dst_submessage), Box::new(acknack_submessage)];
 
-            for (seq_num, owning_data_frag_list) in self.frag_buffer.iter() {
-                let total_fragments_expected = total_fragments_expected(&owning_data_frag_list[0]);
-                let mut missing_fragment_number = Vec::new();
-                for fragment_number in 1..=total_fragments_expected {
-                    if !owning_data_frag_list.iter().any(|x| {
-                        fragment_number >= x.fragment_starting_num()
-                            && fragment_number
-                                < x.fragment_starting_num() + (x.fragments_in_submessage() as u32)
-                    }) {
-                        missing_fragment_number.push(fragment_number)
-                    }
-                }
+            let mut frag_buffer_seq_num_list: Vec<_> = self.frag_buffer.keys().cloned().collect();
+            frag_buffer_seq_num_list.sort();
+            for seq_num in frag_buffer_seq_num_list {
+                let missing_fragment_number = self.missing_fragments(seq_num, FragmentNumber::MAX);
 
                 if !missing_fragment_number.is_empty() {
                     self.nack_frag_count = self.nack_frag_count.wrapping_add(1);
                     let nack_frag_submessage = NackFragSubmessage::new(
                         reader_guid.entity_id(),
                         self.remote_writer_guid().entity_id(),
-                        *seq_num,
-                        FragmentNumberSet::new(
-                            missing_fragment_number[0],
-                            missing_fragment_number.into_iter(),
-                        ),
+                        seq_num,
+                        FragmentNumberSet::new(missing_fragment_number[0], missing_fragment_number),
                         self.nack_frag_count,
                     );
 
@@ -291,8 +294,202 @@ impl RtpsWriterProxy {
         }
     }
 
+    pub fn send_nack_frag(
+        &mut self,
+        reader_guid: &Guid,
+        writer_sn: SequenceNumber,
+        last_fragment_num: FragmentNumber,
+        message_sender_actor: &ActorAddress<MessageSenderActor>,
+    ) {
+        let missing_fragment_number = self.missing_fragments(writer_sn, last_fragment_num);
+        if !missing_fragment_number.is_empty() {
+            self.nack_frag_count = self.nack_frag_count.wrapping_add(1);
+
+            let info_dst_submessage =
+                InfoDestinationSubmessage::new(self.remote_writer_guid().prefix());
+
+            let nack_frag_submessage = NackFragSubmessage::new(
+                reader_guid.entity_id(),
+                self.remote_writer_guid().entity_id(),
+                writer_sn,
+                FragmentNumberSet::new(missing_fragment_number[0], missing_fragment_number),
+                self.nack_frag_count,
+            );
+
+            message_sender_actor
+                .send_actor_mail(message_sender_actor::WriteMessage {
+                    submessages: vec![
+                        Box::new(info_dst_submessage),
+                        Box::new(nack_frag_submessage),
+                    ],
+                    destination_locator_list: self.unicast_locator_list().to_vec(),
+                })
+                .ok();
+        }
+    }
+
+    pub fn missing_fragments(
+        &self,
+        seq_num: SequenceNumber,
+        last_fragment_num: FragmentNumber,
+    ) -> Vec<FragmentNumber> {
+        let mut missing_fragments: Box<dyn Iterator<Item = FragmentNumber>> =
+            match self.frag_buffer.get(&seq_num) {
+                Some(owning_data_frag_list) => {
+                    let last_fragment_num = std::cmp::min(
+                        last_fragment_num,
+                        total_fragments_expected(&owning_data_frag_list[0]),
+                    );
+                    Box::new((1..=last_fragment_num).filter(|fragment_number| {
+                        !owning_data_frag_list.iter().any(|x| {
+                            *fragment_number >= x.fragment_starting_num()
+                                && *fragment_number
+                                    < x.fragment_starting_num()
+                                        + (x.fragments_in_submessage() as u32)
+                        })
+                    }))
+                }
+                // If no fragment was received for a change which is not yet available
+                // then all the fragments announced by the writer are missing
+                None if seq_num > self.available_changes_max() => Box::new(1..=last_fragment_num),
+                None => Box::new(std::iter::empty()),
+            };
+
+        // A FragmentNumberSet can only represent the 256 fragments following its base
+        // so the remaining missing fragments are left for a later NackFrag
+        match missing_fragments.next() {
+            Some(base) => std::iter::once(base)
+                .chain(missing_fragments.take_while(|f| *f < base.saturating_add(256)))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
     pub fn is_historical_data_received(&self) -> bool {
         let at_least_one_heartbeat_received = self.last_received_heartbeat_count > 0;
         at_least_one_heartbeat_received && self.missing_changes().count() == 0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rtps::{
+        messages::submessage_elements::{ParameterList, SerializedDataFragment},
+        types::{ENTITYID_UNKNOWN, USER_DEFINED_WRITER_WITH_KEY},
+    };
+
+    fn data_frag(
+        writer_id: EntityId,
+        fragment_starting_num: u32,
+        data: &[u8],
+        range: std::ops::Range<usize>,
+    ) -> DataFragSubmessage {
+        DataFragSubmessage::new(
+            true,
+            false,
+            false,
+            ENTITYID_UNKNOWN,
+            writer_id,
+            1,
+            fragment_starting_num,
+            1,
+            4,
+            data.len() as u32,
+            ParameterList::empty(),
+            SerializedDataFragment::new(Data::new(Arc::from(data)), range),
+        )
+    }
+
+    #[test]
+    fn reconstruct_data_only_after_all_fragments_received() {
+        let writer_id = EntityId::new([1, 2, 3], USER_DEFINED_WRITER_WITH_KEY);
+        let mut writer_proxy = RtpsWriterProxy::new(
+            Guid::new([1; 12], writer_id),
+            &[],
+            &[],
+            None,
+            ENTITYID_UNKNOWN,
+        );
+        let data = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+
+        writer_proxy.push_data_frag(data_frag(writer_id, 3, &data, 8..10));
+        assert!(writer_proxy.reconstruct_data_from_frag(1).is_none());
+        writer_proxy.push_data_frag(data_frag(writer_id, 1, &data, 0..4));
+        assert!(writer_proxy.reconstruct_data_from_frag(1).is_none());
+        writer_proxy.push_data_frag(data_frag(writer_id, 2, &data, 4..8));
+
+        let data_submessage = writer_proxy.reconstruct_data_from_frag(1).unwrap();
+        assert_eq!(data_submessage.serialized_payload().as_ref(), &data);
+        assert!(writer_proxy.reconstruct_data_from_frag(1).is_none());
+    }
+
+    #[test]
+    fn missing_fragments_up_to_last_fragment_num() {
+        let writer_id = EntityId::new([1, 2, 3], USER_DEFINED_WRITER_WITH_KEY);
+        let mut writer_proxy = RtpsWriterProxy::new(
+            Guid::new([1; 12], writer_id),
+            &[],
+            &[],
+            None,
+            ENTITYID_UNKNOWN,
+        );
+        let data = [0; 20];
+        writer_proxy.push_data_frag(data_frag(writer_id, 1, &data, 0..4));
+        writer_proxy.push_data_frag(data_frag(writer_id, 2, &data, 4..8));
+        writer_proxy.push_data_frag(data_frag(writer_id, 4, &data, 12..16));
+        writer_proxy.push_data_frag(data_frag(writer_id, 5, &data, 16..20));
+
+        assert_eq!(writer_proxy.missing_fragments(1, 5), vec![3]);
+        assert_eq!(writer_proxy.missing_fragments(1, 2), vec![]);
+        assert_eq!(writer_proxy.missing_fragments(2, 3), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn data_frag_with_zero_fragment_size_is_ignored() {
+        let writer_id = EntityId::new([1, 2, 3], USER_DEFINED_WRITER_WITH_KEY);
+        let mut writer_proxy = RtpsWriterProxy::new(
+            Guid::new([1; 12], writer_id),
+            &[],
+            &[],
+            None,
+            ENTITYID_UNKNOWN,
+        );
+        let data_frag = DataFragSubmessage::new(
+            true,
+            false,
+            false,
+            ENTITYID_UNKNOWN,
+            writer_id,
+            1,
+            1,
+            1,
+            0,
+            4,
+            ParameterList::empty(),
+            SerializedDataFragment::default(),
+        );
+
+        writer_proxy.push_data_frag(data_frag);
+
+        assert!(writer_proxy.reconstruct_data_from_frag(1).is_none());
+    }
+
+    #[test]
+    fn data_frag_of_available_change_is_ignored() {
+        let writer_id = EntityId::new([1, 2, 3], USER_DEFINED_WRITER_WITH_KEY);
+        let mut writer_proxy = RtpsWriterProxy::new(
+            Guid::new([1; 12], writer_id),
+            &[],
+            &[],
+            None,
+            ENTITYID_UNKNOWN,
+        );
+        let data = [0; 8];
+        writer_proxy.received_change_set(1);
+
+        writer_proxy.push_data_frag(data_frag(writer_id, 1, &data, 0..4));
+
+        assert_eq!(writer_proxy.missing_fragments(1, 2), vec![]);
+    }
+}
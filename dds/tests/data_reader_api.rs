@@ -0,0 +1,127 @@
+use dust_dds::{
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{
+        error::DdsError,
+        qos::{DataReaderQos, QosKind},
+        qos_policy::{
+            DataRepresentationQosPolicy, DeadlineQosPolicy, DestinationOrderQosPolicy,
+            DestinationOrderQosPolicyKind, DurabilityQosPolicy, DurabilityQosPolicyKind,
+            HistoryQosPolicy, HistoryQosPolicyKind, LatencyBudgetQosPolicy,
+            ReaderDataLifecycleQosPolicy, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
+            TimeBasedFilterQosPolicy, UserDataQosPolicy, XCDR2_DATA_REPRESENTATION,
+        },
+        status::NO_STATUS,
+        time::{Duration, DurationKind},
+    },
+    topic_definition::type_support::DdsType,
+};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[derive(Clone, Debug, PartialEq, DdsType)]
+struct KeyedData {
+    #[dust_dds(key)]
+    id: u8,
+    value: u32,
+}
+
+#[test]
+fn enabled_data_reader_mutable_qos_can_be_changed() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let qos = DataReaderQos {
+        deadline: DeadlineQosPolicy {
+            period: DurationKind::Finite(Duration::new(2, 0)),
+        },
+        latency_budget: LatencyBudgetQosPolicy {
+            duration: DurationKind::Finite(Duration::new(3, 0)),
+        },
+        time_based_filter: TimeBasedFilterQosPolicy {
+            minimum_separation: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        user_data: UserDataQosPolicy {
+            value: vec![1, 2, 3],
+        },
+        reader_data_lifecycle: ReaderDataLifecycleQosPolicy {
+            autopurge_nowriter_samples_delay: DurationKind::Finite(Duration::new(4, 0)),
+            autopurge_disposed_samples_delay: DurationKind::Finite(Duration::new(5, 0)),
+        },
+        ..data_reader.get_qos().unwrap()
+    };
+    data_reader.set_qos(QosKind::Specific(qos.clone())).unwrap();
+
+    assert_eq!(data_reader.get_qos().unwrap(), qos);
+}
+
+#[test]
+fn enabled_data_reader_immutable_qos_can_not_be_changed() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let original_qos = data_reader.get_qos().unwrap();
+
+    let immutable_changes = [
+        DataReaderQos {
+            durability: DurabilityQosPolicy {
+                kind: DurabilityQosPolicyKind::TransientLocal,
+            },
+            ..original_qos.clone()
+        },
+        DataReaderQos {
+            reliability: ReliabilityQosPolicy {
+                kind: ReliabilityQosPolicyKind::Reliable,
+                max_blocking_time: original_qos.reliability.max_blocking_time,
+            },
+            ..original_qos.clone()
+        },
+        DataReaderQos {
+            destination_order: DestinationOrderQosPolicy {
+                kind: DestinationOrderQosPolicyKind::BySourceTimestamp,
+            },
+            ..original_qos.clone()
+        },
+        DataReaderQos {
+            history: HistoryQosPolicy {
+                kind: HistoryQosPolicyKind::KeepAll,
+            },
+            ..original_qos.clone()
+        },
+        DataReaderQos {
+            representation: DataRepresentationQosPolicy {
+                value: vec![XCDR2_DATA_REPRESENTATION],
+            },
+            ..original_qos.clone()
+        },
+    ];
+    for qos in immutable_changes {
+        assert_eq!(
+            data_reader.set_qos(QosKind::Specific(qos)),
+            Err(DdsError::ImmutablePolicy)
+        );
+    }
+
+    assert_eq!(data_reader.get_qos().unwrap(), original_qos);
+}
@@ -3,8 +3,17 @@ use dust_dds::{
     infrastructure::{
         error::DdsError,
         qos::{DataWriterQos, QosKind},
-        qos_policy::{HistoryQosPolicy, HistoryQosPolicyKind, Length, ResourceLimitsQosPolicy},
+        qos_policy::{
+            DataRepresentationQosPolicy, DeadlineQosPolicy, DurabilityQosPolicy,
+            DurabilityQosPolicyKind, FlowControllerQosPolicy, HistoryQosPolicy,
+            HistoryQosPolicyKind, LatencyBudgetQosPolicy, Length, LifespanQosPolicy,
+            OwnershipQosPolicy, OwnershipQosPolicyKind, OwnershipStrengthQosPolicy,
+            ReliabilityQosPolicy, ReliabilityQosPolicyKind, ResourceLimitsQosPolicy,
+            TransportPriorityQosPolicy, UserDataQosPolicy, WriterDataLifecycleQosPolicy,
+            XCDR2_DATA_REPRESENTATION,
+        },
         status::NO_STATUS,
+        time::{Duration, DurationKind},
     },
     topic_definition::type_support::DdsType,
 };
@@ -125,3 +134,263 @@ fn data_writer_write_more_than_max_samples_should_fail() {
     let result = data_writer.write(&data_instance3, None);
     assert_eq!(result, Err(DdsError::OutOfResources));
 }
+
+#[test]
+fn data_writer_with_zero_flow_controller_rate_is_inconsistent() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let data_writer_qos = DataWriterQos {
+        flow_controller: FlowControllerQosPolicy {
+            max_bytes_per_second: Some(0),
+        },
+        ..Default::default()
+    };
+    let result = publisher.create_datawriter::<KeyedData>(
+        &topic,
+        QosKind::Specific(data_writer_qos),
+        None,
+        NO_STATUS,
+    );
+    assert!(matches!(result, Err(DdsError::InconsistentPolicy)));
+}
+
+#[test]
+fn data_writer_write_batch_above_max_samples_should_write_no_sample() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let data_writer_qos = DataWriterQos {
+        resource_limits: ResourceLimitsQosPolicy {
+            max_samples: Length::Limited(2),
+            max_instances: Length::Unlimited,
+            max_samples_per_instance: Length::Limited(1),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let data_writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(data_writer_qos), None, NO_STATUS)
+        .unwrap();
+    let data_instance1 = KeyedData { id: 1, value: 0 };
+    let data_instance2 = KeyedData { id: 2, value: 0 };
+    let data_instance3 = KeyedData { id: 3, value: 0 };
+
+    let result = data_writer.write_batch(
+        &[
+            data_instance1.clone(),
+            data_instance2.clone(),
+            data_instance3,
+        ],
+        None,
+    );
+    assert_eq!(result, Err(DdsError::OutOfResources));
+
+    data_writer
+        .write_batch(&[data_instance1, data_instance2], None)
+        .unwrap();
+}
+
+#[test]
+fn data_writer_write_with_registered_instance_handle() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let data_instance1 = KeyedData { id: 1, value: 0 };
+    let data_instance2 = KeyedData { id: 2, value: 0 };
+    let handle1 = data_writer
+        .register_instance(&data_instance1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        data_writer.lookup_instance(&data_instance1).unwrap(),
+        Some(handle1)
+    );
+
+    data_writer.write(&data_instance1, Some(handle1)).unwrap();
+    assert_eq!(
+        data_writer.write(&data_instance2, Some(handle1)),
+        Err(DdsError::BadParameter)
+    );
+
+    let handle2 = data_writer
+        .register_instance(&data_instance2)
+        .unwrap()
+        .unwrap();
+    assert!(matches!(
+        data_writer.write(&data_instance2, Some(handle1)),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+    data_writer.write(&data_instance2, Some(handle2)).unwrap();
+}
+
+#[test]
+fn data_writer_unregistered_instance_is_not_found() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let data_instance1 = KeyedData { id: 1, value: 0 };
+    assert!(matches!(
+        data_writer.unregister_instance(&data_instance1, None),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+
+    let handle1 = data_writer
+        .register_instance(&data_instance1)
+        .unwrap()
+        .unwrap();
+    data_writer
+        .unregister_instance(&data_instance1, Some(handle1))
+        .unwrap();
+
+    assert_eq!(data_writer.lookup_instance(&data_instance1).unwrap(), None);
+}
+
+#[test]
+fn enabled_data_writer_mutable_qos_can_be_changed() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter::<KeyedData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let qos = DataWriterQos {
+        deadline: DeadlineQosPolicy {
+            period: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        latency_budget: LatencyBudgetQosPolicy {
+            duration: DurationKind::Finite(Duration::new(2, 0)),
+        },
+        ownership_strength: OwnershipStrengthQosPolicy { value: 3 },
+        user_data: UserDataQosPolicy {
+            value: vec![1, 2, 3],
+        },
+        writer_data_lifecycle: WriterDataLifecycleQosPolicy {
+            autodispose_unregistered_instances: false,
+        },
+        lifespan: LifespanQosPolicy {
+            duration: DurationKind::Finite(Duration::new(4, 0)),
+        },
+        transport_priority: TransportPriorityQosPolicy { value: 5 },
+        ..data_writer.get_qos().unwrap()
+    };
+    data_writer.set_qos(QosKind::Specific(qos.clone())).unwrap();
+
+    assert_eq!(data_writer.get_qos().unwrap(), qos);
+}
+
+#[test]
+fn enabled_data_writer_immutable_qos_can_not_be_changed() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter::<KeyedData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let original_qos = data_writer.get_qos().unwrap();
+
+    let immutable_changes = [
+        DataWriterQos {
+            durability: DurabilityQosPolicy {
+                kind: DurabilityQosPolicyKind::TransientLocal,
+            },
+            ..original_qos.clone()
+        },
+        DataWriterQos {
+            reliability: ReliabilityQosPolicy {
+                kind: ReliabilityQosPolicyKind::BestEffort,
+                max_blocking_time: original_qos.reliability.max_blocking_time,
+            },
+            ..original_qos.clone()
+        },
+        DataWriterQos {
+            history: HistoryQosPolicy {
+                kind: HistoryQosPolicyKind::KeepAll,
+            },
+            ..original_qos.clone()
+        },
+        DataWriterQos {
+            ownership: OwnershipQosPolicy {
+                kind: OwnershipQosPolicyKind::Exclusive,
+            },
+            ..original_qos.clone()
+        },
+        DataWriterQos {
+            representation: DataRepresentationQosPolicy {
+                value: vec![XCDR2_DATA_REPRESENTATION],
+            },
+            ..original_qos.clone()
+        },
+    ];
+    for qos in immutable_changes {
+        assert_eq!(
+            data_writer.set_qos(QosKind::Specific(qos)),
+            Err(DdsError::ImmutablePolicy)
+        );
+    }
+
+    assert_eq!(data_writer.get_qos().unwrap(), original_qos);
+}
@@ -1,19 +1,57 @@
 use std::time::Instant;
 
 use dust_dds::{
+    builtin_topics::{
+        BuiltInTopicKey, ParticipantBuiltinTopicData, PublicationBuiltinTopicData,
+        SubscriptionBuiltinTopicData,
+    },
+    data_representation_builtin_endpoints::{
+        discovered_reader_data::{DiscoveredReaderData, ReaderProxy},
+        discovered_writer_data::{DiscoveredWriterData, WriterProxy},
+        spdp_discovered_participant_data::{
+            ParticipantProxy, SpdpDiscoveredParticipantData, DCPS_PARTICIPANT,
+        },
+    },
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
-        qos::{DataReaderQos, DataWriterQos, PublisherQos, QosKind, SubscriberQos},
+        error::DdsError,
+        instance::InstanceHandle,
+        qos::{DataReaderQos, DataWriterQos, PublisherQos, QosKind, SubscriberQos, TopicQos},
         qos_policy::{
-            DataRepresentationQosPolicy, OwnershipQosPolicy, OwnershipQosPolicyKind,
-            PartitionQosPolicy, UserDataQosPolicy, XCDR2_DATA_REPRESENTATION,
-            XCDR_DATA_REPRESENTATION,
+            DataRepresentationQosPolicy, DurabilityQosPolicy, DurabilityQosPolicyKind,
+            LivelinessQosPolicy, LivelinessQosPolicyKind, OwnershipQosPolicy,
+            OwnershipQosPolicyKind, PartitionQosPolicy, ReliabilityQosPolicy,
+            ReliabilityQosPolicyKind, TopicDataQosPolicy, UserDataQosPolicy,
+            DURABILITY_QOS_POLICY_ID, OWNERSHIP_QOS_POLICY_ID, RELIABILITY_QOS_POLICY_ID,
+            XCDR2_DATA_REPRESENTATION, XCDR_DATA_REPRESENTATION,
         },
-        status::{StatusKind, NO_STATUS},
-        time::Duration,
+        status::{QosPolicyCount, StatusKind, NO_STATUS},
+        time::{Duration, DurationKind},
         wait_set::{Condition, WaitSet},
     },
-    topic_definition::type_support::DdsType,
+    rtps::{
+        discovery_types::{
+            BuiltinEndpointQos, BuiltinEndpointSet, ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER,
+            ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR,
+            ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER,
+            ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_DETECTOR, ENTITYID_SPDP_BUILTIN_PARTICIPANT_READER,
+            ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER,
+        },
+        messages::{
+            overall_structure::{RtpsMessageHeader, RtpsMessageWrite},
+            submessage_elements::{Data, ParameterList},
+            submessages::data::DataSubmessage,
+        },
+        types::{
+            EntityId, Guid, Locator, ENTITYID_PARTICIPANT, ENTITYID_UNKNOWN, LOCATOR_KIND_UDP_V4,
+            PROTOCOLVERSION, PROTOCOLVERSION_2_1, USER_DEFINED_READER_NO_KEY,
+            USER_DEFINED_WRITER_NO_KEY, VENDOR_ID_S2E,
+        },
+    },
+    subscription::sample_info::{
+        InstanceStateKind, ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE,
+    },
+    topic_definition::type_support::{DdsSerialize, DdsType},
 };
 
 mod utils;
@@ -95,6 +133,58 @@ fn deleted_readers_are_disposed_from_writer() {
     assert_eq!(data_writer.get_matched_subscriptions().unwrap().len(), 0);
 }
 
+#[test]
+fn deleted_reader_subscription_data_is_not_available_to_writer() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let dp = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = dp
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = dp
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = dp
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let cond = data_writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+    data_writer.get_publication_matched_status().unwrap();
+
+    let subscription_handle = data_writer.get_matched_subscriptions().unwrap()[0];
+    assert_eq!(
+        data_writer
+            .get_matched_subscription_data(subscription_handle)
+            .unwrap()
+            .key()
+            .value[..],
+        data_reader.get_instance_handle().unwrap().as_ref()[..]
+    );
+
+    subscriber.delete_datareader(&data_reader).unwrap();
+
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    assert_eq!(
+        data_writer.get_matched_subscription_data(subscription_handle),
+        Err(DdsError::BadParameter)
+    );
+}
+
 #[test]
 fn updated_readers_are_announced_to_writer() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -219,6 +309,187 @@ fn deleted_writers_are_disposed_from_reader() {
     assert_eq!(data_reader.get_matched_publications().unwrap().len(), 0);
 }
 
+#[test]
+fn subscription_matched_status_tracks_matched_and_removed_writers() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let dp = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = dp
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = dp
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_handle = data_writer.get_instance_handle().unwrap();
+    let subscriber = dp
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let cond = data_reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let status = data_reader.get_subscription_matched_status().unwrap();
+    assert_eq!(status.total_count, 1);
+    assert_eq!(status.total_count_change, 1);
+    assert_eq!(status.current_count, 1);
+    assert_eq!(status.current_count_change, 1);
+    assert_eq!(status.last_publication_handle, writer_handle);
+
+    let status = data_reader.get_subscription_matched_status().unwrap();
+    assert_eq!(status.total_count_change, 0);
+    assert_eq!(status.current_count_change, 0);
+
+    publisher.delete_datawriter(&data_writer).unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    let status = data_reader.get_subscription_matched_status().unwrap();
+    assert_eq!(status.total_count, 1);
+    assert_eq!(status.total_count_change, 0);
+    assert_eq!(status.current_count, 0);
+    assert_eq!(status.current_count_change, -1);
+    assert_eq!(status.last_publication_handle, writer_handle);
+}
+
+#[test]
+fn reader_gets_matched_publication_data_of_writer() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let dp = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = dp
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = dp
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = dp
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let cond = data_reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+    data_reader.get_subscription_matched_status().unwrap();
+
+    let publication_handle = data_reader.get_matched_publications().unwrap()[0];
+    let publication_data = data_reader
+        .get_matched_publication_data(publication_handle)
+        .unwrap();
+    assert_eq!(
+        publication_data.key().value[..],
+        data_writer.get_instance_handle().unwrap().as_ref()[..]
+    );
+    assert_eq!(publication_data.topic_name(), "topic_name");
+    assert_eq!(publication_data.get_type_name(), "UserType");
+
+    publisher.delete_datawriter(&data_writer).unwrap();
+
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    assert_eq!(
+        data_reader.get_matched_publication_data(publication_handle),
+        Err(DdsError::BadParameter)
+    );
+}
+
+#[test]
+fn remote_participant_discovers_created_writer_and_reader_through_sedp() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+    let participant1 = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant2 = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant1
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant1
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant1
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_handle = data_writer.get_instance_handle().unwrap();
+    let reader_handle = data_reader.get_instance_handle().unwrap();
+
+    let builtin_subscriber = participant2.get_builtin_subscriber();
+    let publications_reader = builtin_subscriber
+        .lookup_datareader::<PublicationBuiltinTopicData>("DCPSPublication")
+        .unwrap()
+        .unwrap();
+    let subscriptions_reader = builtin_subscriber
+        .lookup_datareader::<SubscriptionBuiltinTopicData>("DCPSSubscription")
+        .unwrap()
+        .unwrap();
+
+    let start = Instant::now();
+    let mut publication_data = None;
+    let mut subscription_data = None;
+    while publication_data.is_none() || subscription_data.is_none() {
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(10),
+            "Writer and reader not discovered by remote participant"
+        );
+        if let Ok(samples) =
+            publications_reader.read(100, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        {
+            publication_data = samples
+                .into_iter()
+                .filter_map(|s| s.data().ok())
+                .find(|d| d.key().value == <[u8; 16]>::from(writer_handle));
+        }
+        if let Ok(samples) =
+            subscriptions_reader.read(100, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        {
+            subscription_data = samples
+                .into_iter()
+                .filter_map(|s| s.data().ok())
+                .find(|d| d.key().value == <[u8; 16]>::from(reader_handle));
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    let publication_data = publication_data.unwrap();
+    assert_eq!(publication_data.topic_name(), "topic_name");
+    assert_eq!(publication_data.get_type_name(), "UserType");
+    let subscription_data = subscription_data.unwrap();
+    assert_eq!(subscription_data.topic_name(), "topic_name");
+    assert_eq!(subscription_data.get_type_name(), "UserType");
+}
+
 #[test]
 fn updated_writers_are_announced_to_reader() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -362,6 +633,49 @@ fn participant_records_discovered_topics() {
     assert!(discovered_topic_names.contains(&"Topic 2".to_string()));
 }
 
+#[test]
+fn found_topic_has_qos_of_remote_topic() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant1 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant2 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic_qos = TopicQos {
+        topic_data: TopicDataQosPolicy {
+            value: vec![1, 2, 3],
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let _topic = participant1
+        .create_topic::<UserType>(
+            "RemoteTopic",
+            "RemoteType",
+            QosKind::Specific(topic_qos.clone()),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let found_topic = participant2
+        .find_topic::<UserType>("RemoteTopic", Duration::new(10, 0))
+        .unwrap();
+
+    assert_eq!(found_topic.get_name(), "RemoteTopic");
+    assert_eq!(found_topic.get_type_name(), "RemoteType");
+    let found_topic_qos = found_topic.get_qos().unwrap();
+    assert_eq!(found_topic_qos.topic_data, topic_qos.topic_data);
+    assert_eq!(found_topic_qos.reliability, topic_qos.reliability);
+}
+
 #[test]
 fn participant_announces_updated_qos() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -390,6 +704,47 @@ fn participant_announces_updated_qos() {
     std::thread::sleep(std::time::Duration::from_secs(5));
 }
 
+#[test]
+fn updated_participant_qos_is_announced_without_waiting_for_the_announcement_period() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant1 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant2 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant1_handle = participant1.get_instance_handle().unwrap();
+
+    let start = std::time::Instant::now();
+    while !participant2
+        .get_discovered_participants()
+        .unwrap()
+        .contains(&participant1_handle)
+    {
+        assert!(start.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    let mut qos = participant1.get_qos().unwrap();
+    qos.user_data.value = vec![1, 2, 3];
+    participant1.set_qos(QosKind::Specific(qos)).unwrap();
+
+    // The update must arrive well before the next periodic announcement
+    let start = std::time::Instant::now();
+    while participant2
+        .get_discovered_participant_data(participant1_handle)
+        .unwrap()
+        .user_data()
+        .value
+        != vec![1, 2, 3]
+    {
+        assert!(start.elapsed() < std::time::Duration::from_secs(2));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+}
+
 #[test]
 fn reader_discovers_disposed_writer_same_participant() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -592,17 +947,18 @@ fn publisher_regex_and_subscriber_partition_is_matched() {
 }
 
 #[test]
-fn publisher_regex_and_subscriber_regex_partition_is_matched() {
+fn publisher_and_subscriber_different_partition_is_not_incompatible_qos() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
     let dp = DomainParticipantFactory::get_instance()
         .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
         .unwrap();
+
     let topic = dp
         .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
         .unwrap();
     let publisher_qos = PublisherQos {
         partition: PartitionQosPolicy {
-            name: vec!["A[1-2]+".to_string()],
+            name: vec!["A".to_string()],
         },
         ..Default::default()
     };
@@ -612,14 +968,10 @@ fn publisher_regex_and_subscriber_regex_partition_is_matched() {
     let data_writer = publisher
         .create_datawriter::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
         .unwrap();
-    let cond_data_writer = data_writer.get_statuscondition();
-    cond_data_writer
-        .set_enabled_statuses(&[StatusKind::PublicationMatched])
-        .unwrap();
 
     let subscriber_qos = SubscriberQos {
         partition: PartitionQosPolicy {
-            name: vec!["A[1-2]+".to_string()],
+            name: vec!["B".to_string()],
         },
         ..Default::default()
     };
@@ -629,27 +981,39 @@ fn publisher_regex_and_subscriber_regex_partition_is_matched() {
     let data_reader = subscriber
         .create_datareader::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
         .unwrap();
-    let cond_data_reader = data_reader.get_statuscondition();
-    cond_data_reader
-        .set_enabled_statuses(&[StatusKind::SubscriptionMatched])
-        .unwrap();
-
-    let mut wait_set_data_reader = WaitSet::new();
-    wait_set_data_reader
-        .attach_condition(Condition::StatusCondition(cond_data_reader))
-        .unwrap();
+    let cond = data_reader.get_statuscondition();
+    cond.set_enabled_statuses(&[
+        StatusKind::SubscriptionMatched,
+        StatusKind::RequestedIncompatibleQos,
+    ])
+    .unwrap();
 
-    let mut wait_set_data_writer = WaitSet::new();
-    wait_set_data_writer
-        .attach_condition(Condition::StatusCondition(cond_data_writer))
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
         .unwrap();
 
-    assert!(wait_set_data_reader.wait(Duration::new(5, 0)).is_ok());
-    assert!(wait_set_data_writer.wait(Duration::new(5, 0)).is_ok());
+    // Partition is not a requested/offered policy: a partition mismatch
+    // prevents the match without raising an incompatible QoS status
+    assert!(wait_set.wait(Duration::new(5, 0)).is_err());
+    assert_eq!(
+        data_reader
+            .get_requested_incompatible_qos_status()
+            .unwrap()
+            .total_count,
+        0
+    );
+    assert_eq!(
+        data_writer
+            .get_offered_incompatible_qos_status()
+            .unwrap()
+            .total_count,
+        0
+    );
 }
 
 #[test]
-fn writer_matched_to_already_existing_reader_with_matched_writer() {
+fn reader_requesting_reliable_from_best_effort_writer_has_incompatible_qos_status() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
     let dp = DomainParticipantFactory::get_instance()
         .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
@@ -657,20 +1021,36 @@ fn writer_matched_to_already_existing_reader_with_matched_writer() {
     let topic = dp
         .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
         .unwrap();
+
     let publisher = dp
         .create_publisher(QosKind::Default, None, NO_STATUS)
         .unwrap();
-    let data_writer = publisher
-        .create_datawriter::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::BestEffort,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let _data_writer = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
         .unwrap();
+
     let subscriber = dp
         .create_subscriber(QosKind::Default, None, NO_STATUS)
         .unwrap();
-    let _data_reader = subscriber
-        .create_datareader::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let data_reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
         .unwrap();
-    let cond = data_writer.get_statuscondition();
-    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+    let cond = data_reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::RequestedIncompatibleQos])
         .unwrap();
 
     let mut wait_set = WaitSet::new();
@@ -679,13 +1059,185 @@ fn writer_matched_to_already_existing_reader_with_matched_writer() {
         .unwrap();
     wait_set.wait(Duration::new(10, 0)).unwrap();
 
-    let data_writer2 = publisher
-        .create_datawriter::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
-        .unwrap();
-    let dw2_cond = data_writer2.get_statuscondition();
-    dw2_cond
-        .set_enabled_statuses(&[StatusKind::PublicationMatched])
-        .unwrap();
+    let status = data_reader.get_requested_incompatible_qos_status().unwrap();
+    assert_eq!(status.total_count, 1);
+    assert_eq!(status.total_count_change, 1);
+    assert_eq!(status.last_policy_id, RELIABILITY_QOS_POLICY_ID);
+    assert_eq!(
+        status.policies,
+        vec![QosPolicyCount {
+            policy_id: RELIABILITY_QOS_POLICY_ID,
+            count: 1
+        }]
+    );
+    assert!(data_reader.get_matched_publications().unwrap().is_empty());
+
+    let status = data_reader.get_requested_incompatible_qos_status().unwrap();
+    assert_eq!(status.total_count, 1);
+    assert_eq!(status.total_count_change, 0);
+}
+
+#[test]
+fn writer_offering_weaker_qos_than_requested_has_incompatible_qos_status() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let dp = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = dp
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = dp
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let cond = data_writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::OfferedIncompatibleQos])
+        .unwrap();
+
+    let subscriber = dp
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::TransientLocal,
+        },
+        ownership: OwnershipQosPolicy {
+            kind: OwnershipQosPolicyKind::Exclusive,
+        },
+        ..Default::default()
+    };
+    let _data_reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let status = data_writer.get_offered_incompatible_qos_status().unwrap();
+    assert_eq!(status.total_count, 1);
+    assert_eq!(status.total_count_change, 1);
+    assert_eq!(status.last_policy_id, DURABILITY_QOS_POLICY_ID);
+    assert_eq!(
+        status.policies,
+        vec![
+            QosPolicyCount {
+                policy_id: DURABILITY_QOS_POLICY_ID,
+                count: 1
+            },
+            QosPolicyCount {
+                policy_id: OWNERSHIP_QOS_POLICY_ID,
+                count: 1
+            }
+        ]
+    );
+    assert!(data_writer.get_matched_subscriptions().unwrap().is_empty());
+
+    let status = data_writer.get_offered_incompatible_qos_status().unwrap();
+    assert_eq!(status.total_count, 1);
+    assert_eq!(status.total_count_change, 0);
+}
+
+#[test]
+fn publisher_regex_and_subscriber_regex_partition_is_matched() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let dp = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = dp
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher_qos = PublisherQos {
+        partition: PartitionQosPolicy {
+            name: vec!["A[1-2]+".to_string()],
+        },
+        ..Default::default()
+    };
+    let publisher = dp
+        .create_publisher(QosKind::Specific(publisher_qos), None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let cond_data_writer = data_writer.get_statuscondition();
+    cond_data_writer
+        .set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let subscriber_qos = SubscriberQos {
+        partition: PartitionQosPolicy {
+            name: vec!["A[1-2]+".to_string()],
+        },
+        ..Default::default()
+    };
+    let subscriber = dp
+        .create_subscriber(QosKind::Specific(subscriber_qos), None, NO_STATUS)
+        .unwrap();
+    let data_reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let cond_data_reader = data_reader.get_statuscondition();
+    cond_data_reader
+        .set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+
+    let mut wait_set_data_reader = WaitSet::new();
+    wait_set_data_reader
+        .attach_condition(Condition::StatusCondition(cond_data_reader))
+        .unwrap();
+
+    let mut wait_set_data_writer = WaitSet::new();
+    wait_set_data_writer
+        .attach_condition(Condition::StatusCondition(cond_data_writer))
+        .unwrap();
+
+    assert!(wait_set_data_reader.wait(Duration::new(5, 0)).is_ok());
+    assert!(wait_set_data_writer.wait(Duration::new(5, 0)).is_ok());
+}
+
+#[test]
+fn writer_matched_to_already_existing_reader_with_matched_writer() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let dp = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = dp
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = dp
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = dp
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let _data_reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let cond = data_writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data_writer2 = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let dw2_cond = data_writer2.get_statuscondition();
+    dw2_cond
+        .set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
     let mut wait_set2 = WaitSet::new();
     wait_set2
         .attach_condition(Condition::StatusCondition(dw2_cond))
@@ -778,6 +1330,194 @@ fn discovered_participant_removed_after_deletion() {
     }
 }
 
+#[test]
+fn deleted_participant_is_removed_without_waiting_for_lease_expiry() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant1 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant2 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant2_handle = participant2.get_instance_handle().unwrap();
+    let start_time = Instant::now();
+    while !participant1
+        .get_discovered_participants()
+        .unwrap()
+        .contains(&participant2_handle)
+    {
+        assert!(
+            start_time.elapsed() < std::time::Duration::from_secs(10),
+            "Participant not discovered before timeout"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    domain_participant_factory
+        .delete_participant(&participant2)
+        .unwrap();
+
+    // The default lease duration is 100 seconds so the removal can only be caused
+    // by the disposed announcement sent when the participant is deleted
+    let start_time = Instant::now();
+    while participant1
+        .get_discovered_participants()
+        .unwrap()
+        .contains(&participant2_handle)
+    {
+        assert!(
+            start_time.elapsed() < std::time::Duration::from_secs(2),
+            "Deleted participant not removed immediately"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    let participant_samples = participant1
+        .get_builtin_subscriber()
+        .lookup_datareader::<SpdpDiscoveredParticipantData>(DCPS_PARTICIPANT)
+        .unwrap()
+        .unwrap()
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert!(participant_samples
+        .iter()
+        .any(|s| s.sample_info().instance_state == InstanceStateKind::NotAliveDisposed));
+}
+
+#[test]
+fn publication_matched_status_decreases_when_reader_participant_is_removed() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant1 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic1 = participant1
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant1
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter::<UserType>(&topic1, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let participant2 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic2 = participant2
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant2
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_reader = subscriber
+        .create_datareader::<UserType>(&topic2, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_handle = data_reader.get_instance_handle().unwrap();
+
+    let cond = data_writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let status = data_writer.get_publication_matched_status().unwrap();
+    assert_eq!(status.total_count, 1);
+    assert_eq!(status.total_count_change, 1);
+    assert_eq!(status.current_count, 1);
+    assert_eq!(status.current_count_change, 1);
+    assert_eq!(status.last_subscription_handle, reader_handle);
+
+    participant2.delete_contained_entities().unwrap();
+    domain_participant_factory
+        .delete_participant(&participant2)
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let status = data_writer.get_publication_matched_status().unwrap();
+    assert_eq!(status.total_count, 1);
+    assert_eq!(status.total_count_change, 0);
+    assert_eq!(status.current_count, 0);
+    assert_eq!(status.current_count_change, -1);
+}
+
+#[test]
+fn participant_asserting_liveliness_keeps_manual_by_participant_writer_alive() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant1 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic1 = participant1
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant1
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        liveliness: LivelinessQosPolicy {
+            kind: LivelinessQosPolicyKind::ManualByParticipant,
+            lease_duration: DurationKind::Finite(Duration::new(0, 500_000_000)),
+        },
+        ..Default::default()
+    };
+    let data_reader = subscriber
+        .create_datareader::<UserType>(&topic1, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let participant2 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic2 = participant2
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant2
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        liveliness: LivelinessQosPolicy {
+            kind: LivelinessQosPolicyKind::ManualByParticipant,
+            lease_duration: DurationKind::Finite(Duration::new(0, 500_000_000)),
+        },
+        ..Default::default()
+    };
+    let _data_writer = publisher
+        .create_datawriter::<UserType>(&topic2, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = data_reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::LivelinessChanged])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+    assert_eq!(
+        data_reader
+            .get_liveliness_changed_status()
+            .unwrap()
+            .alive_count,
+        1
+    );
+
+    // Without the assertions the writer lease of 500 ms would expire well within this time
+    for _ in 0..20 {
+        participant2.assert_liveliness().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+
+    let status = data_reader.get_liveliness_changed_status().unwrap();
+    assert_eq!(status.alive_count, 1);
+    assert_eq!(status.not_alive_count, 0);
+}
+
 #[test]
 fn writer_offering_xcdr1_should_not_match_reader_requesting_xcdr2() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -984,3 +1724,594 @@ fn participant_removed_after_lease_duration() {
 
     assert_eq!(discovered_participant.len(), 1);
 }
+
+#[test]
+fn endpoints_of_participant_are_removed_after_lease_expires() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let metatraffic_port = participant
+        .get_builtin_subscriber()
+        .lookup_datareader::<SpdpDiscoveredParticipantData>(DCPS_PARTICIPANT)
+        .unwrap()
+        .unwrap()
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap()[0]
+        .data()
+        .unwrap()
+        .participant_proxy()
+        .metatraffic_unicast_locator_list()[0]
+        .port();
+
+    // Mock a remote participant with a short lease duration which stops announcing itself
+    let mock_socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let mock_locator = Locator::new(
+        LOCATOR_KIND_UDP_V4,
+        mock_socket.local_addr().unwrap().port() as u32,
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 127, 0, 0, 1],
+    );
+    let guid_prefix = [7; 12];
+    let mock_participant_guid = Guid::new(guid_prefix, ENTITYID_PARTICIPANT);
+    let mock_participant_key = BuiltInTopicKey::from(<[u8; 16]>::from(mock_participant_guid));
+    let spdp_discovered_participant_data = SpdpDiscoveredParticipantData::new(
+        ParticipantBuiltinTopicData::new(
+            mock_participant_key.clone(),
+            UserDataQosPolicy::default(),
+        ),
+        ParticipantProxy::new(
+            Some(domain_id),
+            String::new(),
+            PROTOCOLVERSION,
+            guid_prefix,
+            VENDOR_ID_S2E,
+            false,
+            vec![mock_locator],
+            vec![],
+            vec![mock_locator],
+            vec![],
+            BuiltinEndpointSet(
+                BuiltinEndpointSet::BUILTIN_ENDPOINT_PUBLICATIONS_ANNOUNCER
+                    | BuiltinEndpointSet::BUILTIN_ENDPOINT_SUBSCRIPTIONS_ANNOUNCER,
+            ),
+            0,
+            BuiltinEndpointQos::default(),
+        ),
+        Duration::new(2, 0),
+        vec![],
+    );
+
+    let mock_writer_guid = Guid::new(
+        guid_prefix,
+        EntityId::new([0, 0, 1], USER_DEFINED_WRITER_NO_KEY),
+    );
+    let discovered_writer_data = DiscoveredWriterData::new(
+        PublicationBuiltinTopicData::new(
+            BuiltInTopicKey::from(<[u8; 16]>::from(mock_writer_guid)),
+            mock_participant_key.clone(),
+            "topic_name".to_string(),
+            "UserType".to_string(),
+            DataWriterQos::default(),
+            PublisherQos::default(),
+            TopicDataQosPolicy::default(),
+            String::new(),
+        ),
+        WriterProxy::new(
+            mock_writer_guid,
+            ENTITYID_UNKNOWN,
+            vec![mock_locator],
+            vec![],
+            None,
+        ),
+    );
+    let mock_reader_guid = Guid::new(
+        guid_prefix,
+        EntityId::new([0, 0, 2], USER_DEFINED_READER_NO_KEY),
+    );
+    let discovered_reader_data = DiscoveredReaderData::new(
+        ReaderProxy::new(
+            mock_reader_guid,
+            ENTITYID_UNKNOWN,
+            vec![mock_locator],
+            vec![],
+            false,
+        ),
+        SubscriptionBuiltinTopicData::new(
+            BuiltInTopicKey::from(<[u8; 16]>::from(mock_reader_guid)),
+            mock_participant_key,
+            "topic_name".to_string(),
+            "UserType".to_string(),
+            DataReaderQos::default(),
+            SubscriberQos::default(),
+            TopicDataQosPolicy::default(),
+            String::new(),
+        ),
+    );
+    let sedp_message = RtpsMessageWrite::new(
+        &RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, guid_prefix),
+        &[
+            Box::new(DataSubmessage::new(
+                false,
+                true,
+                false,
+                false,
+                ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR,
+                ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER,
+                1,
+                ParameterList::empty(),
+                Data::new(discovered_writer_data.serialize_data().unwrap().into()),
+            )),
+            Box::new(DataSubmessage::new(
+                false,
+                true,
+                false,
+                false,
+                ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_DETECTOR,
+                ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER,
+                1,
+                ParameterList::empty(),
+                Data::new(discovered_reader_data.serialize_data().unwrap().into()),
+            )),
+        ],
+    );
+
+    let mock_participant_handle = InstanceHandle::new(mock_participant_guid.into());
+    let mock_writer_handle = InstanceHandle::new(mock_writer_guid.into());
+    let mock_reader_handle = InstanceHandle::new(mock_reader_guid.into());
+    let start = Instant::now();
+    let mut sequence_number = 1;
+    while !data_reader
+        .get_matched_publications()
+        .unwrap()
+        .contains(&mock_writer_handle)
+        || !data_writer
+            .get_matched_subscriptions()
+            .unwrap()
+            .contains(&mock_reader_handle)
+    {
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(10),
+            "Mock endpoints not matched"
+        );
+        let spdp_message = RtpsMessageWrite::new(
+            &RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, guid_prefix),
+            &[Box::new(DataSubmessage::new(
+                false,
+                true,
+                false,
+                false,
+                ENTITYID_SPDP_BUILTIN_PARTICIPANT_READER,
+                ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER,
+                sequence_number,
+                ParameterList::empty(),
+                Data::new(
+                    spdp_discovered_participant_data
+                        .serialize_data()
+                        .unwrap()
+                        .into(),
+                ),
+            ))],
+        );
+        sequence_number += 1;
+        mock_socket
+            .send_to(
+                spdp_message.buffer(),
+                ("127.0.0.1", metatraffic_port as u16),
+            )
+            .unwrap();
+        mock_socket
+            .send_to(
+                sedp_message.buffer(),
+                ("127.0.0.1", metatraffic_port as u16),
+            )
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    assert!(participant
+        .get_discovered_participants()
+        .unwrap()
+        .contains(&mock_participant_handle));
+
+    // The mock participant stops announcing itself so its lease expires
+    let start = Instant::now();
+    while participant
+        .get_discovered_participants()
+        .unwrap()
+        .contains(&mock_participant_handle)
+        || data_reader
+            .get_matched_publications()
+            .unwrap()
+            .contains(&mock_writer_handle)
+        || data_writer
+            .get_matched_subscriptions()
+            .unwrap()
+            .contains(&mock_reader_handle)
+    {
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(10),
+            "Mock participant not removed after lease expiry"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+}
+
+#[test]
+fn guid_prefix_is_changed_when_remote_participant_has_the_same_guid_prefix() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<UserType>("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let participant_handle = participant.get_instance_handle().unwrap();
+    let publisher_handle = publisher.get_instance_handle().unwrap();
+    let subscriber_handle = subscriber.get_instance_handle().unwrap();
+    let writer_handle = data_writer.get_instance_handle().unwrap();
+    let reader_handle = data_reader.get_instance_handle().unwrap();
+    let metatraffic_port = participant
+        .get_builtin_subscriber()
+        .lookup_datareader::<SpdpDiscoveredParticipantData>(DCPS_PARTICIPANT)
+        .unwrap()
+        .unwrap()
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap()[0]
+        .data()
+        .unwrap()
+        .participant_proxy()
+        .metatraffic_unicast_locator_list()[0]
+        .port();
+
+    // Mock a remote participant which announces itself with the GUID prefix of the local one
+    let mock_socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let mock_locator = Locator::new(
+        LOCATOR_KIND_UDP_V4,
+        mock_socket.local_addr().unwrap().port() as u32,
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 127, 0, 0, 1],
+    );
+    let guid_prefix = Guid::from(<[u8; 16]>::from(participant_handle)).prefix();
+    let spdp_discovered_participant_data = SpdpDiscoveredParticipantData::new(
+        ParticipantBuiltinTopicData::new(
+            BuiltInTopicKey::from(<[u8; 16]>::from(participant_handle)),
+            UserDataQosPolicy::default(),
+        ),
+        ParticipantProxy::new(
+            Some(domain_id),
+            String::new(),
+            PROTOCOLVERSION,
+            guid_prefix,
+            VENDOR_ID_S2E,
+            false,
+            vec![mock_locator],
+            vec![],
+            vec![mock_locator],
+            vec![],
+            BuiltinEndpointSet::default(),
+            0,
+            BuiltinEndpointQos::default(),
+        ),
+        Duration::new(100, 0),
+        vec![],
+    );
+
+    let start = Instant::now();
+    // Use sequence numbers which are not used by the local participant announcer
+    let mut sequence_number = 1000;
+    while participant.get_instance_handle().unwrap() == participant_handle {
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(2),
+            "GUID prefix not changed"
+        );
+        let spdp_message = RtpsMessageWrite::new(
+            &RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, guid_prefix),
+            &[Box::new(DataSubmessage::new(
+                false,
+                true,
+                false,
+                false,
+                ENTITYID_SPDP_BUILTIN_PARTICIPANT_READER,
+                ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER,
+                sequence_number,
+                ParameterList::empty(),
+                Data::new(
+                    spdp_discovered_participant_data
+                        .serialize_data()
+                        .unwrap()
+                        .into(),
+                ),
+            ))],
+        );
+        sequence_number += 1;
+        mock_socket
+            .send_to(
+                spdp_message.buffer(),
+                ("127.0.0.1", metatraffic_port as u16),
+            )
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    let new_participant_handle = participant.get_instance_handle().unwrap();
+    assert_ne!(
+        Guid::from(<[u8; 16]>::from(new_participant_handle)).prefix(),
+        guid_prefix
+    );
+
+    // The instance handles of the contained entities change together with the GUID prefix
+    assert_ne!(publisher.get_instance_handle().unwrap(), publisher_handle);
+    assert_ne!(subscriber.get_instance_handle().unwrap(), subscriber_handle);
+    assert_ne!(data_writer.get_instance_handle().unwrap(), writer_handle);
+    let new_reader_handle = data_reader.get_instance_handle().unwrap();
+    assert_ne!(new_reader_handle, reader_handle);
+
+    // The local endpoints are matched again using the new GUID prefix
+    let start = Instant::now();
+    while data_writer.get_matched_subscriptions().unwrap() != vec![new_reader_handle] {
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(10),
+            "Endpoints not matched with the new GUID prefix"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+
+    // The entities can still be deleted after the GUID prefix has changed
+    publisher.delete_datawriter(&data_writer).unwrap();
+    subscriber.delete_datareader(&data_reader).unwrap();
+    participant.delete_publisher(&publisher).unwrap();
+    participant.delete_subscriber(&subscriber).unwrap();
+    participant.delete_topic(&topic).unwrap();
+    DomainParticipantFactory::get_instance()
+        .delete_participant(&participant)
+        .unwrap();
+}
+
+#[test]
+fn participants_of_different_domains_are_not_matched() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let other_domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let other_participant = DomainParticipantFactory::get_instance()
+        .create_participant(other_domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let metatraffic_port = participant
+        .get_builtin_subscriber()
+        .lookup_datareader::<SpdpDiscoveredParticipantData>(DCPS_PARTICIPANT)
+        .unwrap()
+        .unwrap()
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap()[0]
+        .data()
+        .unwrap()
+        .participant_proxy()
+        .metatraffic_unicast_locator_list()[0]
+        .port();
+
+    // Mock a participant of the other domain announcing itself directly to the participant
+    let mock_socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let mock_locator = Locator::new(
+        LOCATOR_KIND_UDP_V4,
+        mock_socket.local_addr().unwrap().port() as u32,
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 127, 0, 0, 1],
+    );
+    let guid_prefix = [9; 12];
+    let mock_participant_guid = Guid::new(guid_prefix, ENTITYID_PARTICIPANT);
+    let spdp_discovered_participant_data = SpdpDiscoveredParticipantData::new(
+        ParticipantBuiltinTopicData::new(
+            BuiltInTopicKey::from(<[u8; 16]>::from(mock_participant_guid)),
+            UserDataQosPolicy::default(),
+        ),
+        ParticipantProxy::new(
+            Some(other_domain_id),
+            String::new(),
+            PROTOCOLVERSION,
+            guid_prefix,
+            VENDOR_ID_S2E,
+            false,
+            vec![mock_locator],
+            vec![],
+            vec![mock_locator],
+            vec![],
+            BuiltinEndpointSet::default(),
+            0,
+            BuiltinEndpointQos::default(),
+        ),
+        Duration::new(100, 0),
+        vec![],
+    );
+
+    let start = Instant::now();
+    let mut sequence_number = 1;
+    while participant.get_stats().unwrap().domain_isolation_violations == 0 {
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(10),
+            "Announcement of participant from a different domain not received"
+        );
+        let spdp_message = RtpsMessageWrite::new(
+            &RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, guid_prefix),
+            &[Box::new(DataSubmessage::new(
+                false,
+                true,
+                false,
+                false,
+                ENTITYID_SPDP_BUILTIN_PARTICIPANT_READER,
+                ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER,
+                sequence_number,
+                ParameterList::empty(),
+                Data::new(
+                    spdp_discovered_participant_data
+                        .serialize_data()
+                        .unwrap()
+                        .into(),
+                ),
+            ))],
+        );
+        sequence_number += 1;
+        mock_socket
+            .send_to(
+                spdp_message.buffer(),
+                ("127.0.0.1", metatraffic_port as u16),
+            )
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+
+    assert_eq!(participant.get_discovered_participants().unwrap().len(), 1);
+    assert_eq!(
+        other_participant
+            .get_discovered_participants()
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(
+        other_participant
+            .get_stats()
+            .unwrap()
+            .domain_isolation_violations,
+        0
+    );
+}
+
+#[test]
+fn participant_with_older_rtps_version_is_discovered() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant_reader = participant
+        .get_builtin_subscriber()
+        .lookup_datareader::<SpdpDiscoveredParticipantData>(DCPS_PARTICIPANT)
+        .unwrap()
+        .unwrap();
+
+    let metatraffic_port = participant_reader
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap()[0]
+        .data()
+        .unwrap()
+        .participant_proxy()
+        .metatraffic_unicast_locator_list()[0]
+        .port();
+
+    // Mock a participant implementing RTPS 2.1 announcing itself directly to the participant
+    let mock_socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let mock_locator = Locator::new(
+        LOCATOR_KIND_UDP_V4,
+        mock_socket.local_addr().unwrap().port() as u32,
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 127, 0, 0, 1],
+    );
+    let guid_prefix = [10; 12];
+    let mock_participant_guid = Guid::new(guid_prefix, ENTITYID_PARTICIPANT);
+    let mock_participant_handle = InstanceHandle::new(mock_participant_guid.into());
+    let spdp_discovered_participant_data = SpdpDiscoveredParticipantData::new(
+        ParticipantBuiltinTopicData::new(
+            BuiltInTopicKey::from(<[u8; 16]>::from(mock_participant_guid)),
+            UserDataQosPolicy::default(),
+        ),
+        ParticipantProxy::new(
+            Some(domain_id),
+            String::new(),
+            PROTOCOLVERSION_2_1,
+            guid_prefix,
+            VENDOR_ID_S2E,
+            false,
+            vec![mock_locator],
+            vec![],
+            vec![mock_locator],
+            vec![],
+            BuiltinEndpointSet::default(),
+            0,
+            BuiltinEndpointQos::default(),
+        ),
+        Duration::new(100, 0),
+        vec![],
+    );
+
+    let start = Instant::now();
+    let mut sequence_number = 1;
+    while !participant
+        .get_discovered_participants()
+        .unwrap()
+        .contains(&mock_participant_handle)
+    {
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(10),
+            "RTPS 2.1 participant not discovered"
+        );
+        let spdp_message = RtpsMessageWrite::new(
+            &RtpsMessageHeader::new(PROTOCOLVERSION_2_1, VENDOR_ID_S2E, guid_prefix),
+            &[Box::new(DataSubmessage::new(
+                false,
+                true,
+                false,
+                false,
+                ENTITYID_SPDP_BUILTIN_PARTICIPANT_READER,
+                ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER,
+                sequence_number,
+                ParameterList::empty(),
+                Data::new(
+                    spdp_discovered_participant_data
+                        .serialize_data()
+                        .unwrap()
+                        .into(),
+                ),
+            ))],
+        );
+        sequence_number += 1;
+        mock_socket
+            .send_to(
+                spdp_message.buffer(),
+                ("127.0.0.1", metatraffic_port as u16),
+            )
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+
+    let mock_participant_data = participant_reader
+        .read_instance(
+            10,
+            mock_participant_handle,
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        )
+        .unwrap()[0]
+        .data()
+        .unwrap();
+    assert_eq!(
+        mock_participant_data.participant_proxy().protocol_version(),
+        PROTOCOLVERSION_2_1
+    );
+}
@@ -11,8 +11,8 @@ use dust_dds::{
             SubscriberQos, TopicQos,
         },
         qos_policy::{
-            GroupDataQosPolicy, ReliabilityQosPolicy, ReliabilityQosPolicyKind, TopicDataQosPolicy,
-            UserDataQosPolicy,
+            EntityFactoryQosPolicy, GroupDataQosPolicy, ReliabilityQosPolicy,
+            ReliabilityQosPolicyKind, TopicDataQosPolicy, UserDataQosPolicy,
         },
         status::{StatusKind, NO_STATUS},
         time::{Duration, DurationKind},
@@ -95,6 +95,44 @@ fn create_delete_topic() {
     );
 }
 
+#[test]
+fn find_topic_returns_existing_topic_without_waiting() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<TestType>("abc", "TestType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let start = std::time::Instant::now();
+    let found_topic = participant
+        .find_topic::<TestType>("abc", Duration::new(10, 0))
+        .unwrap();
+
+    assert!(start.elapsed() < std::time::Duration::from_secs(1));
+    assert_eq!(
+        found_topic.get_instance_handle(),
+        topic.get_instance_handle()
+    );
+}
+
+#[test]
+fn find_topic_times_out_after_timeout() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let start = std::time::Instant::now();
+    let result = participant.find_topic::<TestType>("abc", Duration::new(0, 500_000_000));
+    let elapsed = start.elapsed();
+
+    assert!(matches!(result, Err(DdsError::Timeout)));
+    assert!(elapsed >= std::time::Duration::from_millis(500));
+    assert!(elapsed < std::time::Duration::from_secs(5));
+}
+
 #[test]
 fn not_allowed_to_delete_publisher_from_different_participant() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -394,6 +432,52 @@ fn allowed_to_delete_topic_with_created_and_deleted_reader() {
     assert_eq!(participant.delete_topic(&reader_topic), Ok(()));
 }
 
+#[test]
+fn enabled_participant_qos_policies_are_changeable() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<TestType>("MyTopic", "TestType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher_before_change = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    // Both the user data and the entity factory policies can be changed on an enabled participant
+    let qos = DomainParticipantQos {
+        user_data: UserDataQosPolicy {
+            value: vec![1, 2, 3],
+        },
+        entity_factory: EntityFactoryQosPolicy {
+            autoenable_created_entities: false,
+        },
+        ..Default::default()
+    };
+    participant.set_qos(QosKind::Specific(qos.clone())).unwrap();
+    assert_eq!(participant.get_qos().unwrap(), qos);
+
+    // The entity factory policy only affects the entities created after the change
+    let publisher_after_change = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_of_enabled_publisher = publisher_before_change
+        .create_datawriter::<TestType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_of_disabled_publisher = publisher_after_change
+        .create_datawriter::<TestType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    assert_eq!(
+        writer_of_enabled_publisher.write(&TestType(1), None),
+        Ok(())
+    );
+    assert_eq!(
+        writer_of_disabled_publisher.write(&TestType(1), None),
+        Err(DdsError::NotEnabled)
+    );
+}
+
 #[test]
 fn default_publisher_qos() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -552,6 +636,53 @@ fn builtin_topics_accessible_after_delete_contained_entities() {
         .is_ok());
 }
 
+#[test]
+fn delete_contained_entities_deletes_nested_entities() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<MyData>("MyTopic", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<MyData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<MyData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let read_condition = reader
+        .create_readcondition(ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    reader.delete_contained_entities().unwrap();
+    assert!(matches!(
+        reader.delete_readcondition(&read_condition),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+
+    participant.delete_contained_entities().unwrap();
+
+    assert!(writer.get_qos().is_err());
+    assert!(reader.get_qos().is_err());
+    assert!(publisher.lookup_datawriter::<MyData>("MyTopic").is_err());
+    assert!(subscriber.lookup_datareader::<MyData>("MyTopic").is_err());
+    assert!(participant
+        .lookup_topicdescription("MyTopic")
+        .unwrap()
+        .is_none());
+    assert!(DomainParticipantFactory::get_instance()
+        .delete_participant(&participant)
+        .is_ok());
+}
+
 #[test]
 fn builtin_reader_access() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -579,6 +710,55 @@ fn builtin_reader_access() {
         .is_ok());
 }
 
+#[test]
+fn contains_entity() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let other_participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<MyData>("MyTopic", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<MyData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<MyData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let builtin_reader = participant
+        .get_builtin_subscriber()
+        .lookup_datareader::<ParticipantBuiltinTopicData>("DCPSParticipant")
+        .unwrap()
+        .unwrap();
+
+    for handle in [
+        topic.get_instance_handle().unwrap(),
+        publisher.get_instance_handle().unwrap(),
+        writer.get_instance_handle().unwrap(),
+        subscriber.get_instance_handle().unwrap(),
+        reader.get_instance_handle().unwrap(),
+        builtin_reader.get_instance_handle().unwrap(),
+    ] {
+        assert_eq!(participant.contains_entity(handle), Ok(true));
+        assert_eq!(other_participant.contains_entity(handle), Ok(false));
+    }
+
+    let reader_handle = reader.get_instance_handle().unwrap();
+    subscriber.delete_datareader(&reader).unwrap();
+    assert_eq!(participant.contains_entity(reader_handle), Ok(false));
+}
+
 #[test]
 fn get_discovery_data_from_builtin_reader() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -866,3 +1046,73 @@ fn ignore_participant() {
     // Participant should only discover itself
     assert_eq!(participant1.get_discovered_participants().unwrap().len(), 1);
 }
+
+#[test]
+fn participant_stats_count_written_and_read_samples() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<MyData>("MyTopic", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter::<MyData>(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<MyData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    for id in 0..3 {
+        writer.write(&MyData { id, value: 1 }, None).unwrap();
+    }
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    let samples = reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 3);
+
+    let stats = participant.get_stats().unwrap();
+    // The built-in discovery entities also contribute to the counters
+    assert!(stats.samples_written >= 3);
+    assert!(stats.samples_read >= 3);
+    assert!(stats.messages_sent > 0);
+    assert!(stats.messages_received > 0);
+    assert!(stats.bytes_sent >= stats.messages_sent * 20);
+    assert!(stats.bytes_received >= stats.messages_received * 20);
+    assert!(stats.heartbeats_sent > 0);
+    assert!(stats.discovery_events > 0);
+}
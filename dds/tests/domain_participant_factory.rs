@@ -18,6 +18,30 @@ struct KeyedData {
     value: u8,
 }
 
+#[test]
+fn get_instance_returns_the_same_factory() {
+    let factory = DomainParticipantFactory::get_instance();
+    assert!(std::ptr::eq(
+        factory,
+        DomainParticipantFactory::get_instance()
+    ));
+
+    let factory_address = factory as *const DomainParticipantFactory as usize;
+    let thread_factory_address_list: Vec<_> = (0..4)
+        .map(|_| {
+            std::thread::spawn(|| {
+                DomainParticipantFactory::get_instance() as *const DomainParticipantFactory as usize
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|t| t.join().unwrap())
+        .collect();
+    assert!(thread_factory_address_list
+        .iter()
+        .all(|&address| address == factory_address));
+}
+
 #[test]
 fn default_participant_qos() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -32,7 +56,10 @@ fn default_participant_qos() {
     };
 
     domain_participant_factory
-        .set_default_participant_qos(QosKind::Specific(qos))
+        .set_default_participant_qos(QosKind::Specific(qos.clone()))
+        .unwrap();
+    let default_participant_qos = domain_participant_factory
+        .get_default_participant_qos()
         .unwrap();
 
     let participant = domain_participant_factory
@@ -43,6 +70,7 @@ fn default_participant_qos() {
         .set_default_participant_qos(QosKind::Default)
         .unwrap();
 
+    assert_eq!(default_participant_qos, qos);
     assert_eq!(participant.get_qos().unwrap().user_data.value, user_data);
 }
 
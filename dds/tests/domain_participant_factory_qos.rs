@@ -7,15 +7,48 @@ use dust_dds::{
         qos_policy::EntityFactoryQosPolicy,
         status::NO_STATUS,
     },
+    topic_definition::type_support::DdsType,
 };
+use std::{sync::Mutex, time::Instant};
 
 mod utils;
 use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
 
+// The factory QoS is shared by all the tests of this file so they can not run concurrently
+static FACTORY_QOS_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Clone, Debug, PartialEq, DdsType)]
+struct UserType(i32);
+
+#[test]
+fn factory_qos_is_kept_until_changed() {
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let _lock = FACTORY_QOS_LOCK.lock().unwrap();
+
+    let qos = DomainParticipantFactoryQos {
+        entity_factory: EntityFactoryQosPolicy {
+            autoenable_created_entities: false,
+        },
+    };
+    domain_participant_factory
+        .set_qos(QosKind::Specific(qos.clone()))
+        .unwrap();
+    let specific_qos = domain_participant_factory.get_qos().unwrap();
+
+    domain_participant_factory
+        .set_qos(QosKind::Default)
+        .unwrap();
+    let default_qos = domain_participant_factory.get_qos().unwrap();
+
+    assert_eq!(specific_qos, qos);
+    assert_eq!(default_qos, DomainParticipantFactoryQos::default());
+}
+
 #[test]
 fn create_not_enabled_entities() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
     let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let _lock = FACTORY_QOS_LOCK.lock().unwrap();
 
     let qos = DomainParticipantFactoryQos {
         entity_factory: EntityFactoryQosPolicy {
@@ -41,3 +74,57 @@ fn create_not_enabled_entities() {
 
     assert_eq!(result, Err(DdsError::NotEnabled));
 }
+
+#[test]
+fn not_enabled_entities_send_no_traffic_until_enabled() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let _lock = FACTORY_QOS_LOCK.lock().unwrap();
+
+    let qos = DomainParticipantFactoryQos {
+        entity_factory: EntityFactoryQosPolicy {
+            autoenable_created_entities: false,
+        },
+    };
+    domain_participant_factory
+        .set_qos(QosKind::Specific(qos))
+        .unwrap();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    // Teardown right after creation: Set qos back to original to prevent it affecting other test
+    domain_participant_factory
+        .set_qos(QosKind::Default)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<UserType>("MyTopic", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let _reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert_eq!(writer.write(&UserType(1), None), Err(DdsError::NotEnabled));
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    assert_eq!(participant.get_stats().unwrap().messages_sent, 0);
+
+    participant.enable().unwrap();
+
+    let start = Instant::now();
+    while participant.get_stats().unwrap().messages_sent == 0 {
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(10),
+            "Participant not announced after enable"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+}
@@ -0,0 +1,78 @@
+use std::time::Instant;
+
+use dust_dds::{
+    configuration::DustDdsConfigurationBuilder,
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{qos::QosKind, status::NO_STATUS},
+};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[test]
+fn participants_with_different_domain_tag_are_not_discovered() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    participant_factory
+        .set_configuration(
+            DustDdsConfigurationBuilder::new()
+                .domain_tag("tag1".to_string())
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    let participant_tag1 = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    participant_factory
+        .set_configuration(
+            DustDdsConfigurationBuilder::new()
+                .domain_tag("tag2".to_string())
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    let participant1_tag2 = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant2_tag2 = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    // Wait until the participants with the same tag discover each other so that the
+    // announcements of all the participants have been exchanged
+    let start = Instant::now();
+    while participant1_tag2
+        .get_discovered_participants()
+        .unwrap()
+        .len()
+        < 2
+        || participant2_tag2
+            .get_discovered_participants()
+            .unwrap()
+            .len()
+            < 2
+    {
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(10),
+            "Participants with the same domain tag not discovered"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+
+    let participant_tag1_handle = participant_tag1.get_instance_handle().unwrap();
+    assert_eq!(
+        participant_tag1.get_discovered_participants().unwrap(),
+        vec![participant_tag1_handle]
+    );
+    assert!(!participant1_tag2
+        .get_discovered_participants()
+        .unwrap()
+        .contains(&participant_tag1_handle));
+    assert!(!participant2_tag2
+        .get_discovered_participants()
+        .unwrap()
+        .contains(&participant_tag1_handle));
+}
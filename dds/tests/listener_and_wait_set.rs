@@ -1,6 +1,7 @@
 use dust_dds::{
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
+        error::DdsError,
         qos::{DataReaderQos, DataWriterQos, QosKind},
         qos_policy::{
             HistoryQosPolicy, HistoryQosPolicyKind, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
@@ -9,7 +10,11 @@ use dust_dds::{
         time::{Duration, DurationKind},
         wait_set::{Condition, WaitSet},
     },
-    subscription::{data_reader::DataReader, data_reader_listener::DataReaderListener},
+    subscription::{
+        data_reader::DataReader,
+        data_reader_listener::DataReaderListener,
+        sample_info::{SampleStateKind, ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+    },
 };
 use dust_dds_derive::DdsType;
 
@@ -118,3 +123,165 @@ fn reader_subscription_matched_listener_and_wait_set_should_both_trigger() {
     assert_eq!(status.total_count, 1);
     assert_eq!(status.total_count_change, 1);
 }
+
+#[test]
+fn wait_set_only_triggers_on_enabled_statuses() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<MyData>(
+            "EnabledStatusesTopic",
+            "MyData",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<MyData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_condition = reader.get_statuscondition();
+    reader_condition
+        .set_enabled_statuses(&[StatusKind::DataAvailable])
+        .unwrap();
+    assert_eq!(
+        reader_condition.get_enabled_statuses(),
+        Ok(vec![StatusKind::DataAvailable])
+    );
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<MyData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_condition = writer.get_statuscondition();
+    writer_condition
+        .set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut writer_wait_set = WaitSet::new();
+    writer_wait_set
+        .attach_condition(Condition::StatusCondition(writer_condition))
+        .unwrap();
+    writer_wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let mut reader_wait_set = WaitSet::new();
+    reader_wait_set
+        .attach_condition(Condition::StatusCondition(reader_condition.clone()))
+        .unwrap();
+    assert_eq!(
+        reader_wait_set.wait(Duration::new(1, 0)).err(),
+        Some(DdsError::Timeout)
+    );
+
+    reader_condition
+        .set_enabled_statuses(&[StatusKind::DataAvailable, StatusKind::SubscriptionMatched])
+        .unwrap();
+    assert_eq!(reader_wait_set.wait(Duration::new(10, 0)).unwrap().len(), 1);
+}
+
+#[test]
+fn subscriber_wait_set_triggers_on_data_on_readers() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<MyData>(
+            "DataOnReadersTopic",
+            "MyData",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter::<MyData>(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<MyData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let writer_condition = writer.get_statuscondition();
+    writer_condition
+        .set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut writer_wait_set = WaitSet::new();
+    writer_wait_set
+        .attach_condition(Condition::StatusCondition(writer_condition))
+        .unwrap();
+    writer_wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let subscriber_condition = subscriber.get_statuscondition();
+    subscriber_condition
+        .set_enabled_statuses(&[StatusKind::DataOnReaders])
+        .unwrap();
+    let mut subscriber_wait_set = WaitSet::new();
+    subscriber_wait_set
+        .attach_condition(Condition::StatusCondition(subscriber_condition))
+        .unwrap();
+
+    writer.write(&MyData { id: 1, value: 1 }, None).unwrap();
+    subscriber_wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let readers = subscriber
+        .get_datareaders(
+            &[SampleStateKind::NotRead],
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        )
+        .unwrap();
+    assert_eq!(readers.len(), 1);
+    assert_eq!(
+        readers[0].get_instance_handle(),
+        reader.get_instance_handle()
+    );
+
+    reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(
+        subscriber_wait_set.wait(Duration::new(1, 0)).err(),
+        Some(DdsError::Timeout)
+    );
+    assert!(subscriber
+        .get_datareaders(
+            &[SampleStateKind::NotRead],
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE
+        )
+        .unwrap()
+        .is_empty());
+}
@@ -6,13 +6,14 @@ use dust_dds::{
     infrastructure::{
         qos::{DataReaderQos, DataWriterQos, QosKind},
         qos_policy::{
-            DeadlineQosPolicy, HistoryQosPolicy, HistoryQosPolicyKind, Length,
-            ReliabilityQosPolicy, ReliabilityQosPolicyKind, ResourceLimitsQosPolicy,
+            DeadlineQosPolicy, HistoryQosPolicy, HistoryQosPolicyKind, Length, LivelinessQosPolicy,
+            LivelinessQosPolicyKind, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
+            ResourceLimitsQosPolicy,
         },
         status::{
-            OfferedIncompatibleQosStatus, PublicationMatchedStatus, RequestedDeadlineMissedStatus,
-            RequestedIncompatibleQosStatus, SampleRejectedStatus, SampleRejectedStatusKind,
-            StatusKind, SubscriptionMatchedStatus, NO_STATUS,
+            LivelinessChangedStatus, OfferedIncompatibleQosStatus, PublicationMatchedStatus,
+            RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus, SampleRejectedStatus,
+            SampleRejectedStatusKind, StatusKind, SubscriptionMatchedStatus, NO_STATUS,
         },
         time::{Duration, DurationKind},
         wait_set::{Condition, WaitSet},
@@ -22,7 +23,10 @@ use dust_dds::{
         publisher_listener::PublisherListener,
     },
     subscription::{
-        data_reader::DataReader, data_reader_listener::DataReaderListener, subscriber::Subscriber,
+        data_reader::DataReader,
+        data_reader_listener::DataReaderListener,
+        sample_info::{InstanceStateKind, ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+        subscriber::Subscriber,
         subscriber_listener::SubscriberListener,
     },
     topic_definition::{topic_listener::TopicListener, type_support::DdsType},
@@ -656,6 +660,96 @@ fn on_data_available_listener() {
         .is_ok());
 }
 
+#[test]
+fn on_data_available_listener_called_on_dispose() {
+    struct DataAvailableListener {
+        sender: std::sync::mpsc::SyncSender<()>,
+    }
+
+    impl DataReaderListener<'_> for DataAvailableListener {
+        type Foo = MyData;
+        fn on_data_available(&mut self, _the_reader: DataReader<MyData>) {
+            self.sender.send(()).unwrap();
+        }
+    }
+
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<MyData>("MyTopic", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let (sender, receiver) = std::sync::mpsc::sync_channel(2);
+    let reader_listener = DataAvailableListener { sender };
+
+    let reader = subscriber
+        .create_datareader(
+            &topic,
+            QosKind::Specific(reader_qos),
+            Some(Box::new(reader_listener)),
+            &[StatusKind::DataAvailable],
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data1 = MyData { id: 1, value: 1 };
+    writer.write(&data1, None).unwrap();
+    receiver
+        .recv_timeout(std::time::Duration::from_secs(10))
+        .unwrap();
+
+    writer.dispose(&data1, None).unwrap();
+    receiver
+        .recv_timeout(std::time::Duration::from_secs(10))
+        .unwrap();
+
+    let samples = reader
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(
+        samples[0].sample_info().instance_state,
+        InstanceStateKind::NotAliveDisposed
+    );
+}
+
 #[test]
 fn data_on_readers_listener() {
     struct DataOnReadersListener {
@@ -830,6 +924,98 @@ fn data_available_listener_not_called_when_data_on_readers_listener() {
     assert!(data_available_receiver.try_recv().is_err());
 }
 
+#[test]
+fn notify_datareaders_calls_data_available_listener() {
+    struct NotifyDataReadersListener;
+
+    impl SubscriberListener for NotifyDataReadersListener {
+        fn on_data_on_readers(&mut self, the_subscriber: Subscriber) {
+            the_subscriber.notify_datareaders().unwrap();
+        }
+    }
+
+    struct DataAvailableListener {
+        sender: std::sync::mpsc::SyncSender<()>,
+    }
+
+    impl DataReaderListener<'_> for DataAvailableListener {
+        type Foo = MyData;
+        fn on_data_available(&mut self, _the_reader: DataReader<MyData>) {
+            self.sender.send(()).unwrap();
+        }
+    }
+
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<MyData>("MyTopic", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(
+            QosKind::Default,
+            Some(Box::new(NotifyDataReadersListener)),
+            &[StatusKind::DataOnReaders],
+        )
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+
+    let (sender, data_available_receiver) = std::sync::mpsc::sync_channel(1);
+    let reader_listener = DataAvailableListener { sender };
+
+    // The reader listener is called through notify_datareaders even though its mask is not set
+    let _reader = subscriber
+        .create_datareader(
+            &topic,
+            QosKind::Specific(reader_qos),
+            Some(Box::new(reader_listener)),
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data1 = MyData { id: 1, value: 1 };
+    writer.write(&data1, None).unwrap();
+
+    assert!(data_available_receiver
+        .recv_timeout(std::time::Duration::from_secs(10))
+        .is_ok());
+}
+
 #[test]
 fn participant_deadline_missed_listener() {
     struct DeadlineMissedListener {
@@ -1980,3 +2166,195 @@ fn non_sync_listener_should_be_accepted() {
 
     // This test doesn't assert. If trait bounds are not correct compilation will fail.
 }
+
+#[test]
+fn liveliness_changed_listener() {
+    struct LivelinessChangedListener {
+        sender: std::sync::mpsc::SyncSender<LivelinessChangedStatus>,
+    }
+
+    impl DataReaderListener<'_> for LivelinessChangedListener {
+        type Foo = MyData;
+        fn on_liveliness_changed(
+            &mut self,
+            _the_reader: DataReader<Self::Foo>,
+            status: LivelinessChangedStatus,
+        ) {
+            self.sender.send(status).ok();
+        }
+    }
+
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<MyData>("MyTopic", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let (sender, receiver) = std::sync::mpsc::sync_channel(10);
+    let reader_listener = LivelinessChangedListener { sender };
+    let reader = subscriber
+        .create_datareader(
+            &topic,
+            QosKind::Default,
+            Some(Box::new(reader_listener)),
+            &[StatusKind::LivelinessChanged],
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        liveliness: LivelinessQosPolicy {
+            kind: LivelinessQosPolicyKind::ManualByTopic,
+            lease_duration: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter::<MyData>(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+    let writer_handle = writer.get_instance_handle().unwrap();
+
+    let status = receiver
+        .recv_timeout(std::time::Duration::from_secs(10))
+        .unwrap();
+    assert_eq!(status.alive_count, 1);
+    assert_eq!(status.not_alive_count, 0);
+    assert_eq!(status.last_publication_handle, writer_handle);
+
+    // The writer does not assert its liveliness so the lease expires
+    let status = receiver
+        .recv_timeout(std::time::Duration::from_secs(10))
+        .unwrap();
+    assert_eq!(status.alive_count, 0);
+    assert_eq!(status.not_alive_count, 1);
+    assert_eq!(status.alive_count_change, -1);
+    assert_eq!(status.not_alive_count_change, 1);
+
+    writer.assert_liveliness().unwrap();
+    let status = receiver
+        .recv_timeout(std::time::Duration::from_secs(10))
+        .unwrap();
+    assert_eq!(status.alive_count, 1);
+    assert_eq!(status.not_alive_count, 0);
+    assert_eq!(status.last_publication_handle, writer_handle);
+
+    reader.set_listener(None, NO_STATUS).unwrap();
+}
+
+#[test]
+fn automatic_liveliness_keeps_writer_alive() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<MyData>("MyTopic", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<MyData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        liveliness: LivelinessQosPolicy {
+            kind: LivelinessQosPolicyKind::Automatic,
+            lease_duration: DurationKind::Finite(Duration::new(0, 500_000_000)),
+        },
+        ..Default::default()
+    };
+    let _writer = publisher
+        .create_datawriter::<MyData>(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::LivelinessChanged])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+    assert_eq!(
+        reader.get_liveliness_changed_status().unwrap().alive_count,
+        1
+    );
+
+    std::thread::sleep(std::time::Duration::from_secs(2));
+
+    let status = reader.get_liveliness_changed_status().unwrap();
+    assert_eq!(status.alive_count, 1);
+    assert_eq!(status.not_alive_count, 0);
+}
+
+#[test]
+fn liveliness_changed_status_change_counts_are_reset_after_read() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<MyData>("MyTopic", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<MyData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<MyData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_handle = writer.get_instance_handle().unwrap();
+
+    let cond = reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::LivelinessChanged])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let status = reader.get_liveliness_changed_status().unwrap();
+    assert_eq!(status.alive_count, 1);
+    assert_eq!(status.alive_count_change, 1);
+    assert_eq!(status.last_publication_handle, writer_handle);
+
+    let status = reader.get_liveliness_changed_status().unwrap();
+    assert_eq!(status.alive_count, 1);
+    assert_eq!(status.alive_count_change, 0);
+    assert_eq!(status.not_alive_count_change, 0);
+
+    publisher.delete_datawriter(&writer).unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let status = reader.get_liveliness_changed_status().unwrap();
+    assert_eq!(status.alive_count, 0);
+    assert_eq!(status.alive_count_change, -1);
+    assert_eq!(status.not_alive_count, 0);
+    assert_eq!(status.last_publication_handle, writer_handle);
+}
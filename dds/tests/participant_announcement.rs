@@ -0,0 +1,61 @@
+use dust_dds::{
+    configuration::DustDdsConfigurationBuilder,
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{qos::QosKind, status::NO_STATUS},
+    rtps::{
+        discovery_types::ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER,
+        messages::overall_structure::{RtpsMessageRead, RtpsSubmessageReadKind},
+    },
+};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[test]
+fn participant_is_announced_at_configured_interval() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let mock_peer_socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    mock_peer_socket
+        .set_read_timeout(Some(std::time::Duration::from_secs(10)))
+        .unwrap();
+
+    let participant_factory = DomainParticipantFactory::get_instance();
+    participant_factory
+        .set_configuration(
+            DustDdsConfigurationBuilder::new()
+                .initial_peers(vec![mock_peer_socket.local_addr().unwrap()])
+                .participant_announcement_interval(std::time::Duration::from_millis(100))
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    let _participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let mut buffer = [0; 65535];
+    let mut receive_announcement = || loop {
+        let bytes = mock_peer_socket.recv(&mut buffer).unwrap();
+        let message = RtpsMessageRead::try_from(&buffer[0..bytes]).unwrap();
+        if message.submessages().iter().any(|s| matches!(
+            s,
+            RtpsSubmessageReadKind::Data(d) if d.writer_id() == ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER
+        )) {
+            break;
+        }
+    };
+
+    receive_announcement();
+    let start = std::time::Instant::now();
+    for _ in 0..10 {
+        receive_announcement();
+    }
+    let elapsed = start.elapsed();
+
+    assert!(
+        elapsed >= std::time::Duration::from_millis(900)
+            && elapsed < std::time::Duration::from_millis(2000),
+        "10 announcements received in {:?}",
+        elapsed
+    );
+}
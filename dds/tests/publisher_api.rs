@@ -1,17 +1,29 @@
 use dust_dds::{
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
-        qos::{DataWriterQos, QosKind},
-        qos_policy::UserDataQosPolicy,
-        status::NO_STATUS,
+        error::DdsError,
+        qos::{DataReaderQos, DataWriterQos, PublisherQos, QosKind, SubscriberQos, TopicQos},
+        qos_policy::{
+            DeadlineQosPolicy, DestinationOrderQosPolicy, DestinationOrderQosPolicyKind,
+            DurabilityQosPolicy, DurabilityQosPolicyKind, HistoryQosPolicy, HistoryQosPolicyKind,
+            LatencyBudgetQosPolicy, Length, LifespanQosPolicy, LivelinessQosPolicy,
+            LivelinessQosPolicyKind, OwnershipQosPolicy, OwnershipQosPolicyKind,
+            PresentationQosPolicy, PresentationQosPolicyAccessScopeKind, ReliabilityQosPolicy,
+            ReliabilityQosPolicyKind, ResourceLimitsQosPolicy, TransportPriorityQosPolicy,
+            UserDataQosPolicy,
+        },
+        status::{StatusKind, NO_STATUS},
+        time::{Duration, DurationKind},
+        wait_set::{Condition, WaitSet},
     },
+    subscription::sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
     topic_definition::type_support::DdsType,
 };
 
 mod utils;
 use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
 
-#[derive(DdsType)]
+#[derive(Clone, Debug, PartialEq, DdsType)]
 struct UserType(i32);
 
 #[test]
@@ -163,3 +175,347 @@ fn data_writer_get_topic_should_return_same_topic_as_used_for_creation() {
         writer.get_topic().get_instance_handle().unwrap() == topic.get_instance_handle().unwrap()
     );
 }
+
+#[test]
+fn suspended_publications_are_sent_after_resume() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<UserType>("MyTopic", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    publisher.suspend_publications().unwrap();
+    writer.write(&UserType(1), None).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(500));
+
+    assert_eq!(
+        reader.read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE),
+        Err(DdsError::NoData)
+    );
+
+    publisher.resume_publications().unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(5, 0))
+        .unwrap();
+
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data().unwrap(), UserType(1));
+}
+
+#[test]
+fn resume_publications_without_suspend_fails() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert!(matches!(
+        publisher.resume_publications(),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+}
+
+#[test]
+fn end_coherent_changes_must_match_begin_coherent_changes() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    publisher.begin_coherent_changes().unwrap();
+    publisher.begin_coherent_changes().unwrap();
+    assert_eq!(publisher.end_coherent_changes(), Ok(()));
+    assert_eq!(publisher.end_coherent_changes(), Ok(()));
+    assert!(matches!(
+        publisher.end_coherent_changes(),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+}
+
+#[test]
+fn coherent_set_is_received_completely() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<UserType>("MyTopic", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let presentation = PresentationQosPolicy {
+        access_scope: PresentationQosPolicyAccessScopeKind::Topic,
+        coherent_access: true,
+        ordered_access: false,
+    };
+    let publisher_qos = PublisherQos {
+        presentation: presentation.clone(),
+        ..Default::default()
+    };
+    let publisher = participant
+        .create_publisher(QosKind::Specific(publisher_qos), None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber_qos = SubscriberQos {
+        presentation,
+        ..Default::default()
+    };
+    let subscriber = participant
+        .create_subscriber(QosKind::Specific(subscriber_qos), None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    publisher.begin_coherent_changes().unwrap();
+    writer.write(&UserType(1), None).unwrap();
+    writer.write(&UserType(2), None).unwrap();
+    writer.write(&UserType(3), None).unwrap();
+    publisher.end_coherent_changes().unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(5, 0))
+        .unwrap();
+
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 3);
+}
+
+#[test]
+fn publisher_wait_for_acknowledgments_waits_for_all_reliable_writers() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic1 = participant
+        .create_topic::<UserType>("MyTopic1", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic2 = participant
+        .create_topic::<UserType>("MyTopic2", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer1 = publisher
+        .create_datawriter(
+            &topic1,
+            QosKind::Specific(writer_qos.clone()),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer2 = publisher
+        .create_datawriter(&topic2, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader1 = subscriber
+        .create_datareader::<UserType>(
+            &topic1,
+            QosKind::Specific(reader_qos.clone()),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader2 = subscriber
+        .create_datareader::<UserType>(&topic2, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    for writer in [&writer1, &writer2] {
+        let cond = writer.get_statuscondition();
+        cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+            .unwrap();
+        let mut wait_set = WaitSet::new();
+        wait_set
+            .attach_condition(Condition::StatusCondition(cond))
+            .unwrap();
+        wait_set.wait(Duration::new(10, 0)).unwrap();
+    }
+
+    writer1.write(&UserType(1), None).unwrap();
+    writer2.write(&UserType(2), None).unwrap();
+
+    publisher
+        .wait_for_acknowledgments(Duration::new(5, 0))
+        .unwrap();
+
+    let samples1 = reader1
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples1[0].data().unwrap(), UserType(1));
+    let samples2 = reader2
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples2[0].data().unwrap(), UserType(2));
+}
+
+#[test]
+fn copy_from_topic_qos_copies_topic_policies() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic_qos = TopicQos {
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::TransientLocal,
+        },
+        deadline: DeadlineQosPolicy {
+            period: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        latency_budget: LatencyBudgetQosPolicy {
+            duration: DurationKind::Finite(Duration::new(2, 0)),
+        },
+        liveliness: LivelinessQosPolicy {
+            kind: LivelinessQosPolicyKind::ManualByTopic,
+            lease_duration: DurationKind::Finite(Duration::new(3, 0)),
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(4, 0)),
+        },
+        destination_order: DestinationOrderQosPolicy {
+            kind: DestinationOrderQosPolicyKind::BySourceTimestamp,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepLast(5),
+        },
+        resource_limits: ResourceLimitsQosPolicy {
+            max_samples: Length::Limited(20),
+            max_instances: Length::Limited(4),
+            max_samples_per_instance: Length::Limited(5),
+        },
+        transport_priority: TransportPriorityQosPolicy { value: 6 },
+        lifespan: LifespanQosPolicy {
+            duration: DurationKind::Finite(Duration::new(7, 0)),
+        },
+        ownership: OwnershipQosPolicy {
+            kind: OwnershipQosPolicyKind::Exclusive,
+        },
+        ..Default::default()
+    };
+    let user_data = UserDataQosPolicy {
+        value: vec![1, 2, 3],
+    };
+    let mut writer_qos = DataWriterQos {
+        user_data: user_data.clone(),
+        ..publisher.get_default_datawriter_qos().unwrap()
+    };
+
+    publisher
+        .copy_from_topic_qos(&mut writer_qos, &topic_qos)
+        .unwrap();
+
+    assert_eq!(writer_qos.durability, topic_qos.durability);
+    assert_eq!(writer_qos.deadline, topic_qos.deadline);
+    assert_eq!(writer_qos.latency_budget, topic_qos.latency_budget);
+    assert_eq!(writer_qos.liveliness, topic_qos.liveliness);
+    assert_eq!(writer_qos.reliability, topic_qos.reliability);
+    assert_eq!(writer_qos.destination_order, topic_qos.destination_order);
+    assert_eq!(writer_qos.history, topic_qos.history);
+    assert_eq!(writer_qos.resource_limits, topic_qos.resource_limits);
+    assert_eq!(writer_qos.transport_priority, topic_qos.transport_priority);
+    assert_eq!(writer_qos.lifespan, topic_qos.lifespan);
+    assert_eq!(writer_qos.ownership, topic_qos.ownership);
+    assert_eq!(writer_qos.user_data, user_data);
+}
@@ -0,0 +1,191 @@
+#![cfg(feature = "xml-profiles")]
+
+use dust_dds::{
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{
+        error::DdsError,
+        qos::{DomainParticipantQos, PublisherQos, QosKind, SubscriberQos},
+        qos_policy::{
+            DurabilityQosPolicyKind, HistoryQosPolicyKind, PartitionQosPolicy,
+            ReliabilityQosPolicyKind, UserDataQosPolicy,
+        },
+        status::NO_STATUS,
+    },
+    topic_definition::type_support::DdsType,
+};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[derive(Clone, Debug, PartialEq, DdsType)]
+struct UserType(i32);
+
+const QOS_PROFILES: &str = r#"
+<dds xmlns="http://www.omg.org/spec/DDS-XML">
+    <qos_library name="TestLibrary">
+        <qos_profile name="TestProfile">
+            <domain_participant_qos>
+                <user_data><value><element>4</element><element>2</element></value></user_data>
+            </domain_participant_qos>
+            <publisher_qos>
+                <partition><name><element>A</element></name></partition>
+            </publisher_qos>
+            <topic_qos>
+                <durability><kind>TRANSIENT_LOCAL_DURABILITY_QOS</kind></durability>
+            </topic_qos>
+            <datawriter_qos>
+                <reliability><kind>BEST_EFFORT_RELIABILITY_QOS</kind></reliability>
+                <history><kind>KEEP_ALL_HISTORY_QOS</kind></history>
+            </datawriter_qos>
+        </qos_profile>
+    </qos_library>
+</dds>
+"#;
+
+fn write_profiles_file(file_name: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(file_name);
+    std::fs::write(&path, QOS_PROFILES).unwrap();
+    path
+}
+
+#[test]
+fn participant_created_with_profile_uses_profile_qos() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let path = write_profiles_file("dust_dds_participant_created_with_profile.xml");
+    domain_participant_factory
+        .load_profiles_from_file(&path)
+        .unwrap();
+
+    let participant = domain_participant_factory
+        .create_participant_with_profile(domain_id, "TestLibrary::TestProfile")
+        .unwrap();
+
+    assert_eq!(
+        participant.get_qos().unwrap(),
+        DomainParticipantQos {
+            user_data: UserDataQosPolicy { value: vec![4, 2] },
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        participant.get_default_publisher_qos().unwrap(),
+        PublisherQos {
+            partition: PartitionQosPolicy {
+                name: vec!["A".to_string()],
+            },
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        participant.get_default_subscriber_qos().unwrap(),
+        SubscriberQos::default()
+    );
+    assert_eq!(
+        participant.get_default_topic_qos().unwrap().durability.kind,
+        DurabilityQosPolicyKind::TransientLocal
+    );
+}
+
+#[test]
+fn writer_created_with_profile_qos() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let path = write_profiles_file("dust_dds_writer_created_with_profile.xml");
+    domain_participant_factory
+        .load_profiles_from_file(&path)
+        .unwrap();
+    let qos_profile = domain_participant_factory
+        .get_qos_profile("TestLibrary::TestProfile")
+        .unwrap();
+    assert_eq!(qos_profile.datareader_qos, None);
+
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<UserType>(
+            "MyTopic",
+            "UserType",
+            QosKind::Specific(qos_profile.topic_qos.unwrap_or_default()),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<UserType>(
+            &topic,
+            QosKind::Specific(qos_profile.datawriter_qos.unwrap()),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let writer_qos = writer.get_qos().unwrap();
+    assert_eq!(
+        writer_qos.reliability.kind,
+        ReliabilityQosPolicyKind::BestEffort
+    );
+    assert_eq!(writer_qos.history.kind, HistoryQosPolicyKind::KeepAll);
+}
+
+#[test]
+fn participant_is_not_created_with_inconsistent_profile() {
+    const INCONSISTENT_QOS_PROFILES: &str = r#"
+<dds xmlns="http://www.omg.org/spec/DDS-XML">
+    <qos_library name="InconsistentLibrary">
+        <qos_profile name="InconsistentProfile">
+            <topic_qos>
+                <history><kind>KEEP_LAST_HISTORY_QOS</kind><depth>0</depth></history>
+            </topic_qos>
+        </qos_profile>
+    </qos_library>
+</dds>
+"#;
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let path = std::env::temp_dir().join("dust_dds_inconsistent_qos_profiles.xml");
+    std::fs::write(&path, INCONSISTENT_QOS_PROFILES).unwrap();
+    domain_participant_factory
+        .load_profiles_from_file(&path)
+        .unwrap();
+
+    assert!(matches!(
+        domain_participant_factory
+            .create_participant_with_profile(domain_id, "InconsistentLibrary::InconsistentProfile"),
+        Err(DdsError::InconsistentPolicy)
+    ));
+    assert!(domain_participant_factory
+        .lookup_participant(domain_id)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn unknown_profile_is_not_found() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+
+    assert!(matches!(
+        domain_participant_factory.get_qos_profile("TestLibrary::UnknownProfile"),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+    assert!(matches!(
+        domain_participant_factory
+            .create_participant_with_profile(domain_id, "TestLibrary::UnknownProfile"),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+}
+
+#[test]
+fn loading_missing_profiles_file_fails() {
+    let path = std::env::temp_dir().join("dust_dds_missing_qos_profiles.xml");
+
+    assert!(matches!(
+        DomainParticipantFactory::get_instance().load_profiles_from_file(&path),
+        Err(DdsError::Error(_))
+    ));
+}
@@ -1,16 +1,19 @@
 use dust_dds::{
-    builtin_topics::{BuiltInTopicKey, SubscriptionBuiltinTopicData},
+    builtin_topics::{BuiltInTopicKey, PublicationBuiltinTopicData, SubscriptionBuiltinTopicData},
     data_representation_builtin_endpoints::{
         discovered_reader_data::{DiscoveredReaderData, ReaderProxy, DCPS_SUBSCRIPTION},
+        discovered_writer_data::{DiscoveredWriterData, WriterProxy, DCPS_PUBLICATION},
         spdp_discovered_participant_data::{SpdpDiscoveredParticipantData, DCPS_PARTICIPANT},
     },
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
         error::DdsError,
-        qos::{DataReaderQos, DataWriterQos, QosKind, SubscriberQos},
+        qos::{DataReaderQos, DataWriterQos, PublisherQos, QosKind, SubscriberQos},
         qos_policy::{
-            DurabilityQosPolicy, DurabilityQosPolicyKind, HistoryQosPolicy, HistoryQosPolicyKind,
-            ReliabilityQosPolicy, ReliabilityQosPolicyKind, TopicDataQosPolicy,
+            DestinationOrderQosPolicy, DestinationOrderQosPolicyKind, DurabilityQosPolicy,
+            DurabilityQosPolicyKind, HistoryQosPolicy, HistoryQosPolicyKind, PresentationQosPolicy,
+            PresentationQosPolicyAccessScopeKind, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
+            TopicDataQosPolicy,
         },
         status::{StatusKind, NO_STATUS},
         time::{Duration, DurationKind},
@@ -18,19 +21,25 @@ use dust_dds::{
     },
     rtps::{
         discovery_types::{
+            ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER,
+            ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR,
             ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER,
             ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_DETECTOR,
         },
         messages::{
             overall_structure::{
                 RtpsMessageHeader, RtpsMessageRead, RtpsMessageWrite, RtpsSubmessageReadKind,
+                Submessage,
+            },
+            submessage_elements::{Data, FragmentNumberSet, ParameterList, SequenceNumberSet},
+            submessages::{
+                ack_nack::AckNackSubmessage, data::DataSubmessage, gap::GapSubmessage,
+                heartbeat::HeartbeatSubmessage, nack_frag::NackFragSubmessage,
             },
-            submessage_elements::{Data, ParameterList, SequenceNumberSet},
-            submessages::{ack_nack::AckNackSubmessage, data::DataSubmessage},
         },
         types::{
             EntityId, Guid, Locator, ENTITYID_UNKNOWN, LOCATOR_KIND_UDP_V4, PROTOCOLVERSION,
-            USER_DEFINED_READER_WITH_KEY, VENDOR_ID_S2E,
+            USER_DEFINED_READER_WITH_KEY, USER_DEFINED_WRITER_WITH_KEY, VENDOR_ID_S2E,
         },
     },
     subscription::sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
@@ -48,6 +57,13 @@ struct KeyedData {
     value: u32,
 }
 
+#[derive(Clone, Debug, PartialEq, DdsType)]
+struct LargeData {
+    #[dust_dds(key)]
+    id: u8,
+    value: Vec<u8>,
+}
+
 #[test]
 fn writer_should_send_heartbeat_periodically() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -613,7 +629,7 @@ fn writer_should_resend_data_after_acknack_request() {
 }
 
 #[test]
-fn volatile_writer_should_send_gap_submessage_after_discovery() {
+fn writer_should_ignore_acknack_addressed_to_other_writer() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
 
     let mock_reader_socket = std::net::UdpSocket::bind("0.0.0.0:0").unwrap();
@@ -669,10 +685,8 @@ fn volatile_writer_should_send_gap_submessage_after_discovery() {
     // Add discovered dummy reader
     let participant_handle = participant.get_instance_handle().unwrap();
     let guid_prefix = participant_handle.as_ref()[0..12].try_into().unwrap();
-    let remote_reader_guid = Guid::new(
-        guid_prefix,
-        EntityId::new([0, 0, 0], USER_DEFINED_READER_WITH_KEY),
-    );
+    let reader_id = EntityId::new([0, 0, 0], USER_DEFINED_READER_WITH_KEY);
+    let remote_reader_guid = Guid::new(guid_prefix, reader_id);
     let reader_proxy = ReaderProxy::new(
         remote_reader_guid,
         ENTITYID_UNKNOWN,
@@ -711,8 +725,9 @@ fn volatile_writer_should_send_gap_submessage_after_discovery() {
         ParameterList::empty(),
         Data::new(serialized_dummy_reader_discovery_bytes.into()),
     );
+    let rtps_message_header = RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, guid_prefix);
     let discovered_reader_rtps_message = RtpsMessageWrite::new(
-        &RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, guid_prefix),
+        &rtps_message_header,
         &[Box::new(discovered_reader_data_submessage)],
     );
 
@@ -720,9 +735,6 @@ fn volatile_writer_should_send_gap_submessage_after_discovery() {
         .wait(dust_dds::infrastructure::time::Duration::new(10, 0))
         .unwrap();
 
-    // Send data with the writer before discovery
-    writer.write(&KeyedData { id: 1, value: 2 }, None).unwrap();
-
     let dcps_participant_reader = builtin_subscriber
         .lookup_datareader::<SpdpDiscoveredParticipantData>(DCPS_PARTICIPANT)
         .unwrap()
@@ -736,6 +748,12 @@ fn volatile_writer_should_send_gap_submessage_after_discovery() {
         .participant_proxy()
         .metatraffic_unicast_locator_list()[0]
         .port();
+    let user_defined_traffic_port = dcps_sample_list[0]
+        .data()
+        .unwrap()
+        .participant_proxy()
+        .default_unicast_locator_list()[0]
+        .port();
     mock_reader_socket
         .send_to(
             discovered_reader_rtps_message.buffer(),
@@ -753,6 +771,9 @@ fn volatile_writer_should_send_gap_submessage_after_discovery() {
         .unwrap();
     waitset_writer.wait(Duration::new(10, 0)).unwrap();
 
+    // Send data with the writer
+    writer.write(&KeyedData { id: 1, value: 2 }, None).unwrap();
+
     let mut buffer = [0; 65535];
     mock_reader_socket.set_nonblocking(false).unwrap();
     mock_reader_socket
@@ -760,12 +781,36 @@ fn volatile_writer_should_send_gap_submessage_after_discovery() {
         .unwrap();
     mock_reader_socket.recv(&mut buffer).unwrap();
 
-    let received_gap = RtpsMessageRead::try_from(buffer.as_slice()).unwrap();
-    assert!(received_gap
-        .submessages()
+    let received_data_heartbeat = RtpsMessageRead::try_from(buffer.as_slice())
+        .unwrap()
+        .submessages();
+    let received_heartbeat = received_data_heartbeat
         .iter()
-        .find(|s| matches!(s, RtpsSubmessageReadKind::Gap(_)))
-        .is_some());
+        .find(|s| matches!(s, RtpsSubmessageReadKind::Heartbeat(_)))
+        .unwrap();
+
+    let writer_id = match received_heartbeat {
+        RtpsSubmessageReadKind::Heartbeat(h) => h.writer_id(),
+        _ => panic!("Wrong message type"),
+    };
+
+    // Request sample 1 to be resent but address the AckNack to a writer which doesn't exist
+    let other_writer_id = EntityId::new(
+        [writer_id.entity_key()[0].wrapping_add(1), 0, 0],
+        writer_id.entity_kind(),
+    );
+    let reader_sn_state = SequenceNumberSet::new(1, [1]);
+    let reader_acknack_submessage =
+        AckNackSubmessage::new(true, reader_id, other_writer_id, reader_sn_state, 1);
+
+    let acknack_message =
+        RtpsMessageWrite::new(&rtps_message_header, &[Box::new(reader_acknack_submessage)]);
+    mock_reader_socket
+        .send_to(
+            acknack_message.buffer(),
+            ("127.0.0.1", user_defined_traffic_port as u16),
+        )
+        .unwrap();
 
     // Default heartbeat period is 200ms
     let mut buffer = [0; 65535];
@@ -774,15 +819,19 @@ fn volatile_writer_should_send_gap_submessage_after_discovery() {
         .unwrap();
     mock_reader_socket.recv(&mut buffer).unwrap();
     let received_heartbeat = RtpsMessageRead::try_from(buffer.as_slice()).unwrap();
-    assert!(received_heartbeat
-        .submessages()
+    let submessages = received_heartbeat.submessages();
+    assert!(submessages
+        .iter()
+        .find(|s| matches!(s, RtpsSubmessageReadKind::Data(_)))
+        .is_none());
+    assert!(submessages
         .iter()
         .find(|s| matches!(s, RtpsSubmessageReadKind::Heartbeat(_)))
         .is_some());
 }
 
 #[test]
-fn transient_local_writer_should_send_data_submessage_after_discovery() {
+fn writer_should_resend_only_fragments_requested_by_nack_frag() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
 
     let mock_reader_socket = std::net::UdpSocket::bind("0.0.0.0:0").unwrap();
@@ -816,9 +865,9 @@ fn transient_local_writer_should_send_data_submessage_after_discovery() {
         .unwrap();
 
     let topic_name = "MyTopic";
-    let type_name = "KeyedData";
+    let type_name = "LargeData";
     let topic = participant
-        .create_topic::<KeyedData>(topic_name, type_name, QosKind::Default, None, NO_STATUS)
+        .create_topic::<LargeData>(topic_name, type_name, QosKind::Default, None, NO_STATUS)
         .unwrap();
 
     let publisher = participant
@@ -829,9 +878,6 @@ fn transient_local_writer_should_send_data_submessage_after_discovery() {
             kind: ReliabilityQosPolicyKind::Reliable,
             max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
         },
-        durability: DurabilityQosPolicy {
-            kind: DurabilityQosPolicyKind::TransientLocal,
-        },
         ..Default::default()
     };
     let writer = publisher
@@ -841,10 +887,8 @@ fn transient_local_writer_should_send_data_submessage_after_discovery() {
     // Add discovered dummy reader
     let participant_handle = participant.get_instance_handle().unwrap();
     let guid_prefix = participant_handle.as_ref()[0..12].try_into().unwrap();
-    let remote_reader_guid = Guid::new(
-        guid_prefix,
-        EntityId::new([0, 0, 0], USER_DEFINED_READER_WITH_KEY),
-    );
+    let reader_id = EntityId::new([0, 0, 0], USER_DEFINED_READER_WITH_KEY);
+    let remote_reader_guid = Guid::new(guid_prefix, reader_id);
     let reader_proxy = ReaderProxy::new(
         remote_reader_guid,
         ENTITYID_UNKNOWN,
@@ -862,9 +906,6 @@ fn transient_local_writer_should_send_data_submessage_after_discovery() {
                 kind: ReliabilityQosPolicyKind::Reliable,
                 max_blocking_time: DurationKind::Infinite,
             },
-            durability: DurabilityQosPolicy {
-                kind: DurabilityQosPolicyKind::TransientLocal,
-            },
             ..Default::default()
         },
         SubscriberQos::default(),
@@ -886,8 +927,9 @@ fn transient_local_writer_should_send_data_submessage_after_discovery() {
         ParameterList::empty(),
         Data::new(serialized_dummy_reader_discovery_bytes.into()),
     );
+    let rtps_message_header = RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, guid_prefix);
     let discovered_reader_rtps_message = RtpsMessageWrite::new(
-        &RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, guid_prefix),
+        &rtps_message_header,
         &[Box::new(discovered_reader_data_submessage)],
     );
 
@@ -895,9 +937,6 @@ fn transient_local_writer_should_send_data_submessage_after_discovery() {
         .wait(dust_dds::infrastructure::time::Duration::new(10, 0))
         .unwrap();
 
-    // Send data with the writer before discovery
-    writer.write(&KeyedData { id: 1, value: 2 }, None).unwrap();
-
     let dcps_participant_reader = builtin_subscriber
         .lookup_datareader::<SpdpDiscoveredParticipantData>(DCPS_PARTICIPANT)
         .unwrap()
@@ -911,6 +950,12 @@ fn transient_local_writer_should_send_data_submessage_after_discovery() {
         .participant_proxy()
         .metatraffic_unicast_locator_list()[0]
         .port();
+    let user_defined_traffic_port = dcps_sample_list[0]
+        .data()
+        .unwrap()
+        .participant_proxy()
+        .default_unicast_locator_list()[0]
+        .port();
     mock_reader_socket
         .send_to(
             discovered_reader_rtps_message.buffer(),
@@ -928,40 +973,74 @@ fn transient_local_writer_should_send_data_submessage_after_discovery() {
         .unwrap();
     waitset_writer.wait(Duration::new(10, 0)).unwrap();
 
+    // Send data large enough to be fragmented with the default fragment size
+    writer
+        .write(
+            &LargeData {
+                id: 1,
+                value: vec![7; 6000],
+            },
+            None,
+        )
+        .unwrap();
+
     let mut buffer = [0; 65535];
     mock_reader_socket.set_nonblocking(false).unwrap();
     mock_reader_socket
         .set_read_timeout(Some(std::time::Duration::from_secs(10)))
         .unwrap();
-    mock_reader_socket.recv(&mut buffer).unwrap();
-
-    let received_data_heartbeat = RtpsMessageRead::try_from(buffer.as_slice()).unwrap();
-    let submessages = received_data_heartbeat.submessages();
-    assert!(submessages
-        .iter()
-        .find(|s| matches!(s, RtpsSubmessageReadKind::Data(_)))
-        .is_some());
-    assert!(submessages
-        .iter()
-        .find(|s| matches!(s, RtpsSubmessageReadKind::Heartbeat(_)))
-        .is_some());
-
-    // Default heartbeat period is 200ms
-    let mut buffer = [0; 65535];
+    let (writer_id, writer_sn, last_fragment_num) = loop {
+        let len = mock_reader_socket.recv(&mut buffer).unwrap();
+        let received_message = RtpsMessageRead::try_from(&buffer[..len]).unwrap();
+        if let Some(RtpsSubmessageReadKind::HeartbeatFrag(h)) = received_message
+            .submessages()
+            .into_iter()
+            .find(|s| matches!(s, RtpsSubmessageReadKind::HeartbeatFrag(_)))
+        {
+            break (h.writer_id(), h.writer_sn(), h.last_fragment_num());
+        }
+    };
+    assert!(last_fragment_num >= 3);
+
+    // Request only fragment 3 to be resent
+    let nack_frag_submessage = NackFragSubmessage::new(
+        reader_id,
+        writer_id,
+        writer_sn,
+        FragmentNumberSet::new(3, [3]),
+        1,
+    );
+    let nack_frag_message =
+        RtpsMessageWrite::new(&rtps_message_header, &[Box::new(nack_frag_submessage)]);
     mock_reader_socket
-        .set_read_timeout(Some(std::time::Duration::from_millis(300)))
+        .send_to(
+            nack_frag_message.buffer(),
+            ("127.0.0.1", user_defined_traffic_port as u16),
+        )
         .unwrap();
-    mock_reader_socket.recv(&mut buffer).unwrap();
-    let received_heartbeat = RtpsMessageRead::try_from(buffer.as_slice()).unwrap();
-    let submessages = received_heartbeat.submessages();
-    assert!(submessages
-        .iter()
-        .find(|s| matches!(s, RtpsSubmessageReadKind::Heartbeat(_)))
-        .is_some());
+
+    mock_reader_socket
+        .set_read_timeout(Some(std::time::Duration::from_millis(100)))
+        .unwrap();
+    let mut resent_fragments = Vec::new();
+    let deadline = std::time::Instant::now() + std::time::Duration::from_millis(500);
+    while std::time::Instant::now() < deadline {
+        let Ok(len) = mock_reader_socket.recv(&mut buffer) else {
+            continue;
+        };
+        let received_message = RtpsMessageRead::try_from(&buffer[..len]).unwrap();
+        for submessage in received_message.submessages() {
+            if let RtpsSubmessageReadKind::DataFrag(data_frag) = submessage {
+                resent_fragments.push(data_frag.fragment_starting_num());
+            }
+        }
+    }
+
+    assert_eq!(resent_fragments, vec![3]);
 }
 
 #[test]
-fn reliable_writer_should_not_remove_unacked_sample_from_history() {
+fn writer_should_send_gap_for_requested_change_removed_from_history() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
 
     let mock_reader_socket = std::net::UdpSocket::bind("0.0.0.0:0").unwrap();
@@ -1006,7 +1085,7 @@ fn reliable_writer_should_not_remove_unacked_sample_from_history() {
     let writer_qos = DataWriterQos {
         reliability: ReliabilityQosPolicy {
             kind: ReliabilityQosPolicyKind::Reliable,
-            max_blocking_time: DurationKind::Finite(Duration::new(0, 100_000_000)),
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
         },
         history: HistoryQosPolicy {
             kind: HistoryQosPolicyKind::KeepLast(1),
@@ -1083,6 +1162,12 @@ fn reliable_writer_should_not_remove_unacked_sample_from_history() {
         .participant_proxy()
         .metatraffic_unicast_locator_list()[0]
         .port();
+    let user_defined_traffic_port = dcps_sample_list[0]
+        .data()
+        .unwrap()
+        .participant_proxy()
+        .default_unicast_locator_list()[0]
+        .port();
     mock_reader_socket
         .send_to(
             discovered_reader_rtps_message.buffer(),
@@ -1102,9 +1187,1664 @@ fn reliable_writer_should_not_remove_unacked_sample_from_history() {
 
     // Send data with the writer
     writer.write(&KeyedData { id: 1, value: 2 }, None).unwrap();
-    // Second send fails because sample is not acknowledged
-    assert_eq!(
-        writer.write(&KeyedData { id: 1, value: 2 }, None),
-        Err(DdsError::Timeout)
+
+    let mut buffer = [0; 65535];
+    mock_reader_socket.set_nonblocking(false).unwrap();
+    mock_reader_socket
+        .set_read_timeout(Some(std::time::Duration::from_secs(10)))
+        .unwrap();
+    mock_reader_socket.recv(&mut buffer).unwrap();
+
+    let received_data_heartbeat = RtpsMessageRead::try_from(buffer.as_slice())
+        .unwrap()
+        .submessages();
+    let received_heartbeat = received_data_heartbeat
+        .iter()
+        .find(|s| matches!(s, RtpsSubmessageReadKind::Heartbeat(_)))
+        .unwrap();
+
+    let writer_id = match received_heartbeat {
+        RtpsSubmessageReadKind::Heartbeat(h) => h.writer_id(),
+        _ => panic!("Wrong message type"),
+    };
+
+    // Acknowledge sample 1 so that the next write replaces it in the history
+    let reader_acknack_submessage =
+        AckNackSubmessage::new(true, reader_id, writer_id, SequenceNumberSet::new(2, []), 1);
+    let acknack_message =
+        RtpsMessageWrite::new(&rtps_message_header, &[Box::new(reader_acknack_submessage)]);
+    mock_reader_socket
+        .send_to(
+            acknack_message.buffer(),
+            ("127.0.0.1", user_defined_traffic_port as u16),
+        )
+        .unwrap();
+
+    writer.write(&KeyedData { id: 1, value: 3 }, None).unwrap();
+
+    // Request sample 1 which is no longer in the writer history
+    let reader_acknack_submessage = AckNackSubmessage::new(
+        true,
+        reader_id,
+        writer_id,
+        SequenceNumberSet::new(1, [1]),
+        2,
+    );
+    let acknack_message =
+        RtpsMessageWrite::new(&rtps_message_header, &[Box::new(reader_acknack_submessage)]);
+    mock_reader_socket
+        .send_to(
+            acknack_message.buffer(),
+            ("127.0.0.1", user_defined_traffic_port as u16),
+        )
+        .unwrap();
+
+    mock_reader_socket
+        .set_read_timeout(Some(std::time::Duration::from_millis(100)))
+        .unwrap();
+    let mut gap_start_list = Vec::new();
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(1);
+    while std::time::Instant::now() < deadline {
+        let Ok(len) = mock_reader_socket.recv(&mut buffer) else {
+            continue;
+        };
+        let received_message = RtpsMessageRead::try_from(&buffer[..len]).unwrap();
+        for submessage in received_message.submessages() {
+            if let RtpsSubmessageReadKind::Gap(gap) = submessage {
+                gap_start_list.push(gap.gap_start());
+            }
+        }
+    }
+
+    assert!(gap_start_list.contains(&1));
+}
+
+#[test]
+fn volatile_writer_should_send_gap_submessage_after_discovery() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let mock_reader_socket = std::net::UdpSocket::bind("0.0.0.0:0").unwrap();
+
+    let reader_socket_port = mock_reader_socket.local_addr().unwrap().port();
+    println!("Socket open on port {}", reader_socket_port);
+    let reader_unicast_locator = Locator::new(
+        LOCATOR_KIND_UDP_V4,
+        reader_socket_port as u32,
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 127, 0, 0, 1],
+    );
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let builtin_subscriber = participant.get_builtin_subscriber();
+    let dcps_subscription_reader = builtin_subscriber
+        .lookup_datareader::<DiscoveredReaderData>(DCPS_SUBSCRIPTION)
+        .unwrap()
+        .unwrap();
+    let dcps_subscription_reader_statuscondition = dcps_subscription_reader.get_statuscondition();
+    dcps_subscription_reader_statuscondition
+        .set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    let mut waitset_builtin_reader = WaitSet::new();
+    waitset_builtin_reader
+        .attach_condition(Condition::StatusCondition(
+            dcps_subscription_reader_statuscondition,
+        ))
+        .unwrap();
+
+    let topic_name = "MyTopic";
+    let type_name = "KeyedData";
+    let topic = participant
+        .create_topic::<KeyedData>(topic_name, type_name, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    // Add discovered dummy reader
+    let participant_handle = participant.get_instance_handle().unwrap();
+    let guid_prefix = participant_handle.as_ref()[0..12].try_into().unwrap();
+    let remote_reader_guid = Guid::new(
+        guid_prefix,
+        EntityId::new([0, 0, 0], USER_DEFINED_READER_WITH_KEY),
+    );
+    let reader_proxy = ReaderProxy::new(
+        remote_reader_guid,
+        ENTITYID_UNKNOWN,
+        vec![reader_unicast_locator],
+        vec![],
+        false,
     );
+    let subscription_builtin_topic_data = SubscriptionBuiltinTopicData::new(
+        BuiltInTopicKey::from(<[u8; 16]>::from(remote_reader_guid)),
+        BuiltInTopicKey::from(*participant_handle.as_ref()),
+        topic_name.to_string(),
+        type_name.to_string(),
+        DataReaderQos {
+            reliability: ReliabilityQosPolicy {
+                kind: ReliabilityQosPolicyKind::Reliable,
+                max_blocking_time: DurationKind::Infinite,
+            },
+            ..Default::default()
+        },
+        SubscriberQos::default(),
+        TopicDataQosPolicy::default(),
+        String::new(),
+    );
+    let dummy_reader_discovery =
+        DiscoveredReaderData::new(reader_proxy, subscription_builtin_topic_data);
+    let serialized_dummy_reader_discovery_bytes = dummy_reader_discovery.serialize_data().unwrap();
+
+    let discovered_reader_data_submessage = DataSubmessage::new(
+        false,
+        true,
+        false,
+        false,
+        ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_DETECTOR,
+        ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER,
+        1,
+        ParameterList::empty(),
+        Data::new(serialized_dummy_reader_discovery_bytes.into()),
+    );
+    let discovered_reader_rtps_message = RtpsMessageWrite::new(
+        &RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, guid_prefix),
+        &[Box::new(discovered_reader_data_submessage)],
+    );
+
+    waitset_builtin_reader
+        .wait(dust_dds::infrastructure::time::Duration::new(10, 0))
+        .unwrap();
+
+    // Send data with the writer before discovery
+    writer.write(&KeyedData { id: 1, value: 2 }, None).unwrap();
+
+    let dcps_participant_reader = builtin_subscriber
+        .lookup_datareader::<SpdpDiscoveredParticipantData>(DCPS_PARTICIPANT)
+        .unwrap()
+        .unwrap();
+    let dcps_sample_list = dcps_participant_reader
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    let metatraffic_port = dcps_sample_list[0]
+        .data()
+        .unwrap()
+        .participant_proxy()
+        .metatraffic_unicast_locator_list()[0]
+        .port();
+    mock_reader_socket
+        .send_to(
+            discovered_reader_rtps_message.buffer(),
+            ("127.0.0.1", metatraffic_port as u16),
+        )
+        .unwrap();
+
+    let mut waitset_writer = WaitSet::new();
+    let writer_status_condition = writer.get_statuscondition();
+    writer_status_condition
+        .set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    waitset_writer
+        .attach_condition(Condition::StatusCondition(writer_status_condition))
+        .unwrap();
+    waitset_writer.wait(Duration::new(10, 0)).unwrap();
+
+    let mut buffer = [0; 65535];
+    mock_reader_socket.set_nonblocking(false).unwrap();
+    mock_reader_socket
+        .set_read_timeout(Some(std::time::Duration::from_secs(10)))
+        .unwrap();
+    mock_reader_socket.recv(&mut buffer).unwrap();
+
+    let received_gap = RtpsMessageRead::try_from(buffer.as_slice()).unwrap();
+    assert!(received_gap
+        .submessages()
+        .iter()
+        .find(|s| matches!(s, RtpsSubmessageReadKind::Gap(_)))
+        .is_some());
+
+    // Default heartbeat period is 200ms
+    let mut buffer = [0; 65535];
+    mock_reader_socket
+        .set_read_timeout(Some(std::time::Duration::from_millis(300)))
+        .unwrap();
+    mock_reader_socket.recv(&mut buffer).unwrap();
+    let received_heartbeat = RtpsMessageRead::try_from(buffer.as_slice()).unwrap();
+    assert!(received_heartbeat
+        .submessages()
+        .iter()
+        .find(|s| matches!(s, RtpsSubmessageReadKind::Heartbeat(_)))
+        .is_some());
+}
+
+#[test]
+fn transient_local_writer_should_send_data_submessage_after_discovery() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let mock_reader_socket = std::net::UdpSocket::bind("0.0.0.0:0").unwrap();
+
+    let reader_socket_port = mock_reader_socket.local_addr().unwrap().port();
+    println!("Socket open on port {}", reader_socket_port);
+    let reader_unicast_locator = Locator::new(
+        LOCATOR_KIND_UDP_V4,
+        reader_socket_port as u32,
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 127, 0, 0, 1],
+    );
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let builtin_subscriber = participant.get_builtin_subscriber();
+    let dcps_subscription_reader = builtin_subscriber
+        .lookup_datareader::<DiscoveredReaderData>(DCPS_SUBSCRIPTION)
+        .unwrap()
+        .unwrap();
+    let dcps_subscription_reader_statuscondition = dcps_subscription_reader.get_statuscondition();
+    dcps_subscription_reader_statuscondition
+        .set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    let mut waitset_builtin_reader = WaitSet::new();
+    waitset_builtin_reader
+        .attach_condition(Condition::StatusCondition(
+            dcps_subscription_reader_statuscondition,
+        ))
+        .unwrap();
+
+    let topic_name = "MyTopic";
+    let type_name = "KeyedData";
+    let topic = participant
+        .create_topic::<KeyedData>(topic_name, type_name, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::TransientLocal,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    // Add discovered dummy reader
+    let participant_handle = participant.get_instance_handle().unwrap();
+    let guid_prefix = participant_handle.as_ref()[0..12].try_into().unwrap();
+    let remote_reader_guid = Guid::new(
+        guid_prefix,
+        EntityId::new([0, 0, 0], USER_DEFINED_READER_WITH_KEY),
+    );
+    let reader_proxy = ReaderProxy::new(
+        remote_reader_guid,
+        ENTITYID_UNKNOWN,
+        vec![reader_unicast_locator],
+        vec![],
+        false,
+    );
+    let subscription_builtin_topic_data = SubscriptionBuiltinTopicData::new(
+        BuiltInTopicKey::from(<[u8; 16]>::from(remote_reader_guid)),
+        BuiltInTopicKey::from(*participant_handle.as_ref()),
+        topic_name.to_string(),
+        type_name.to_string(),
+        DataReaderQos {
+            reliability: ReliabilityQosPolicy {
+                kind: ReliabilityQosPolicyKind::Reliable,
+                max_blocking_time: DurationKind::Infinite,
+            },
+            durability: DurabilityQosPolicy {
+                kind: DurabilityQosPolicyKind::TransientLocal,
+            },
+            ..Default::default()
+        },
+        SubscriberQos::default(),
+        TopicDataQosPolicy::default(),
+        String::new(),
+    );
+    let dummy_reader_discovery =
+        DiscoveredReaderData::new(reader_proxy, subscription_builtin_topic_data);
+    let serialized_dummy_reader_discovery_bytes = dummy_reader_discovery.serialize_data().unwrap();
+
+    let discovered_reader_data_submessage = DataSubmessage::new(
+        false,
+        true,
+        false,
+        false,
+        ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_DETECTOR,
+        ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER,
+        1,
+        ParameterList::empty(),
+        Data::new(serialized_dummy_reader_discovery_bytes.into()),
+    );
+    let discovered_reader_rtps_message = RtpsMessageWrite::new(
+        &RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, guid_prefix),
+        &[Box::new(discovered_reader_data_submessage)],
+    );
+
+    waitset_builtin_reader
+        .wait(dust_dds::infrastructure::time::Duration::new(10, 0))
+        .unwrap();
+
+    // Send data with the writer before discovery
+    writer.write(&KeyedData { id: 1, value: 2 }, None).unwrap();
+
+    let dcps_participant_reader = builtin_subscriber
+        .lookup_datareader::<SpdpDiscoveredParticipantData>(DCPS_PARTICIPANT)
+        .unwrap()
+        .unwrap();
+    let dcps_sample_list = dcps_participant_reader
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    let metatraffic_port = dcps_sample_list[0]
+        .data()
+        .unwrap()
+        .participant_proxy()
+        .metatraffic_unicast_locator_list()[0]
+        .port();
+    mock_reader_socket
+        .send_to(
+            discovered_reader_rtps_message.buffer(),
+            ("127.0.0.1", metatraffic_port as u16),
+        )
+        .unwrap();
+
+    let mut waitset_writer = WaitSet::new();
+    let writer_status_condition = writer.get_statuscondition();
+    writer_status_condition
+        .set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    waitset_writer
+        .attach_condition(Condition::StatusCondition(writer_status_condition))
+        .unwrap();
+    waitset_writer.wait(Duration::new(10, 0)).unwrap();
+
+    let mut buffer = [0; 65535];
+    mock_reader_socket.set_nonblocking(false).unwrap();
+    mock_reader_socket
+        .set_read_timeout(Some(std::time::Duration::from_secs(10)))
+        .unwrap();
+    mock_reader_socket.recv(&mut buffer).unwrap();
+
+    let received_data_heartbeat = RtpsMessageRead::try_from(buffer.as_slice()).unwrap();
+    let submessages = received_data_heartbeat.submessages();
+    assert!(submessages
+        .iter()
+        .find(|s| matches!(s, RtpsSubmessageReadKind::Data(_)))
+        .is_some());
+    assert!(submessages
+        .iter()
+        .find(|s| matches!(s, RtpsSubmessageReadKind::Heartbeat(_)))
+        .is_some());
+
+    // Default heartbeat period is 200ms
+    let mut buffer = [0; 65535];
+    mock_reader_socket
+        .set_read_timeout(Some(std::time::Duration::from_millis(300)))
+        .unwrap();
+    mock_reader_socket.recv(&mut buffer).unwrap();
+    let received_heartbeat = RtpsMessageRead::try_from(buffer.as_slice()).unwrap();
+    let submessages = received_heartbeat.submessages();
+    assert!(submessages
+        .iter()
+        .find(|s| matches!(s, RtpsSubmessageReadKind::Heartbeat(_)))
+        .is_some());
+}
+
+#[test]
+fn transient_local_writer_should_send_gap_for_samples_removed_from_history() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let mock_reader_socket = std::net::UdpSocket::bind("0.0.0.0:0").unwrap();
+
+    let reader_socket_port = mock_reader_socket.local_addr().unwrap().port();
+    println!("Socket open on port {}", reader_socket_port);
+    let reader_unicast_locator = Locator::new(
+        LOCATOR_KIND_UDP_V4,
+        reader_socket_port as u32,
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 127, 0, 0, 1],
+    );
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let builtin_subscriber = participant.get_builtin_subscriber();
+    let dcps_subscription_reader = builtin_subscriber
+        .lookup_datareader::<DiscoveredReaderData>(DCPS_SUBSCRIPTION)
+        .unwrap()
+        .unwrap();
+    let dcps_subscription_reader_statuscondition = dcps_subscription_reader.get_statuscondition();
+    dcps_subscription_reader_statuscondition
+        .set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    let mut waitset_builtin_reader = WaitSet::new();
+    waitset_builtin_reader
+        .attach_condition(Condition::StatusCondition(
+            dcps_subscription_reader_statuscondition,
+        ))
+        .unwrap();
+
+    let topic_name = "MyTopic";
+    let type_name = "KeyedData";
+    let topic = participant
+        .create_topic::<KeyedData>(topic_name, type_name, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::TransientLocal,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepLast(1),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    // Add discovered dummy reader
+    let participant_handle = participant.get_instance_handle().unwrap();
+    let guid_prefix = participant_handle.as_ref()[0..12].try_into().unwrap();
+    let remote_reader_guid = Guid::new(
+        guid_prefix,
+        EntityId::new([0, 0, 0], USER_DEFINED_READER_WITH_KEY),
+    );
+    let reader_proxy = ReaderProxy::new(
+        remote_reader_guid,
+        ENTITYID_UNKNOWN,
+        vec![reader_unicast_locator],
+        vec![],
+        false,
+    );
+    let subscription_builtin_topic_data = SubscriptionBuiltinTopicData::new(
+        BuiltInTopicKey::from(<[u8; 16]>::from(remote_reader_guid)),
+        BuiltInTopicKey::from(*participant_handle.as_ref()),
+        topic_name.to_string(),
+        type_name.to_string(),
+        DataReaderQos {
+            reliability: ReliabilityQosPolicy {
+                kind: ReliabilityQosPolicyKind::Reliable,
+                max_blocking_time: DurationKind::Infinite,
+            },
+            durability: DurabilityQosPolicy {
+                kind: DurabilityQosPolicyKind::TransientLocal,
+            },
+            ..Default::default()
+        },
+        SubscriberQos::default(),
+        TopicDataQosPolicy::default(),
+        String::new(),
+    );
+    let dummy_reader_discovery =
+        DiscoveredReaderData::new(reader_proxy, subscription_builtin_topic_data);
+    let serialized_dummy_reader_discovery_bytes = dummy_reader_discovery.serialize_data().unwrap();
+
+    let discovered_reader_data_submessage = DataSubmessage::new(
+        false,
+        true,
+        false,
+        false,
+        ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_DETECTOR,
+        ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER,
+        1,
+        ParameterList::empty(),
+        Data::new(serialized_dummy_reader_discovery_bytes.into()),
+    );
+    let discovered_reader_rtps_message = RtpsMessageWrite::new(
+        &RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, guid_prefix),
+        &[Box::new(discovered_reader_data_submessage)],
+    );
+
+    waitset_builtin_reader
+        .wait(dust_dds::infrastructure::time::Duration::new(10, 0))
+        .unwrap();
+
+    // Only the last sample is kept in the writer history
+    writer.write(&KeyedData { id: 1, value: 1 }, None).unwrap();
+    writer.write(&KeyedData { id: 1, value: 2 }, None).unwrap();
+    writer.write(&KeyedData { id: 1, value: 3 }, None).unwrap();
+
+    let dcps_participant_reader = builtin_subscriber
+        .lookup_datareader::<SpdpDiscoveredParticipantData>(DCPS_PARTICIPANT)
+        .unwrap()
+        .unwrap();
+    let dcps_sample_list = dcps_participant_reader
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    let metatraffic_port = dcps_sample_list[0]
+        .data()
+        .unwrap()
+        .participant_proxy()
+        .metatraffic_unicast_locator_list()[0]
+        .port();
+    mock_reader_socket
+        .send_to(
+            discovered_reader_rtps_message.buffer(),
+            ("127.0.0.1", metatraffic_port as u16),
+        )
+        .unwrap();
+
+    let mut waitset_writer = WaitSet::new();
+    let writer_status_condition = writer.get_statuscondition();
+    writer_status_condition
+        .set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    waitset_writer
+        .attach_condition(Condition::StatusCondition(writer_status_condition))
+        .unwrap();
+    waitset_writer.wait(Duration::new(10, 0)).unwrap();
+
+    let mut buffer = [0; 65535];
+    mock_reader_socket.set_nonblocking(false).unwrap();
+    mock_reader_socket
+        .set_read_timeout(Some(std::time::Duration::from_millis(100)))
+        .unwrap();
+    let mut gap_list = Vec::new();
+    let mut data_sequence_number_list = Vec::new();
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(1);
+    while std::time::Instant::now() < deadline {
+        let Ok(len) = mock_reader_socket.recv(&mut buffer) else {
+            continue;
+        };
+        let received_message = RtpsMessageRead::try_from(&buffer[..len]).unwrap();
+        for submessage in received_message.submessages() {
+            match submessage {
+                RtpsSubmessageReadKind::Gap(gap) => {
+                    gap_list.push((gap.gap_start(), gap.gap_list().base()))
+                }
+                RtpsSubmessageReadKind::Data(data) => {
+                    data_sequence_number_list.push(data.writer_sn())
+                }
+                _ => (),
+            }
+        }
+    }
+
+    // The gap covers the removed samples 1 and 2
+    assert_eq!(gap_list, vec![(1, 3)]);
+    assert_eq!(data_sequence_number_list, vec![3]);
+}
+
+#[test]
+fn reliable_writer_should_not_remove_unacked_sample_from_history() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let mock_reader_socket = std::net::UdpSocket::bind("0.0.0.0:0").unwrap();
+
+    let reader_socket_port = mock_reader_socket.local_addr().unwrap().port();
+    println!("Socket open on port {}", reader_socket_port);
+    let reader_unicast_locator = Locator::new(
+        LOCATOR_KIND_UDP_V4,
+        reader_socket_port as u32,
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 127, 0, 0, 1],
+    );
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let builtin_subscriber = participant.get_builtin_subscriber();
+    let dcps_subscription_reader = builtin_subscriber
+        .lookup_datareader::<DiscoveredReaderData>(DCPS_SUBSCRIPTION)
+        .unwrap()
+        .unwrap();
+    let dcps_subscription_reader_statuscondition = dcps_subscription_reader.get_statuscondition();
+    dcps_subscription_reader_statuscondition
+        .set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    let mut waitset_builtin_reader = WaitSet::new();
+    waitset_builtin_reader
+        .attach_condition(Condition::StatusCondition(
+            dcps_subscription_reader_statuscondition,
+        ))
+        .unwrap();
+
+    let topic_name = "MyTopic";
+    let type_name = "KeyedData";
+    let topic = participant
+        .create_topic::<KeyedData>(topic_name, type_name, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(0, 100_000_000)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepLast(1),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    // Add discovered dummy reader
+    let participant_handle = participant.get_instance_handle().unwrap();
+    let guid_prefix = participant_handle.as_ref()[0..12].try_into().unwrap();
+    let reader_id = EntityId::new([0, 0, 0], USER_DEFINED_READER_WITH_KEY);
+    let remote_reader_guid = Guid::new(guid_prefix, reader_id);
+    let reader_proxy = ReaderProxy::new(
+        remote_reader_guid,
+        ENTITYID_UNKNOWN,
+        vec![reader_unicast_locator],
+        vec![],
+        false,
+    );
+    let subscription_builtin_topic_data = SubscriptionBuiltinTopicData::new(
+        BuiltInTopicKey::from(<[u8; 16]>::from(remote_reader_guid)),
+        BuiltInTopicKey::from(*participant_handle.as_ref()),
+        topic_name.to_string(),
+        type_name.to_string(),
+        DataReaderQos {
+            reliability: ReliabilityQosPolicy {
+                kind: ReliabilityQosPolicyKind::Reliable,
+                max_blocking_time: DurationKind::Infinite,
+            },
+            ..Default::default()
+        },
+        SubscriberQos::default(),
+        TopicDataQosPolicy::default(),
+        String::new(),
+    );
+    let dummy_reader_discovery =
+        DiscoveredReaderData::new(reader_proxy, subscription_builtin_topic_data);
+    let serialized_dummy_reader_discovery_bytes = dummy_reader_discovery.serialize_data().unwrap();
+
+    let discovered_reader_data_submessage = DataSubmessage::new(
+        false,
+        true,
+        false,
+        false,
+        ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_DETECTOR,
+        ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER,
+        1,
+        ParameterList::empty(),
+        Data::new(serialized_dummy_reader_discovery_bytes.into()),
+    );
+    let rtps_message_header = RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, guid_prefix);
+    let discovered_reader_rtps_message = RtpsMessageWrite::new(
+        &rtps_message_header,
+        &[Box::new(discovered_reader_data_submessage)],
+    );
+
+    waitset_builtin_reader
+        .wait(dust_dds::infrastructure::time::Duration::new(10, 0))
+        .unwrap();
+
+    let dcps_participant_reader = builtin_subscriber
+        .lookup_datareader::<SpdpDiscoveredParticipantData>(DCPS_PARTICIPANT)
+        .unwrap()
+        .unwrap();
+    let dcps_sample_list = dcps_participant_reader
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    let metatraffic_port = dcps_sample_list[0]
+        .data()
+        .unwrap()
+        .participant_proxy()
+        .metatraffic_unicast_locator_list()[0]
+        .port();
+    mock_reader_socket
+        .send_to(
+            discovered_reader_rtps_message.buffer(),
+            ("127.0.0.1", metatraffic_port as u16),
+        )
+        .unwrap();
+
+    let mut waitset_writer = WaitSet::new();
+    let writer_status_condition = writer.get_statuscondition();
+    writer_status_condition
+        .set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    waitset_writer
+        .attach_condition(Condition::StatusCondition(writer_status_condition))
+        .unwrap();
+    waitset_writer.wait(Duration::new(10, 0)).unwrap();
+
+    // Send data with the writer
+    writer.write(&KeyedData { id: 1, value: 2 }, None).unwrap();
+    // Second send fails because sample is not acknowledged
+    assert_eq!(
+        writer.write(&KeyedData { id: 1, value: 2 }, None),
+        Err(DdsError::Timeout)
+    );
+}
+
+#[test]
+fn reader_by_source_timestamp_should_accept_data_without_info_timestamp() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let mock_writer_socket = std::net::UdpSocket::bind("0.0.0.0:0").unwrap();
+
+    let writer_socket_port = mock_writer_socket.local_addr().unwrap().port();
+    let writer_unicast_locator = Locator::new(
+        LOCATOR_KIND_UDP_V4,
+        writer_socket_port as u32,
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 127, 0, 0, 1],
+    );
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let builtin_subscriber = participant.get_builtin_subscriber();
+    let dcps_publication_reader = builtin_subscriber
+        .lookup_datareader::<DiscoveredWriterData>(DCPS_PUBLICATION)
+        .unwrap()
+        .unwrap();
+    let dcps_publication_reader_statuscondition = dcps_publication_reader.get_statuscondition();
+    dcps_publication_reader_statuscondition
+        .set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    let mut waitset_builtin_reader = WaitSet::new();
+    waitset_builtin_reader
+        .attach_condition(Condition::StatusCondition(
+            dcps_publication_reader_statuscondition,
+        ))
+        .unwrap();
+
+    let topic_name = "MyTopic";
+    let type_name = "KeyedData";
+    let topic = participant
+        .create_topic::<KeyedData>(topic_name, type_name, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        destination_order: DestinationOrderQosPolicy {
+            kind: DestinationOrderQosPolicyKind::BySourceTimestamp,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    // Add discovered dummy writer
+    let participant_handle = participant.get_instance_handle().unwrap();
+    let guid_prefix = participant_handle.as_ref()[0..12].try_into().unwrap();
+    let writer_id = EntityId::new([0, 0, 0], USER_DEFINED_WRITER_WITH_KEY);
+    let remote_writer_guid = Guid::new(guid_prefix, writer_id);
+    let writer_proxy = WriterProxy::new(
+        remote_writer_guid,
+        ENTITYID_UNKNOWN,
+        vec![writer_unicast_locator],
+        vec![],
+        None,
+    );
+    let publication_builtin_topic_data = PublicationBuiltinTopicData::new(
+        BuiltInTopicKey::from(<[u8; 16]>::from(remote_writer_guid)),
+        BuiltInTopicKey::from(*participant_handle.as_ref()),
+        topic_name.to_string(),
+        type_name.to_string(),
+        DataWriterQos {
+            reliability: ReliabilityQosPolicy {
+                kind: ReliabilityQosPolicyKind::Reliable,
+                max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+            },
+            destination_order: DestinationOrderQosPolicy {
+                kind: DestinationOrderQosPolicyKind::BySourceTimestamp,
+            },
+            ..Default::default()
+        },
+        PublisherQos::default(),
+        TopicDataQosPolicy::default(),
+        String::new(),
+    );
+    let dummy_writer_discovery =
+        DiscoveredWriterData::new(publication_builtin_topic_data, writer_proxy);
+    let serialized_dummy_writer_discovery_bytes = dummy_writer_discovery.serialize_data().unwrap();
+
+    let discovered_writer_data_submessage = DataSubmessage::new(
+        false,
+        true,
+        false,
+        false,
+        ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR,
+        ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER,
+        1,
+        ParameterList::empty(),
+        Data::new(serialized_dummy_writer_discovery_bytes.into()),
+    );
+    let rtps_message_header = RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, guid_prefix);
+    let discovered_writer_rtps_message = RtpsMessageWrite::new(
+        &rtps_message_header,
+        &[Box::new(discovered_writer_data_submessage)],
+    );
+
+    waitset_builtin_reader
+        .wait(dust_dds::infrastructure::time::Duration::new(10, 0))
+        .unwrap();
+
+    let dcps_participant_reader = builtin_subscriber
+        .lookup_datareader::<SpdpDiscoveredParticipantData>(DCPS_PARTICIPANT)
+        .unwrap()
+        .unwrap();
+    let dcps_sample_list = dcps_participant_reader
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    let metatraffic_port = dcps_sample_list[0]
+        .data()
+        .unwrap()
+        .participant_proxy()
+        .metatraffic_unicast_locator_list()[0]
+        .port();
+    let user_defined_traffic_port = dcps_sample_list[0]
+        .data()
+        .unwrap()
+        .participant_proxy()
+        .default_unicast_locator_list()[0]
+        .port();
+    mock_writer_socket
+        .send_to(
+            discovered_writer_rtps_message.buffer(),
+            ("127.0.0.1", metatraffic_port as u16),
+        )
+        .unwrap();
+
+    let mut waitset_reader = WaitSet::new();
+    let reader_status_condition = reader.get_statuscondition();
+    reader_status_condition
+        .set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    waitset_reader
+        .attach_condition(Condition::StatusCondition(reader_status_condition))
+        .unwrap();
+    waitset_reader.wait(Duration::new(10, 0)).unwrap();
+
+    // The samples are sent without an INFO_TS submessage so they have no source timestamp
+    let data_submessages: Vec<_> = (1..=2)
+        .map(|sequence_number| {
+            Box::new(DataSubmessage::new(
+                false,
+                true,
+                false,
+                false,
+                ENTITYID_UNKNOWN,
+                writer_id,
+                sequence_number,
+                ParameterList::empty(),
+                Data::new(
+                    KeyedData {
+                        id: 1,
+                        value: sequence_number as u32,
+                    }
+                    .serialize_data()
+                    .unwrap()
+                    .into(),
+                ),
+            )) as Box<dyn Submessage + Send>
+        })
+        .collect();
+    let data_message = RtpsMessageWrite::new(&rtps_message_header, &data_submessages);
+    mock_writer_socket
+        .send_to(
+            data_message.buffer(),
+            ("127.0.0.1", user_defined_traffic_port as u16),
+        )
+        .unwrap();
+
+    let start = std::time::Instant::now();
+    let mut received_values = Vec::new();
+    while received_values.len() < 2 {
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(10),
+            "Samples without source timestamp not received"
+        );
+        if let Ok(samples) = reader.take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE) {
+            received_values.extend(samples.iter().map(|s| s.data().unwrap().value));
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    assert_eq!(received_values, vec![1, 2]);
+}
+
+#[test]
+fn reader_should_send_acknack_for_changes_announced_in_heartbeat() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let mock_writer_socket = std::net::UdpSocket::bind("0.0.0.0:0").unwrap();
+
+    let writer_socket_port = mock_writer_socket.local_addr().unwrap().port();
+    let writer_unicast_locator = Locator::new(
+        LOCATOR_KIND_UDP_V4,
+        writer_socket_port as u32,
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 127, 0, 0, 1],
+    );
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let builtin_subscriber = participant.get_builtin_subscriber();
+    let dcps_publication_reader = builtin_subscriber
+        .lookup_datareader::<DiscoveredWriterData>(DCPS_PUBLICATION)
+        .unwrap()
+        .unwrap();
+    let dcps_publication_reader_statuscondition = dcps_publication_reader.get_statuscondition();
+    dcps_publication_reader_statuscondition
+        .set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    let mut waitset_builtin_reader = WaitSet::new();
+    waitset_builtin_reader
+        .attach_condition(Condition::StatusCondition(
+            dcps_publication_reader_statuscondition,
+        ))
+        .unwrap();
+
+    let topic_name = "MyTopic";
+    let type_name = "KeyedData";
+    let topic = participant
+        .create_topic::<KeyedData>(topic_name, type_name, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    // Add discovered dummy writer
+    let participant_handle = participant.get_instance_handle().unwrap();
+    let guid_prefix = participant_handle.as_ref()[0..12].try_into().unwrap();
+    let writer_id = EntityId::new([0, 0, 0], USER_DEFINED_WRITER_WITH_KEY);
+    let remote_writer_guid = Guid::new(guid_prefix, writer_id);
+    let writer_proxy = WriterProxy::new(
+        remote_writer_guid,
+        ENTITYID_UNKNOWN,
+        vec![writer_unicast_locator],
+        vec![],
+        None,
+    );
+    let publication_builtin_topic_data = PublicationBuiltinTopicData::new(
+        BuiltInTopicKey::from(<[u8; 16]>::from(remote_writer_guid)),
+        BuiltInTopicKey::from(*participant_handle.as_ref()),
+        topic_name.to_string(),
+        type_name.to_string(),
+        DataWriterQos {
+            reliability: ReliabilityQosPolicy {
+                kind: ReliabilityQosPolicyKind::Reliable,
+                max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+            },
+            ..Default::default()
+        },
+        PublisherQos::default(),
+        TopicDataQosPolicy::default(),
+        String::new(),
+    );
+    let dummy_writer_discovery =
+        DiscoveredWriterData::new(publication_builtin_topic_data, writer_proxy);
+    let serialized_dummy_writer_discovery_bytes = dummy_writer_discovery.serialize_data().unwrap();
+
+    let discovered_writer_data_submessage = DataSubmessage::new(
+        false,
+        true,
+        false,
+        false,
+        ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR,
+        ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER,
+        1,
+        ParameterList::empty(),
+        Data::new(serialized_dummy_writer_discovery_bytes.into()),
+    );
+    let rtps_message_header = RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, guid_prefix);
+    let discovered_writer_rtps_message = RtpsMessageWrite::new(
+        &rtps_message_header,
+        &[Box::new(discovered_writer_data_submessage)],
+    );
+
+    waitset_builtin_reader
+        .wait(dust_dds::infrastructure::time::Duration::new(10, 0))
+        .unwrap();
+
+    let dcps_participant_reader = builtin_subscriber
+        .lookup_datareader::<SpdpDiscoveredParticipantData>(DCPS_PARTICIPANT)
+        .unwrap()
+        .unwrap();
+    let dcps_sample_list = dcps_participant_reader
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    let metatraffic_port = dcps_sample_list[0]
+        .data()
+        .unwrap()
+        .participant_proxy()
+        .metatraffic_unicast_locator_list()[0]
+        .port();
+    let user_defined_traffic_port = dcps_sample_list[0]
+        .data()
+        .unwrap()
+        .participant_proxy()
+        .default_unicast_locator_list()[0]
+        .port();
+    mock_writer_socket
+        .send_to(
+            discovered_writer_rtps_message.buffer(),
+            ("127.0.0.1", metatraffic_port as u16),
+        )
+        .unwrap();
+
+    let mut waitset_reader = WaitSet::new();
+    let reader_status_condition = reader.get_statuscondition();
+    reader_status_condition
+        .set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    waitset_reader
+        .attach_condition(Condition::StatusCondition(reader_status_condition))
+        .unwrap();
+    waitset_reader.wait(Duration::new(10, 0)).unwrap();
+
+    // Announce samples 1 and 2 which were never sent to the reader. The final flag is set so
+    // the AckNack is only expected because of the missing changes
+    let heartbeat_submessage =
+        HeartbeatSubmessage::new(true, false, ENTITYID_UNKNOWN, writer_id, 1, 2, 1);
+    let heartbeat_message =
+        RtpsMessageWrite::new(&rtps_message_header, &[Box::new(heartbeat_submessage)]);
+    mock_writer_socket
+        .send_to(
+            heartbeat_message.buffer(),
+            ("127.0.0.1", user_defined_traffic_port as u16),
+        )
+        .unwrap();
+
+    let mut buffer = [0; 65535];
+    mock_writer_socket.set_nonblocking(false).unwrap();
+    mock_writer_socket
+        .set_read_timeout(Some(std::time::Duration::from_secs(10)))
+        .unwrap();
+    let acknack_submessage = loop {
+        let len = mock_writer_socket.recv(&mut buffer).unwrap();
+        let received_message = RtpsMessageRead::try_from(&buffer[..len]).unwrap();
+        if let Some(RtpsSubmessageReadKind::AckNack(acknack)) = received_message
+            .submessages()
+            .into_iter()
+            .find(|s| matches!(s, RtpsSubmessageReadKind::AckNack(_)))
+        {
+            break acknack;
+        }
+    };
+
+    assert_eq!(acknack_submessage.writer_id(), &writer_id);
+    assert_eq!(acknack_submessage.reader_sn_state().base(), 1);
+    assert_eq!(
+        acknack_submessage
+            .reader_sn_state()
+            .set()
+            .collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+}
+
+#[test]
+fn reader_should_not_request_changes_covered_by_gap() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let mock_writer_socket = std::net::UdpSocket::bind("0.0.0.0:0").unwrap();
+
+    let writer_socket_port = mock_writer_socket.local_addr().unwrap().port();
+    let writer_unicast_locator = Locator::new(
+        LOCATOR_KIND_UDP_V4,
+        writer_socket_port as u32,
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 127, 0, 0, 1],
+    );
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let builtin_subscriber = participant.get_builtin_subscriber();
+    let dcps_publication_reader = builtin_subscriber
+        .lookup_datareader::<DiscoveredWriterData>(DCPS_PUBLICATION)
+        .unwrap()
+        .unwrap();
+    let dcps_publication_reader_statuscondition = dcps_publication_reader.get_statuscondition();
+    dcps_publication_reader_statuscondition
+        .set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    let mut waitset_builtin_reader = WaitSet::new();
+    waitset_builtin_reader
+        .attach_condition(Condition::StatusCondition(
+            dcps_publication_reader_statuscondition,
+        ))
+        .unwrap();
+
+    let topic_name = "MyTopic";
+    let type_name = "KeyedData";
+    let topic = participant
+        .create_topic::<KeyedData>(topic_name, type_name, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    // Add discovered dummy writer
+    let participant_handle = participant.get_instance_handle().unwrap();
+    let guid_prefix = participant_handle.as_ref()[0..12].try_into().unwrap();
+    let writer_id = EntityId::new([0, 0, 0], USER_DEFINED_WRITER_WITH_KEY);
+    let remote_writer_guid = Guid::new(guid_prefix, writer_id);
+    let writer_proxy = WriterProxy::new(
+        remote_writer_guid,
+        ENTITYID_UNKNOWN,
+        vec![writer_unicast_locator],
+        vec![],
+        None,
+    );
+    let publication_builtin_topic_data = PublicationBuiltinTopicData::new(
+        BuiltInTopicKey::from(<[u8; 16]>::from(remote_writer_guid)),
+        BuiltInTopicKey::from(*participant_handle.as_ref()),
+        topic_name.to_string(),
+        type_name.to_string(),
+        DataWriterQos {
+            reliability: ReliabilityQosPolicy {
+                kind: ReliabilityQosPolicyKind::Reliable,
+                max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+            },
+            ..Default::default()
+        },
+        PublisherQos::default(),
+        TopicDataQosPolicy::default(),
+        String::new(),
+    );
+    let dummy_writer_discovery =
+        DiscoveredWriterData::new(publication_builtin_topic_data, writer_proxy);
+    let serialized_dummy_writer_discovery_bytes = dummy_writer_discovery.serialize_data().unwrap();
+
+    let discovered_writer_data_submessage = DataSubmessage::new(
+        false,
+        true,
+        false,
+        false,
+        ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR,
+        ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER,
+        1,
+        ParameterList::empty(),
+        Data::new(serialized_dummy_writer_discovery_bytes.into()),
+    );
+    let rtps_message_header = RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, guid_prefix);
+    let discovered_writer_rtps_message = RtpsMessageWrite::new(
+        &rtps_message_header,
+        &[Box::new(discovered_writer_data_submessage)],
+    );
+
+    waitset_builtin_reader
+        .wait(dust_dds::infrastructure::time::Duration::new(10, 0))
+        .unwrap();
+
+    let dcps_participant_reader = builtin_subscriber
+        .lookup_datareader::<SpdpDiscoveredParticipantData>(DCPS_PARTICIPANT)
+        .unwrap()
+        .unwrap();
+    let dcps_sample_list = dcps_participant_reader
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    let metatraffic_port = dcps_sample_list[0]
+        .data()
+        .unwrap()
+        .participant_proxy()
+        .metatraffic_unicast_locator_list()[0]
+        .port();
+    let user_defined_traffic_port = dcps_sample_list[0]
+        .data()
+        .unwrap()
+        .participant_proxy()
+        .default_unicast_locator_list()[0]
+        .port();
+    mock_writer_socket
+        .send_to(
+            discovered_writer_rtps_message.buffer(),
+            ("127.0.0.1", metatraffic_port as u16),
+        )
+        .unwrap();
+
+    let mut waitset_reader = WaitSet::new();
+    let reader_status_condition = reader.get_statuscondition();
+    reader_status_condition
+        .set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    waitset_reader
+        .attach_condition(Condition::StatusCondition(reader_status_condition))
+        .unwrap();
+    waitset_reader.wait(Duration::new(10, 0)).unwrap();
+
+    // Samples 1 and 2 are irrelevant for the reader. The heartbeat is not final so the reader
+    // always answers with an AckNack
+    let gap_submessage = GapSubmessage::new(
+        ENTITYID_UNKNOWN,
+        writer_id,
+        1,
+        SequenceNumberSet::new(3, []),
+    );
+    let heartbeat_submessage =
+        HeartbeatSubmessage::new(false, false, ENTITYID_UNKNOWN, writer_id, 1, 2, 1);
+    let gap_heartbeat_message = RtpsMessageWrite::new(
+        &rtps_message_header,
+        &[Box::new(gap_submessage), Box::new(heartbeat_submessage)],
+    );
+    mock_writer_socket
+        .send_to(
+            gap_heartbeat_message.buffer(),
+            ("127.0.0.1", user_defined_traffic_port as u16),
+        )
+        .unwrap();
+
+    let mut buffer = [0; 65535];
+    mock_writer_socket.set_nonblocking(false).unwrap();
+    mock_writer_socket
+        .set_read_timeout(Some(std::time::Duration::from_secs(10)))
+        .unwrap();
+    let acknack_submessage = loop {
+        let len = mock_writer_socket.recv(&mut buffer).unwrap();
+        let received_message = RtpsMessageRead::try_from(&buffer[..len]).unwrap();
+        if let Some(RtpsSubmessageReadKind::AckNack(acknack)) = received_message
+            .submessages()
+            .into_iter()
+            .find(|s| matches!(s, RtpsSubmessageReadKind::AckNack(_)))
+        {
+            break acknack;
+        }
+    };
+
+    assert_eq!(acknack_submessage.writer_id(), &writer_id);
+    assert_eq!(acknack_submessage.reader_sn_state().base(), 3);
+    assert_eq!(
+        acknack_submessage
+            .reader_sn_state()
+            .set()
+            .collect::<Vec<_>>(),
+        Vec::<i64>::new()
+    );
+}
+
+#[test]
+fn writer_should_mark_coherent_set_in_inline_qos() {
+    const PID_COHERENT_SET: i16 = 0x0056;
+    const PID_STATUS_INFO: i16 = 0x0071;
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let mock_reader_socket = std::net::UdpSocket::bind("0.0.0.0:0").unwrap();
+
+    let reader_socket_port = mock_reader_socket.local_addr().unwrap().port();
+    let reader_unicast_locator = Locator::new(
+        LOCATOR_KIND_UDP_V4,
+        reader_socket_port as u32,
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 127, 0, 0, 1],
+    );
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic_name = "MyTopic";
+    let type_name = "KeyedData";
+    let topic = participant
+        .create_topic::<KeyedData>(topic_name, type_name, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let presentation = PresentationQosPolicy {
+        access_scope: PresentationQosPolicyAccessScopeKind::Topic,
+        coherent_access: true,
+        ordered_access: false,
+    };
+    let publisher_qos = PublisherQos {
+        presentation: presentation.clone(),
+        ..Default::default()
+    };
+    let publisher = participant
+        .create_publisher(QosKind::Specific(publisher_qos), None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    // Add discovered dummy reader
+    let participant_handle = participant.get_instance_handle().unwrap();
+    let guid_prefix = participant_handle.as_ref()[0..12].try_into().unwrap();
+    let remote_reader_guid = Guid::new(
+        guid_prefix,
+        EntityId::new([0, 0, 0], USER_DEFINED_READER_WITH_KEY),
+    );
+    let reader_proxy = ReaderProxy::new(
+        remote_reader_guid,
+        ENTITYID_UNKNOWN,
+        vec![reader_unicast_locator],
+        vec![],
+        false,
+    );
+    let subscription_builtin_topic_data = SubscriptionBuiltinTopicData::new(
+        BuiltInTopicKey::from(<[u8; 16]>::from(remote_reader_guid)),
+        BuiltInTopicKey::from(*participant_handle.as_ref()),
+        topic_name.to_string(),
+        type_name.to_string(),
+        DataReaderQos {
+            reliability: ReliabilityQosPolicy {
+                kind: ReliabilityQosPolicyKind::Reliable,
+                max_blocking_time: DurationKind::Infinite,
+            },
+            ..Default::default()
+        },
+        SubscriberQos {
+            presentation,
+            ..Default::default()
+        },
+        TopicDataQosPolicy::default(),
+        String::new(),
+    );
+    let dummy_reader_discovery =
+        DiscoveredReaderData::new(reader_proxy, subscription_builtin_topic_data);
+    let discovered_reader_rtps_message = RtpsMessageWrite::new(
+        &RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, guid_prefix),
+        &[Box::new(DataSubmessage::new(
+            false,
+            true,
+            false,
+            false,
+            ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_DETECTOR,
+            ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER,
+            1,
+            ParameterList::empty(),
+            Data::new(dummy_reader_discovery.serialize_data().unwrap().into()),
+        ))],
+    );
+
+    let metatraffic_port = participant
+        .get_builtin_subscriber()
+        .lookup_datareader::<SpdpDiscoveredParticipantData>(DCPS_PARTICIPANT)
+        .unwrap()
+        .unwrap()
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap()[0]
+        .data()
+        .unwrap()
+        .participant_proxy()
+        .metatraffic_unicast_locator_list()[0]
+        .port();
+    mock_reader_socket
+        .send_to(
+            discovered_reader_rtps_message.buffer(),
+            ("127.0.0.1", metatraffic_port as u16),
+        )
+        .unwrap();
+
+    let mut waitset_writer = WaitSet::new();
+    let writer_status_condition = writer.get_statuscondition();
+    writer_status_condition
+        .set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    waitset_writer
+        .attach_condition(Condition::StatusCondition(writer_status_condition))
+        .unwrap();
+    waitset_writer.wait(Duration::new(10, 0)).unwrap();
+
+    publisher.begin_coherent_changes().unwrap();
+    writer.write(&KeyedData { id: 1, value: 1 }, None).unwrap();
+    writer.write(&KeyedData { id: 2, value: 2 }, None).unwrap();
+    publisher.end_coherent_changes().unwrap();
+
+    let mut received_data = Vec::new();
+    mock_reader_socket
+        .set_read_timeout(Some(std::time::Duration::from_secs(10)))
+        .unwrap();
+    while received_data.len() < 2 {
+        let mut buffer = [0; 65535];
+        mock_reader_socket.recv(&mut buffer).unwrap();
+        let received_message = RtpsMessageRead::try_from(buffer.as_slice()).unwrap();
+        for submessage in received_message.submessages() {
+            if let RtpsSubmessageReadKind::Data(d) = submessage {
+                if !received_data
+                    .iter()
+                    .any(|r: &DataSubmessage| r.writer_sn() == d.writer_sn())
+                {
+                    received_data.push(d);
+                }
+            }
+        }
+    }
+    received_data.sort_by_key(|d| d.writer_sn());
+
+    // Both samples belong to the set starting at the first sequence number
+    let first_sn = received_data[0].writer_sn();
+    for data in &received_data {
+        let coherent_set = data
+            .inline_qos()
+            .parameter()
+            .iter()
+            .find(|p| p.parameter_id() == PID_COHERENT_SET)
+            .expect("Coherent set parameter missing");
+        assert_eq!(coherent_set.value()[4..8], (first_sn as u32).to_le_bytes());
+    }
+    // Only the last sample of the set marks its end
+    let status_info = |data: &DataSubmessage| {
+        data.inline_qos()
+            .parameter()
+            .iter()
+            .find(|p| p.parameter_id() == PID_STATUS_INFO)
+            .map(|p| p.value().to_vec())
+    };
+    assert_eq!(status_info(&received_data[0]), None);
+    assert_eq!(
+        status_info(&received_data[1]),
+        Some(vec![0, 0, 0, 0b0000_1000])
+    );
+}
+
+#[test]
+fn writer_should_not_pack_messages_above_data_max_size_serialized() {
+    // Default fragment size of the configuration
+    const DATA_MAX_SIZE_SERIALIZED: usize = 1344;
+    const NUMBER_OF_SAMPLES: usize = 100;
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let mock_reader_socket = std::net::UdpSocket::bind("0.0.0.0:0").unwrap();
+
+    let reader_socket_port = mock_reader_socket.local_addr().unwrap().port();
+    let reader_unicast_locator = Locator::new(
+        LOCATOR_KIND_UDP_V4,
+        reader_socket_port as u32,
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 127, 0, 0, 1],
+    );
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic_name = "MyTopic";
+    let type_name = "KeyedData";
+    let topic = participant
+        .create_topic::<KeyedData>(topic_name, type_name, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::TransientLocal,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    // The samples are written before the reader is discovered so that they are all sent,
+    // and packed together, as soon as it is matched
+    for id in 0..NUMBER_OF_SAMPLES {
+        writer
+            .write(
+                &KeyedData {
+                    id: id as u8,
+                    value: 0,
+                },
+                None,
+            )
+            .unwrap();
+    }
+
+    // Add discovered dummy reader
+    let participant_handle = participant.get_instance_handle().unwrap();
+    let guid_prefix = participant_handle.as_ref()[0..12].try_into().unwrap();
+    let remote_reader_guid = Guid::new(
+        guid_prefix,
+        EntityId::new([0, 0, 0], USER_DEFINED_READER_WITH_KEY),
+    );
+    let reader_proxy = ReaderProxy::new(
+        remote_reader_guid,
+        ENTITYID_UNKNOWN,
+        vec![reader_unicast_locator],
+        vec![],
+        false,
+    );
+    let subscription_builtin_topic_data = SubscriptionBuiltinTopicData::new(
+        BuiltInTopicKey::from(<[u8; 16]>::from(remote_reader_guid)),
+        BuiltInTopicKey::from(*participant_handle.as_ref()),
+        topic_name.to_string(),
+        type_name.to_string(),
+        DataReaderQos {
+            reliability: ReliabilityQosPolicy {
+                kind: ReliabilityQosPolicyKind::Reliable,
+                max_blocking_time: DurationKind::Infinite,
+            },
+            durability: DurabilityQosPolicy {
+                kind: DurabilityQosPolicyKind::TransientLocal,
+            },
+            ..Default::default()
+        },
+        SubscriberQos::default(),
+        TopicDataQosPolicy::default(),
+        String::new(),
+    );
+    let dummy_reader_discovery =
+        DiscoveredReaderData::new(reader_proxy, subscription_builtin_topic_data);
+    let discovered_reader_rtps_message = RtpsMessageWrite::new(
+        &RtpsMessageHeader::new(PROTOCOLVERSION, VENDOR_ID_S2E, guid_prefix),
+        &[Box::new(DataSubmessage::new(
+            false,
+            true,
+            false,
+            false,
+            ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_DETECTOR,
+            ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER,
+            1,
+            ParameterList::empty(),
+            Data::new(dummy_reader_discovery.serialize_data().unwrap().into()),
+        ))],
+    );
+
+    let metatraffic_port = participant
+        .get_builtin_subscriber()
+        .lookup_datareader::<SpdpDiscoveredParticipantData>(DCPS_PARTICIPANT)
+        .unwrap()
+        .unwrap()
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap()[0]
+        .data()
+        .unwrap()
+        .participant_proxy()
+        .metatraffic_unicast_locator_list()[0]
+        .port();
+    mock_reader_socket
+        .send_to(
+            discovered_reader_rtps_message.buffer(),
+            ("127.0.0.1", metatraffic_port as u16),
+        )
+        .unwrap();
+
+    let mut received_sequence_numbers = Vec::new();
+    let mut max_data_per_message = 0;
+    mock_reader_socket
+        .set_read_timeout(Some(std::time::Duration::from_secs(10)))
+        .unwrap();
+    while received_sequence_numbers.len() < NUMBER_OF_SAMPLES {
+        let mut buffer = [0; 65535];
+        let bytes = mock_reader_socket.recv(&mut buffer).unwrap();
+        assert!(
+            bytes <= DATA_MAX_SIZE_SERIALIZED,
+            "Received message of {} bytes",
+            bytes
+        );
+        let received_message = RtpsMessageRead::try_from(&buffer[0..bytes]).unwrap();
+        let mut data_in_message = 0;
+        for submessage in received_message.submessages() {
+            if let RtpsSubmessageReadKind::Data(d) = submessage {
+                data_in_message += 1;
+                if !received_sequence_numbers.contains(&d.writer_sn()) {
+                    received_sequence_numbers.push(d.writer_sn());
+                }
+            }
+        }
+        max_data_per_message = max_data_per_message.max(data_in_message);
+    }
+
+    // The samples were packed together instead of each being sent on its own
+    assert!(max_data_per_message > 1);
 }
@@ -1,9 +1,23 @@
 use dust_dds::{
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
-        qos::{DataReaderQos, QosKind},
-        qos_policy::UserDataQosPolicy,
-        status::NO_STATUS,
+        error::DdsError,
+        qos::{DataReaderQos, DataWriterQos, PublisherQos, QosKind, SubscriberQos, TopicQos},
+        qos_policy::{
+            DeadlineQosPolicy, DestinationOrderQosPolicy, DestinationOrderQosPolicyKind,
+            DurabilityQosPolicy, DurabilityQosPolicyKind, HistoryQosPolicy, HistoryQosPolicyKind,
+            LatencyBudgetQosPolicy, Length, LivelinessQosPolicy, LivelinessQosPolicyKind,
+            OwnershipQosPolicy, OwnershipQosPolicyKind, PresentationQosPolicy,
+            PresentationQosPolicyAccessScopeKind, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
+            ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy, UserDataQosPolicy,
+        },
+        status::{StatusKind, NO_STATUS},
+        time::{Duration, DurationKind, Time},
+        wait_set::{Condition, WaitSet},
+    },
+    subscription::{
+        sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+        subscriber::Subscriber,
     },
     topic_definition::type_support::DdsType,
 };
@@ -164,3 +178,316 @@ fn data_reader_get_topicdescription_should_return_same_topic_as_used_for_creatio
             == topic.get_instance_handle().unwrap()
     );
 }
+
+#[test]
+fn end_access_without_begin_access_should_fail() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert!(matches!(
+        subscriber.end_access(),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+
+    subscriber.begin_access().unwrap();
+    subscriber.begin_access().unwrap();
+    subscriber.end_access().unwrap();
+    subscriber.end_access().unwrap();
+    assert!(matches!(
+        subscriber.end_access(),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+}
+
+#[test]
+fn ordered_group_access_returns_readers_in_sample_order() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic_a = participant
+        .create_topic::<UserType>("TopicA", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic_b = participant
+        .create_topic::<UserType>("TopicB", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let presentation = PresentationQosPolicy {
+        access_scope: PresentationQosPolicyAccessScopeKind::Group,
+        coherent_access: false,
+        ordered_access: true,
+    };
+    let publisher_qos = PublisherQos {
+        presentation: presentation.clone(),
+        ..Default::default()
+    };
+    let publisher = participant
+        .create_publisher(QosKind::Specific(publisher_qos), None, NO_STATUS)
+        .unwrap();
+    let subscriber_qos = SubscriberQos {
+        presentation,
+        ..Default::default()
+    };
+    let subscriber = participant
+        .create_subscriber(QosKind::Specific(subscriber_qos), None, NO_STATUS)
+        .unwrap();
+
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let writer_a = publisher
+        .create_datawriter(
+            &topic_a,
+            QosKind::Specific(writer_qos.clone()),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer_b = publisher
+        .create_datawriter(&topic_b, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+    let _reader_a = subscriber
+        .create_datareader::<UserType>(
+            &topic_a,
+            QosKind::Specific(reader_qos.clone()),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let _reader_b = subscriber
+        .create_datareader::<UserType>(&topic_b, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    for cond in [
+        writer_a.get_statuscondition(),
+        writer_b.get_statuscondition(),
+    ] {
+        cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+            .unwrap();
+        let mut wait_set = WaitSet::new();
+        wait_set
+            .attach_condition(Condition::StatusCondition(cond))
+            .unwrap();
+        wait_set.wait(Duration::new(5, 0)).unwrap();
+    }
+
+    // The source timestamps decrease so that they can not be used to order the samples
+    writer_a
+        .write_w_timestamp(&UserType(1), None, Time::new(4, 0))
+        .unwrap();
+    writer_b
+        .write_w_timestamp(&UserType(2), None, Time::new(3, 0))
+        .unwrap();
+    writer_a
+        .write_w_timestamp(&UserType(3), None, Time::new(2, 0))
+        .unwrap();
+    writer_b
+        .write_w_timestamp(&UserType(4), None, Time::new(1, 0))
+        .unwrap();
+    writer_a
+        .wait_for_acknowledgments(Duration::new(5, 0))
+        .unwrap();
+    writer_b
+        .wait_for_acknowledgments(Duration::new(5, 0))
+        .unwrap();
+
+    assert!(matches!(
+        subscriber.get_datareaders(ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+
+    subscriber.begin_access().unwrap();
+    let reader_topic_names: Vec<String> = subscriber
+        .get_datareaders(ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap()
+        .iter()
+        .map(|r| r.get_topicdescription().get_name())
+        .collect();
+    subscriber.end_access().unwrap();
+
+    assert_eq!(
+        reader_topic_names,
+        vec!["TopicA", "TopicB", "TopicA", "TopicB"]
+    );
+}
+
+#[test]
+fn samples_received_during_access_are_hidden_until_end_access() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<UserType>("MyTopic", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let presentation = PresentationQosPolicy {
+        access_scope: PresentationQosPolicyAccessScopeKind::Group,
+        coherent_access: true,
+        ordered_access: false,
+    };
+    let publisher_qos = PublisherQos {
+        presentation: presentation.clone(),
+        ..Default::default()
+    };
+    let publisher = participant
+        .create_publisher(QosKind::Specific(publisher_qos), None, NO_STATUS)
+        .unwrap();
+    let subscriber_qos = SubscriberQos {
+        presentation,
+        ..Default::default()
+    };
+    let subscriber = participant
+        .create_subscriber(QosKind::Specific(subscriber_qos), None, NO_STATUS)
+        .unwrap();
+
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    writer.write(&UserType(1), None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(5, 0))
+        .unwrap();
+
+    subscriber.begin_access().unwrap();
+    writer.write(&UserType(2), None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(5, 0))
+        .unwrap();
+    let samples_during_access = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    subscriber.end_access().unwrap();
+    let samples_after_access = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples_during_access.len(), 1);
+    assert_eq!(samples_during_access[0].data().unwrap().0, 1);
+    assert_eq!(samples_after_access.len(), 2);
+    assert_eq!(samples_after_access[1].data().unwrap().0, 2);
+}
+
+#[test]
+fn copy_from_topic_qos_keeps_time_based_filter() {
+    let topic_qos = TopicQos {
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::TransientLocal,
+        },
+        deadline: DeadlineQosPolicy {
+            period: DurationKind::Finite(Duration::new(5, 0)),
+        },
+        latency_budget: LatencyBudgetQosPolicy {
+            duration: DurationKind::Finite(Duration::new(2, 0)),
+        },
+        liveliness: LivelinessQosPolicy {
+            kind: LivelinessQosPolicyKind::ManualByTopic,
+            lease_duration: DurationKind::Finite(Duration::new(3, 0)),
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(4, 0)),
+        },
+        destination_order: DestinationOrderQosPolicy {
+            kind: DestinationOrderQosPolicyKind::BySourceTimestamp,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepLast(5),
+        },
+        resource_limits: ResourceLimitsQosPolicy {
+            max_samples: Length::Limited(20),
+            max_instances: Length::Limited(4),
+            max_samples_per_instance: Length::Limited(5),
+        },
+        ownership: OwnershipQosPolicy {
+            kind: OwnershipQosPolicyKind::Exclusive,
+        },
+        ..Default::default()
+    };
+    let time_based_filter = TimeBasedFilterQosPolicy {
+        minimum_separation: DurationKind::Finite(Duration::new(1, 0)),
+    };
+    let user_data = UserDataQosPolicy {
+        value: vec![1, 2, 3],
+    };
+    let mut reader_qos = DataReaderQos {
+        time_based_filter: time_based_filter.clone(),
+        user_data: user_data.clone(),
+        ..Default::default()
+    };
+
+    Subscriber::copy_from_topic_qos(&mut reader_qos, &topic_qos).unwrap();
+
+    assert_eq!(reader_qos.durability, topic_qos.durability);
+    assert_eq!(reader_qos.deadline, topic_qos.deadline);
+    assert_eq!(reader_qos.latency_budget, topic_qos.latency_budget);
+    assert_eq!(reader_qos.liveliness, topic_qos.liveliness);
+    assert_eq!(reader_qos.reliability, topic_qos.reliability);
+    assert_eq!(reader_qos.destination_order, topic_qos.destination_order);
+    assert_eq!(reader_qos.history, topic_qos.history);
+    assert_eq!(reader_qos.resource_limits, topic_qos.resource_limits);
+    assert_eq!(reader_qos.ownership, topic_qos.ownership);
+    assert_eq!(reader_qos.time_based_filter, time_based_filter);
+    assert_eq!(reader_qos.user_data, user_data);
+}
@@ -113,3 +113,89 @@ async fn dust_dds_should_run_inside_tokio_runtime() {
     assert_eq!(samples.len(), 1);
     assert_eq!(samples[0].data().unwrap(), data);
 }
+
+#[tokio::test(flavor = "current_thread")]
+async fn waiting_for_data_does_not_block_other_tokio_tasks() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant_factory = DomainParticipantFactoryAsync::new();
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+    let topic = participant
+        .create_topic::<UserData>("MyTopic", "UserData", QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .await
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .await
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<UserData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .await
+        .unwrap();
+
+    // The reader task waits for data on the same thread on which the writer task later writes
+    let reader_task = tokio::spawn(async move {
+        let cond = reader.get_statuscondition();
+        cond.set_enabled_statuses(&[StatusKind::DataAvailable])
+            .await
+            .unwrap();
+        let mut reader_wait_set = WaitSetAsync::new();
+        reader_wait_set
+            .attach_condition(ConditionAsync::StatusCondition(cond))
+            .await
+            .unwrap();
+        reader_wait_set.wait(Duration::new(10, 0)).await.unwrap();
+        reader
+            .take(3, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+            .await
+            .unwrap()
+    });
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .await
+        .unwrap();
+    let mut wait_set = WaitSetAsync::new();
+    wait_set
+        .attach_condition(ConditionAsync::StatusCondition(cond))
+        .await
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).await.unwrap();
+
+    let data = UserData {
+        id: 1,
+        value: vec![1; 10],
+    };
+    writer.write(&data, None).await.unwrap();
+
+    let samples = reader_task.await.unwrap();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data().unwrap(), data);
+}
@@ -0,0 +1,48 @@
+use dust_dds::{
+    configuration::DustDdsConfigurationBuilder,
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{qos::QosKind, status::NO_STATUS},
+    rtps::{
+        discovery_types::ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER,
+        messages::overall_structure::{RtpsMessageRead, RtpsSubmessageReadKind},
+    },
+};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[test]
+fn participant_is_announced_to_initial_peers() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let mock_peer_socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    mock_peer_socket
+        .set_read_timeout(Some(std::time::Duration::from_secs(10)))
+        .unwrap();
+
+    let participant_factory = DomainParticipantFactory::get_instance();
+    participant_factory
+        .set_configuration(
+            DustDdsConfigurationBuilder::new()
+                .initial_peers(vec![mock_peer_socket.local_addr().unwrap()])
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let mut buffer = [0; 65535];
+    let bytes = mock_peer_socket.recv(&mut buffer).unwrap();
+    let message = RtpsMessageRead::try_from(&buffer[0..bytes]).unwrap();
+
+    let participant_handle = participant.get_instance_handle().unwrap();
+    assert_eq!(
+        message.header().guid_prefix(),
+        participant_handle.as_ref()[0..12]
+    );
+    assert!(message.submessages().iter().any(|s| matches!(
+        s,
+        RtpsSubmessageReadKind::Data(d) if d.writer_id() == ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER
+    )));
+}
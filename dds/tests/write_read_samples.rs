@@ -3,23 +3,34 @@ use dust_dds::{
     infrastructure::{
         error::DdsError,
         instance::InstanceHandle,
-        qos::{DataReaderQos, DataWriterQos, QosKind, TopicQos},
+        qos::{DataReaderQos, DataWriterQos, DomainParticipantQos, QosKind, TopicQos},
         qos_policy::{
-            DestinationOrderQosPolicy, DestinationOrderQosPolicyKind, DurabilityQosPolicy,
-            DurabilityQosPolicyKind, HistoryQosPolicy, HistoryQosPolicyKind, Length,
-            LifespanQosPolicy, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
-            ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy, WriterDataLifecycleQosPolicy,
+            DeadlineQosPolicy, DestinationOrderQosPolicy, DestinationOrderQosPolicyKind,
+            DurabilityQosPolicy, DurabilityQosPolicyKind, FlowControllerQosPolicy,
+            HistoryQosPolicy, HistoryQosPolicyKind, Length, LifespanQosPolicy, LivelinessQosPolicy,
+            LivelinessQosPolicyKind, OwnershipQosPolicy, OwnershipQosPolicyKind,
+            OwnershipStrengthQosPolicy, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
+            ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy, TimeSourceQosPolicy,
+            WriterDataLifecycleQosPolicy,
         },
-        status::{StatusKind, NO_STATUS},
+        status::{RequestedDeadlineMissedStatus, SampleRejectedStatusKind, StatusKind, NO_STATUS},
         time::{Duration, DurationKind, Time},
         wait_set::{Condition, WaitSet},
     },
-    subscription::sample_info::{
-        InstanceStateKind, SampleStateKind, ViewStateKind, ANY_INSTANCE_STATE, ANY_SAMPLE_STATE,
-        ANY_VIEW_STATE,
+    subscription::{
+        data_reader::{DataReader, Sample},
+        data_reader_listener::DataReaderListener,
+        sample_info::{
+            InstanceStateKind, SampleStateKind, ViewStateKind, ANY_INSTANCE_STATE,
+            ANY_SAMPLE_STATE, ANY_VIEW_STATE,
+        },
     },
     topic_definition::type_support::DdsType,
 };
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
 
 mod utils;
 use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
@@ -485,6 +496,82 @@ fn samples_are_taken() {
     assert_eq!(samples2[1].data().unwrap(), data5);
 }
 
+#[test]
+fn write_batch_samples_are_received_together() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data: Vec<_> = (1..=5)
+        .map(|id| KeyedData {
+            id,
+            value: id as u32 * 10,
+        })
+        .collect();
+    writer.write_batch(&data, Some(Time::new(100, 0))).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 5);
+    for (sample, expected_data) in samples.iter().zip(data) {
+        assert_eq!(sample.data().unwrap(), expected_data);
+        assert_eq!(
+            sample.sample_info().source_timestamp,
+            Some(Time::new(100, 0))
+        );
+    }
+}
+
 #[test]
 fn wait_for_samples_to_be_taken_best_effort() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -806,6 +893,87 @@ fn take_next_sample() {
     assert_eq!(reader.take_next_sample(), Err(DdsError::NoData));
 }
 
+#[test]
+fn read_and_take_next_sample_interleaved() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 2, value: 10 };
+
+    writer.write(&data1, None).unwrap();
+    writer.write(&data2, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    assert_eq!(reader.read_next_sample().unwrap().data().unwrap(), data1);
+    assert_eq!(reader.take_next_sample().unwrap().data().unwrap(), data2);
+    assert_eq!(reader.read_next_sample(), Err(DdsError::NoData));
+
+    let data3 = KeyedData { id: 3, value: 20 };
+    writer.write(&data3, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    assert_eq!(reader.take_next_sample().unwrap().data().unwrap(), data3);
+    assert_eq!(reader.take_next_sample(), Err(DdsError::NoData));
+
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data().unwrap(), data1);
+    assert_eq!(samples[0].sample_info().sample_state, SampleStateKind::Read);
+}
+
 #[test]
 fn each_key_sample_is_read() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -975,7 +1143,7 @@ fn read_specific_instance() {
 }
 
 #[test]
-fn read_next_instance() {
+fn reader_lookup_instance() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
 
     let participant = DomainParticipantFactory::get_instance()
@@ -1022,66 +1190,41 @@ fn read_next_instance() {
     wait_set
         .attach_condition(Condition::StatusCondition(cond))
         .unwrap();
-    wait_set.wait(Duration::new(5, 0)).unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
 
     let data1 = KeyedData { id: 1, value: 1 };
     let data2 = KeyedData { id: 2, value: 10 };
-    let data3 = KeyedData { id: 3, value: 20 };
+    let unknown_data = KeyedData { id: 3, value: 20 };
 
     writer.write(&data1, None).unwrap();
     writer.write(&data2, None).unwrap();
-    writer.write(&data3, None).unwrap();
 
     writer
         .wait_for_acknowledgments(Duration::new(10, 0))
         .unwrap();
 
-    let samples1 = reader
-        .read_next_instance(
-            3,
-            None,
-            ANY_SAMPLE_STATE,
-            ANY_VIEW_STATE,
-            ANY_INSTANCE_STATE,
-        )
-        .unwrap();
-
-    let samples2 = reader
-        .read_next_instance(
-            3,
-            Some(samples1[0].sample_info().instance_handle),
-            ANY_SAMPLE_STATE,
-            ANY_VIEW_STATE,
-            ANY_INSTANCE_STATE,
-        )
-        .unwrap();
-
-    let samples3 = reader
-        .read_next_instance(
-            3,
-            Some(samples2[0].sample_info().instance_handle),
-            ANY_SAMPLE_STATE,
-            ANY_VIEW_STATE,
-            ANY_INSTANCE_STATE,
-        )
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
         .unwrap();
 
-    let samples4 = reader.read_next_instance(
-        3,
-        Some(samples3[0].sample_info().instance_handle),
-        ANY_SAMPLE_STATE,
-        ANY_VIEW_STATE,
-        ANY_INSTANCE_STATE,
+    assert_eq!(samples.len(), 2);
+    assert_eq!(
+        reader.lookup_instance(&data1).unwrap(),
+        Some(samples[0].sample_info().instance_handle)
     );
-
-    assert_eq!(samples1[0].data().unwrap(), data1);
-    assert_eq!(samples2[0].data().unwrap(), data2);
-    assert_eq!(samples3[0].data().unwrap(), data3);
-    assert_eq!(samples4, Err(DdsError::NoData));
+    assert_eq!(
+        reader.lookup_instance(&data2).unwrap(),
+        Some(samples[1].sample_info().instance_handle)
+    );
+    assert_eq!(
+        reader.lookup_instance(&data1).unwrap(),
+        writer.lookup_instance(&data1).unwrap()
+    );
+    assert_eq!(reader.lookup_instance(&unknown_data).unwrap(), None);
 }
 
 #[test]
-fn take_next_instance() {
+fn read_next_instance() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
 
     let participant = DomainParticipantFactory::get_instance()
@@ -1128,7 +1271,7 @@ fn take_next_instance() {
     wait_set
         .attach_condition(Condition::StatusCondition(cond))
         .unwrap();
-    wait_set.wait(Duration::new(10, 0)).unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
 
     let data1 = KeyedData { id: 1, value: 1 };
     let data2 = KeyedData { id: 2, value: 10 };
@@ -1143,7 +1286,7 @@ fn take_next_instance() {
         .unwrap();
 
     let samples1 = reader
-        .take_next_instance(
+        .read_next_instance(
             3,
             None,
             ANY_SAMPLE_STATE,
@@ -1153,7 +1296,7 @@ fn take_next_instance() {
         .unwrap();
 
     let samples2 = reader
-        .take_next_instance(
+        .read_next_instance(
             3,
             Some(samples1[0].sample_info().instance_handle),
             ANY_SAMPLE_STATE,
@@ -1163,7 +1306,7 @@ fn take_next_instance() {
         .unwrap();
 
     let samples3 = reader
-        .take_next_instance(
+        .read_next_instance(
             3,
             Some(samples2[0].sample_info().instance_handle),
             ANY_SAMPLE_STATE,
@@ -1172,7 +1315,7 @@ fn take_next_instance() {
         )
         .unwrap();
 
-    let samples4 = reader.take_next_instance(
+    let samples4 = reader.read_next_instance(
         3,
         Some(samples3[0].sample_info().instance_handle),
         ANY_SAMPLE_STATE,
@@ -1187,7 +1330,7 @@ fn take_next_instance() {
 }
 
 #[test]
-fn take_specific_instance() {
+fn take_next_instance() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
 
     let participant = DomainParticipantFactory::get_instance()
@@ -1243,28 +1386,57 @@ fn take_specific_instance() {
     writer.write(&data1, None).unwrap();
     writer.write(&data2, None).unwrap();
     writer.write(&data3, None).unwrap();
-    let data1_handle = writer.lookup_instance(&data1).unwrap();
 
     writer
         .wait_for_acknowledgments(Duration::new(10, 0))
         .unwrap();
 
-    let samples = reader
-        .take_instance(
+    let samples1 = reader
+        .take_next_instance(
             3,
-            data1_handle.unwrap(),
+            None,
             ANY_SAMPLE_STATE,
             ANY_VIEW_STATE,
             ANY_INSTANCE_STATE,
         )
         .unwrap();
 
-    assert_eq!(samples.len(), 1);
-    assert_eq!(samples[0].data().unwrap(), data1);
+    let samples2 = reader
+        .take_next_instance(
+            3,
+            Some(samples1[0].sample_info().instance_handle),
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        )
+        .unwrap();
+
+    let samples3 = reader
+        .take_next_instance(
+            3,
+            Some(samples2[0].sample_info().instance_handle),
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        )
+        .unwrap();
+
+    let samples4 = reader.take_next_instance(
+        3,
+        Some(samples3[0].sample_info().instance_handle),
+        ANY_SAMPLE_STATE,
+        ANY_VIEW_STATE,
+        ANY_INSTANCE_STATE,
+    );
+
+    assert_eq!(samples1[0].data().unwrap(), data1);
+    assert_eq!(samples2[0].data().unwrap(), data2);
+    assert_eq!(samples3[0].data().unwrap(), data3);
+    assert_eq!(samples4, Err(DdsError::NoData));
 }
 
 #[test]
-fn take_specific_unknown_instance() {
+fn next_instance_skips_instances_without_matching_samples() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
 
     let participant = DomainParticipantFactory::get_instance()
@@ -1325,24 +1497,69 @@ fn take_specific_unknown_instance() {
         .wait_for_acknowledgments(Duration::new(10, 0))
         .unwrap();
 
-    assert_eq!(
-        reader.take_instance(
+    let samples1 = reader
+        .take_next_instance(
             3,
-            InstanceHandle::new([99; 16]),
+            None,
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        )
+        .unwrap();
+    assert_eq!(samples1[0].data().unwrap(), data1);
+
+    // The first instance has no samples left so the iteration starts at the second one
+    let samples2 = reader
+        .read_next_instance(
+            3,
+            None,
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        )
+        .unwrap();
+    assert_eq!(samples2[0].data().unwrap(), data2);
+
+    // The second instance has already been read so only the third one matches
+    let samples3 = reader
+        .read_next_instance(
+            3,
+            None,
+            &[SampleStateKind::NotRead],
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        )
+        .unwrap();
+    assert_eq!(samples3[0].data().unwrap(), data3);
+
+    let samples4 = reader
+        .take_next_instance(
+            3,
+            Some(samples2[0].sample_info().instance_handle),
+            &[SampleStateKind::Read],
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        )
+        .unwrap();
+    assert_eq!(samples4[0].data().unwrap(), data3);
+
+    assert_eq!(
+        reader.read_next_instance(
+            3,
+            Some(samples2[0].sample_info().instance_handle),
             ANY_SAMPLE_STATE,
             ANY_VIEW_STATE,
             ANY_INSTANCE_STATE,
         ),
-        Err(DdsError::BadParameter)
+        Err(DdsError::NoData)
     );
 }
 
 #[test]
-fn write_read_disposed_samples() {
+fn take_specific_instance() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
-    let participant_factory = DomainParticipantFactory::get_instance();
 
-    let participant = participant_factory
+    let participant = DomainParticipantFactory::get_instance()
         .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
         .unwrap();
 
@@ -1358,9 +1575,6 @@ fn write_read_disposed_samples() {
             kind: ReliabilityQosPolicyKind::Reliable,
             max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
         },
-        history: HistoryQosPolicy {
-            kind: HistoryQosPolicyKind::KeepAll,
-        },
         ..Default::default()
     };
     let writer = publisher
@@ -1375,12 +1589,8 @@ fn write_read_disposed_samples() {
             kind: ReliabilityQosPolicyKind::Reliable,
             max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
         },
-        history: HistoryQosPolicy {
-            kind: HistoryQosPolicyKind::KeepAll,
-        },
         ..Default::default()
     };
-
     let reader = subscriber
         .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
         .unwrap();
@@ -1396,76 +1606,70 @@ fn write_read_disposed_samples() {
     wait_set.wait(Duration::new(10, 0)).unwrap();
 
     let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 2, value: 10 };
+    let data3 = KeyedData { id: 3, value: 20 };
 
     writer.write(&data1, None).unwrap();
-    writer.dispose(&data1, None).unwrap();
+    writer.write(&data2, None).unwrap();
+    writer.write(&data3, None).unwrap();
+    let data1_handle = writer.lookup_instance(&data1).unwrap();
 
     writer
         .wait_for_acknowledgments(Duration::new(10, 0))
         .unwrap();
 
     let samples = reader
-        .read(2, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .take_instance(
+            3,
+            data1_handle.unwrap(),
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        )
         .unwrap();
 
-    assert_eq!(samples.len(), 2);
-    assert_eq!(
-        samples[0].sample_info().instance_state,
-        InstanceStateKind::NotAliveDisposed
-    );
-    assert_eq!(
-        samples[1].sample_info().instance_state,
-        InstanceStateKind::NotAliveDisposed
-    );
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data().unwrap(), data1);
 }
 
 #[test]
-#[ignore = "Dispose message not always sent after timing changes"]
-fn write_read_disposed_samples_when_writer_is_immediately_deleted() {
+fn take_specific_unknown_instance() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
-    let participant_factory = DomainParticipantFactory::get_instance();
-
-    let participant1 = participant_factory
-        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
-        .unwrap();
 
-    let participant2 = participant_factory
+    let participant = DomainParticipantFactory::get_instance()
         .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
         .unwrap();
 
-    let topic1 = participant1
+    let topic = participant
         .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
         .unwrap();
 
-    let publisher = participant1
+    let publisher = participant
         .create_publisher(QosKind::Default, None, NO_STATUS)
         .unwrap();
     let writer_qos = DataWriterQos {
-        history: HistoryQosPolicy {
-            kind: HistoryQosPolicyKind::KeepAll,
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
         },
         ..Default::default()
     };
     let writer = publisher
-        .create_datawriter(&topic1, QosKind::Specific(writer_qos), None, NO_STATUS)
-        .unwrap();
-
-    let topic2 = participant2
-        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
         .unwrap();
 
-    let subscriber = participant2
+    let subscriber = participant
         .create_subscriber(QosKind::Default, None, NO_STATUS)
         .unwrap();
     let reader_qos = DataReaderQos {
-        history: HistoryQosPolicy {
-            kind: HistoryQosPolicyKind::KeepAll,
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
         },
         ..Default::default()
     };
-
     let reader = subscriber
-        .create_datareader::<KeyedData>(&topic2, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
         .unwrap();
 
     let cond = writer.get_statuscondition();
@@ -1479,47 +1683,39 @@ fn write_read_disposed_samples_when_writer_is_immediately_deleted() {
     wait_set.wait(Duration::new(10, 0)).unwrap();
 
     let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 2, value: 10 };
+    let data3 = KeyedData { id: 3, value: 20 };
 
     writer.write(&data1, None).unwrap();
-    writer.dispose(&data1, None).unwrap();
-    publisher.delete_datawriter(&writer).unwrap();
-
-    let start_loop_time = std::time::Instant::now();
-    let wait_for_disposed_timeout = std::time::Duration::from_secs(10);
-    let samples = loop {
-        if let Ok(samples) = reader.read(2, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE) {
-            if samples.len() == 2 {
-                break samples;
-            }
-        }
+    writer.write(&data2, None).unwrap();
+    writer.write(&data3, None).unwrap();
 
-        if start_loop_time.elapsed() > wait_for_disposed_timeout {
-            panic!("Disposed sample not received within expected time.")
-        }
-    };
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
 
-    assert_eq!(samples.len(), 2);
-    assert_eq!(
-        samples[0].sample_info().instance_state,
-        InstanceStateKind::NotAliveDisposed
-    );
     assert_eq!(
-        samples[1].sample_info().instance_state,
-        InstanceStateKind::NotAliveDisposed
+        reader.take_instance(
+            3,
+            InstanceHandle::new([99; 16]),
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        ),
+        Err(DdsError::BadParameter)
     );
 }
 
 #[test]
-fn write_read_sample_view_state() {
+fn read_and_take_instance_without_matching_samples() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
-    let participant_factory = DomainParticipantFactory::get_instance();
 
-    let participant = participant_factory
+    let participant = DomainParticipantFactory::get_instance()
         .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
         .unwrap();
 
     let topic = participant
-        .create_topic::<KeyedData>("OtherTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
         .unwrap();
 
     let publisher = participant
@@ -1558,41 +1754,72 @@ fn write_read_sample_view_state() {
     wait_set
         .attach_condition(Condition::StatusCondition(cond))
         .unwrap();
-    wait_set.wait(Duration::new(5, 0)).unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
 
     let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 2, value: 10 };
 
     writer.write(&data1, None).unwrap();
+    writer.write(&data2, None).unwrap();
+    let data1_handle = writer.lookup_instance(&data1).unwrap().unwrap();
 
     writer
         .wait_for_acknowledgments(Duration::new(10, 0))
         .unwrap();
 
-    reader
-        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+    let samples = reader
+        .read_instance(
+            3,
+            data1_handle,
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        )
         .unwrap();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data().unwrap(), data1);
 
-    let data1_2 = KeyedData { id: 1, value: 2 };
-    let data2 = KeyedData { id: 2, value: 1 };
-
-    writer.write(&data1_2, None).unwrap();
-    writer.write(&data2, None).unwrap();
+    assert_eq!(
+        reader.read_instance(
+            3,
+            data1_handle,
+            &[SampleStateKind::NotRead],
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        ),
+        Err(DdsError::NoData)
+    );
 
-    writer
-        .wait_for_acknowledgments(Duration::new(1, 0))
+    let samples = reader
+        .take_instance(
+            3,
+            data1_handle,
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        )
         .unwrap();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(
+        reader.take_instance(
+            3,
+            data1_handle,
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        ),
+        Err(DdsError::NoData)
+    );
 
     let samples = reader
-        .read(2, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .read(3, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
         .unwrap();
-
-    assert_eq!(samples.len(), 2);
-    assert_eq!(samples[0].sample_info().view_state, ViewStateKind::NotNew);
-    assert_eq!(samples[1].sample_info().view_state, ViewStateKind::New);
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data().unwrap(), data2);
 }
 
 #[test]
-fn inconsistent_topic_status_condition() {
+fn write_read_disposed_samples() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
     let participant_factory = DomainParticipantFactory::get_instance();
 
@@ -1600,70 +1827,85 @@ fn inconsistent_topic_status_condition() {
         .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
         .unwrap();
 
-    let best_effort_topic_qos = TopicQos {
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
         reliability: ReliabilityQosPolicy {
-            kind: ReliabilityQosPolicyKind::BestEffort,
+            kind: ReliabilityQosPolicyKind::Reliable,
             max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
         },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
         ..Default::default()
     };
-    let topic_best_effort = participant
-        .create_topic::<KeyedData>(
-            "Topic",
-            "KeyedData",
-            QosKind::Specific(best_effort_topic_qos),
-            None,
-            NO_STATUS,
-        )
-        .unwrap();
-
-    let status_cond = topic_best_effort.get_statuscondition();
-    status_cond
-        .set_enabled_statuses(&[StatusKind::InconsistentTopic])
-        .unwrap();
-
-    let mut wait_set = WaitSet::new();
-    wait_set
-        .attach_condition(Condition::StatusCondition(status_cond))
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
         .unwrap();
 
-    let participant2 = participant_factory
-        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
         .unwrap();
-
-    let reliable_topic_qos = TopicQos {
+    let reader_qos = DataReaderQos {
         reliability: ReliabilityQosPolicy {
             kind: ReliabilityQosPolicyKind::Reliable,
             max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
         },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
         ..Default::default()
     };
-    let _topic_reliable = participant2
-        .create_topic::<KeyedData>(
-            "Topic",
-            "KeyedData",
-            QosKind::Specific(reliable_topic_qos),
-            None,
-            NO_STATUS,
-        )
+
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
         .unwrap();
 
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
     wait_set.wait(Duration::new(10, 0)).unwrap();
 
-    assert!(
-        topic_best_effort
-            .get_inconsistent_topic_status()
-            .unwrap()
-            .total_count
-            > 0
+    let data1 = KeyedData { id: 1, value: 1 };
+
+    writer.write(&data1, None).unwrap();
+    writer.dispose(&data1, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .read(2, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 2);
+    assert_eq!(
+        samples[0].sample_info().instance_state,
+        InstanceStateKind::NotAliveDisposed
+    );
+    assert_eq!(
+        samples[1].sample_info().instance_state,
+        InstanceStateKind::NotAliveDisposed
     );
 }
 
 #[test]
-fn reader_with_minimum_time_separation_qos() {
+fn get_key_value_of_disposed_instance() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
 
-    let participant = DomainParticipantFactory::get_instance()
+    let participant = participant_factory
         .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
         .unwrap();
 
@@ -1675,13 +1917,13 @@ fn reader_with_minimum_time_separation_qos() {
         .create_publisher(QosKind::Default, None, NO_STATUS)
         .unwrap();
     let writer_qos = DataWriterQos {
-        history: HistoryQosPolicy {
-            kind: HistoryQosPolicyKind::KeepAll,
-        },
         reliability: ReliabilityQosPolicy {
             kind: ReliabilityQosPolicyKind::Reliable,
             max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
         },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
         ..Default::default()
     };
     let writer = publisher
@@ -1692,18 +1934,16 @@ fn reader_with_minimum_time_separation_qos() {
         .create_subscriber(QosKind::Default, None, NO_STATUS)
         .unwrap();
     let reader_qos = DataReaderQos {
-        history: HistoryQosPolicy {
-            kind: HistoryQosPolicyKind::KeepAll,
-        },
         reliability: ReliabilityQosPolicy {
             kind: ReliabilityQosPolicyKind::Reliable,
             max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
         },
-        time_based_filter: TimeBasedFilterQosPolicy {
-            minimum_separation: DurationKind::Finite(Duration::new(2, 0)),
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
         },
         ..Default::default()
     };
+
     let reader = subscriber
         .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
         .unwrap();
@@ -1718,151 +1958,140 @@ fn reader_with_minimum_time_separation_qos() {
         .unwrap();
     wait_set.wait(Duration::new(10, 0)).unwrap();
 
-    let data1_1 = KeyedData { id: 1, value: 1 };
-    let data1_2 = KeyedData { id: 1, value: 2 };
-    let data1_3 = KeyedData { id: 1, value: 3 };
-    let data2_1 = KeyedData { id: 2, value: 10 };
-    let data2_2 = KeyedData { id: 2, value: 20 };
-    let data2_3 = KeyedData { id: 2, value: 30 };
+    let data1 = KeyedData { id: 1, value: 1 };
 
-    writer
-        .write_w_timestamp(&data1_1, None, Time::new(1, 0))
-        .unwrap();
-    writer
-        .write_w_timestamp(&data1_2, None, Time::new(2, 0))
-        .unwrap();
-    writer
-        .write_w_timestamp(&data1_3, None, Time::new(3, 0))
-        .unwrap();
-    writer
-        .write_w_timestamp(&data2_1, None, Time::new(4, 0))
-        .unwrap();
-    writer
-        .write_w_timestamp(&data2_2, None, Time::new(5, 0))
-        .unwrap();
-    writer
-        .write_w_timestamp(&data2_3, None, Time::new(6, 0))
-        .unwrap();
+    writer.write(&data1, None).unwrap();
+    writer.dispose(&data1, None).unwrap();
 
     writer
         .wait_for_acknowledgments(Duration::new(10, 0))
         .unwrap();
 
     let samples = reader
-        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .read(2, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
         .unwrap();
+    assert_eq!(samples.len(), 2);
+    assert!(samples[1].data().is_err());
 
-    assert_eq!(samples.len(), 4);
-    assert_eq!(samples[0].data().unwrap(), data1_1);
-    assert_eq!(samples[1].data().unwrap(), data1_3);
-    assert_eq!(samples[2].data().unwrap(), data2_1);
-    assert_eq!(samples[3].data().unwrap(), data2_3);
+    let mut key_holder = KeyedData { id: 0, value: 5 };
+    reader
+        .get_key_value(&mut key_holder, samples[1].sample_info().instance_handle)
+        .unwrap();
+
+    assert_eq!(key_holder, KeyedData { id: 1, value: 5 });
+    assert_eq!(
+        reader.get_key_value(&mut key_holder, InstanceHandle::new([9; 16])),
+        Err(DdsError::BadParameter)
+    );
 }
 
 #[test]
-fn transient_local_writer_reader_wait_for_historical_data() {
+#[ignore = "Dispose message not always sent after timing changes"]
+fn write_read_disposed_samples_when_writer_is_immediately_deleted() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
 
-    let participant = DomainParticipantFactory::get_instance()
+    let participant1 = participant_factory
         .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
         .unwrap();
 
-    let topic = participant
+    let participant2 = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic1 = participant1
         .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
         .unwrap();
 
-    let publisher = participant
+    let publisher = participant1
         .create_publisher(QosKind::Default, None, NO_STATUS)
         .unwrap();
     let writer_qos = DataWriterQos {
-        durability: DurabilityQosPolicy {
-            kind: DurabilityQosPolicyKind::TransientLocal,
-        },
         history: HistoryQosPolicy {
             kind: HistoryQosPolicyKind::KeepAll,
         },
-        reliability: ReliabilityQosPolicy {
-            kind: ReliabilityQosPolicyKind::Reliable,
-            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
-        },
         ..Default::default()
     };
     let writer = publisher
-        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .create_datawriter(&topic1, QosKind::Specific(writer_qos), None, NO_STATUS)
         .unwrap();
-    let data1 = KeyedData { id: 1, value: 1 };
-    let data2 = KeyedData { id: 2, value: 2 };
-    writer.write(&data1, None).unwrap();
-    writer.write(&data2, None).unwrap();
 
-    let subscriber = participant
+    let topic2 = participant2
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant2
         .create_subscriber(QosKind::Default, None, NO_STATUS)
         .unwrap();
     let reader_qos = DataReaderQos {
-        durability: DurabilityQosPolicy {
-            kind: DurabilityQosPolicyKind::TransientLocal,
-        },
         history: HistoryQosPolicy {
             kind: HistoryQosPolicyKind::KeepAll,
         },
-        reliability: ReliabilityQosPolicy {
-            kind: ReliabilityQosPolicyKind::Reliable,
-            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
-        },
-        time_based_filter: TimeBasedFilterQosPolicy {
-            minimum_separation: DurationKind::Finite(Duration::new(2, 0)),
-        },
         ..Default::default()
     };
 
     let reader = subscriber
-        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .create_datareader::<KeyedData>(&topic2, QosKind::Specific(reader_qos), None, NO_STATUS)
         .unwrap();
 
-    let cond = reader.get_statuscondition();
-    cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
         .unwrap();
 
     let mut wait_set = WaitSet::new();
     wait_set
         .attach_condition(Condition::StatusCondition(cond))
         .unwrap();
-    wait_set.wait(Duration::new(5, 0)).unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
 
-    reader
-        .wait_for_historical_data(Duration::new(10, 0))
-        .unwrap();
-    let samples = reader
-        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
-        .unwrap();
+    let data1 = KeyedData { id: 1, value: 1 };
+
+    writer.write(&data1, None).unwrap();
+    writer.dispose(&data1, None).unwrap();
+    publisher.delete_datawriter(&writer).unwrap();
+
+    let start_loop_time = std::time::Instant::now();
+    let wait_for_disposed_timeout = std::time::Duration::from_secs(10);
+    let samples = loop {
+        if let Ok(samples) = reader.read(2, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE) {
+            if samples.len() == 2 {
+                break samples;
+            }
+        }
+
+        if start_loop_time.elapsed() > wait_for_disposed_timeout {
+            panic!("Disposed sample not received within expected time.")
+        }
+    };
 
     assert_eq!(samples.len(), 2);
-    assert_eq!(samples[0].data().unwrap(), data1);
-    assert_eq!(samples[1].data().unwrap(), data2);
+    assert_eq!(
+        samples[0].sample_info().instance_state,
+        InstanceStateKind::NotAliveDisposed
+    );
+    assert_eq!(
+        samples[1].sample_info().instance_state,
+        InstanceStateKind::NotAliveDisposed
+    );
 }
 
 #[test]
-fn volatile_writer_reader_receives_only_new_samples() {
+fn write_read_sample_view_state() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
 
-    let participant = DomainParticipantFactory::get_instance()
+    let participant = participant_factory
         .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
         .unwrap();
 
     let topic = participant
-        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .create_topic::<KeyedData>("OtherTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
         .unwrap();
 
     let publisher = participant
         .create_publisher(QosKind::Default, None, NO_STATUS)
         .unwrap();
     let writer_qos = DataWriterQos {
-        durability: DurabilityQosPolicy {
-            kind: DurabilityQosPolicyKind::Volatile,
-        },
-        history: HistoryQosPolicy {
-            kind: HistoryQosPolicyKind::KeepAll,
-        },
         reliability: ReliabilityQosPolicy {
             kind: ReliabilityQosPolicyKind::Reliable,
             max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
@@ -1873,19 +2102,10 @@ fn volatile_writer_reader_receives_only_new_samples() {
         .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
         .unwrap();
 
-    let data1 = KeyedData { id: 1, value: 1 };
-    writer.write(&data1, None).unwrap();
-
     let subscriber = participant
         .create_subscriber(QosKind::Default, None, NO_STATUS)
         .unwrap();
     let reader_qos = DataReaderQos {
-        durability: DurabilityQosPolicy {
-            kind: DurabilityQosPolicyKind::Volatile,
-        },
-        history: HistoryQosPolicy {
-            kind: HistoryQosPolicyKind::KeepAll,
-        },
         reliability: ReliabilityQosPolicy {
             kind: ReliabilityQosPolicyKind::Reliable,
             max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
@@ -1906,23 +2126,39 @@ fn volatile_writer_reader_receives_only_new_samples() {
         .unwrap();
     wait_set.wait(Duration::new(5, 0)).unwrap();
 
-    let data2 = KeyedData { id: 2, value: 10 };
-    writer.write(&data2, None).unwrap();
+    let data1 = KeyedData { id: 1, value: 1 };
+
+    writer.write(&data1, None).unwrap();
 
     writer
         .wait_for_acknowledgments(Duration::new(10, 0))
         .unwrap();
 
+    reader
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    let data1_2 = KeyedData { id: 1, value: 2 };
+    let data2 = KeyedData { id: 2, value: 1 };
+
+    writer.write(&data1_2, None).unwrap();
+    writer.write(&data2, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(1, 0))
+        .unwrap();
+
     let samples = reader
-        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .read(2, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
         .unwrap();
 
-    assert_eq!(samples.len(), 1);
-    assert_eq!(samples[0].data().unwrap(), data2);
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0].sample_info().view_state, ViewStateKind::NotNew);
+    assert_eq!(samples[1].sample_info().view_state, ViewStateKind::New);
 }
 
 #[test]
-fn write_read_unkeyed_topic() {
+fn read_sample_view_state_of_reborn_instance() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
     let participant_factory = DomainParticipantFactory::get_instance();
 
@@ -1931,13 +2167,7 @@ fn write_read_unkeyed_topic() {
         .unwrap();
 
     let topic = participant
-        .create_topic::<UserData>(
-            "write_read_unkeyed_topic",
-            "UserData",
-            QosKind::Default,
-            None,
-            NO_STATUS,
-        )
+        .create_topic::<KeyedData>("OtherTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
         .unwrap();
 
     let publisher = participant
@@ -1965,7 +2195,7 @@ fn write_read_unkeyed_topic() {
         ..Default::default()
     };
     let reader = subscriber
-        .create_datareader::<UserData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
         .unwrap();
 
     let cond = writer.get_statuscondition();
@@ -1976,21 +2206,55 @@ fn write_read_unkeyed_topic() {
     wait_set
         .attach_condition(Condition::StatusCondition(cond))
         .unwrap();
-    wait_set.wait(Duration::new(10, 0)).unwrap();
-
-    writer.write(&UserData(8), None).unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
 
+    let data1 = KeyedData { id: 1, value: 1 };
+    writer.write(&data1, None).unwrap();
     writer
-        .wait_for_acknowledgments(Duration::new(5, 0))
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
         .unwrap();
+    assert_eq!(samples[0].sample_info().view_state, ViewStateKind::New);
 
-    let samples = reader.read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE);
+    // Disposing the instance does not make it a new view
+    writer.dispose(&data1, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples[0].sample_info().view_state, ViewStateKind::NotNew);
+    assert_eq!(
+        samples[0].sample_info().instance_state,
+        InstanceStateKind::NotAliveDisposed
+    );
 
-    assert_eq!(samples.unwrap()[0].data().unwrap(), UserData(8));
+    // Writing the disposed instance again makes it reborn
+    let data1_2 = KeyedData { id: 1, value: 2 };
+    writer.write(&data1_2, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples[0].sample_info().view_state, ViewStateKind::New);
+    assert_eq!(
+        samples[0].sample_info().instance_state,
+        InstanceStateKind::Alive
+    );
+
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples[0].sample_info().view_state, ViewStateKind::NotNew);
 }
 
 #[test]
-fn data_reader_resource_limits() {
+fn read_sample_generation_counts_of_reborn_instance() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
     let participant_factory = DomainParticipantFactory::get_instance();
 
@@ -1999,31 +2263,27 @@ fn data_reader_resource_limits() {
         .unwrap();
 
     let topic = participant
-        .create_topic::<UserData>(
-            "data_reader_resource_limits",
-            "UserData",
-            QosKind::Default,
-            None,
-            NO_STATUS,
-        )
+        .create_topic::<KeyedData>("OtherTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
         .unwrap();
 
     let publisher = participant
         .create_publisher(QosKind::Default, None, NO_STATUS)
         .unwrap();
-    let data_writer_qos = DataWriterQos {
+    let writer_qos = DataWriterQos {
         reliability: ReliabilityQosPolicy {
             kind: ReliabilityQosPolicyKind::Reliable,
             max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
         },
         history: HistoryQosPolicy {
             kind: HistoryQosPolicyKind::KeepAll,
-            ..Default::default()
+        },
+        writer_data_lifecycle: WriterDataLifecycleQosPolicy {
+            autodispose_unregistered_instances: false,
         },
         ..Default::default()
     };
     let writer = publisher
-        .create_datawriter(&topic, QosKind::Specific(data_writer_qos), None, NO_STATUS)
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
         .unwrap();
 
     let subscriber = participant
@@ -2036,17 +2296,11 @@ fn data_reader_resource_limits() {
         },
         history: HistoryQosPolicy {
             kind: HistoryQosPolicyKind::KeepAll,
-            ..Default::default()
-        },
-        resource_limits: ResourceLimitsQosPolicy {
-            max_samples: Length::Limited(2),
-            max_instances: Length::Unlimited,
-            max_samples_per_instance: Length::Limited(2),
         },
         ..Default::default()
     };
     let reader = subscriber
-        .create_datareader::<UserData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
         .unwrap();
 
     let cond = writer.get_statuscondition();
@@ -2057,32 +2311,39 @@ fn data_reader_resource_limits() {
     wait_set
         .attach_condition(Condition::StatusCondition(cond))
         .unwrap();
-    wait_set.wait(Duration::new(10, 0)).unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
 
-    writer.write(&UserData(1), None).unwrap();
-    writer.write(&UserData(2), None).unwrap();
-    writer.write(&UserData(3), None).unwrap();
+    let data1 = KeyedData { id: 1, value: 1 };
+    let data1_2 = KeyedData { id: 1, value: 2 };
+    let data1_3 = KeyedData { id: 1, value: 3 };
 
-    let reader_cond = reader.get_statuscondition();
-    reader_cond
-        .set_enabled_statuses(&[StatusKind::SampleRejected])
-        .unwrap();
-    let mut wait_set = WaitSet::new();
-    wait_set
-        .attach_condition(Condition::StatusCondition(reader_cond))
+    writer.write(&data1, None).unwrap();
+    writer.dispose(&data1, None).unwrap();
+    writer.write(&data1_2, None).unwrap();
+    writer.unregister_instance(&data1_2, None).unwrap();
+    writer.write(&data1_3, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
         .unwrap();
 
-    wait_set.wait(Duration::new(5, 0)).unwrap();
-
     let samples = reader
-        .read(3, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
-        .unwrap();
-
-    assert_eq!(samples.len(), 2);
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    let generation_counts: Vec<_> = samples
+        .iter()
+        .filter(|s| s.sample_info().valid_data)
+        .map(|s| {
+            (
+                s.sample_info().disposed_generation_count,
+                s.sample_info().no_writers_generation_count,
+            )
+        })
+        .collect();
+    assert_eq!(generation_counts, vec![(0, 0), (1, 0), (1, 1)]);
 }
 
 #[test]
-fn data_reader_order_by_source_timestamp() {
+fn read_sample_ranks() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
     let participant_factory = DomainParticipantFactory::get_instance();
 
@@ -2091,29 +2352,21 @@ fn data_reader_order_by_source_timestamp() {
         .unwrap();
 
     let topic = participant
-        .create_topic::<UserData>("MyTopic", "UserData", QosKind::Default, None, NO_STATUS)
+        .create_topic::<KeyedData>("OtherTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
         .unwrap();
 
     let publisher = participant
         .create_publisher(QosKind::Default, None, NO_STATUS)
         .unwrap();
-
-    let data_writer_qos = DataWriterQos {
+    let writer_qos = DataWriterQos {
         reliability: ReliabilityQosPolicy {
             kind: ReliabilityQosPolicyKind::Reliable,
             max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
         },
-        destination_order: DestinationOrderQosPolicy {
-            kind: DestinationOrderQosPolicyKind::BySourceTimestamp,
-        },
-        history: HistoryQosPolicy {
-            kind: HistoryQosPolicyKind::KeepAll,
-            ..Default::default()
-        },
         ..Default::default()
     };
     let writer = publisher
-        .create_datawriter(&topic, QosKind::Specific(data_writer_qos), None, NO_STATUS)
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
         .unwrap();
 
     let subscriber = participant
@@ -2127,13 +2380,10 @@ fn data_reader_order_by_source_timestamp() {
         history: HistoryQosPolicy {
             kind: HistoryQosPolicyKind::KeepAll,
         },
-        destination_order: DestinationOrderQosPolicy {
-            kind: DestinationOrderQosPolicyKind::BySourceTimestamp,
-        },
         ..Default::default()
     };
     let reader = subscriber
-        .create_datareader::<UserData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
         .unwrap();
 
     let cond = writer.get_statuscondition();
@@ -2144,34 +2394,61 @@ fn data_reader_order_by_source_timestamp() {
     wait_set
         .attach_condition(Condition::StatusCondition(cond))
         .unwrap();
-    wait_set.wait(Duration::new(10, 0)).unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
 
+    let ranks = |samples: &[Sample<KeyedData>]| -> Vec<_> {
+        samples
+            .iter()
+            .map(|s| {
+                let sample_info = s.sample_info();
+                (
+                    sample_info.sample_rank,
+                    sample_info.generation_rank,
+                    sample_info.absolute_generation_rank,
+                )
+            })
+            .collect()
+    };
+
+    // Two generations of the same instance separated by a dispose
+    writer.write(&KeyedData { id: 1, value: 1 }, None).unwrap();
     writer
-        .write_w_timestamp(&UserData(1), None, Time::new(30, 0))
-        .unwrap();
-    writer
-        .write_w_timestamp(&UserData(2), None, Time::new(20, 0))
+        .dispose(&KeyedData { id: 1, value: 1 }, None)
         .unwrap();
+    writer.write(&KeyedData { id: 1, value: 2 }, None).unwrap();
+    writer.write(&KeyedData { id: 1, value: 3 }, None).unwrap();
     writer
-        .write_w_timestamp(&UserData(3), None, Time::new(10, 0))
+        .wait_for_acknowledgments(Duration::new(10, 0))
         .unwrap();
 
-    writer
-        .wait_for_acknowledgments(Duration::new(1, 0))
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
         .unwrap();
+    assert_eq!(
+        ranks(&samples),
+        vec![(3, 1, 1), (2, 1, 1), (1, 0, 0), (0, 0, 0)]
+    );
 
+    // The generation rank is relative to the collection while the absolute generation rank
+    // is relative to the most recent sample of the instance in the reader
     let samples = reader
-        .read(3, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .read(2, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
         .unwrap();
+    assert_eq!(ranks(&samples), vec![(1, 0, 1), (0, 0, 1)]);
 
-    assert_eq!(samples.len(), 3);
-    assert_eq!(samples[0].data().unwrap(), UserData(3));
-    assert_eq!(samples[1].data().unwrap(), UserData(2));
-    assert_eq!(samples[2].data().unwrap(), UserData(1));
+    // Once the samples of the previous generation are taken the remaining ones are of
+    // the most recent generation
+    reader
+        .take(2, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(ranks(&samples), vec![(1, 0, 0), (0, 0, 0)]);
 }
 
 #[test]
-fn data_reader_publication_handle_sample_info() {
+fn inconsistent_topic_status_condition() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
     let participant_factory = DomainParticipantFactory::get_instance();
 
@@ -2179,15 +2456,84 @@ fn data_reader_publication_handle_sample_info() {
         .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
         .unwrap();
 
+    let best_effort_topic_qos = TopicQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::BestEffort,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let topic_best_effort = participant
+        .create_topic::<KeyedData>(
+            "Topic",
+            "KeyedData",
+            QosKind::Specific(best_effort_topic_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let status_cond = topic_best_effort.get_statuscondition();
+    status_cond
+        .set_enabled_statuses(&[StatusKind::InconsistentTopic])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(status_cond))
+        .unwrap();
+
+    let participant2 = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let reliable_topic_qos = TopicQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let _topic_reliable = participant2
+        .create_topic::<KeyedData>(
+            "Topic",
+            "KeyedData",
+            QosKind::Specific(reliable_topic_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    assert!(
+        topic_best_effort
+            .get_inconsistent_topic_status()
+            .unwrap()
+            .total_count
+            > 0
+    );
+}
+
+#[test]
+fn reader_with_minimum_time_separation_qos() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
     let topic = participant
-        .create_topic::<UserData>("MyTopic", "UserData", QosKind::Default, None, NO_STATUS)
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
         .unwrap();
 
     let publisher = participant
         .create_publisher(QosKind::Default, None, NO_STATUS)
         .unwrap();
-
     let writer_qos = DataWriterQos {
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
         reliability: ReliabilityQosPolicy {
             kind: ReliabilityQosPolicyKind::Reliable,
             max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
@@ -2201,16 +2547,21 @@ fn data_reader_publication_handle_sample_info() {
     let subscriber = participant
         .create_subscriber(QosKind::Default, None, NO_STATUS)
         .unwrap();
-
     let reader_qos = DataReaderQos {
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
         reliability: ReliabilityQosPolicy {
             kind: ReliabilityQosPolicyKind::Reliable,
             max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
         },
+        time_based_filter: TimeBasedFilterQosPolicy {
+            minimum_separation: DurationKind::Finite(Duration::new(2, 0)),
+        },
         ..Default::default()
     };
     let reader = subscriber
-        .create_datareader::<UserData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
         .unwrap();
 
     let cond = writer.get_statuscondition();
@@ -2223,24 +2574,49 @@ fn data_reader_publication_handle_sample_info() {
         .unwrap();
     wait_set.wait(Duration::new(10, 0)).unwrap();
 
-    writer.write(&UserData(1), None).unwrap();
+    let data1_1 = KeyedData { id: 1, value: 1 };
+    let data1_2 = KeyedData { id: 1, value: 2 };
+    let data1_3 = KeyedData { id: 1, value: 3 };
+    let data2_1 = KeyedData { id: 2, value: 10 };
+    let data2_2 = KeyedData { id: 2, value: 20 };
+    let data2_3 = KeyedData { id: 2, value: 30 };
+
+    writer
+        .write_w_timestamp(&data1_1, None, Time::new(1, 0))
+        .unwrap();
+    writer
+        .write_w_timestamp(&data1_2, None, Time::new(2, 0))
+        .unwrap();
+    writer
+        .write_w_timestamp(&data1_3, None, Time::new(3, 0))
+        .unwrap();
+    writer
+        .write_w_timestamp(&data2_1, None, Time::new(4, 0))
+        .unwrap();
+    writer
+        .write_w_timestamp(&data2_2, None, Time::new(5, 0))
+        .unwrap();
+    writer
+        .write_w_timestamp(&data2_3, None, Time::new(6, 0))
+        .unwrap();
 
     writer
         .wait_for_acknowledgments(Duration::new(10, 0))
         .unwrap();
 
     let samples = reader
-        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
         .unwrap();
 
-    assert_eq!(samples.len(), 1);
-    assert!(reader
-        .get_matched_publication_data(samples[0].sample_info().publication_handle)
-        .is_ok());
+    assert_eq!(samples.len(), 4);
+    assert_eq!(samples[0].data().unwrap(), data1_1);
+    assert_eq!(samples[1].data().unwrap(), data1_3);
+    assert_eq!(samples[2].data().unwrap(), data2_1);
+    assert_eq!(samples[3].data().unwrap(), data2_3);
 }
 
 #[test]
-fn volatile_writer_with_reader_new_reader_receives_only_new_samples() {
+fn reader_with_minimum_time_separation_qos_filters_high_frequency_stream() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
 
     let participant = DomainParticipantFactory::get_instance()
@@ -2255,9 +2631,6 @@ fn volatile_writer_with_reader_new_reader_receives_only_new_samples() {
         .create_publisher(QosKind::Default, None, NO_STATUS)
         .unwrap();
     let writer_qos = DataWriterQos {
-        durability: DurabilityQosPolicy {
-            kind: DurabilityQosPolicyKind::Volatile,
-        },
         history: HistoryQosPolicy {
             kind: HistoryQosPolicyKind::KeepAll,
         },
@@ -2275,7 +2648,284 @@ fn volatile_writer_with_reader_new_reader_receives_only_new_samples() {
         .create_subscriber(QosKind::Default, None, NO_STATUS)
         .unwrap();
     let reader_qos = DataReaderQos {
-        durability: DurabilityQosPolicy {
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        time_based_filter: TimeBasedFilterQosPolicy {
+            minimum_separation: DurationKind::Finite(Duration::new(0, 500_000_000)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    // Samples with 100ms separation of which only one every 500ms is accepted
+    for i in 0..10 {
+        writer
+            .write_w_timestamp(
+                &KeyedData { id: 1, value: i },
+                None,
+                Time::new(1, i * 100_000_000),
+            )
+            .unwrap();
+    }
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    // Taking the samples must not reset the separation from the last accepted sample
+    let first_samples = reader
+        .take(20, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    for i in 10..20 {
+        writer
+            .write_w_timestamp(
+                &KeyedData { id: 1, value: i },
+                None,
+                Time::new(2, (i - 10) * 100_000_000),
+            )
+            .unwrap();
+    }
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let second_samples = reader
+        .take(20, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    let received_values: Vec<u32> = first_samples
+        .iter()
+        .chain(second_samples.iter())
+        .map(|s| s.data().unwrap().value)
+        .collect();
+    assert_eq!(received_values, vec![0, 5, 10, 15]);
+    assert_eq!(reader.get_sample_lost_status().unwrap().total_count, 16);
+}
+
+#[test]
+fn transient_local_writer_reader_wait_for_historical_data() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::TransientLocal,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+    let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 2, value: 2 };
+    writer.write(&data1, None).unwrap();
+    writer.write(&data2, None).unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::TransientLocal,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        time_based_filter: TimeBasedFilterQosPolicy {
+            minimum_separation: DurationKind::Finite(Duration::new(2, 0)),
+        },
+        ..Default::default()
+    };
+
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    reader
+        .wait_for_historical_data(Duration::new(10, 0))
+        .unwrap();
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0].data().unwrap(), data1);
+    assert_eq!(samples[1].data().unwrap(), data2);
+}
+
+#[test]
+fn volatile_reader_wait_for_historical_data_is_illegal_operation() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::Volatile,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    assert_eq!(
+        reader.wait_for_historical_data(Duration::new(1, 0)),
+        Err(DdsError::IllegalOperation)
+    );
+}
+
+#[test]
+fn transient_local_writer_sends_historical_data_to_best_effort_late_joiner() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::TransientLocal,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+    let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 2, value: 2 };
+    writer.write(&data1, None).unwrap();
+    writer.write(&data2, None).unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::TransientLocal,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::BestEffort,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::DataAvailable])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+    // Both historical samples are sent in the same send cycle
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0].data().unwrap(), data1);
+    assert_eq!(samples[1].data().unwrap(), data2);
+}
+
+#[test]
+fn volatile_writer_reader_receives_only_new_samples() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        durability: DurabilityQosPolicy {
             kind: DurabilityQosPolicyKind::Volatile,
         },
         history: HistoryQosPolicy {
@@ -2287,10 +2937,2244 @@ fn volatile_writer_with_reader_new_reader_receives_only_new_samples() {
         },
         ..Default::default()
     };
-    let _reader = subscriber
-        .create_datareader::<KeyedData>(
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let data1 = KeyedData { id: 1, value: 1 };
+    writer.write(&data1, None).unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::Volatile,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    let data2 = KeyedData { id: 2, value: 10 };
+    writer.write(&data2, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data().unwrap(), data2);
+}
+
+#[test]
+fn write_read_unkeyed_topic() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<UserData>(
+            "write_read_unkeyed_topic",
+            "UserData",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<UserData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    writer.write(&UserData(8), None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(5, 0))
+        .unwrap();
+
+    let samples = reader.read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE);
+
+    assert_eq!(samples.unwrap()[0].data().unwrap(), UserData(8));
+}
+
+#[test]
+fn data_reader_resource_limits() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<UserData>(
+            "data_reader_resource_limits",
+            "UserData",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(data_writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+            ..Default::default()
+        },
+        resource_limits: ResourceLimitsQosPolicy {
+            max_samples: Length::Limited(2),
+            max_instances: Length::Unlimited,
+            max_samples_per_instance: Length::Limited(2),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<UserData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    writer.write(&UserData(1), None).unwrap();
+    writer.write(&UserData(2), None).unwrap();
+    writer.write(&UserData(3), None).unwrap();
+
+    let reader_cond = reader.get_statuscondition();
+    reader_cond
+        .set_enabled_statuses(&[StatusKind::SampleRejected])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(reader_cond))
+        .unwrap();
+
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    let samples = reader
+        .read(3, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 2);
+}
+
+#[test]
+fn data_reader_order_by_source_timestamp() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<UserData>("MyTopic", "UserData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let data_writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        destination_order: DestinationOrderQosPolicy {
+            kind: DestinationOrderQosPolicyKind::BySourceTimestamp,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(data_writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        destination_order: DestinationOrderQosPolicy {
+            kind: DestinationOrderQosPolicyKind::BySourceTimestamp,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<UserData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    writer
+        .write_w_timestamp(&UserData(1), None, Time::new(30, 0))
+        .unwrap();
+    writer
+        .write_w_timestamp(&UserData(2), None, Time::new(20, 0))
+        .unwrap();
+    writer
+        .write_w_timestamp(&UserData(3), None, Time::new(10, 0))
+        .unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(1, 0))
+        .unwrap();
+
+    let samples = reader
+        .read(3, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data().unwrap(), UserData(1));
+}
+
+#[test]
+fn data_reader_order_by_source_timestamp_multiple_instances() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let data_writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        destination_order: DestinationOrderQosPolicy {
+            kind: DestinationOrderQosPolicyKind::BySourceTimestamp,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(data_writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        destination_order: DestinationOrderQosPolicy {
+            kind: DestinationOrderQosPolicyKind::BySourceTimestamp,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data1_1 = KeyedData { id: 1, value: 1 };
+    let data2_1 = KeyedData { id: 2, value: 10 };
+    let data1_2 = KeyedData { id: 1, value: 2 };
+    let data2_2 = KeyedData { id: 2, value: 20 };
+    writer
+        .write_w_timestamp(&data1_1, None, Time::new(30, 0))
+        .unwrap();
+    writer
+        .write_w_timestamp(&data2_1, None, Time::new(20, 0))
+        .unwrap();
+    writer
+        .write_w_timestamp(&data1_2, None, Time::new(10, 0))
+        .unwrap(); // Older than the last sample of the instance so it is discarded
+    writer
+        .write_w_timestamp(&data2_2, None, Time::new(40, 0))
+        .unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(1, 0))
+        .unwrap();
+
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 3);
+    assert_eq!(samples[0].data().unwrap(), data2_1);
+    assert_eq!(samples[1].data().unwrap(), data1_1);
+    assert_eq!(samples[2].data().unwrap(), data2_2);
+}
+
+#[test]
+fn data_reader_publication_handle_sample_info() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<UserData>("MyTopic", "UserData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<UserData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    writer.write(&UserData(1), None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 1);
+    assert_eq!(
+        samples[0].sample_info().publication_handle,
+        writer.get_instance_handle().unwrap()
+    );
+    assert!(reader
+        .get_matched_publication_data(samples[0].sample_info().publication_handle)
+        .is_ok());
+}
+
+#[test]
+fn data_reader_source_timestamp_sample_info() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<UserData>("MyTopic", "UserData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<UserData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    writer
+        .write_w_timestamp(&UserData(1), None, Time::new(1234, 5678))
+        .unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .read(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 1);
+    assert_eq!(
+        samples[0].sample_info().source_timestamp,
+        Some(Time::new(1234, 5678))
+    );
+}
+
+#[test]
+fn data_reader_source_timestamp_from_logical_time_source() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let logical_time = Arc::new(AtomicU64::new(1_000_000_000));
+    let participant_qos = DomainParticipantQos {
+        time_source: TimeSourceQosPolicy::Logical(logical_time.clone()),
+        ..Default::default()
+    };
+    let participant = participant_factory
+        .create_participant(
+            domain_id,
+            QosKind::Specific(participant_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<UserData>("MyTopic", "UserData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<UserData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    assert_eq!(participant.get_current_time().unwrap(), Time::new(1, 0));
+    writer.write(&UserData(1), None).unwrap();
+
+    logical_time.store(2_500_000_000, Ordering::Release);
+    assert_eq!(
+        participant.get_current_time().unwrap(),
+        Time::new(2, 500_000_000)
+    );
+    writer.write(&UserData(2), None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .read(2, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 2);
+    assert_eq!(
+        samples[0].sample_info().source_timestamp,
+        Some(Time::new(1, 0))
+    );
+    assert_eq!(
+        samples[1].sample_info().source_timestamp,
+        Some(Time::new(2, 500_000_000))
+    );
+}
+
+#[test]
+fn deadline_follows_logical_time_source() {
+    const DEADLINE_MS: u32 = 100;
+    struct DeadlineMissedListener {
+        sender: std::sync::mpsc::SyncSender<RequestedDeadlineMissedStatus>,
+    }
+
+    impl DataReaderListener<'_> for DeadlineMissedListener {
+        type Foo = UserData;
+        fn on_requested_deadline_missed(
+            &mut self,
+            _the_reader: DataReader<Self::Foo>,
+            status: RequestedDeadlineMissedStatus,
+        ) {
+            self.sender.send(status).ok();
+        }
+    }
+
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let logical_time = Arc::new(AtomicU64::new(1_000_000_000));
+    let participant_qos = DomainParticipantQos {
+        time_source: TimeSourceQosPolicy::Logical(logical_time.clone()),
+        ..Default::default()
+    };
+    let participant = participant_factory
+        .create_participant(
+            domain_id,
+            QosKind::Specific(participant_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<UserData>("MyTopic", "UserData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        deadline: DeadlineQosPolicy {
+            period: DurationKind::Finite(Duration::new(0, DEADLINE_MS * 1_000_000)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        deadline: DeadlineQosPolicy {
+            period: DurationKind::Finite(Duration::new(0, DEADLINE_MS * 1_000_000)),
+        },
+        ..Default::default()
+    };
+    let (sender, receiver) = std::sync::mpsc::sync_channel(10);
+    let _reader = subscriber
+        .create_datareader(
+            &topic,
+            QosKind::Specific(reader_qos),
+            Some(Box::new(DeadlineMissedListener { sender })),
+            &[StatusKind::RequestedDeadlineMissed],
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    writer.write(&UserData(1), None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    // The logical time does not advance so the deadline is not missed
+    assert!(receiver
+        .recv_timeout(std::time::Duration::from_millis(DEADLINE_MS as u64 * 3))
+        .is_err());
+
+    logical_time.store(2_000_000_000, Ordering::Release);
+    let status = receiver
+        .recv_timeout(std::time::Duration::from_secs(5))
+        .unwrap();
+    assert_eq!(status.total_count, 1);
+}
+
+#[test]
+fn liveliness_lease_follows_logical_time_source() {
+    const LEASE_DURATION_MS: u32 = 100;
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let logical_time = Arc::new(AtomicU64::new(1_000_000_000));
+    let participant_qos = DomainParticipantQos {
+        time_source: TimeSourceQosPolicy::Logical(logical_time.clone()),
+        ..Default::default()
+    };
+    let participant = participant_factory
+        .create_participant(
+            domain_id,
+            QosKind::Specific(participant_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<UserData>("MyTopic", "UserData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<UserData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        liveliness: LivelinessQosPolicy {
+            kind: LivelinessQosPolicyKind::ManualByTopic,
+            lease_duration: DurationKind::Finite(Duration::new(0, LEASE_DURATION_MS * 1_000_000)),
+        },
+        ..Default::default()
+    };
+    let _writer = publisher
+        .create_datawriter::<UserData>(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let start = std::time::Instant::now();
+    while reader.get_liveliness_changed_status().unwrap().alive_count == 0 {
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(10),
+            "Writer not alive"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    // The logical time does not advance so the lease does not expire
+    std::thread::sleep(std::time::Duration::from_millis(
+        LEASE_DURATION_MS as u64 * 3,
+    ));
+    let status = reader.get_liveliness_changed_status().unwrap();
+    assert_eq!(status.alive_count, 1);
+    assert_eq!(status.not_alive_count, 0);
+
+    logical_time.store(2_000_000_000, Ordering::Release);
+    let start = std::time::Instant::now();
+    while reader
+        .get_liveliness_changed_status()
+        .unwrap()
+        .not_alive_count
+        == 0
+    {
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(5),
+            "Lease not expired after the logical time advanced"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn lifespan_follows_logical_time_source() {
+    const LIFESPAN_MS: u32 = 100;
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let logical_time = Arc::new(AtomicU64::new(1_000_000_000));
+    let participant_qos = DomainParticipantQos {
+        time_source: TimeSourceQosPolicy::Logical(logical_time.clone()),
+        ..Default::default()
+    };
+    let participant = participant_factory
+        .create_participant(
+            domain_id,
+            QosKind::Specific(participant_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<UserData>("MyTopic", "UserData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::TransientLocal,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        lifespan: LifespanQosPolicy {
+            duration: DurationKind::Finite(Duration::new(0, LIFESPAN_MS * 1_000_000)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+    writer.write(&UserData(1), None).unwrap();
+
+    // The logical time does not advance so the sample is still in the writer history
+    std::thread::sleep(std::time::Duration::from_millis(LIFESPAN_MS as u64 * 3));
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::TransientLocal,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<UserData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    reader
+        .wait_for_historical_data(Duration::new(10, 0))
+        .unwrap();
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 1);
+
+    logical_time.store(2_000_000_000, Ordering::Release);
+    assert_eq!(
+        reader.read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE),
+        Err(DdsError::NoData)
+    );
+}
+
+#[test]
+fn volatile_writer_with_reader_new_reader_receives_only_new_samples() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::Volatile,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::Volatile,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let _reader = subscriber
+        .create_datareader::<KeyedData>(
+            &topic,
+            QosKind::Specific(reader_qos.clone()),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+    writer.get_publication_matched_status().unwrap(); // To reset wait_set for subsequent calls
+
+    let data1 = KeyedData { id: 1, value: 1 };
+    writer.write(&data1, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(3, 0))
+        .unwrap();
+
+    let reader_new = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    // Wait for writer to match reader
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_secs(1));
+
+    let data2 = KeyedData { id: 2, value: 10 };
+    writer.write(&data2, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader_new
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data().unwrap(), data2);
+}
+
+#[test]
+fn write_read_unregistered_samples_are_also_disposed() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        writer_data_lifecycle: WriterDataLifecycleQosPolicy {
+            autodispose_unregistered_instances: true,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data1 = KeyedData { id: 1, value: 1 };
+
+    writer.write(&data1, None).unwrap();
+    writer.unregister_instance(&data1, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .read(2, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 2);
+    assert_eq!(
+        samples[0].sample_info().instance_state,
+        InstanceStateKind::NotAliveDisposed
+    );
+    assert_eq!(
+        samples[1].sample_info().instance_state,
+        InstanceStateKind::NotAliveDisposed
+    );
+}
+
+#[test]
+fn instance_has_no_writers_only_after_all_writers_unregister() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        writer_data_lifecycle: WriterDataLifecycleQosPolicy {
+            autodispose_unregistered_instances: false,
+        },
+        ..Default::default()
+    };
+    let writer1 = publisher
+        .create_datawriter(
+            &topic,
+            QosKind::Specific(writer_qos.clone()),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer2 = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    for writer in [&writer1, &writer2] {
+        let cond = writer.get_statuscondition();
+        cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+            .unwrap();
+
+        let mut wait_set = WaitSet::new();
+        wait_set
+            .attach_condition(Condition::StatusCondition(cond))
+            .unwrap();
+        wait_set.wait(Duration::new(10, 0)).unwrap();
+    }
+
+    let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 1, value: 2 };
+
+    writer1.write(&data1, None).unwrap();
+    writer2.write(&data2, None).unwrap();
+    writer1
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    writer2
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    writer1.unregister_instance(&data1, None).unwrap();
+    writer1
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(
+        samples[0].sample_info().instance_state,
+        InstanceStateKind::Alive
+    );
+
+    writer2.unregister_instance(&data2, None).unwrap();
+    writer2
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(
+        samples[0].sample_info().instance_state,
+        InstanceStateKind::NotAliveNoWriters
+    );
+}
+
+#[test]
+fn instance_has_no_writers_after_all_writers_are_deleted() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        writer_data_lifecycle: WriterDataLifecycleQosPolicy {
+            autodispose_unregistered_instances: false,
+        },
+        ..Default::default()
+    };
+    let writer1 = publisher
+        .create_datawriter(
+            &topic,
+            QosKind::Specific(writer_qos.clone()),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer2 = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    for writer in [&writer1, &writer2] {
+        let cond = writer.get_statuscondition();
+        cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+            .unwrap();
+
+        let mut wait_set = WaitSet::new();
+        wait_set
+            .attach_condition(Condition::StatusCondition(cond))
+            .unwrap();
+        wait_set.wait(Duration::new(10, 0)).unwrap();
+    }
+
+    let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 1, value: 2 };
+
+    writer1.write(&data1, None).unwrap();
+    writer2.write(&data2, None).unwrap();
+    writer1
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    writer2
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let cond = reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+
+    // The writers are deleted without unregistering the instance
+    publisher.delete_datawriter(&writer1).unwrap();
+    while reader
+        .get_subscription_matched_status()
+        .unwrap()
+        .current_count
+        != 1
+    {
+        wait_set.wait(Duration::new(10, 0)).unwrap();
+    }
+
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(
+        samples[0].sample_info().instance_state,
+        InstanceStateKind::Alive
+    );
+
+    publisher.delete_datawriter(&writer2).unwrap();
+    while reader
+        .get_subscription_matched_status()
+        .unwrap()
+        .current_count
+        != 0
+    {
+        wait_set.wait(Duration::new(10, 0)).unwrap();
+    }
+
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(
+        samples[0].sample_info().instance_state,
+        InstanceStateKind::NotAliveNoWriters
+    );
+}
+
+#[test]
+fn transient_local_writer_does_not_deliver_lifespan_expired_data_at_write() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::TransientLocal,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        lifespan: LifespanQosPolicy {
+            duration: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+    let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 2, value: 2 };
+    writer
+        .write_w_timestamp(&data1, None, Time::new(0, 0))
+        .unwrap();
+    writer
+        .write_w_timestamp(&data2, None, Time::new(i32::MAX, 0))
+        .unwrap(); // Never stale sample
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::TransientLocal,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    reader
+        .wait_for_historical_data(Duration::new(10, 0))
+        .unwrap();
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data().unwrap(), data2);
+}
+
+#[test]
+fn transient_local_writer_does_not_deliver_lifespan_expired_data_after_write() {
+    const LIFESPAN_MS: u32 = 100;
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::TransientLocal,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        lifespan: LifespanQosPolicy {
+            duration: DurationKind::Finite(Duration::new(0, LIFESPAN_MS * 1_000_000)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+    let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 2, value: 2 };
+    writer.write(&data1, None).unwrap();
+    writer
+        .write_w_timestamp(&data2, None, Time::new(i32::MAX, 0))
+        .unwrap(); // Never stale sample
+
+    std::thread::sleep(std::time::Duration::from_millis(LIFESPAN_MS as u64 * 2));
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::TransientLocal,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    reader
+        .wait_for_historical_data(Duration::new(10, 0))
+        .unwrap();
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data().unwrap(), data2);
+}
+
+#[test]
+fn reader_does_not_return_lifespan_expired_data() {
+    const LIFESPAN_MS: u32 = 500;
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        lifespan: LifespanQosPolicy {
+            duration: DurationKind::Finite(Duration::new(0, LIFESPAN_MS * 1_000_000)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 2, value: 2 };
+    writer.write(&data1, None).unwrap();
+    writer
+        .write_w_timestamp(&data2, None, Time::new(i32::MAX, 0))
+        .unwrap(); // Never stale sample
+    writer
+        .wait_for_acknowledgments(Duration::new(5, 0))
+        .unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(LIFESPAN_MS as u64 * 2));
+
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data().unwrap(), data2);
+}
+
+#[test]
+fn exclusive_ownership_reader_only_receives_samples_from_strongest_writer() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = |strength| DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ownership: OwnershipQosPolicy {
+            kind: OwnershipQosPolicyKind::Exclusive,
+        },
+        ownership_strength: OwnershipStrengthQosPolicy { value: strength },
+        ..Default::default()
+    };
+    let strong_writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos(20)), None, NO_STATUS)
+        .unwrap();
+    let weak_writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos(10)), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ownership: OwnershipQosPolicy {
+            kind: OwnershipQosPolicyKind::Exclusive,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    for writer in [&strong_writer, &weak_writer] {
+        let cond = writer.get_statuscondition();
+        cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+            .unwrap();
+        let mut wait_set = WaitSet::new();
+        wait_set
+            .attach_condition(Condition::StatusCondition(cond))
+            .unwrap();
+        wait_set.wait(Duration::new(5, 0)).unwrap();
+    }
+
+    let instance1_strong = KeyedData { id: 1, value: 1 };
+    let instance1_weak = KeyedData { id: 1, value: 2 };
+    let instance2_weak = KeyedData { id: 2, value: 3 };
+    strong_writer.write(&instance1_strong, None).unwrap();
+    strong_writer
+        .wait_for_acknowledgments(Duration::new(5, 0))
+        .unwrap();
+    weak_writer.write(&instance1_weak, None).unwrap();
+    weak_writer.write(&instance2_weak, None).unwrap();
+    weak_writer
+        .wait_for_acknowledgments(Duration::new(5, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0].data().unwrap(), instance1_strong);
+    assert_eq!(samples[1].data().unwrap(), instance2_weak);
+}
+
+#[test]
+fn exclusive_ownership_is_transferred_when_owner_writer_is_deleted() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = |strength| DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ownership: OwnershipQosPolicy {
+            kind: OwnershipQosPolicyKind::Exclusive,
+        },
+        ownership_strength: OwnershipStrengthQosPolicy { value: strength },
+        ..Default::default()
+    };
+    let strong_writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos(20)), None, NO_STATUS)
+        .unwrap();
+    let weak_writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos(10)), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ownership: OwnershipQosPolicy {
+            kind: OwnershipQosPolicyKind::Exclusive,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    for writer in [&strong_writer, &weak_writer] {
+        let cond = writer.get_statuscondition();
+        cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+            .unwrap();
+        let mut wait_set = WaitSet::new();
+        wait_set
+            .attach_condition(Condition::StatusCondition(cond))
+            .unwrap();
+        wait_set.wait(Duration::new(5, 0)).unwrap();
+    }
+
+    let instance1_strong = KeyedData { id: 1, value: 1 };
+    let instance1_weak = KeyedData { id: 1, value: 2 };
+    strong_writer.write(&instance1_strong, None).unwrap();
+    strong_writer
+        .wait_for_acknowledgments(Duration::new(5, 0))
+        .unwrap();
+    reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    reader.get_subscription_matched_status().unwrap();
+    let cond = reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    publisher.delete_datawriter(&strong_writer).unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    weak_writer.write(&instance1_weak, None).unwrap();
+    weak_writer
+        .wait_for_acknowledgments(Duration::new(5, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert!(samples
+        .iter()
+        .any(|s| s.sample_info().valid_data && s.data().unwrap() == instance1_weak));
+}
+
+#[test]
+fn reader_rejects_samples_above_max_samples() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        resource_limits: ResourceLimitsQosPolicy {
+            max_samples: Length::Limited(2),
+            max_instances: Length::Unlimited,
+            max_samples_per_instance: Length::Limited(2),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    writer.write(&KeyedData { id: 1, value: 1 }, None).unwrap();
+    writer.write(&KeyedData { id: 2, value: 2 }, None).unwrap();
+    writer.write(&KeyedData { id: 3, value: 3 }, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(5, 0))
+        .unwrap();
+
+    let status = reader.get_sample_rejected_status().unwrap();
+    assert_eq!(status.total_count, 1);
+    assert_eq!(
+        status.last_reason,
+        SampleRejectedStatusKind::RejectedBySamplesLimit
+    );
+    // The rejected sample was received by the reader so it is not lost
+    assert_eq!(reader.get_sample_lost_status().unwrap().total_count, 0);
+
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0].data().unwrap(), KeyedData { id: 1, value: 1 });
+    assert_eq!(samples[1].data().unwrap(), KeyedData { id: 2, value: 2 });
+}
+
+#[test]
+fn reader_rejects_samples_above_max_instances() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        resource_limits: ResourceLimitsQosPolicy {
+            max_samples: Length::Unlimited,
+            max_instances: Length::Limited(1),
+            max_samples_per_instance: Length::Unlimited,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    writer.write(&KeyedData { id: 1, value: 1 }, None).unwrap();
+    writer.write(&KeyedData { id: 2, value: 2 }, None).unwrap();
+    writer.write(&KeyedData { id: 1, value: 3 }, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(5, 0))
+        .unwrap();
+
+    let status = reader.get_sample_rejected_status().unwrap();
+    assert_eq!(status.total_count, 1);
+    assert_eq!(
+        status.last_reason,
+        SampleRejectedStatusKind::RejectedByInstancesLimit
+    );
+
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0].data().unwrap(), KeyedData { id: 1, value: 1 });
+    assert_eq!(samples[1].data().unwrap(), KeyedData { id: 1, value: 3 });
+}
+
+#[test]
+fn reader_rejects_samples_above_max_samples_per_instance() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        resource_limits: ResourceLimitsQosPolicy {
+            max_samples: Length::Unlimited,
+            max_instances: Length::Unlimited,
+            max_samples_per_instance: Length::Limited(2),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    writer.write(&KeyedData { id: 1, value: 1 }, None).unwrap();
+    writer.write(&KeyedData { id: 1, value: 2 }, None).unwrap();
+    writer.write(&KeyedData { id: 1, value: 3 }, None).unwrap();
+    writer.write(&KeyedData { id: 2, value: 4 }, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(5, 0))
+        .unwrap();
+
+    let status = reader.get_sample_rejected_status().unwrap();
+    assert_eq!(status.total_count, 1);
+    assert_eq!(
+        status.last_reason,
+        SampleRejectedStatusKind::RejectedBySamplesPerInstanceLimit
+    );
+
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 3);
+    assert_eq!(samples[0].data().unwrap(), KeyedData { id: 1, value: 1 });
+    assert_eq!(samples[1].data().unwrap(), KeyedData { id: 1, value: 2 });
+    assert_eq!(samples[2].data().unwrap(), KeyedData { id: 2, value: 4 });
+}
+
+#[test]
+fn keep_last_reader_does_not_reject_samples_replaced_by_history() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepLast(1),
+        },
+        resource_limits: ResourceLimitsQosPolicy {
+            max_samples: Length::Limited(2),
+            max_instances: Length::Unlimited,
+            max_samples_per_instance: Length::Limited(1),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    writer.write(&KeyedData { id: 1, value: 1 }, None).unwrap();
+    writer.write(&KeyedData { id: 1, value: 2 }, None).unwrap();
+    writer.write(&KeyedData { id: 2, value: 3 }, None).unwrap();
+    writer.write(&KeyedData { id: 2, value: 4 }, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(5, 0))
+        .unwrap();
+
+    let status = reader.get_sample_rejected_status().unwrap();
+    assert_eq!(status.total_count, 0);
+
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0].data().unwrap(), KeyedData { id: 1, value: 2 });
+    assert_eq!(samples[1].data().unwrap(), KeyedData { id: 2, value: 4 });
+}
+
+#[test]
+fn reader_joining_after_writer_writes_many_samples() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    for value in 0..500 {
+        let data = KeyedData { id: 1, value };
+        writer.write(&data, None).unwrap();
+    }
+
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let new_data = KeyedData { id: 1, value: 1000 };
+    writer.write(&new_data, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(20, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(5, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data().unwrap(), new_data);
+}
+
+#[test]
+fn content_filtered_topic_reader_receives_only_matching_samples() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let content_filtered_topic = participant
+        .create_contentfilteredtopic::<KeyedData>(
+            "MyFilteredTopic",
             &topic,
-            QosKind::Specific(reader_qos.clone()),
+            "value > %0",
+            &["5".to_string()],
+        )
+        .unwrap();
+    assert_eq!(content_filtered_topic.get_name(), "MyFilteredTopic");
+    assert_eq!(content_filtered_topic.get_type_name(), "KeyedData");
+    assert_eq!(content_filtered_topic.get_filter_expression(), "value > %0");
+    assert_eq!(
+        content_filtered_topic.get_expression_parameters(),
+        vec!["5".to_string()]
+    );
+    assert_eq!(
+        content_filtered_topic.get_related_topic().get_name(),
+        "MyTopic"
+    );
+
+    let reader = subscriber
+        .create_datareader::<KeyedData>(
+            &content_filtered_topic,
+            QosKind::Specific(reader_qos),
             None,
             NO_STATUS,
         )
@@ -2305,41 +5189,360 @@ fn volatile_writer_with_reader_new_reader_receives_only_new_samples() {
         .attach_condition(Condition::StatusCondition(cond))
         .unwrap();
     wait_set.wait(Duration::new(10, 0)).unwrap();
-    writer.get_publication_matched_status().unwrap(); // To reset wait_set for subsequent calls
 
     let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 2, value: 10 };
+    let data3 = KeyedData { id: 3, value: 3 };
+    let data4 = KeyedData { id: 4, value: 20 };
+
     writer.write(&data1, None).unwrap();
+    writer.write(&data2, None).unwrap();
+    writer.write(&data3, None).unwrap();
+    writer.write(&data4, None).unwrap();
 
     writer
-        .wait_for_acknowledgments(Duration::new(3, 0))
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0].data().unwrap(), data2);
+    assert_eq!(samples[1].data().unwrap(), data4);
+}
+
+#[test]
+fn content_filtered_topic_with_invalid_expression() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert!(matches!(
+        participant.create_contentfilteredtopic::<KeyedData>(
+            "MyFilteredTopic",
+            &topic,
+            "value > %1",
+            &["5".to_string()],
+        ),
+        Err(DdsError::BadParameter)
+    ));
+    assert!(matches!(
+        participant.create_contentfilteredtopic::<KeyedData>(
+            "MyFilteredTopic",
+            &topic,
+            "value >",
+            &[]
+        ),
+        Err(DdsError::BadParameter)
+    ));
+}
+
+#[test]
+fn read_and_take_with_query_condition() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let query_condition = reader
+        .create_querycondition(
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+            "value BETWEEN 10 AND 30 AND id <> %0",
+            &["3".to_string()],
+        )
+        .unwrap();
+    assert_eq!(query_condition.get_trigger_value(), Ok(false));
+
+    let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 2, value: 10 };
+    let data3 = KeyedData { id: 3, value: 20 };
+    let data4 = KeyedData { id: 4, value: 30 };
+    let data5 = KeyedData { id: 5, value: 40 };
+
+    writer.write(&data1, None).unwrap();
+    writer.write(&data2, None).unwrap();
+    writer.write(&data3, None).unwrap();
+    writer.write(&data4, None).unwrap();
+    writer.write(&data5, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    assert_eq!(query_condition.get_trigger_value(), Ok(true));
+
+    let samples = reader.read_w_condition(10, &query_condition).unwrap();
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0].data().unwrap(), data2);
+    assert_eq!(samples[1].data().unwrap(), data4);
+
+    query_condition
+        .set_query_parameters(&["4".to_string()])
+        .unwrap();
+    assert_eq!(
+        query_condition.get_query_parameters(),
+        Ok(vec!["4".to_string()])
+    );
+
+    let samples = reader.take_w_condition(10, &query_condition).unwrap();
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0].data().unwrap(), data2);
+    assert_eq!(samples[1].data().unwrap(), data3);
+
+    assert_eq!(query_condition.get_trigger_value(), Ok(false));
+    assert_eq!(
+        reader.take_w_condition(10, &query_condition).err(),
+        Some(DdsError::NoData)
+    );
+
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 3);
+    assert_eq!(samples[0].data().unwrap(), data1);
+    assert_eq!(samples[1].data().unwrap(), data4);
+    assert_eq!(samples[2].data().unwrap(), data5);
+}
+
+#[test]
+fn query_condition_errors() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader1 = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader2 = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert_eq!(
+        reader1
+            .create_querycondition(
+                ANY_SAMPLE_STATE,
+                ANY_VIEW_STATE,
+                ANY_INSTANCE_STATE,
+                "value >",
+                &[],
+            )
+            .err(),
+        Some(DdsError::BadParameter)
+    );
+    assert_eq!(
+        reader1
+            .create_querycondition(
+                ANY_SAMPLE_STATE,
+                ANY_VIEW_STATE,
+                ANY_INSTANCE_STATE,
+                "value > %0",
+                &[],
+            )
+            .err(),
+        Some(DdsError::BadParameter)
+    );
+
+    let query_condition = reader1
+        .create_querycondition(
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+            "value > %0",
+            &["10".to_string()],
+        )
+        .unwrap();
+    assert_eq!(
+        query_condition.set_query_parameters(&[]),
+        Err(DdsError::BadParameter)
+    );
+    assert_eq!(
+        query_condition.get_query_parameters(),
+        Ok(vec!["10".to_string()])
+    );
+    assert!(matches!(
+        reader2.read_w_condition(10, &query_condition),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+}
+
+#[test]
+fn read_and_take_with_read_condition() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
         .unwrap();
 
-    let reader_new = subscriber
-        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
         .unwrap();
 
-    // Wait for writer to match reader
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
     wait_set.wait(Duration::new(10, 0)).unwrap();
 
-    std::thread::sleep(std::time::Duration::from_secs(1));
+    let read_condition = reader
+        .create_readcondition(
+            &[SampleStateKind::NotRead],
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        )
+        .unwrap();
+    assert_eq!(
+        read_condition.get_sample_state_mask(),
+        vec![SampleStateKind::NotRead]
+    );
+    assert_eq!(read_condition.get_trigger_value(), Ok(false));
 
+    let data1 = KeyedData { id: 1, value: 1 };
     let data2 = KeyedData { id: 2, value: 10 };
+    let data3 = KeyedData { id: 3, value: 20 };
+
+    writer.write(&data1, None).unwrap();
     writer.write(&data2, None).unwrap();
+    writer.write(&data3, None).unwrap();
 
     writer
         .wait_for_acknowledgments(Duration::new(10, 0))
         .unwrap();
 
-    let samples = reader_new
-        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
-        .unwrap();
+    assert_eq!(read_condition.get_trigger_value(), Ok(true));
+    assert_eq!(reader.read_next_sample().unwrap().data().unwrap(), data1);
 
-    assert_eq!(samples.len(), 1);
+    let samples = reader.read_w_condition(10, &read_condition).unwrap();
+    assert_eq!(samples.len(), 2);
     assert_eq!(samples[0].data().unwrap(), data2);
+    assert_eq!(samples[1].data().unwrap(), data3);
+
+    assert_eq!(read_condition.get_trigger_value(), Ok(false));
+    assert_eq!(
+        reader.take_w_condition(10, &read_condition).err(),
+        Some(DdsError::NoData)
+    );
+
+    reader.delete_readcondition(&read_condition).unwrap();
+    assert!(matches!(
+        reader.read_w_condition(10, &read_condition),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+    assert!(matches!(
+        reader.delete_readcondition(&read_condition),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+
+    let query_condition = reader
+        .create_querycondition(
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+            "id = 3",
+            &[],
+        )
+        .unwrap();
+    let samples = reader.take_w_condition(10, &query_condition).unwrap();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data().unwrap(), data3);
+    reader.delete_readcondition(&query_condition).unwrap();
 }
 
 #[test]
-fn write_read_unregistered_samples_are_also_disposed() {
+fn read_and_take_with_read_condition_instance_state_mask() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
     let participant_factory = DomainParticipantFactory::get_instance();
 
@@ -2362,9 +5565,6 @@ fn write_read_unregistered_samples_are_also_disposed() {
         history: HistoryQosPolicy {
             kind: HistoryQosPolicyKind::KeepAll,
         },
-        writer_data_lifecycle: WriterDataLifecycleQosPolicy {
-            autodispose_unregistered_instances: true,
-        },
         ..Default::default()
     };
     let writer = publisher
@@ -2399,32 +5599,54 @@ fn write_read_unregistered_samples_are_also_disposed() {
         .unwrap();
     wait_set.wait(Duration::new(10, 0)).unwrap();
 
+    let disposed_condition = reader
+        .create_readcondition(
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            &[InstanceStateKind::NotAliveDisposed],
+        )
+        .unwrap();
+
     let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 2, value: 10 };
 
     writer.write(&data1, None).unwrap();
-    writer.unregister_instance(&data1, None).unwrap();
+    writer.write(&data2, None).unwrap();
 
     writer
         .wait_for_acknowledgments(Duration::new(10, 0))
         .unwrap();
 
-    let samples = reader
-        .read(2, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
-        .unwrap();
-
-    assert_eq!(samples.len(), 2);
-    assert_eq!(
-        samples[0].sample_info().instance_state,
-        InstanceStateKind::NotAliveDisposed
-    );
+    assert_eq!(disposed_condition.get_trigger_value(), Ok(false));
     assert_eq!(
-        samples[1].sample_info().instance_state,
-        InstanceStateKind::NotAliveDisposed
+        reader.read_w_condition(10, &disposed_condition).err(),
+        Some(DdsError::NoData)
     );
+
+    writer.dispose(&data1, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    assert_eq!(disposed_condition.get_trigger_value(), Ok(true));
+    let data1_handle = writer.lookup_instance(&data1).unwrap().unwrap();
+    let samples = reader.take_w_condition(10, &disposed_condition).unwrap();
+    assert!(!samples.is_empty());
+    assert!(samples.iter().all(|s| {
+        s.sample_info().instance_handle == data1_handle
+            && s.sample_info().instance_state == InstanceStateKind::NotAliveDisposed
+    }));
+
+    assert_eq!(disposed_condition.get_trigger_value(), Ok(false));
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data().unwrap(), data2);
 }
 
 #[test]
-fn transient_local_writer_does_not_deliver_lifespan_expired_data_at_write() {
+fn wait_set_with_read_and_query_conditions() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
 
     let participant = DomainParticipantFactory::get_instance()
@@ -2439,171 +5661,216 @@ fn transient_local_writer_does_not_deliver_lifespan_expired_data_at_write() {
         .create_publisher(QosKind::Default, None, NO_STATUS)
         .unwrap();
     let writer_qos = DataWriterQos {
-        durability: DurabilityQosPolicy {
-            kind: DurabilityQosPolicyKind::TransientLocal,
-        },
-        history: HistoryQosPolicy {
-            kind: HistoryQosPolicyKind::KeepAll,
-        },
         reliability: ReliabilityQosPolicy {
             kind: ReliabilityQosPolicyKind::Reliable,
             max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
         },
-        lifespan: LifespanQosPolicy {
-            duration: DurationKind::Finite(Duration::new(1, 0)),
-        },
         ..Default::default()
     };
     let writer = publisher
         .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
         .unwrap();
-    let data1 = KeyedData { id: 1, value: 1 };
-    let data2 = KeyedData { id: 2, value: 2 };
-    writer
-        .write_w_timestamp(&data1, None, Time::new(0, 0))
-        .unwrap();
-    writer
-        .write_w_timestamp(&data2, None, Time::new(i32::MAX, 0))
-        .unwrap(); // Never stale sample
 
     let subscriber = participant
         .create_subscriber(QosKind::Default, None, NO_STATUS)
         .unwrap();
     let reader_qos = DataReaderQos {
-        durability: DurabilityQosPolicy {
-            kind: DurabilityQosPolicyKind::TransientLocal,
-        },
-        history: HistoryQosPolicy {
-            kind: HistoryQosPolicyKind::KeepAll,
-        },
         reliability: ReliabilityQosPolicy {
             kind: ReliabilityQosPolicyKind::Reliable,
             max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
         },
         ..Default::default()
     };
-
     let reader = subscriber
         .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
         .unwrap();
 
-    let cond = reader.get_statuscondition();
-    cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
         .unwrap();
 
     let mut wait_set = WaitSet::new();
     wait_set
-        .attach_condition(Condition::StatusCondition(cond))
+        .attach_condition(Condition::StatusCondition(cond.clone()))
         .unwrap();
-    wait_set.wait(Duration::new(5, 0)).unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+    wait_set
+        .detach_condition(Condition::StatusCondition(cond.clone()))
+        .unwrap();
+    assert!(matches!(
+        wait_set.detach_condition(Condition::StatusCondition(cond)),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
 
-    reader
-        .wait_for_historical_data(Duration::new(10, 0))
+    let read_condition = reader
+        .create_readcondition(ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
         .unwrap();
-    let samples = reader
-        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+    let query_condition = reader
+        .create_querycondition(
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+            "value > 5",
+            &[],
+        )
+        .unwrap();
+    wait_set
+        .attach_condition(Condition::ReadCondition(read_condition.clone()))
+        .unwrap();
+    wait_set
+        .attach_condition(Condition::ReadCondition(read_condition.clone()))
+        .unwrap();
+    wait_set
+        .attach_condition(Condition::QueryCondition(query_condition.clone()))
         .unwrap();
+    assert_eq!(wait_set.get_conditions().unwrap().len(), 2);
+    assert_eq!(
+        wait_set.wait(Duration::new(0, 100_000_000)).err(),
+        Some(DdsError::Timeout)
+    );
 
-    assert_eq!(samples.len(), 1);
-    assert_eq!(samples[0].data().unwrap(), data2);
+    writer.write(&KeyedData { id: 1, value: 1 }, None).unwrap();
+    let triggered_conditions = wait_set.wait(Duration::new(10, 0)).unwrap();
+    assert_eq!(triggered_conditions.len(), 1);
+    assert!(matches!(
+        triggered_conditions[0],
+        Condition::ReadCondition(_)
+    ));
+
+    writer.write(&KeyedData { id: 2, value: 10 }, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    assert_eq!(wait_set.wait(Duration::new(10, 0)).unwrap().len(), 2);
+
+    wait_set
+        .detach_condition(Condition::ReadCondition(read_condition))
+        .unwrap();
+    let triggered_conditions = wait_set.wait(Duration::new(10, 0)).unwrap();
+    assert_eq!(triggered_conditions.len(), 1);
+    assert!(matches!(
+        triggered_conditions[0],
+        Condition::QueryCondition(_)
+    ));
 }
 
 #[test]
-fn transient_local_writer_does_not_deliver_lifespan_expired_data_after_write() {
-    const LIFESPAN_MS: u32 = 100;
-    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+fn flow_controller_limits_writer_throughput() {
+    const MAX_BYTES_PER_SECOND: u64 = 100_000;
+    const NUMBER_OF_SAMPLES: usize = 200;
+    const SAMPLE_SIZE: usize = 2000;
 
-    let participant = DomainParticipantFactory::get_instance()
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+    let participant = participant_factory
         .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
         .unwrap();
-
     let topic = participant
-        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .create_topic::<LargeData>(
+            "LargeDataTopic",
+            "LargeData",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
         .unwrap();
 
     let publisher = participant
         .create_publisher(QosKind::Default, None, NO_STATUS)
         .unwrap();
     let writer_qos = DataWriterQos {
-        durability: DurabilityQosPolicy {
-            kind: DurabilityQosPolicyKind::TransientLocal,
-        },
-        history: HistoryQosPolicy {
-            kind: HistoryQosPolicyKind::KeepAll,
-        },
         reliability: ReliabilityQosPolicy {
             kind: ReliabilityQosPolicyKind::Reliable,
             max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
         },
-        lifespan: LifespanQosPolicy {
-            duration: DurationKind::Finite(Duration::new(0, LIFESPAN_MS * 1_000_000)),
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        flow_controller: FlowControllerQosPolicy {
+            max_bytes_per_second: Some(MAX_BYTES_PER_SECOND),
         },
         ..Default::default()
     };
     let writer = publisher
         .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
         .unwrap();
-    let data1 = KeyedData { id: 1, value: 1 };
-    let data2 = KeyedData { id: 2, value: 2 };
-    writer.write(&data1, None).unwrap();
-    writer
-        .write_w_timestamp(&data2, None, Time::new(i32::MAX, 0))
-        .unwrap(); // Never stale sample
-
-    std::thread::sleep(std::time::Duration::from_millis(LIFESPAN_MS as u64 * 2));
 
     let subscriber = participant
         .create_subscriber(QosKind::Default, None, NO_STATUS)
         .unwrap();
     let reader_qos = DataReaderQos {
-        durability: DurabilityQosPolicy {
-            kind: DurabilityQosPolicyKind::TransientLocal,
-        },
-        history: HistoryQosPolicy {
-            kind: HistoryQosPolicyKind::KeepAll,
-        },
         reliability: ReliabilityQosPolicy {
             kind: ReliabilityQosPolicyKind::Reliable,
             max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
         },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
         ..Default::default()
     };
-
     let reader = subscriber
-        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .create_datareader::<LargeData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
         .unwrap();
 
-    let cond = reader.get_statuscondition();
-    cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
         .unwrap();
-
     let mut wait_set = WaitSet::new();
     wait_set
         .attach_condition(Condition::StatusCondition(cond))
         .unwrap();
-    wait_set.wait(Duration::new(5, 0)).unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
 
-    reader
-        .wait_for_historical_data(Duration::new(10, 0))
-        .unwrap();
-    let samples = reader
-        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
-        .unwrap();
+    let start = std::time::Instant::now();
+    for i in 0..NUMBER_OF_SAMPLES {
+        let data = LargeData {
+            id: (i % 256) as u8,
+            value: vec![1; SAMPLE_SIZE],
+        };
+        writer.write(&data, None).unwrap();
+    }
 
-    assert_eq!(samples.len(), 1);
-    assert_eq!(samples[0].data().unwrap(), data2);
+    let mut received_samples = 0;
+    while received_samples < NUMBER_OF_SAMPLES {
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(20),
+            "Not all samples received"
+        );
+        if let Ok(samples) = reader.take(
+            NUMBER_OF_SAMPLES as i32,
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        ) {
+            received_samples += samples.len();
+        }
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+    let elapsed = start.elapsed();
+
+    let bytes_per_second = (NUMBER_OF_SAMPLES * SAMPLE_SIZE) as f64 / elapsed.as_secs_f64();
+    let max_bytes_per_second = MAX_BYTES_PER_SECOND as f64;
+    assert!(
+        (bytes_per_second - max_bytes_per_second).abs() < max_bytes_per_second * 0.1,
+        "Measured rate {bytes_per_second} bytes/s is not within 10% of {max_bytes_per_second} bytes/s"
+    );
 }
 
 #[test]
-fn reader_joining_after_writer_writes_many_samples() {
+fn write_blocks_when_send_queue_is_full() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
-
-    let participant = DomainParticipantFactory::get_instance()
+    let participant_factory = DomainParticipantFactory::get_instance();
+    let participant = participant_factory
         .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
         .unwrap();
-
     let topic = participant
-        .create_topic::<KeyedData>("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .create_topic::<LargeData>(
+            "LargeDataTopic",
+            "LargeData",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
         .unwrap();
 
     let publisher = participant
@@ -2611,12 +5878,17 @@ fn reader_joining_after_writer_writes_many_samples() {
         .unwrap();
     let writer_qos = DataWriterQos {
         reliability: ReliabilityQosPolicy {
-            kind: ReliabilityQosPolicyKind::Reliable,
-            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+            kind: ReliabilityQosPolicyKind::BestEffort,
+            max_blocking_time: DurationKind::Finite(Duration::new(0, 100_000_000)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        flow_controller: FlowControllerQosPolicy {
+            max_bytes_per_second: Some(100),
         },
         ..Default::default()
     };
-
     let writer = publisher
         .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
         .unwrap();
@@ -2624,43 +5896,33 @@ fn reader_joining_after_writer_writes_many_samples() {
     let subscriber = participant
         .create_subscriber(QosKind::Default, None, NO_STATUS)
         .unwrap();
-
-    for value in 0..500 {
-        let data = KeyedData { id: 1, value };
-        writer.write(&data, None).unwrap();
-    }
-
     let reader_qos = DataReaderQos {
         reliability: ReliabilityQosPolicy {
-            kind: ReliabilityQosPolicyKind::Reliable,
+            kind: ReliabilityQosPolicyKind::BestEffort,
             max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
         },
         ..Default::default()
     };
-    let reader = subscriber
-        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+    let _reader = subscriber
+        .create_datareader::<LargeData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
         .unwrap();
 
     let cond = writer.get_statuscondition();
     cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
         .unwrap();
-
     let mut wait_set = WaitSet::new();
     wait_set
         .attach_condition(Condition::StatusCondition(cond))
         .unwrap();
     wait_set.wait(Duration::new(10, 0)).unwrap();
 
-    let new_data = KeyedData { id: 1, value: 1000 };
-    writer.write(&new_data, None).unwrap();
-    writer
-        .wait_for_acknowledgments(Duration::new(20, 0))
-        .unwrap();
-
-    let samples = reader
-        .take(5, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
-        .unwrap();
+    let data = LargeData {
+        id: 1,
+        value: vec![1; 1000],
+    };
+    let result = (0..200)
+        .map(|_| writer.write(&data, None))
+        .find(|r| r.is_err());
 
-    assert_eq!(samples.len(), 1);
-    assert_eq!(samples[0].data().unwrap(), new_data);
+    assert_eq!(result, Some(Err(DdsError::Timeout)));
 }
@@ -0,0 +1,77 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Index, Result};
+
+fn is_basic_type(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(p) => match p.path.get_ident() {
+            Some(i) => matches!(
+                i.to_string().as_str(),
+                "bool"
+                    | "char"
+                    | "i8"
+                    | "u8"
+                    | "i16"
+                    | "u16"
+                    | "i32"
+                    | "u32"
+                    | "i64"
+                    | "u64"
+                    | "f32"
+                    | "f64"
+                    | "String"
+            ),
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+pub fn expand_dds_get_field(input: &DeriveInput) -> Result<TokenStream> {
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    match &input.data {
+        syn::Data::Struct(data_struct) => {
+            let mut field_arms = Vec::new();
+            for (field_index, field) in data_struct.fields.iter().enumerate() {
+                if !is_basic_type(&field.ty) {
+                    continue;
+                }
+                let field_arm = match &field.ident {
+                    Some(field_ident) => {
+                        let field_name = field_ident.to_string();
+                        quote! {#field_name => Some(self.#field_ident.clone().into()),}
+                    }
+                    None => {
+                        let field_name = field_index.to_string();
+                        let index = Index::from(field_index);
+                        quote! {#field_name => Some(self.#index.clone().into()),}
+                    }
+                };
+                field_arms.push(field_arm);
+            }
+
+            Ok(quote! {
+                impl #impl_generics dust_dds::topic_definition::type_support::DdsGetField for #ident #type_generics #where_clause {
+                    fn get_field(&self, field_name: &str) -> Option<dust_dds::topic_definition::type_support::FieldValue> {
+                        match field_name {
+                            #(#field_arms)*
+                            _ => None,
+                        }
+                    }
+                }
+            })
+        }
+        syn::Data::Enum(_) => Ok(quote! {
+            impl #impl_generics dust_dds::topic_definition::type_support::DdsGetField for #ident #type_generics #where_clause {
+                fn get_field(&self, _field_name: &str) -> Option<dust_dds::topic_definition::type_support::FieldValue> {
+                    None
+                }
+            }
+        }),
+        syn::Data::Union(data_union) => Err(syn::Error::new(
+            data_union.union_token.span,
+            "Union not supported",
+        )),
+    }
+}
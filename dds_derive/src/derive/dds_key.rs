@@ -67,10 +67,15 @@ pub fn expand_dds_key(input: &DeriveInput) -> Result<TokenStream> {
         syn::Data::Struct(data_struct) => {
             let has_key = struct_has_key(data_struct)?;
 
-            let (key_holder_struct_definition, key_holder_struct_construction) = match has_key {
+            let (
+                key_holder_struct_definition,
+                key_holder_struct_construction,
+                set_key_fields_definition,
+            ) = match has_key {
                 true => {
                     let mut key_holder_fields = quote! {};
                     let mut key_holder_field_assignment = quote! {};
+                    let mut key_field_assignment = quote! {};
 
                     for field in data_struct.fields.iter() {
                         if field_has_key_attribute(field)? {
@@ -79,6 +84,8 @@ pub fn expand_dds_key(input: &DeriveInput) -> Result<TokenStream> {
                             key_holder_fields.extend(quote! {#field_ident: #field_type,});
                             key_holder_field_assignment
                                 .extend(quote! {#field_ident: self.#field_ident.clone(),});
+                            key_field_assignment
+                                .extend(quote! {self.#field_ident = key.#field_ident;});
                         }
                     }
 
@@ -96,7 +103,18 @@ pub fn expand_dds_key(input: &DeriveInput) -> Result<TokenStream> {
                         }
                     };
 
-                    (key_holder_struct_definition, key_holder_struct_construction)
+                    let set_key_fields_definition = quote! {
+                        fn set_key_fields(&mut self, key: Self::Key) -> dust_dds::infrastructure::error::DdsResult<()> {
+                            #key_field_assignment
+                            Ok(())
+                        }
+                    };
+
+                    (
+                        key_holder_struct_definition,
+                        key_holder_struct_construction,
+                        set_key_fields_definition,
+                    )
                 }
                 false => {
                     let key_holder_struct_definition = quote! {
@@ -109,7 +127,17 @@ pub fn expand_dds_key(input: &DeriveInput) -> Result<TokenStream> {
                         __key_holder
                     };
 
-                    (key_holder_struct_definition, key_holder_struct_construction)
+                    let set_key_fields_definition = quote! {
+                        fn set_key_fields(&mut self, _key: Self::Key) -> dust_dds::infrastructure::error::DdsResult<()> {
+                            Ok(())
+                        }
+                    };
+
+                    (
+                        key_holder_struct_definition,
+                        key_holder_struct_construction,
+                        set_key_fields_definition,
+                    )
                 }
             };
             Ok(quote! {
@@ -128,6 +156,8 @@ pub fn expand_dds_key(input: &DeriveInput) -> Result<TokenStream> {
                             <#ident as dust_dds::topic_definition::type_support::DdsDeserialize>::deserialize_data(serialized_foo)?
                                 .get_key()
                         }
+
+                        #set_key_fields_definition
                     }
                 };
             })
@@ -144,6 +174,10 @@ pub fn expand_dds_key(input: &DeriveInput) -> Result<TokenStream> {
                     fn get_key_from_serialized_data(serialized_foo: &[u8]) -> dust_dds::infrastructure::error::DdsResult<Self::Key> {
                         Ok(())
                     }
+
+                    fn set_key_fields(&mut self, _key: Self::Key) -> dust_dds::infrastructure::error::DdsResult<()> {
+                        Ok(())
+                    }
                 }
             };
         }),
@@ -156,7 +190,7 @@ pub fn expand_dds_key(input: &DeriveInput) -> Result<TokenStream> {
 
 #[cfg(test)]
 mod tests {
-    use syn::ItemImpl;
+    use syn::{ItemConst, ItemImpl};
 
     use super::*;
 
@@ -214,4 +248,139 @@ mod tests {
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn struct_with_multiple_key_fields_should_be_has_key_true() {
+        let input = syn::parse2::<DeriveInput>(
+            "
+            struct WithMultipleKeys {
+                #[dust_dds(key)]
+                id: u8,
+                data: u32,
+                #[dust_dds(key)]
+                name: String,
+            }
+        "
+            .parse()
+            .unwrap(),
+        )
+        .unwrap();
+
+        let result = syn::parse2::<ItemImpl>(expand_has_key(&input).unwrap()).unwrap();
+        let expected = syn::parse2::<ItemImpl>(
+            "
+            impl dust_dds::topic_definition::type_support::DdsHasKey for WithMultipleKeys {
+                const HAS_KEY: bool = true;
+            }"
+            .parse()
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn key_holder_of_struct_with_multiple_key_fields_contains_only_key_fields() {
+        let input = syn::parse2::<DeriveInput>(
+            "
+            struct WithMultipleKeys {
+                #[dust_dds(key)]
+                id: u8,
+                data: u32,
+                #[dust_dds(key)]
+                name: String,
+            }
+        "
+            .parse()
+            .unwrap(),
+        )
+        .unwrap();
+
+        let result = syn::parse2::<ItemConst>(expand_dds_key(&input).unwrap()).unwrap();
+        let expected = syn::parse2::<ItemConst>(
+            "
+            const _ : () = {
+                #[allow(non_camel_case_types)]
+                #[derive(dust_dds::serialized_payload::cdr::serialize::CdrSerialize, dust_dds::serialized_payload::cdr::deserialize::CdrDeserialize)]
+                pub struct __key_holder {
+                    id: u8,
+                    name: String,
+                }
+
+                impl dust_dds::topic_definition::type_support::DdsKey for WithMultipleKeys {
+                    type Key = __key_holder;
+
+                    fn get_key(&self) -> dust_dds::infrastructure::error::DdsResult<Self::Key> {
+                        Ok(__key_holder {
+                            id: self.id.clone(),
+                            name: self.name.clone(),
+                        })
+                    }
+
+                    fn get_key_from_serialized_data(serialized_foo: &[u8]) -> dust_dds::infrastructure::error::DdsResult<Self::Key> {
+                        <WithMultipleKeys as dust_dds::topic_definition::type_support::DdsDeserialize>::deserialize_data(serialized_foo)?
+                            .get_key()
+                    }
+
+                    fn set_key_fields(&mut self, key: Self::Key) -> dust_dds::infrastructure::error::DdsResult<()> {
+                        self.id = key.id;
+                        self.name = key.name;
+                        Ok(())
+                    }
+                }
+            };"
+            .parse()
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn key_holder_of_struct_without_key_fields_is_empty() {
+        let input = syn::parse2::<DeriveInput>(
+            "
+            struct WithoutKey {
+                data: u32
+            }
+        "
+            .parse()
+            .unwrap(),
+        )
+        .unwrap();
+
+        let result = syn::parse2::<ItemConst>(expand_dds_key(&input).unwrap()).unwrap();
+        let expected = syn::parse2::<ItemConst>(
+            "
+            const _ : () = {
+                #[allow(non_camel_case_types)]
+                #[derive(dust_dds::serialized_payload::cdr::serialize::CdrSerialize, dust_dds::serialized_payload::cdr::deserialize::CdrDeserialize)]
+                pub struct __key_holder;
+
+                impl dust_dds::topic_definition::type_support::DdsKey for WithoutKey {
+                    type Key = __key_holder;
+
+                    fn get_key(&self) -> dust_dds::infrastructure::error::DdsResult<Self::Key> {
+                        Ok(__key_holder)
+                    }
+
+                    fn get_key_from_serialized_data(serialized_foo: &[u8]) -> dust_dds::infrastructure::error::DdsResult<Self::Key> {
+                        <WithoutKey as dust_dds::topic_definition::type_support::DdsDeserialize>::deserialize_data(serialized_foo)?
+                            .get_key()
+                    }
+
+                    fn set_key_fields(&mut self, _key: Self::Key) -> dust_dds::infrastructure::error::DdsResult<()> {
+                        Ok(())
+                    }
+                }
+            };"
+            .parse()
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(result, expected);
+    }
 }
@@ -1,4 +1,5 @@
 pub mod cdr;
+pub mod dds_get_field;
 pub mod dds_key;
 pub mod dds_serialize_data;
 pub mod dds_type_xml;
@@ -2,6 +2,7 @@ mod derive;
 
 use derive::{
     cdr::{expand_cdr_deserialize, expand_cdr_serialize},
+    dds_get_field::expand_dds_get_field,
     dds_key::{expand_dds_key, expand_has_key},
     dds_serialize_data::{expand_dds_deserialize_data, expand_dds_serialize_data},
     dds_type_xml::expand_dds_type_xml,
@@ -82,6 +83,14 @@ pub fn derive_dds_type_xml(input: TokenStream) -> TokenStream {
         .into()
 }
 
+#[proc_macro_derive(DdsGetField, attributes(dust_dds))]
+pub fn derive_dds_get_field(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input);
+    expand_dds_get_field(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
 #[proc_macro_derive(DdsType, attributes(dust_dds))]
 pub fn derive_dds_type(input: TokenStream) -> TokenStream {
     let mut output = TokenStream::new();
@@ -92,7 +101,8 @@ pub fn derive_dds_type(input: TokenStream) -> TokenStream {
     output.extend(derive_dds_deserialize(input.clone()));
     output.extend(derive_dds_key(input.clone()));
     output.extend(derive_dds_has_key(input.clone()));
-    output.extend(derive_dds_type_xml(input));
+    output.extend(derive_dds_type_xml(input.clone()));
+    output.extend(derive_dds_get_field(input));
 
     output
 }